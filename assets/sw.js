@@ -4,8 +4,10 @@
 
 /* A version number is useful when updating the worker logic,
    allowing you to remove outdated cache entries during the update.
+   It is replaced by the commit of every deployment, so a new build
+   replaces the cached bundle.
 */
-var version = 'v1.0.0::';
+var version = 'v2.0.0::';
 
 /* These resources will be downloaded and cached by the service worker
    during the installation process. If any resource fails to be downloaded,
@@ -13,8 +15,15 @@ var version = 'v1.0.0::';
 */
 var offlineFundamentals = [
   // add here the files you want to cache
+  './',
+  'index.html',
+  'manifest.json',
   'favicon.ico',
-  'style.css'
+  'favicon_192x192.png',
+  'style.css',
+  // the WASM bundle, named after the application in `Dioxus.toml`
+  'assets/dioxus/Sudoku.js',
+  'assets/dioxus/Sudoku_bg.wasm'
 ];
 
 /* The install event fires when the service worker is first installed.
@@ -67,6 +76,17 @@ self.addEventListener("fetch", function (event) {
     //console.log('WORKER: fetch event ignored.', event.request.method, event.request.url);
     return;
   }
+
+  /* Nor do we cache the requests to other origins, such as the sync server,
+     or the requests carrying credentials: their responses change with every
+     request and must never be served stale.
+  */
+  if (
+    new URL(event.request.url).origin !== self.location.origin ||
+    event.request.headers.has('Authorization')
+  ) {
+    return;
+  }
   /* Similar to event.waitUntil in that it blocks the fetch event on a promise.
      Fulfillment result will be used as the response, and rejection will end in a
      HTTP response indicating failure.
@@ -132,6 +152,18 @@ self.addEventListener("fetch", function (event) {
            error response.
         */
         function unableToResolve() {
+          /* The routes of the app, such as `play/easy`, are all served by the
+             same page, so offline they are served by the cached one.
+          */
+          if (event.request.mode === 'navigate') {
+            return caches.match('index.html').then(function (page) {
+              return page || serviceUnavailable();
+            });
+          }
+          return serviceUnavailable();
+        }
+
+        function serviceUnavailable() {
           /* There's a couple of things we can do here.
              - Test the Accept header and then return one of the `offlineFundamentals`
                e.g: `return caches.match('/some/cached/image.png')`
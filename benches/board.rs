@@ -0,0 +1,102 @@
+//! # Board Benchmarks
+//!
+//! Benchmarks of the board logic: generating puzzles, detecting conflicts,
+//! solving and finding hints, on representative boards.
+//!
+//! Run them with `cargo bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use sudoku_dioxus::app::SudokuState;
+use sudoku_dioxus::bitboard::BitBoard;
+use sudoku_dioxus::difficulty::{create_puzzle_with_rng, Difficulty};
+use sudoku_dioxus::formats::parse_line;
+use sudoku_dioxus::rng::GameRng;
+use sudoku_dioxus::utils::{
+    create_sudoku_with_rng, find_solution, get_all_conflicting_cells, get_hint_with_rng,
+};
+
+/// A puzzle solvable with singles only
+const EASY: &str =
+    "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+/// One of the hardest known puzzles
+const EXPERT: &str =
+    "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+
+/// The representative boards: an easy puzzle, an expert puzzle,
+/// and the easy puzzle with a few conflicting cells placed
+fn boards() -> Vec<(&'static str, SudokuState)> {
+    let easy = parse_line(EASY).expect("valid puzzle");
+    let expert = parse_line(EXPERT).expect("valid puzzle");
+    let mut conflicting = easy;
+    conflicting[2] = 5;
+    conflicting[10] = 9;
+    conflicting[80] = 7;
+    vec![
+        ("easy", easy),
+        ("expert", expert),
+        ("conflicting", conflicting),
+    ]
+}
+
+fn generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generation");
+    group.sample_size(20);
+    group.bench_function("create_sudoku", |b| {
+        let mut rng = GameRng::seeded(0);
+        b.iter(|| create_sudoku_with_rng(&mut rng));
+    });
+    group.bench_function("create_puzzle/medium", |b| {
+        let mut rng = GameRng::seeded(0);
+        b.iter(|| create_puzzle_with_rng(Difficulty::Medium, &mut rng));
+    });
+    group.finish();
+}
+
+fn conflicts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("conflicts");
+    for (name, board) in boards() {
+        group.bench_with_input(
+            BenchmarkId::new("get_all_conflicting_cells", name),
+            &board,
+            |b, board| b.iter(|| get_all_conflicting_cells(black_box(board))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("bitboard_set", name),
+            &BitBoard::new(board),
+            |b, board| {
+                b.iter(|| {
+                    let mut board = *board;
+                    board.set(black_box(1), 4);
+                    board.conflicting()
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn solving(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solving");
+    for (name, board) in boards()
+        .into_iter()
+        .filter(|(name, _)| *name != "conflicting")
+    {
+        group.bench_with_input(
+            BenchmarkId::new("find_solution", name),
+            &board,
+            |b, board| {
+                b.iter(|| find_solution(black_box(board)));
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("get_hint", name), &board, |b, board| {
+            let mut rng = GameRng::seeded(0);
+            b.iter(|| get_hint_with_rng(black_box(board), &mut rng));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, generation, conflicts, solving);
+criterion_main!(benches);
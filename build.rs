@@ -0,0 +1,76 @@
+//! # Build Script
+//!
+//! Compiles the curated puzzle bank, `puzzles/bank.txt`,
+//! into the compact binary encoding read by the `bank` module.
+//!
+//! Every puzzle is written as a record of [`RECORD_LEN`] bytes:
+//! the index of its difficulty, from 0 for easy to 3 for expert,
+//! followed by its 81 cells packed two per byte,
+//! the first one in the high 4 bits.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Path of the puzzle bank, from the root of the package
+const BANK_PATH: &str = "puzzles/bank.txt";
+
+/// The difficulties, in the order of their index
+const DIFFICULTIES: [&str; 4] = ["easy", "medium", "hard", "expert"];
+
+/// Number of bytes of every puzzle
+const RECORD_LEN: usize = 42;
+
+fn main() {
+    println!("cargo:rerun-if-changed={BANK_PATH}");
+    let text = fs::read_to_string(BANK_PATH).expect("failed to read the puzzle bank");
+
+    let mut bank = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (difficulty, puzzle) = line.split_once(' ').unwrap_or_else(|| {
+            panic!(
+                "{BANK_PATH}:{}: expected a difficulty and a puzzle",
+                number + 1
+            )
+        });
+        bank.extend(encode(difficulty, puzzle.trim()).unwrap_or_else(|err| {
+            panic!("{BANK_PATH}:{}: {err}", number + 1);
+        }));
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("failed to get the output directory");
+    fs::write(Path::new(&out_dir).join("bank.bin"), bank).expect("failed to write the puzzle bank");
+}
+
+/// Encodes a puzzle of the bank into a record.
+fn encode(difficulty: &str, puzzle: &str) -> Result<[u8; RECORD_LEN], String> {
+    let mut record = [0; RECORD_LEN];
+    record[0] = DIFFICULTIES
+        .iter()
+        .position(|&name| name == difficulty)
+        .and_then(|index| u8::try_from(index).ok())
+        .ok_or_else(|| format!("unknown difficulty `{difficulty}`"))?;
+
+    let cells: Vec<u8> = puzzle
+        .chars()
+        .map(|cell| match cell {
+            '.' | '0' => Ok(0),
+            '1'..='9' => Ok(cell as u8 - b'0'),
+            _ => Err(format!("unknown cell `{cell}`")),
+        })
+        .collect::<Result<_, _>>()?;
+    if cells.len() != 81 {
+        return Err(format!(
+            "the puzzle has {} cells instead of 81",
+            cells.len()
+        ));
+    }
+    for (index, pair) in cells.chunks(2).enumerate() {
+        record[index + 1] = pair[0] << 4 | pair.get(1).copied().unwrap_or(0);
+    }
+    Ok(record)
+}
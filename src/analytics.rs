@@ -0,0 +1,279 @@
+//! # Analytics Module
+//!
+//! The `analytics` module is an instrumentation layer recording anonymous
+//! gameplay [`Event`]s, such as puzzles started, hints used, puzzles solved
+//! and puzzles abandoned, to help tune the difficulty curves.
+//!
+//! Analytics are strictly opt-in: events are dropped unless the user picks
+//! an [`AnalyticsSink`] in the [`Settings`](crate::settings::Settings).
+//! Events carry no identity, only what happened in the game.
+//!
+//! Every event is also kept in the [`SessionLog`], in memory only,
+//! so the notable events of the session can be reviewed in the app.
+//! The session log is never sent anywhere, and is gone once the app is
+//! closed.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::app::SudokuState;
+use crate::browser::fetch_json;
+use crate::difficulty::Difficulty;
+use crate::utils::{format_duration, is_solved};
+
+/// How many events the [`SessionLog`] keeps, the oldest are dropped
+pub const MAX_SESSION_EVENTS: usize = 200;
+
+/// Where the gameplay events are recorded
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "sink", rename_all = "snake_case")]
+pub enum AnalyticsSink {
+    /// Events are dropped
+    #[default]
+    Off,
+    /// Events are written to the local log
+    Log,
+    /// Events are sent as JSON with a `POST` request to an HTTP endpoint
+    Http {
+        /// The URL of the endpoint
+        url: String,
+    },
+}
+
+/// An anonymous gameplay event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// A new puzzle was started
+    PuzzleStarted {
+        /// The difficulty of the puzzle
+        difficulty: Difficulty,
+    },
+    /// A hint was used
+    HintUsed {
+        /// The number of empty cells left after the hint
+        empty: usize,
+    },
+    /// A value was placed that conflicts with another one
+    Conflict {
+        /// The number of empty cells left after the placement
+        empty: usize,
+    },
+    /// A puzzle was imported, from a file, a photo or the clipboard
+    PuzzleImported {
+        /// The difficulty of the puzzle
+        difficulty: Difficulty,
+    },
+    /// A puzzle was solved
+    Solved {
+        /// The difficulty of the puzzle
+        difficulty: Difficulty,
+        /// The time taken, in milliseconds
+        millis: u64,
+    },
+    /// A puzzle was left unsolved for a new one
+    Abandoned {
+        /// The number of filled non-clue cells
+        filled: usize,
+        /// The number of non-clue cells
+        empty: usize,
+        /// The time played, in milliseconds
+        millis: u64,
+    },
+}
+
+impl Event {
+    /// Returns the event of leaving a game for a new one.
+    ///
+    /// ## Parameters
+    ///
+    /// - `initial: &SudokuState` - A reference to the initial puzzle.
+    /// - `current: &SudokuState` - A reference to the current board.
+    /// - `millis: u64` - The time played, in milliseconds.
+    ///
+    /// ## Returns
+    ///
+    /// Returns an [`Event::Abandoned`] if the game was started but not
+    /// solved, otherwise returns `None`.
+    #[must_use]
+    pub fn abandoned(initial: &SudokuState, current: &SudokuState, millis: u64) -> Option<Self> {
+        let empty: Vec<usize> = (0..81).filter(|&idx| initial[idx] == 0).collect();
+        let filled = empty.iter().filter(|&&idx| current[idx] != 0).count();
+        (filled > 0 && !is_solved(current)).then_some(Self::Abandoned {
+            filled,
+            empty: empty.len(),
+            millis,
+        })
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PuzzleStarted { difficulty } => write!(f, "{difficulty} puzzle started"),
+            Self::HintUsed { empty } => write!(f, "Hint used, {empty} cells left"),
+            Self::Conflict { empty } => {
+                write!(f, "Conflicting digit placed, {empty} cells left")
+            }
+            Self::PuzzleImported { difficulty } => write!(f, "{difficulty} puzzle imported"),
+            Self::Solved { difficulty, millis } => write!(
+                f,
+                "{difficulty} puzzle solved in {}",
+                format_duration(*millis)
+            ),
+            Self::Abandoned {
+                filled,
+                empty,
+                millis,
+            } => write!(
+                f,
+                "Game left after {}, {filled} of {empty} cells filled",
+                format_duration(*millis)
+            ),
+        }
+    }
+}
+
+/// An event of the session, with when it happened
+///
+/// - `at: u64`: when the event happened, in milliseconds since the Unix
+///   epoch.
+/// - `event: Event`: the event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionEvent {
+    pub at: u64,
+    pub event: Event,
+}
+
+/// The events of the session, the oldest first
+///
+/// - `started_at: u64`: when the session started,
+///   in milliseconds since the Unix epoch.
+/// - `events: VecDeque<SessionEvent>`: the last [`MAX_SESSION_EVENTS`]
+///   events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionLog {
+    pub started_at: u64,
+    pub events: VecDeque<SessionEvent>,
+}
+
+impl SessionLog {
+    /// Creates the log of a session with no event yet.
+    ///
+    /// ## Parameters
+    ///
+    /// - `started_at: u64` - When the session started,
+    ///   in milliseconds since the Unix epoch.
+    #[must_use]
+    pub const fn new(started_at: u64) -> Self {
+        Self {
+            started_at,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Adds an event to the log, dropping the oldest beyond
+    /// [`MAX_SESSION_EVENTS`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `at: u64` - When the event happened,
+    ///   in milliseconds since the Unix epoch.
+    /// - `event: Event` - The event.
+    pub fn push(&mut self, at: u64, event: Event) {
+        self.events.push_back(SessionEvent { at, event });
+        while self.events.len() > MAX_SESSION_EVENTS {
+            self.events.pop_front();
+        }
+    }
+}
+
+/// Records a gameplay event.
+///
+/// ## Parameters
+///
+/// - `sink: &AnalyticsSink` - Where to record the event.
+/// - `event: Event` - The event to record.
+pub fn track(sink: &AnalyticsSink, event: Event) {
+    match sink {
+        AnalyticsSink::Off => {}
+        AnalyticsSink::Log => log::info!("analytics: {}", json!(event)),
+        AnalyticsSink::Http { url } => {
+            let url = url.clone();
+            dioxus::prelude::spawn(async move {
+                if let Err(err) = fetch_json("POST", &url, json!({}), json!(event)).await {
+                    log::error!("failed to send the analytics event: {err:?}");
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku, find_solution};
+
+    #[test]
+    fn test_event_serde() {
+        let event = Event::Solved {
+            difficulty: Difficulty::Medium,
+            millis: 120_000,
+        };
+        assert_eq!(
+            json!(event),
+            json!({ "event": "solved", "difficulty": "medium", "millis": 120_000 })
+        );
+    }
+
+    #[test]
+    fn test_abandoned() {
+        let initial = create_sudoku();
+        assert_eq!(Event::abandoned(&initial, &initial, 1_000), None);
+
+        let mut current = initial;
+        let empty = (0..81).find(|&idx| initial[idx] == 0).expect("empty cell");
+        current[empty] = 1;
+        assert!(matches!(
+            Event::abandoned(&initial, &current, 1_000),
+            Some(Event::Abandoned { filled: 1, .. })
+        ));
+
+        let solved = find_solution(&initial).expect("solution");
+        assert_eq!(Event::abandoned(&initial, &solved, 1_000), None);
+    }
+
+    #[test]
+    fn test_session_log() {
+        let mut log = SessionLog::new(1_000);
+        for empty in 0..MAX_SESSION_EVENTS + 5 {
+            log.push(2_000, Event::HintUsed { empty });
+        }
+        assert_eq!(log.events.len(), MAX_SESSION_EVENTS);
+        assert_eq!(log.events[0].event, Event::HintUsed { empty: 5 });
+
+        let event = Event::Solved {
+            difficulty: Difficulty::Hard,
+            millis: 312_000,
+        };
+        assert_eq!(event.to_string(), "Hard puzzle solved in 5:12");
+    }
+
+    #[test]
+    fn test_sink_defaults_to_off() {
+        let sink: AnalyticsSink = serde_json::from_str(r#"{ "sink": "off" }"#).expect("valid");
+        assert_eq!(sink, AnalyticsSink::default());
+        let sink: AnalyticsSink =
+            serde_json::from_str(r#"{ "sink": "http", "url": "https://example.com" }"#)
+                .expect("valid");
+        assert_eq!(
+            sink,
+            AnalyticsSink::Http {
+                url: "https://example.com".to_string()
+            }
+        );
+    }
+}
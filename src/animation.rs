@@ -0,0 +1,207 @@
+//! # Animation Module
+//!
+//! The `animation` module derives the animations of the cells of the board
+//! from the changes of their values.
+//!
+//! Every cell whose value changes gets a [`CellTransition`],
+//! whichever way it changed: entered by the player, by a hint, or by an
+//! undo or a redo.
+//! The transition is drawn by the stylesheet as a short animation, which is
+//! turned off with the others when motion is reduced,
+//! and cleared once it ends.
+//!
+//! A change that makes a mistake, a conflict or a value that is not the one
+//! of the solution, shakes the offending cells instead,
+//! before they settle into the style of the conflicting cells.
+//!
+//! A new puzzle reveals its clues instead, box by box,
+//! in the [`reveal_order`] of their boxes.
+
+use crate::app::SudokuState;
+use crate::bitboard::BitBoard;
+
+/// How the value of a cell changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellTransition {
+    /// An empty cell got a value
+    Filled,
+    /// The value of the cell was erased
+    Erased,
+    /// The value of the cell was replaced by another one
+    Replaced,
+    /// The cell is part of a mistake that was just made
+    Mistake,
+    /// The cell is a clue of a new puzzle
+    Revealed,
+}
+
+impl CellTransition {
+    /// Returns the transition of a cell whose value changed,
+    /// or `None` if it did not change.
+    ///
+    /// ## Parameters
+    ///
+    /// - `old: u8` - The value before, or 0 if the cell was empty.
+    /// - `new: u8` - The value after, or 0 if the cell is empty.
+    #[must_use]
+    pub const fn between(old: u8, new: u8) -> Option<Self> {
+        match (old, new) {
+            _ if old == new => None,
+            (0, _) => Some(Self::Filled),
+            (_, 0) => Some(Self::Erased),
+            _ => Some(Self::Replaced),
+        }
+    }
+
+    /// Returns the name of the transition in the stylesheet.
+    #[must_use]
+    pub const fn css_name(self) -> &'static str {
+        match self {
+            Self::Filled => "filled",
+            Self::Erased => "erased",
+            Self::Replaced => "replaced",
+            Self::Mistake => "mistake",
+            Self::Revealed => "revealed",
+        }
+    }
+}
+
+/// Returns the transition of every cell between two boards.
+///
+/// The cells that conflict in the new board but did not in the old one,
+/// and the changed cells whose value is not the one of the solution,
+/// get a [`CellTransition::Mistake`].
+///
+/// ## Parameters
+///
+/// - `old: &SudokuState` - The board before.
+/// - `new: &SudokuState` - The board after.
+/// - `solution: Option<&SudokuState>` - The solution of the puzzle,
+///   or `None` to only tell the conflicts.
+#[must_use]
+pub fn transitions(
+    old: &SudokuState,
+    new: &SudokuState,
+    solution: Option<&SudokuState>,
+) -> [Option<CellTransition>; 81] {
+    let mut changes = [None; 81];
+    for (change, (&old, &new)) in changes.iter_mut().zip(old.iter().zip(new)) {
+        *change = CellTransition::between(old, new);
+    }
+
+    let (old_board, new_board) = (BitBoard::new(*old), BitBoard::new(*new));
+    for index in 0..81 {
+        let change = &mut changes[index as usize];
+        let conflict = new_board.is_conflicting(index) && !old_board.is_conflicting(index);
+        let wrong = solution.is_some_and(|solution| {
+            let value = new[index as usize];
+            change.is_some() && value != 0 && value != solution[index as usize]
+        });
+        if conflict || wrong {
+            *change = Some(CellTransition::Mistake);
+        }
+    }
+    changes
+}
+
+/// Returns the transition of every cell of a new puzzle:
+/// its clues are revealed, and its empty cells are left alone.
+///
+/// ## Parameters
+///
+/// - `puzzle: &SudokuState` - The new puzzle.
+#[must_use]
+pub fn reveal(puzzle: &SudokuState) -> [Option<CellTransition>; 81] {
+    puzzle.map(|value| (value != 0).then_some(CellTransition::Revealed))
+}
+
+/// Returns when a clue is revealed in the cascade of a new puzzle.
+///
+/// The order is the index of the box of the cell,
+/// from 0 for the top-left box to 8 for the bottom-right one,
+/// and the stylesheet delays the reveal of every box by it.
+///
+/// ## Parameters
+///
+/// - `index: u8` - The index of the cell, from 0 to 80.
+#[must_use]
+pub const fn reveal_order(index: u8) -> u8 {
+    index / 27 * 3 + index % 9 / 3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        let mut old = [0; 81];
+        old[1] = 5;
+        old[2] = 7;
+        old[3] = 9;
+        let mut new = old;
+        new[0] = 3;
+        new[1] = 0;
+        new[2] = 8;
+
+        let changes = transitions(&old, &new, None);
+        assert_eq!(changes[0], Some(CellTransition::Filled));
+        assert_eq!(changes[1], Some(CellTransition::Erased));
+        assert_eq!(changes[2], Some(CellTransition::Replaced));
+        assert_eq!(changes[3], None);
+        assert_eq!(changes.iter().flatten().count(), 3);
+    }
+
+    #[test]
+    fn test_reveal() {
+        let mut puzzle = [0; 81];
+        puzzle[0] = 4;
+        puzzle[80] = 2;
+        let changes = reveal(&puzzle);
+        assert_eq!(changes[0], Some(CellTransition::Revealed));
+        assert_eq!(changes[80], Some(CellTransition::Revealed));
+        assert_eq!(changes.iter().flatten().count(), 2);
+
+        assert_eq!(reveal_order(0), 0);
+        assert_eq!(reveal_order(5), 1);
+        assert_eq!(reveal_order(26), 2);
+        assert_eq!(reveal_order(30), 4);
+        assert_eq!(reveal_order(60), 8);
+        assert_eq!(reveal_order(80), 8);
+    }
+
+    #[test]
+    fn test_mistakes() {
+        let mut solution = [0; 81];
+        for (index, value) in solution.iter_mut().enumerate() {
+            *value = u8::try_from(index % 9 + 1).expect("a digit");
+        }
+        let mut old = [0; 81];
+        old[0] = 1;
+        let mut new = old;
+        // clashes with the 1 of the first cell
+        new[1] = 1;
+        // does not clash, but is not the one of the solution
+        new[20] = 5;
+        // matches the solution
+        new[30] = 4;
+
+        let changes = transitions(&old, &new, Some(&solution));
+        assert_eq!(changes[0], Some(CellTransition::Mistake));
+        assert_eq!(changes[1], Some(CellTransition::Mistake));
+        assert_eq!(changes[20], Some(CellTransition::Mistake));
+        assert_eq!(changes[30], Some(CellTransition::Filled));
+
+        // without the solution, only the conflicts are mistakes
+        let changes = transitions(&old, &new, None);
+        assert_eq!(changes[20], Some(CellTransition::Filled));
+
+        // the conflicts that were already there do not shake again
+        let mut newer = new;
+        newer[40] = 5;
+        let changes = transitions(&new, &newer, None);
+        assert_eq!(changes[0], None);
+        assert_eq!(changes[1], None);
+        assert_eq!(changes[40], Some(CellTransition::Filled));
+    }
+}
@@ -7,14 +7,175 @@
 //! This module is responsible for initializing and launching the game,
 //! setting up the environment, and managing the application lifecycle.
 
+use dioxus::dioxus_core::CapturedError;
 use dioxus::prelude::*;
 
-use crate::components::board::{InitialSudokuPuzzle, SudokuBoard, SudokuPuzzle, SudokuPuzzleMoves};
+use crate::analytics::{Event, SessionLog};
+use crate::auth::load_session;
+use crate::browser::{
+    location_hash, location_path, now, query_param, remove_query_param, replace_location_hash,
+    replace_location_path,
+};
+use crate::challenge::Challenge;
+use crate::components::analytics::{use_track, SessionEvents};
+use crate::components::auth::{use_magic_link_login, AuthStatus, UserSession};
+use crate::components::board::{use_dispatch, HintsUsed, SudokuBoard};
+use crate::components::challenge::ActiveChallenge;
+use crate::components::daily::DailyPuzzle;
+use crate::components::embed::{use_completion_message, Embedded};
+use crate::components::favorites::use_favorites;
+use crate::components::games::{CurrentSlot, GamesOpen, SaveSlots};
+use crate::components::generator::use_puzzle_queue;
+use crate::components::hotseat::HotSeatGame;
+use crate::components::packs::PackPuzzle;
+use crate::components::profiles::{ActiveProfile, Profiles};
+use crate::components::recovery::{Recoveries, RecoveryCard};
+use crate::components::settings::UserSettings;
+use crate::components::sync::{use_sync, SyncStatus};
+use crate::components::theme::use_theme;
+use crate::components::timer::GameTimer;
+use crate::components::toast::Toasts;
+use crate::components::weekly::WeeklyChallenge;
+use crate::daily::{day_of, load_daily_log, store_daily_log};
+use crate::difficulty::grade_puzzle;
+use crate::embed::{is_embedded, EMBED_PARAM};
+use crate::encoding::{decode_game, encode_game, GameSnapshot};
+use crate::game::{GameAction, GameState};
+use crate::links::{
+    linked_puzzle, parse_puzzle, Route, CHALLENGER_PARAM, CHALLENGE_PARAM, DIFFICULTY_PARAM,
+    PUZZLE_PARAM,
+};
+use crate::packs::{load_pack_progress, load_packs, store_pack_progress};
+use crate::profiles::{load_active_profile, load_profiles};
+use crate::save::{load_slots, next_slot_id, store_slots, SaveSlot, SavedGame};
+use crate::settings::{load_settings, store_settings, Settings};
+use crate::storage::{add_record, GameRecord};
+use crate::utils::{create_daily_sudoku, create_sudoku, is_solved};
+use crate::weekly::{create_weekly_sudoku, load_weekly_log, store_weekly_log};
 
 /// Represents a Sudoku state with the values, as `u8`, of the 81 cells in a
 /// Sodoku game
 pub type SudokuState = [u8; 81];
 
+/// Represents the notes, as `u16` bitmasks, of the 81 cells in a Sudoku game
+///
+/// The candidate `n`, from 1 to 9, is noted in a cell if its bit `n - 1`
+/// is set.
+pub type SudokuNotes = [u16; 81];
+
+/// Generates the puzzle of a new game.
+///
+/// When the app is server-rendered, the puzzle is generated by the server
+/// and sent along with the HTML,
+/// and the browser reads it instead of generating another one,
+/// so the board it hydrates is the one the server rendered.
+fn new_puzzle() -> SudokuState {
+    #[cfg(any(feature = "server", feature = "hydrate"))]
+    {
+        // arrays of 81 cells are not serializable, vectors are
+        let cells = server_cached(|| create_sudoku().to_vec());
+        SudokuState::try_from(cells.as_slice()).unwrap_or_else(|_| create_sudoku())
+    }
+    #[cfg(not(any(feature = "server", feature = "hydrate")))]
+    create_sudoku()
+}
+
+/// Whether the app hydrates the HTML rendered by the server
+///
+/// The first render of a hydrating app must match the one of the server,
+/// which cannot read the URL nor the local storage of the browser,
+/// so they are only read once hydrated.
+const HYDRATES: bool = cfg!(any(feature = "server", feature = "hydrate"));
+
+/// Shared State for whether the state that only the browser knows is loaded
+///
+/// It is loaded from the start, unless the app [`HYDRATES`],
+/// in which case it is loaded once hydrated:
+/// the puzzle of the URL, the settings and the save slots.
+#[derive(Debug, Clone, Copy)]
+pub struct Hydrated(pub bool);
+
+/// The puzzle, daily puzzle, weekly challenge and challenge opened from the
+/// URL query or route
+type Opened = (
+    Option<SudokuState>,
+    Option<u64>,
+    Option<u64>,
+    Option<Challenge>,
+);
+
+/// Reads the puzzle or mode opened from the URL query or route, if any,
+/// and drops them from the URL, unless embedded.
+fn read_opened(embedded: bool) -> Opened {
+    let puzzle = query_param(PUZZLE_PARAM);
+    let difficulty = query_param(DIFFICULTY_PARAM);
+    let time = query_param(CHALLENGE_PARAM);
+    let name = query_param(CHALLENGER_PARAM);
+    // the URL fragment keeps the game from now on
+    if !embedded {
+        remove_query_param(PUZZLE_PARAM);
+        remove_query_param(DIFFICULTY_PARAM);
+        remove_query_param(CHALLENGE_PARAM);
+        remove_query_param(CHALLENGER_PARAM);
+    }
+    // the route is dropped from the URL the same way
+    let route = location_path().and_then(|path| {
+        let (route, base) = Route::parse(&path)?;
+        replace_location_path(base);
+        Some(route)
+    });
+    let linked = linked_puzzle(puzzle.as_deref(), difficulty.as_deref());
+    // the time to beat, if the puzzle was linked as a challenge
+    let challenge = puzzle
+        .as_deref()
+        .and_then(|value| parse_puzzle(value).ok())
+        .zip(time)
+        .and_then(|(puzzle, time)| Challenge::parse(puzzle, &time, name.as_deref()));
+    // the day of the daily puzzle or the week of the challenge,
+    // if the route opens it
+    let route = route.filter(|_| linked.is_none());
+    (
+        linked.or_else(|| route.map(|route| route.puzzle(now()))),
+        route.and_then(|route| route.day(now())),
+        route.and_then(|route| route.week(now())),
+        challenge,
+    )
+}
+
+/// Reads the game of the URL fragment, if any,
+/// unless embedded or a puzzle was opened from the URL query or route.
+fn read_snapshot(embedded: bool, linked: Option<SudokuState>) -> Option<GameSnapshot> {
+    location_hash()
+        .filter(|_| !embedded && linked.is_none())
+        .and_then(|hash| decode_game(&hash).ok())
+}
+
+/// Returns the action loading the game opened from the URL, if any:
+/// the linked puzzle, or the game of the URL fragment with its notes.
+fn open_game(linked: Option<SudokuState>, snapshot: Option<&GameSnapshot>) -> Option<GameAction> {
+    linked.map(GameAction::NewGame).or_else(|| {
+        let game = snapshot?;
+        let moves = if game.current == game.initial {
+            vec![game.initial]
+        } else {
+            vec![game.initial, game.current]
+        };
+        Some(GameAction::Load {
+            moves,
+            notes: game.notes,
+        })
+    })
+}
+
+/// Returns the id of the save slot of a puzzle,
+/// or an unused one if it has none.
+fn slot_of(slots: &[SaveSlot], puzzle: &SudokuState) -> u32 {
+    slots
+        .iter()
+        .find(|slot| slot.game.initial == *puzzle)
+        .map_or_else(|| next_slot_id(slots), |slot| slot.id)
+}
+
 /// This function sets up the main environment for
 /// the Sudoku game in a web browser, initializes the necessary state,
 /// and renders the main [`SudokuBoard`] component.
@@ -22,18 +183,322 @@ pub type SudokuState = [u8; 81];
 /// It is designed to be used as the root of the web application,
 /// orchestrating the entire Sudoku game and its user interface.
 ///
-/// ## Panics
+/// If the page URL has a query or route created by one of the
+/// [`links`](crate::links), its puzzle, difficulty or mode is opened.
+/// Otherwise, if the page URL has a fragment created by
+/// [`encode_game`], the game is restored from it.
+/// The fragment is kept in sync with the current game,
+/// so the URL can be used to continue the game on another device.
+///
+/// The settings of the active player profile are loaded from,
+/// and stored in, the local storage.
+/// So are its save slots, and the current game is saved to its slot
+/// whenever it changes.
+/// Once solved, the game leaves its slot for the history of completed games
+/// in the [`storage`](crate::storage).
+/// If the app is opened from a magic link, the player is signed in with
+/// [`auth`](crate::auth).
+/// If cloud [`sync`](crate::sync) is on, the games are synced on start.
 ///
-/// The app will panic if fails to get initial Sudoku puzzle shared state.
+/// If the board fails, it is replaced by a [`RecoveryCard`] that restores
+/// the game from its save slot and renders the board again,
+/// see [`Recoveries`].
+///
+/// In the [`embed`](crate::embed) mode, only the board is rendered,
+/// and nothing is saved, synced or written to the URL.
+///
+/// With the `server` and `hydrate` features, the app is server-rendered:
+/// the server renders the board of a new puzzle with the default settings,
+/// and the browser hydrates it with the same puzzle and settings.
+/// Once hydrated, the browser loads what the server cannot see,
+/// see [`Hydrated`]:
+/// games opened from a link or restored from the URL replace the new
+/// puzzle, the settings and save slots are loaded,
+/// and the unfinished games are listed.
+/// The [`embed`](crate::embed) mode needs the static app,
+/// since it renders another page than the server.
 #[component]
 pub fn App() -> Element {
-    // set initial puzzle
-    use_context_provider(|| Signal::new(InitialSudokuPuzzle::new()));
+    // check if the game is embedded in another page
+    let embedded = use_context_provider(|| {
+        Signal::new(Embedded(is_embedded(query_param(EMBED_PARAM).as_deref())))
+    });
+    let embedded = embedded.peek().0;
+
+    // the state that only the browser knows is read once hydrated
+    let mut hydrated = use_context_provider(|| Signal::new(Hydrated(!HYDRATES)));
+
+    // open the puzzle or difficulty of the URL query or route, if any
+    let (linked, daily, weekly, challenge) = use_hook(|| {
+        if HYDRATES {
+            (None, None, None, None)
+        } else {
+            read_opened(embedded)
+        }
+    });
+    let mut challenge = use_context_provider(|| Signal::new(ActiveChallenge(challenge)));
+    let mut daily = use_context_provider(|| Signal::new(DailyPuzzle(daily)));
+    let mut weekly = use_context_provider(|| Signal::new(WeeklyChallenge(weekly)));
+    let pack_puzzle = use_context_provider(|| Signal::new(PackPuzzle(None)));
+
+    // otherwise restore a game from the URL fragment, if any, unless embedded
+    let snapshot = use_hook(|| {
+        if HYDRATES {
+            None
+        } else {
+            read_snapshot(embedded, linked)
+        }
+    });
+
+    // the pass-and-play game, if one is under way
+    use_context_provider(|| Signal::new(HotSeatGame(None)));
+
+    // set the game, with the game opened from the URL, if any,
+    // whose moves restored on start are the first branch of the undo tree
+    let game = use_context_provider(|| {
+        let puzzle = linked
+            .or_else(|| snapshot.map(|game| game.initial))
+            .unwrap_or_else(new_puzzle);
+        let mut game = GameState::new(puzzle);
+        if let Some(action) = open_game(None, snapshot.as_ref()) {
+            game.apply(action);
+        }
+        Signal::new(game)
+    });
+    let mut dispatch = use_dispatch();
+    let initial = game.peek().initial;
+    let current = game.peek().current();
+
+    // set the game timer
+    let timer = use_context_provider(|| Signal::new(GameTimer(0)));
+    // the hints are kept with the game in the history
+    let hints_used = use_context_provider(|| Signal::new(HintsUsed(0)));
+
+    // set the toasts
+    use_context_provider(|| Signal::new(Toasts::default()));
 
-    // set current sudoku and cache of user moves
-    let initial_sudoku = use_context::<Signal<InitialSudokuPuzzle>>().read().0;
-    use_context_provider(|| Signal::new(SudokuPuzzle(initial_sudoku)));
-    use_context_provider(|| Signal::new(SudokuPuzzleMoves(vec![initial_sudoku])));
+    // set the player profiles
+    use_context_provider(|| Signal::new(Profiles(load_profiles())));
+    let profile = use_context_provider(|| Signal::new(ActiveProfile(load_active_profile())));
+
+    // set the user's settings and keep them stored, once loaded
+    let mut settings = use_context_provider(|| {
+        Signal::new(UserSettings(if HYDRATES {
+            Settings::default()
+        } else {
+            load_settings(profile.peek().0)
+        }))
+    });
+    use_effect(move || {
+        if hydrated.read().0 {
+            store_settings(profile.peek().0, &settings.read().0);
+        }
+    });
+
+    // apply the settings that change the look of the whole app
+    use_theme();
+
+    // set the save slots, reusing the slot of the current puzzle if any
+    let mut slots = use_context_provider(|| {
+        Signal::new(SaveSlots(if HYDRATES {
+            vec![]
+        } else {
+            load_slots(profile.peek().0)
+        }))
+    });
+    let mut current_slot =
+        use_context_provider(|| Signal::new(CurrentSlot(slot_of(&slots.peek().0, &initial))));
+
+    // list the unfinished games on start, so they can be continued,
+    // unless a game was opened from the URL
+    let mut games_open = use_context_provider(|| {
+        let resume = !embedded && linked.is_none() && snapshot.is_none();
+        Signal::new(GamesOpen(resume && !slots.peek().0.is_empty()))
+    });
+
+    // record gameplay events, if the user opted in,
+    // and keep them in the log of the session
+    use_context_provider(|| Signal::new(SessionEvents(SessionLog::new(now()))));
+    let mut track = use_track();
+
+    // the slot of a game that was already solved when loaded is not recorded
+    let mut recorded = use_signal(|| is_solved(&current).then(|| current_slot.peek().0));
+
+    // once hydrated, load the state that only the browser knows,
+    // as it would have been on start without hydration
+    use_effect(move || {
+        if hydrated.peek().0 {
+            return;
+        }
+        let profile = profile.peek().0;
+        settings.set(UserSettings(load_settings(profile)));
+        let (linked, day, week, opened_challenge) = read_opened(embedded);
+        let snapshot = read_snapshot(embedded, linked);
+        let opened = open_game(linked, snapshot.as_ref());
+        let any_opened = opened.is_some();
+        if let Some(action) = opened {
+            dispatch(action);
+        }
+        daily.set(DailyPuzzle(day));
+        weekly.set(WeeklyChallenge(week));
+        challenge.set(ActiveChallenge(opened_challenge));
+
+        let loaded = load_slots(profile);
+        let id = slot_of(&loaded, &game.peek().initial);
+        current_slot.set(CurrentSlot(id));
+        recorded.set(is_solved(&game.peek().current()).then_some(id));
+        games_open.set(GamesOpen(!embedded && !any_opened && !loaded.is_empty()));
+        slots.set(SaveSlots(loaded));
+        hydrated.set(Hydrated(true));
+    });
+
+    // keep the current game saved to its slot, once it has progress,
+    // and move it to the history once it is solved
+    use_effect(move || {
+        if !hydrated.read().0 {
+            return;
+        }
+        let id = current_slot.read().0;
+        let game = game.read();
+        let history = &game.history;
+        let game = SavedGame {
+            initial: game.initial,
+            moves: history.moves(),
+            notes: game.notes,
+            timer: timer.peek().0,
+            settings: settings.peek().0.clone(),
+            move_notes: history.move_notes(),
+            tree: Some(history.clone()),
+        };
+        if embedded || (game.moves.len() == 1 && game.notes == [0; 81]) {
+            return;
+        }
+        let profile = profile.peek().0;
+        let mut slots = slots.write();
+        let difficulty = slots
+            .0
+            .iter()
+            .find(|slot| slot.id == id && slot.game.initial == game.initial)
+            .map_or_else(|| grade_puzzle(&game.initial), |slot| slot.difficulty);
+        slots.0.retain(|slot| slot.id != id);
+
+        if !is_solved(&game.current()) {
+            slots.0.insert(
+                0,
+                SaveSlot {
+                    id,
+                    difficulty,
+                    game,
+                    modified_at: now(),
+                },
+            );
+        } else if *recorded.peek() != Some(id) {
+            recorded.set(Some(id));
+            track(Event::Solved {
+                difficulty,
+                millis: game.timer,
+            });
+            // a daily puzzle is marked as completed in the archive
+            if let Some(day) = daily
+                .peek()
+                .0
+                .filter(|&day| create_daily_sudoku(day) == game.initial)
+            {
+                let mut log = load_daily_log(profile);
+                log.complete(day, day_of(now()));
+                store_daily_log(profile, &log);
+            }
+            // so is a weekly challenge, with its time
+            if let Some(week) = weekly
+                .peek()
+                .0
+                .filter(|&week| create_weekly_sudoku(week) == game.initial)
+            {
+                let mut log = load_weekly_log(profile);
+                log.complete(week, game.timer);
+                store_weekly_log(profile, &log);
+            }
+            // and a pack puzzle in the progress of its pack
+            if let Some((pack, index)) = pack_puzzle.peek().0.filter(|&(pack, index)| {
+                load_packs()
+                    .iter()
+                    .find(|found| found.id == pack)
+                    .and_then(|found| found.puzzle(index))
+                    == Some(game.initial)
+            }) {
+                let mut progress = load_pack_progress(profile);
+                progress.complete(pack, index);
+                store_pack_progress(profile, &progress);
+            }
+            let record = GameRecord {
+                profile,
+                difficulty,
+                finished_at: now(),
+                game,
+                hints: hints_used.peek().0,
+            };
+            spawn(async move {
+                if let Err(err) = add_record(&record).await {
+                    log::error!("failed to add the game to the history: {err:?}");
+                }
+            });
+        }
+        store_slots(profile, &slots.0);
+    });
+
+    // set the signed in account and sign in with a magic link, if any
+    use_context_provider(|| Signal::new(UserSession(load_session(profile.peek().0))));
+    use_context_provider(|| Signal::new(AuthStatus(None)));
+    use_magic_link_login();
+
+    // sync the games on start, if cloud sync is on
+    use_context_provider(|| Signal::new(SyncStatus(None)));
+    let mut sync_games = use_sync();
+    use_effect(move || {
+        if hydrated.read().0 && !embedded {
+            sync_games();
+        }
+    });
+
+    // tell the embedding page once the puzzle is solved
+    use_completion_message();
+
+    // generate puzzles ahead of time, for new games offline
+    use_puzzle_queue();
+
+    // load the favorite puzzles of the active profile
+    use_favorites();
+
+    // keep the URL fragment in sync with the current game, once read
+    use_effect(move || {
+        if embedded || !hydrated.read().0 {
+            return;
+        }
+        let game = game.read();
+        let snapshot = GameSnapshot {
+            initial: game.initial,
+            current: game.current(),
+            notes: game.notes,
+        };
+        replace_location_hash(&encode_game(&snapshot));
+    });
+
+    // a failing board is replaced by the recovery card,
+    // until a recovery renders the board again
+    let recoveries = use_context_provider(|| Signal::new(Recoveries(0)));
+    recoveries.read();
+    let handle_error = |error: CapturedError| {
+        rsx!(RecoveryCard {
+            error: format!("{:?}", error.error),
+        })
+    };
+
+    if embedded {
+        return rsx!(ErrorBoundary {
+            handle_error: handle_error,
+            SudokuBoard {}
+        });
+    }
 
     rsx!(
         h1 {
@@ -41,7 +506,10 @@ pub fn App() -> Element {
             "Sudoku"
         }
 
-        SudokuBoard {}
+        ErrorBoundary {
+            handle_error: handle_error,
+            SudokuBoard {}
+        }
 
         div{
             class: "github",
@@ -53,7 +521,6 @@ pub fn App() -> Element {
                 svg {
                     class: "github",
                     view_box: "0 0 24 24",
-                    fill: "#3b59a9",
                     path {
                         d: "M12 2C6.477 2 2 6.477 2 12c0 4.418 2.865 8.166 6.839 9.489.5.092.682-.217.682-.482 0-.237-.009-.866-.014-1.7-2.782.603-3.369-1.34-3.369-1.34-.454-1.156-1.11-1.462-1.11-1.462-.908-.62.069-.608.069-.608 1.003.07 1.532 1.03 1.532 1.03.891 1.529 2.341 1.088 2.912.833.091-.646.349-1.086.635-1.337-2.22-.253-4.555-1.11-4.555-4.943 0-1.091.39-1.984 1.03-2.682-.103-.253-.447-1.27.098-2.646 0 0 .84-.27 2.75 1.026A9.564 9.564 0 0112 7.07c.85.004 1.705.115 2.504.337 1.909-1.296 2.747-1.026 2.747-1.026.547 1.376.203 2.394.1 2.646.64.699 1.028 1.591 1.028 2.682 0 3.841-2.337 4.687-4.565 4.934.359.31.678.92.678 1.852 0 1.336-.012 2.415-.012 2.741 0 .267.18.578.688.48A10.017 10.017 0 0022 12C22 6.477 17.523 2 12 2z"
                     }
@@ -0,0 +1,224 @@
+//! # Auth Module
+//!
+//! The `auth` module signs players in to an account on the server set in
+//! the [`Settings`](crate::settings::Settings),
+//! so cloud sync and leaderboard submissions are tied to an identity
+//! instead of anonymous browser storage.
+//!
+//! Accounts sign in with a magic link:
+//!
+//! 1. The player enters their email and [`request_magic_link`] asks the
+//!    server to email them a link back to the app with a `login` token in
+//!    the URL query.
+//! 2. When the app is opened from the link,
+//!    [`verify_magic_link`] trades the token for a [`Session`].
+//! 3. Requests to the server are authorized with the session token,
+//!    see [`auth_headers`].
+//!
+//! The app server, with the `server` feature, checks the sessions it is
+//! sent with [`verify_session`],
+//! asking the auth server at `GET {server}/auth/session` for the email of
+//! the account of a token.
+//!
+//! Sessions are kept in the local storage,
+//! separately for every [`Profile`](crate::profiles::Profile).
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::browser::{fetch_json, local_storage_get, local_storage_set};
+use crate::profiles::profile_key;
+
+/// Local storage key of the session
+const SESSION_KEY: &str = "sudoku-session";
+
+/// Name of the URL query parameter with the magic link token
+pub const LOGIN_PARAM: &str = "login";
+
+/// The session of a signed in account
+///
+/// - `email: String`: the email of the account.
+/// - `token: String`: the token authorizing requests to the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    pub email: String,
+    pub token: String,
+}
+
+/// Returns the headers authorizing a request with a session.
+///
+/// ## Parameters
+///
+/// - `session: &Session` - A reference to the session.
+///
+/// ## Returns
+///
+/// Returns a JSON object with the `Authorization` bearer header.
+#[must_use]
+pub fn auth_headers(session: &Session) -> Value {
+    json!({ "Authorization": format!("Bearer {}", session.token) })
+}
+
+/// Checks that an email looks valid.
+///
+/// Only the shape is checked, the server does the actual validation by
+/// sending the magic link.
+///
+/// ## Parameters
+///
+/// - `email: &str` - The email to check.
+#[must_use]
+pub fn is_valid_email(email: &str) -> bool {
+    email.split_once('@').is_some_and(|(user, domain)| {
+        !user.is_empty()
+            && domain.contains('.')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.')
+            && !email.contains(char::is_whitespace)
+    })
+}
+
+/// Returns the URL of a server endpoint.
+fn endpoint(server: &str, path: &str) -> String {
+    format!("{}/{path}", server.trim_end_matches('/'))
+}
+
+/// Asks the server to email a magic link to sign in.
+///
+/// ## Parameters
+///
+/// - `server: &str` - The URL of the server.
+/// - `email: &str` - The email of the account.
+/// - `redirect: &str` - The URL the magic link opens.
+///
+/// ## Errors
+///
+/// Returns an error if the email is invalid or the request fails.
+#[allow(clippy::future_not_send)] // JavaScript handles are not `Send`
+pub async fn request_magic_link(server: &str, email: &str, redirect: &str) -> Result<()> {
+    if !is_valid_email(email) {
+        return Err(Error::msg("Invalid email"));
+    }
+    let body = json!({ "email": email, "redirect": redirect, "param": LOGIN_PARAM });
+    fetch_json(
+        "POST",
+        &endpoint(server, "auth/magic-link"),
+        json!({}),
+        body,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Trades a magic link token for a [`Session`].
+///
+/// ## Parameters
+///
+/// - `server: &str` - The URL of the server.
+/// - `token: &str` - The token of the magic link.
+///
+/// ## Returns
+///
+/// Returns a Result containing the [`Session`] of the account.
+///
+/// ## Errors
+///
+/// Returns an error if the request fails or the token is rejected.
+#[allow(clippy::future_not_send)]
+pub async fn verify_magic_link(server: &str, token: &str) -> Result<Session> {
+    let body = json!({ "token": token });
+    let session = fetch_json("POST", &endpoint(server, "auth/verify"), json!({}), body).await?;
+    Ok(serde_json::from_value(session)?)
+}
+
+/// The account of a session, as answered by the auth server
+#[cfg(feature = "server")]
+#[derive(Deserialize)]
+struct Account {
+    email: String,
+}
+
+/// Asks the auth server for the account of a session token, on the server.
+///
+/// ## Parameters
+///
+/// - `server: &str` - The URL of the auth server.
+/// - `token: &str` - The token of the session.
+///
+/// ## Returns
+///
+/// Returns a Result containing the email of the account, in lowercase.
+///
+/// ## Errors
+///
+/// Returns an error if the auth server cannot be reached
+/// or rejects the token.
+#[cfg(feature = "server")]
+pub async fn verify_session(server: &str, token: &str) -> Result<String> {
+    let account: Account = reqwest::Client::new()
+        .get(endpoint(server, "auth/session"))
+        .bearer_auth(token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let email = account.email.trim().to_lowercase();
+    if !is_valid_email(&email) {
+        return Err(Error::msg("The auth server answered an invalid email"));
+    }
+    Ok(email)
+}
+
+/// Loads the session of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`Session`], or `None` if the profile is signed out.
+#[must_use]
+pub fn load_session(profile: u32) -> Option<Session> {
+    local_storage_get(&profile_key(profile, SESSION_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+/// Stores the session of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `session: Option<&Session>` - The session, or `None` to sign out.
+pub fn store_session(profile: u32, session: Option<&Session>) {
+    let json = session
+        .and_then(|session| serde_json::to_string(session).ok())
+        .unwrap_or_default();
+    local_storage_set(&profile_key(profile, SESSION_KEY), &json);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_email() {
+        assert!(is_valid_email("player@example.com"));
+        assert!(!is_valid_email("player"));
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("player@example"));
+        assert!(!is_valid_email("player@.com"));
+        assert!(!is_valid_email("pla yer@example.com"));
+    }
+
+    #[test]
+    fn test_auth_headers() {
+        let session = Session {
+            email: "player@example.com".to_string(),
+            token: "secret".to_string(),
+        };
+        assert_eq!(auth_headers(&session)["Authorization"], "Bearer secret");
+    }
+}
@@ -0,0 +1,175 @@
+//! # Backtrack Module
+//!
+//! The `backtrack` module solves Sudoku puzzles by brute force,
+//! one step at a time.
+//!
+//! The [`Backtracker`] is an iterator of [`SearchStep`]s:
+//! every digit it tries in a cell, and every cell it gives up on,
+//! so the search can be watched as it goes, or tested step by step.
+//! It always fills the empty cell with the fewest candidates first,
+//! and tries its candidates from the lowest one,
+//! so the same board always gives the same steps.
+//!
+//! See also: [`logic`](crate::logic), which solves the way a human does.
+
+use crate::app::SudokuState;
+use crate::bitboard::BitBoard;
+
+/// A step of the brute-force search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStep {
+    /// A digit tried in a cell, replacing the one tried before, if any
+    Try { cell: u8, digit: u8 },
+    /// A cell emptied again, since none of its candidates led to a solution
+    Backtrack { cell: u8 },
+    /// The board is solved, which is always the last step
+    Solved,
+}
+
+/// A brute-force solver, that yields the steps of its search
+///
+/// The search ends with [`SearchStep::Solved`] if the board has a
+/// solution,
+/// and without it if the board has none, or has conflicting cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backtracker {
+    board: BitBoard,
+    /// The cells being tried, with the candidates left to try in each
+    stack: Vec<(u8, u16)>,
+    /// Whether the last step tried a digit, so the next one fills a new cell
+    advance: bool,
+    done: bool,
+}
+
+impl Backtracker {
+    /// Creates a solver for a board.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to the board to solve.
+    #[must_use]
+    pub fn new(board: &SudokuState) -> Self {
+        let board = BitBoard::new(*board);
+        Self {
+            done: !board.conflicting().is_empty(),
+            board,
+            stack: vec![],
+            advance: true,
+        }
+    }
+
+    /// Returns the board, with the digits tried so far.
+    #[must_use]
+    pub const fn board(&self) -> &SudokuState {
+        self.board.cells()
+    }
+
+    /// Returns the empty cell with the fewest candidates,
+    /// the first one if there are more, with its candidates.
+    fn next_cell(&self) -> Option<(u8, u16)> {
+        (0..81)
+            .filter(|&index| self.board.cells()[index as usize] == 0)
+            .map(|index| (index, self.board.candidates(index)))
+            .min_by_key(|&(_, candidates)| candidates.count_ones())
+    }
+}
+
+impl Iterator for Backtracker {
+    type Item = SearchStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.advance {
+            self.advance = false;
+            match self.next_cell() {
+                None => {
+                    self.done = true;
+                    return Some(SearchStep::Solved);
+                }
+                // a cell without candidates is a dead end,
+                // so the last cell tries its next candidate
+                Some((_, 0)) => {}
+                Some(frame) => self.stack.push(frame),
+            }
+        }
+
+        let Some((cell, candidates)) = self.stack.last_mut() else {
+            // every candidate of the first cell failed
+            self.done = true;
+            return None;
+        };
+        let cell = *cell;
+        if *candidates == 0 {
+            self.stack.pop();
+            self.board.set(cell, 0);
+            return Some(SearchStep::Backtrack { cell });
+        }
+        let digit = (1..=9).find(|digit| *candidates & (1 << (digit - 1)) != 0)?;
+        *candidates &= !(1 << (digit - 1));
+        self.board.set(cell, digit);
+        self.advance = true;
+        Some(SearchStep::Try { cell, digit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::parse_line;
+    use crate::utils::find_solution;
+
+    #[test]
+    fn test_single_empty_cell() {
+        let solution = parse_line(
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+        )
+        .expect("valid puzzle");
+        let mut board = solution;
+        board[40] = 0;
+        let steps: Vec<SearchStep> = Backtracker::new(&board).collect();
+        assert_eq!(
+            steps,
+            vec![SearchStep::Try { cell: 40, digit: 5 }, SearchStep::Solved]
+        );
+        assert_eq!(
+            Backtracker::new(&solution).collect::<Vec<_>>(),
+            vec![SearchStep::Solved]
+        );
+    }
+
+    #[test]
+    fn test_backtracking() {
+        // one of the hardest known puzzles
+        let puzzle = parse_line(
+            "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+        )
+        .expect("valid puzzle");
+        let mut solver = Backtracker::new(&puzzle);
+        let steps: Vec<SearchStep> = solver.by_ref().collect();
+        assert!(steps
+            .iter()
+            .any(|step| matches!(step, SearchStep::Backtrack { .. })));
+        assert_eq!(steps.last(), Some(&SearchStep::Solved));
+        assert_eq!(Some(*solver.board()), find_solution(&puzzle).ok());
+        assert_eq!(solver.next(), None);
+    }
+
+    #[test]
+    fn test_no_solution() {
+        // the first cell of the first row cannot hold any digit
+        let mut board: SudokuState = [0; 81];
+        for digit in 1..=8 {
+            board[digit as usize] = digit;
+        }
+        board[9] = 9;
+        let mut solver = Backtracker::new(&board);
+        assert_eq!(solver.next(), None);
+        assert_eq!(solver.board(), &board);
+
+        // a conflicting board is not searched
+        board[0] = 9;
+        assert_eq!(Backtracker::new(&board).next(), None);
+    }
+}
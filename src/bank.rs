@@ -0,0 +1,97 @@
+//! # Bank Module
+//!
+//! The `bank` module contains the curated puzzle bank,
+//! a collection of graded puzzles compiled into the app,
+//! so a good puzzle of any difficulty is available at once,
+//! without generating it.
+//!
+//! The bank is written in `puzzles/bank.txt`,
+//! a difficulty and a puzzle in the line format on every line,
+//! and encoded by the build script into records of [`RECORD_LEN`] bytes:
+//! the index of the difficulty in [`Difficulty::ALL`],
+//! followed by the 81 cells packed two per byte,
+//! the first one in the high 4 bits.
+//!
+//! All the puzzles of the bank have the classic rules.
+
+use rand::seq::IteratorRandom;
+
+use crate::app::SudokuState;
+use crate::difficulty::Difficulty;
+use crate::rng::GameRng;
+
+/// The encoded puzzle bank
+const BANK: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/bank.bin"));
+
+/// Number of bytes of every puzzle of the bank
+pub const RECORD_LEN: usize = 42;
+
+/// Decodes a record of the bank.
+///
+/// Records with an unknown difficulty are skipped.
+fn decode(record: &[u8]) -> Option<(Difficulty, SudokuState)> {
+    let difficulty = *Difficulty::ALL.get(usize::from(record[0]))?;
+    let puzzle = std::array::from_fn(|index| {
+        let pair = record[index / 2 + 1];
+        if index % 2 == 0 {
+            pair >> 4
+        } else {
+            pair & 0xF
+        }
+    });
+    Some((difficulty, puzzle))
+}
+
+/// Returns the puzzles of the bank.
+///
+/// ## Returns
+///
+/// Returns the difficulty and the puzzle of every puzzle of the bank,
+/// in the order of the bank.
+pub fn bank_puzzles() -> impl Iterator<Item = (Difficulty, SudokuState)> {
+    BANK.chunks_exact(RECORD_LEN).filter_map(decode)
+}
+
+/// Picks a random puzzle of a difficulty from the bank.
+///
+/// ## Parameters
+///
+/// - `difficulty: Difficulty` - The difficulty of the puzzle.
+/// - `rng: &mut GameRng` - The random number generator.
+///
+/// ## Returns
+///
+/// Returns the puzzle, or `None` if the bank has no puzzle of the
+/// difficulty.
+pub fn pick_bank_puzzle(difficulty: Difficulty, rng: &mut GameRng) -> Option<SudokuState> {
+    bank_puzzles()
+        .filter(|&(grade, _)| grade == difficulty)
+        .map(|(_, puzzle)| puzzle)
+        .choose(rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::grade_puzzle;
+    use sudoku::Sudoku;
+
+    #[test]
+    fn test_bank_puzzles_are_graded() {
+        assert_eq!(BANK.len() % RECORD_LEN, 0);
+        for (difficulty, puzzle) in bank_puzzles() {
+            let sudoku = Sudoku::from_bytes(puzzle).expect("valid puzzle");
+            assert!(sudoku.is_uniquely_solvable());
+            assert_eq!(grade_puzzle(&puzzle), difficulty);
+        }
+    }
+
+    #[test]
+    fn test_pick_bank_puzzle() {
+        for difficulty in Difficulty::ALL {
+            let puzzle = pick_bank_puzzle(difficulty, &mut GameRng::seeded(1))
+                .expect("the bank has puzzles of every difficulty");
+            assert!(bank_puzzles().any(|entry| entry == (difficulty, puzzle)));
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! # Bitboard Module
+//!
+//! The `bitboard` module contains the [`BitBoard`],
+//! a Sudoku board with the digits of every row, column and box kept as
+//! bitmasks alongside its cells.
+//!
+//! With the masks, the candidates of a cell and whether it conflicts with
+//! another are a few bit operations,
+//! instead of a walk over its 20 related cells.
+//! The masks are updated as the cells change, touching only the three units
+//! of the changed cell.
+//!
+//! The digits are in the same representation as
+//! [`SudokuNotes`](crate::app::SudokuNotes),
+//! where bit 0 is the digit 1 and bit 8 the digit 9.
+
+use crate::app::SudokuState;
+
+/// The mask of all the digits, from 1 to 9
+const ALL_DIGITS: u16 = 0x1FF;
+
+/// A Sudoku board with the digits of its units as bitmasks
+///
+/// The 27 units are the 9 rows, the 9 columns and the 9 boxes, in this order.
+/// For every unit, it keeps the digits it contains,
+/// and the digits it contains more than once, which are in conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitBoard {
+    cells: SudokuState,
+    digits: [u16; 27],
+    repeated: [u16; 27],
+}
+
+impl BitBoard {
+    /// Creates a bitboard from the cells of a board.
+    ///
+    /// ## Parameters
+    ///
+    /// - `cells: SudokuState` - The cells of the board.
+    #[must_use]
+    pub fn new(cells: SudokuState) -> Self {
+        let mut board = Self {
+            cells,
+            digits: [0; 27],
+            repeated: [0; 27],
+        };
+        for unit in 0..27 {
+            board.update_unit(unit);
+        }
+        board
+    }
+
+    /// Returns the cells of the board.
+    #[must_use]
+    pub const fn cells(&self) -> &SudokuState {
+        &self.cells
+    }
+
+    /// Changes the value of a cell, and updates the masks of its units.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: u8` - The index of the cell, from 0 to 80.
+    /// - `value: u8` - The new value of the cell, where 0 is an empty cell.
+    pub fn set(&mut self, index: u8, value: u8) {
+        if self.cells[index as usize] == value {
+            return;
+        }
+        self.cells[index as usize] = value;
+        for unit in units(index) {
+            self.update_unit(unit);
+        }
+    }
+
+    /// Changes the cells that differ from another board.
+    ///
+    /// ## Parameters
+    ///
+    /// - `cells: &SudokuState` - The cells of the other board.
+    ///
+    /// ## Panics
+    ///
+    /// The function will panic if cannot convert a cell index into a `u8`.
+    pub fn update(&mut self, cells: &SudokuState) {
+        for (index, &value) in cells.iter().enumerate() {
+            self.set(u8::try_from(index).expect("cannot convert from u8"), value);
+        }
+    }
+
+    /// Returns the candidates of a cell,
+    /// the digits that are in none of its units.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: u8` - The index of the cell, from 0 to 80.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the candidates as a `u16` bitmask.
+    /// Filled cells have no candidates.
+    #[must_use]
+    pub const fn candidates(&self, index: u8) -> u16 {
+        if self.cells[index as usize] != 0 {
+            return 0;
+        }
+        let [row, col, square] = units(index);
+        !(self.digits[row] | self.digits[col] | self.digits[square]) & ALL_DIGITS
+    }
+
+    /// Returns whether a cell has the same value as one of its related cells.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: u8` - The index of the cell, from 0 to 80.
+    #[must_use]
+    pub fn is_conflicting(&self, index: u8) -> bool {
+        let digit = digit_mask(self.cells[index as usize]);
+        units(index)
+            .into_iter()
+            .any(|unit| self.repeated[unit] & digit != 0)
+    }
+
+    /// Returns the cells that have the same value as one of their
+    /// related cells, in the order of the board.
+    #[must_use]
+    pub fn conflicting(&self) -> Vec<u8> {
+        (0..81)
+            .filter(|&index| self.is_conflicting(index))
+            .collect()
+    }
+
+    /// Returns whether the board is solved,
+    /// i.e. every unit has all the digits,
+    /// which fill its 9 cells exactly once.
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.digits.iter().all(|&digits| digits == ALL_DIGITS)
+    }
+
+    /// Computes the masks of a unit from its cells.
+    fn update_unit(&mut self, unit: usize) {
+        let mut digits = 0;
+        let mut repeated = 0;
+        for index in unit_cells(unit) {
+            let digit = digit_mask(self.cells[index]);
+            repeated |= digits & digit;
+            digits |= digit;
+        }
+        self.digits[unit] = digits;
+        self.repeated[unit] = repeated;
+    }
+}
+
+impl From<SudokuState> for BitBoard {
+    fn from(cells: SudokuState) -> Self {
+        Self::new(cells)
+    }
+}
+
+/// Returns the mask of a digit, or 0 for an empty cell or a value out of
+/// range.
+const fn digit_mask(value: u8) -> u16 {
+    match value {
+        1..=9 => 1 << (value - 1),
+        _ => 0,
+    }
+}
+
+/// Returns the row, column and box units of a cell.
+const fn units(index: u8) -> [usize; 3] {
+    let row = index as usize / 9;
+    let col = index as usize % 9;
+    [row, 9 + col, 18 + row / 3 * 3 + col / 3]
+}
+
+/// Returns the indices of the cells of a unit.
+fn unit_cells(unit: usize) -> [usize; 9] {
+    std::array::from_fn(|i| match unit {
+        0..=8 => unit * 9 + i,
+        9..=17 => i * 9 + (unit - 9),
+        _ => {
+            let square = unit - 18;
+            (square / 3 * 3 + i / 3) * 9 + square % 3 * 3 + i % 3
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku, find_solution, get_related_cells};
+
+    #[test]
+    fn test_unit_cells() {
+        for unit in 0..27 {
+            let cells = unit_cells(unit);
+            for index in cells {
+                let index = u8::try_from(index).expect("index fits in u8");
+                assert!(units(index).contains(&unit));
+                for other in cells {
+                    let other = u8::try_from(other).expect("index fits in u8");
+                    assert!(other == index || get_related_cells(index).contains(&other));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_candidates() {
+        let puzzle = create_sudoku();
+        let board = BitBoard::new(puzzle);
+        for index in 0..81 {
+            let expected = if puzzle[index as usize] == 0 {
+                get_related_cells(index)
+                    .into_iter()
+                    .map(|related| digit_mask(puzzle[related as usize]))
+                    .fold(ALL_DIGITS, |candidates, digit| candidates & !digit)
+            } else {
+                0
+            };
+            assert_eq!(board.candidates(index), expected);
+        }
+    }
+
+    #[test]
+    fn test_set_and_conflicts() {
+        let mut board = BitBoard::new([0; 81]);
+        board.set(0, 5);
+        board.set(8, 5);
+        assert_eq!(board.conflicting(), vec![0, 8]);
+        assert_eq!(board.candidates(4) & digit_mask(5), 0);
+
+        board.set(8, 0);
+        assert!(board.conflicting().is_empty());
+        assert_eq!(board.candidates(8) & digit_mask(5), 0);
+        assert_ne!(board.candidates(80) & digit_mask(5), 0);
+    }
+
+    #[test]
+    fn test_update_matches_new() {
+        let puzzle = create_sudoku();
+        let solution = find_solution(&puzzle).expect("the puzzle has a solution");
+        let mut board = BitBoard::new(puzzle);
+        assert!(!board.is_solved());
+
+        board.update(&solution);
+        assert_eq!(board, BitBoard::new(solution));
+        assert!(board.is_solved());
+    }
+}
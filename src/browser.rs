@@ -0,0 +1,493 @@
+//! # Browser Module
+//!
+//! The `browser` module contains thin wrappers around the Web APIs used by
+//! the Sudoku game, such as reading and writing the page URL and the local
+//! storage.
+//!
+//! The wrappers only do something when compiled to Web Assembly.
+//! On any other target they are no-ops, so that the rest of the app,
+//! and its tests, can call them unconditionally.
+//! The asynchronous wrappers, such as [`fetch_json`], run JavaScript and
+//! return an error outside of the browser.
+
+use anyhow::{Error, Result};
+use dioxus::prelude::eval;
+use serde_json::{json, Value};
+
+/// JavaScript that sends an HTTP request with a JSON body
+///
+/// The request is received as `{ method, url, headers, body }` and the
+/// response is sent back as `{ ok }`, with the JSON body if any,
+/// or `{ error }`.
+/// A `404 Not Found` response is not an error and has no body.
+/// The requests are never answered from a cache,
+/// so a sync always sees the latest remote data.
+const FETCH: &str = r#"
+    const { method, url, headers, body } = await dioxus.recv();
+    try {
+        const response = await fetch(url, {
+            method,
+            cache: "no-store",
+            headers: { "Content-Type": "application/json", ...headers },
+            body: body === null ? undefined : JSON.stringify(body),
+        });
+        if (response.status === 404) {
+            dioxus.send({ ok: null });
+        } else if (!response.ok) {
+            dioxus.send({ error: `${response.status} ${response.statusText}` });
+        } else {
+            const text = await response.text();
+            dioxus.send({ ok: text ? JSON.parse(text) : null });
+        }
+    } catch (error) {
+        dioxus.send({ error: String(error) });
+    }
+"#;
+
+/// JavaScript that posts a message to the page embedding the current one
+///
+/// The message is received as JSON and posted to any origin,
+/// since the embedding page is not known in advance.
+const POST_MESSAGE: &str = r"
+    const message = await dioxus.recv();
+    if (window.parent !== window) {
+        window.parent.postMessage(message, '*');
+    }
+";
+
+/// Returns the URL fragment of the current page, without the leading `#`.
+///
+/// ## Returns
+///
+/// Returns `Some(String)` if the page has a non-empty fragment,
+/// otherwise returns `None`.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub fn location_hash() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let hash = hash.trim_start_matches('#');
+    (!hash.is_empty()).then(|| hash.to_string())
+}
+
+/// Returns the URL fragment of the current page, without the leading `#`.
+///
+/// Always `None` outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub const fn location_hash() -> Option<String> {
+    None
+}
+
+/// Replaces the URL fragment of the current page without adding a new
+/// entry to the browser history.
+///
+/// ## Parameters
+///
+/// - `hash: &str` - The new fragment, without the leading `#`.
+#[cfg(target_arch = "wasm32")]
+pub fn replace_location_hash(hash: &str) {
+    if let Some(history) = web_sys::window().and_then(|window| window.history().ok()) {
+        let _ = history.replace_state_with_url(
+            &wasm_bindgen::JsValue::NULL,
+            "",
+            Some(&format!("#{hash}")),
+        );
+    }
+}
+
+/// Replaces the URL fragment of the current page without adding a new
+/// entry to the browser history.
+///
+/// A no-op outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub const fn replace_location_hash(_hash: &str) {}
+
+/// Returns the path of the URL of the current page.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub fn location_path() -> Option<String> {
+    web_sys::window()?.location().pathname().ok()
+}
+
+/// Returns the path of the URL of the current page.
+///
+/// Always `None` outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub const fn location_path() -> Option<String> {
+    None
+}
+
+/// Replaces the path of the URL of the current page, keeping its query and
+/// fragment, without adding a new entry to the browser history.
+///
+/// ## Parameters
+///
+/// - `path: &str` - The new path.
+#[cfg(target_arch = "wasm32")]
+pub fn replace_location_path(path: &str) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let location = window.location();
+    let (Ok(search), Ok(hash)) = (location.search(), location.hash()) else {
+        return;
+    };
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(
+            &wasm_bindgen::JsValue::NULL,
+            "",
+            Some(&format!("{path}{search}{hash}")),
+        );
+    }
+}
+
+/// Replaces the path of the URL of the current page, keeping its query and
+/// fragment, without adding a new entry to the browser history.
+///
+/// A no-op outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub const fn replace_location_path(_path: &str) {}
+
+/// Vibrates the device, if it supports the Vibration API.
+///
+/// ## Parameters
+///
+/// - `pattern: &[u32]` - Alternating vibration and pause durations,
+///   in milliseconds.
+#[cfg(target_arch = "wasm32")]
+pub fn vibrate(pattern: &[u32]) {
+    if let Some(window) = web_sys::window() {
+        let pattern: js_sys::Array = pattern
+            .iter()
+            .copied()
+            .map(wasm_bindgen::JsValue::from)
+            .collect();
+        let _ = window.navigator().vibrate_with_pattern(&pattern);
+    }
+}
+
+/// Vibrates the device, if it supports the Vibration API.
+///
+/// A no-op outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub const fn vibrate(_pattern: &[u32]) {}
+
+/// Returns whether a CSS media query matches the current page.
+///
+/// ## Parameters
+///
+/// - `query: &str` - The media query, such as
+///   `(prefers-reduced-motion: reduce)`.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub fn media_matches(query: &str) -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media(query).ok().flatten())
+        .is_some_and(|list| list.matches())
+}
+
+/// Returns whether a CSS media query matches the current page.
+///
+/// Always `false` outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub const fn media_matches(_query: &str) -> bool {
+    false
+}
+
+/// Returns the preferred language of the user, as set in the browser.
+///
+/// ## Returns
+///
+/// Returns the language as a BCP 47 tag, such as `en-US`,
+/// or `None` if it is not known.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub fn browser_language() -> Option<String> {
+    web_sys::window().and_then(|window| window.navigator().language())
+}
+
+/// Returns the preferred language of the user, as set in the browser.
+///
+/// Always `None` outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub const fn browser_language() -> Option<String> {
+    None
+}
+
+/// Sets an attribute of the root element of the current page,
+/// or removes it if there is no value.
+///
+/// ## Parameters
+///
+/// - `name: &str` - The name of the attribute.
+/// - `value: Option<&str>` - The value of the attribute, if any.
+#[cfg(target_arch = "wasm32")]
+pub fn set_root_attribute(name: &str, value: Option<&str>) {
+    let Some(root) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+    else {
+        return;
+    };
+    let _ = value.map_or_else(
+        || root.remove_attribute(name),
+        |value| root.set_attribute(name, value),
+    );
+}
+
+/// Sets an attribute of the root element of the current page,
+/// or removes it if there is no value.
+///
+/// A no-op outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub const fn set_root_attribute(_name: &str, _value: Option<&str>) {}
+
+/// Moves the keyboard focus to an element of the current page.
+///
+/// ## Parameters
+///
+/// - `id: &str` - The id of the element.
+#[cfg(target_arch = "wasm32")]
+pub fn focus_element(id: &str) {
+    use wasm_bindgen::JsCast;
+
+    let element = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(id))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+    if let Some(element) = element {
+        let _ = element.focus();
+    }
+}
+
+/// Moves the keyboard focus to an element of the current page.
+///
+/// A no-op outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub const fn focus_element(_id: &str) {}
+
+/// Reads a value from the local storage of the browser.
+///
+/// ## Parameters
+///
+/// - `key: &str` - The key of the value.
+///
+/// ## Returns
+///
+/// Returns `Some(String)` if a value is stored under `key`,
+/// otherwise returns `None`.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub fn local_storage_get(key: &str) -> Option<String> {
+    let storage = web_sys::window()?.local_storage().ok()??;
+    storage.get_item(key).ok()?
+}
+
+/// Reads a value from the local storage of the browser.
+///
+/// Always `None` outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub const fn local_storage_get(_key: &str) -> Option<String> {
+    None
+}
+
+/// Writes a value to the local storage of the browser.
+///
+/// ## Parameters
+///
+/// - `key: &str` - The key of the value.
+/// - `value: &str` - The value to store.
+#[cfg(target_arch = "wasm32")]
+pub fn local_storage_set(key: &str, value: &str) {
+    if let Some(storage) =
+        web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+    {
+        let _ = storage.set_item(key, value);
+    }
+}
+
+/// Writes a value to the local storage of the browser.
+///
+/// A no-op outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub const fn local_storage_set(_key: &str, _value: &str) {}
+
+/// Returns the current time.
+///
+/// ## Returns
+///
+/// Returns the number of milliseconds since the Unix epoch.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+/// Returns the current time.
+///
+/// ## Returns
+///
+/// Returns the number of milliseconds since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+        })
+}
+
+/// Returns the URL of the current page, without its query and fragment.
+///
+/// ## Returns
+///
+/// Returns `Some(String)` with the origin and path of the page,
+/// or `None` if it cannot be read.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub fn page_url() -> Option<String> {
+    let location = web_sys::window()?.location();
+    Some(format!(
+        "{}{}",
+        location.origin().ok()?,
+        location.pathname().ok()?
+    ))
+}
+
+/// Returns the URL of the current page, without its query and fragment.
+///
+/// Always `None` outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub const fn page_url() -> Option<String> {
+    None
+}
+
+/// Returns a parameter of the URL query of the current page.
+///
+/// ## Parameters
+///
+/// - `name: &str` - The name of the parameter.
+///
+/// ## Returns
+///
+/// Returns `Some(String)` with the value of the parameter,
+/// or `None` if the query does not have it.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub fn query_param(name: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(name)
+}
+
+/// Returns a parameter of the URL query of the current page.
+///
+/// Always `None` outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub const fn query_param(_name: &str) -> Option<String> {
+    None
+}
+
+/// Removes a parameter from the URL query of the current page without
+/// adding a new entry to the browser history.
+///
+/// ## Parameters
+///
+/// - `name: &str` - The name of the parameter.
+#[cfg(target_arch = "wasm32")]
+pub fn remove_query_param(name: &str) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let location = window.location();
+    let (Ok(path), Ok(search), Ok(hash)) =
+        (location.pathname(), location.search(), location.hash())
+    else {
+        return;
+    };
+    let Ok(params) = web_sys::UrlSearchParams::new_with_str(&search) else {
+        return;
+    };
+    params.delete(name);
+    let query = String::from(params.to_string());
+    let query = if query.is_empty() {
+        query
+    } else {
+        format!("?{query}")
+    };
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(
+            &wasm_bindgen::JsValue::NULL,
+            "",
+            Some(&format!("{path}{query}{hash}")),
+        );
+    }
+}
+
+/// Removes a parameter from the URL query of the current page without
+/// adding a new entry to the browser history.
+///
+/// A no-op outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub const fn remove_query_param(_name: &str) {}
+
+/// Sends an HTTP request with a JSON body.
+///
+/// The request bypasses the HTTP cache,
+/// and the service worker does not cache requests to other origins or
+/// with credentials.
+///
+/// ## Parameters
+///
+/// - `method: &str` - The HTTP method.
+/// - `url: &str` - The URL of the request.
+/// - `headers: Value` - A JSON object of additional headers.
+/// - `body: Value` - The JSON body, or `null` for none.
+///
+/// ## Returns
+///
+/// Returns a Result containing the JSON body of the response,
+/// or `null` if it has none or was not found.
+///
+/// ## Errors
+///
+/// Returns an error if the request fails or the server answers with an
+/// error status.
+#[allow(clippy::future_not_send)] // JavaScript handles are not `Send`
+pub async fn fetch_json(method: &str, url: &str, headers: Value, body: Value) -> Result<Value> {
+    let mut fetch = eval(FETCH);
+    fetch
+        .send(json!({ "method": method, "url": url, "headers": headers, "body": body }))
+        .map_err(|err| Error::msg(format!("Failed to send the request: {err:?}")))?;
+    let mut response = fetch
+        .recv()
+        .await
+        .map_err(|err| Error::msg(format!("Failed to send the request: {err:?}")))?;
+    if let Some(error) = response.get("error").and_then(Value::as_str) {
+        return Err(Error::msg(format!("Request failed: {error}")));
+    }
+    Ok(response["ok"].take())
+}
+
+/// Posts a message to the page embedding the current one in an `iframe`.
+///
+/// Nothing is posted if the page is not embedded.
+///
+/// ## Parameters
+///
+/// - `message: Value` - The JSON message.
+pub fn post_to_parent(message: Value) {
+    let post = eval(POST_MESSAGE);
+    if let Err(err) = post.send(message) {
+        log::error!("failed to post a message to the parent page: {err:?}");
+    }
+}
@@ -0,0 +1,140 @@
+//! # Challenge Module
+//!
+//! The `challenge` module compares the time of a game with the time of the
+//! friend who shared it as a challenge.
+//!
+//! A challenge link opens a puzzle, in the line format, with the time the
+//! challenger solved it in, in milliseconds, and their name,
+//! see [`CHALLENGE_PARAM`](crate::links::CHALLENGE_PARAM):
+//!
+//! ```text
+//! https://sudoku.example.com/?p=53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79&c=312000&n=Ana
+//! ```
+//!
+//! Once the puzzle is solved, the [`Outcome`] of the head-to-head is shown.
+
+use crate::app::SudokuState;
+use crate::leaderboard::MAX_NAME_CHARS;
+
+/// The name of a challenger who did not give one
+const NO_NAME: &str = "Your friend";
+
+/// A challenge to beat the time of a friend on a puzzle
+///
+/// - `puzzle: SudokuState`: the puzzle of the challenge.
+/// - `millis: u64`: the time the challenger solved it in, in milliseconds.
+/// - `name: String`: the name of the challenger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub puzzle: SudokuState,
+    pub millis: u64,
+    pub name: String,
+}
+
+/// The outcome of a challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Faster than the challenger, by the given milliseconds
+    Won(u64),
+    /// Slower than the challenger, by the given milliseconds
+    Lost(u64),
+    /// The same time as the challenger, to the second
+    Tied,
+}
+
+impl Challenge {
+    /// Creates a challenge from the values of a link.
+    ///
+    /// The name is trimmed and cut to [`MAX_NAME_CHARS`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: SudokuState` - The linked puzzle.
+    /// - `time: &str` - The time of the challenger, in milliseconds.
+    /// - `name: Option<&str>` - The name of the challenger, if any.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the [`Challenge`], or `None` if the time is not a number.
+    #[must_use]
+    pub fn parse(puzzle: SudokuState, time: &str, name: Option<&str>) -> Option<Self> {
+        let millis = time.trim().parse().ok()?;
+        let name: String = name
+            .unwrap_or_default()
+            .trim()
+            .chars()
+            .take(MAX_NAME_CHARS)
+            .collect();
+        let name = if name.is_empty() {
+            NO_NAME.to_string()
+        } else {
+            name
+        };
+        Some(Self {
+            puzzle,
+            millis,
+            name,
+        })
+    }
+
+    /// Returns the outcome of the challenge for a time.
+    ///
+    /// The times are compared to the second, as the timer shows them,
+    /// unless both are more precise, as in speedrun mode.
+    ///
+    /// ## Parameters
+    ///
+    /// - `millis: u64` - The time the puzzle was solved in, in milliseconds.
+    #[must_use]
+    pub const fn outcome(&self, millis: u64) -> Outcome {
+        let precise = !self.millis.is_multiple_of(1000) && !millis.is_multiple_of(1000);
+        let (own, theirs) = if precise {
+            (millis, self.millis)
+        } else {
+            (millis / 1000 * 1000, self.millis / 1000 * 1000)
+        };
+        if own < theirs {
+            Outcome::Won(theirs - own)
+        } else if own > theirs {
+            Outcome::Lost(own - theirs)
+        } else {
+            Outcome::Tied
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_sudoku;
+
+    #[test]
+    fn test_parse() {
+        let puzzle = create_sudoku();
+        let challenge = Challenge::parse(puzzle, "312000", Some(" Ana ")).expect("a challenge");
+        assert_eq!(challenge.millis, 312_000);
+        assert_eq!(challenge.name, "Ana");
+
+        let challenge = Challenge::parse(puzzle, "5", None).expect("a challenge");
+        assert_eq!(challenge.name, NO_NAME);
+        let long = "x".repeat(40);
+        let challenge = Challenge::parse(puzzle, "5", Some(&long)).expect("a challenge");
+        assert_eq!(challenge.name.len(), MAX_NAME_CHARS);
+
+        assert_eq!(Challenge::parse(puzzle, "fast", None), None);
+        assert_eq!(Challenge::parse(puzzle, "-5", None), None);
+    }
+
+    #[test]
+    fn test_outcome() {
+        let challenge = Challenge::parse(create_sudoku(), "312000", None).expect("a challenge");
+        assert_eq!(challenge.outcome(300_000), Outcome::Won(12_000));
+        assert_eq!(challenge.outcome(320_000), Outcome::Lost(8000));
+        assert_eq!(challenge.outcome(312_000), Outcome::Tied);
+        // to the second, unless both times are precise
+        assert_eq!(challenge.outcome(312_480), Outcome::Tied);
+        let challenge = Challenge::parse(create_sudoku(), "312250", None).expect("a challenge");
+        assert_eq!(challenge.outcome(312_480), Outcome::Lost(230));
+        assert_eq!(challenge.outcome(311_000), Outcome::Won(1000));
+    }
+}
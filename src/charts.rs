@@ -0,0 +1,134 @@
+//! # Charts Module
+//!
+//! The `charts` module lays out the charts of the statistics,
+//! drawn as SVG by the [`charts`](crate::components::charts) components.
+//!
+//! Every chart is drawn in a view box of [`CHART_WIDTH`] by
+//! [`CHART_HEIGHT`], scaled to fit the panel,
+//! with the values from 0 at the bottom to the highest at the top.
+
+/// Width of the view box of a chart
+pub const CHART_WIDTH: u32 = 300;
+
+/// Height of the view box of a chart
+pub const CHART_HEIGHT: u32 = 120;
+
+/// Gap between the bars of a bar chart, in the units of the view box
+const BAR_GAP: u32 = 8;
+
+/// A bar of a bar chart, in the units of the view box
+///
+/// - `x: u32`: the left of the bar.
+/// - `y: u32`: the top of the bar.
+/// - `width: u32`: the width of the bar.
+/// - `height: u32`: the height of the bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bar {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Returns the height of a value, scaled to the highest value.
+///
+/// ## Parameters
+///
+/// - `value: u64` - The value.
+/// - `max: u64` - The highest value, drawn at the top.
+fn scale(value: u64, max: u64) -> u32 {
+    if max == 0 {
+        return 0;
+    }
+    let height = u128::from(value.min(max)) * u128::from(CHART_HEIGHT) / u128::from(max);
+    u32::try_from(height).unwrap_or(CHART_HEIGHT)
+}
+
+/// Returns the points of a line chart, in the `points` format of an SVG
+/// `polyline`.
+///
+/// The values are spread evenly from the left to the right,
+/// and a single value is drawn as a flat line.
+///
+/// ## Parameters
+///
+/// - `values: &[u64]` - The values, from the left to the right.
+#[must_use]
+pub fn line_points(values: &[u64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    let ys: Vec<u32> = values
+        .iter()
+        .map(|&value| CHART_HEIGHT - scale(value, max))
+        .collect();
+    match ys.as_slice() {
+        [] => String::new(),
+        [y] => format!("0,{y} {CHART_WIDTH},{y}"),
+        _ => {
+            let last = u32::try_from(ys.len() - 1).unwrap_or(u32::MAX);
+            (0..)
+                .zip(&ys)
+                .map(|(index, y)| format!("{},{y}", index * CHART_WIDTH / last))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+}
+
+/// Returns the bars of a bar chart.
+///
+/// The bars share the width of the chart, with a gap between them.
+///
+/// ## Parameters
+///
+/// - `values: &[u64]` - The values, from the left to the right.
+#[must_use]
+pub fn bars(values: &[u64]) -> Vec<Bar> {
+    let max = values.iter().copied().max().unwrap_or(0);
+    let count = u32::try_from(values.len()).unwrap_or(u32::MAX).max(1);
+    let slot = CHART_WIDTH / count;
+    let width = slot.saturating_sub(BAR_GAP).max(1);
+    (0..)
+        .zip(values)
+        .map(|(index, &value)| {
+            let height = scale(value, max);
+            Bar {
+                x: index * slot + (slot - width) / 2,
+                y: CHART_HEIGHT - height,
+                width,
+                height,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_points() {
+        assert_eq!(line_points(&[0, 60, 120]), "0,120 150,60 300,0");
+        assert_eq!(line_points(&[5]), "0,0 300,0");
+        assert_eq!(line_points(&[0, 0]), "0,120 300,120");
+        assert_eq!(line_points(&[]), "");
+    }
+
+    #[test]
+    fn test_bars() {
+        let drawn = bars(&[10, 5, 0]);
+        assert_eq!(drawn.len(), 3);
+        assert_eq!(
+            drawn[0],
+            Bar {
+                x: 4,
+                y: 0,
+                width: 92,
+                height: CHART_HEIGHT,
+            }
+        );
+        assert_eq!(drawn[1].height, CHART_HEIGHT / 2);
+        assert_eq!(drawn[2].height, 0);
+        assert_eq!(drawn[2].x, 204);
+        assert!(bars(&[]).is_empty());
+    }
+}
@@ -0,0 +1,411 @@
+//! # CLI Module
+//!
+//! The `cli` module contains the headless command line interface of the
+//! app, available on native targets with the `--cli` flag:
+//!
+//! ```text
+//! sudoku-dioxus --cli generate [easy|medium|hard|expert] [--count N] [--seed S]
+//! sudoku-dioxus --cli solve [--solver sudoku|dlx] < puzzles.txt
+//! sudoku-dioxus --cli rate < puzzles.txt
+//! sudoku-dioxus --cli print [--candidates] < puzzles.txt
+//! sudoku-dioxus --cli batch puzzles.sdm > report.csv
+//! ```
+//!
+//! Puzzles are read from the standard input and written to the standard
+//! output in the line format, one per line,
+//! so the commands can be chained in scripts,
+//! e.g. to create a pack of puzzles.
+//! Blank lines in the input are skipped.
+//!
+//! The `batch` command checks every puzzle of a file in parallel,
+//! and writes a CSV report of whether each is solvable,
+//! has a unique solution, and its difficulty.
+
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+use std::num::NonZeroUsize;
+use std::thread;
+
+use anyhow::{Error, Result};
+
+use crate::app::SudokuState;
+use crate::difficulty::{create_puzzle_with_rng, grade_puzzle, Difficulty};
+use crate::formats::{parse_line, parse_puzzle_file, to_box_grid, to_line};
+use crate::rng::GameRng;
+use crate::utils::{create_sudoku_with_rng, find_solution_with, SolverBackend};
+
+/// The flag that runs the command line interface instead of the app
+pub const CLI_FLAG: &str = "--cli";
+
+/// The usage of the command line interface
+pub const USAGE: &str = "\
+Usage: sudoku-dioxus --cli <command>
+
+Commands:
+  generate [DIFFICULTY] [--count N] [--seed S]
+                Print N new puzzles, of any difficulty if none is given
+  solve [--solver sudoku|dlx]
+                Print the solution of every puzzle read from stdin,
+                with the solver of the `sudoku` crate or dancing links
+  rate          Print the difficulty of every puzzle read from stdin
+  print [--candidates]
+                Draw every puzzle read from stdin as a grid, with the
+                candidates of the empty cells if asked
+  batch FILE    Print a CSV report of every puzzle of a `.sdm`, `.sdk`,
+                `.ss` or newline-delimited file
+
+Puzzles are in the line format, one per line, with `.` for empty cells.";
+
+/// The header of the CSV report of the `batch` command
+pub const BATCH_HEADER: &str = "line,puzzle,solvable,unique,difficulty,error";
+
+/// A command of the command line interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Generate puzzles, of a difficulty if any,
+    /// from a seed if any
+    Generate {
+        difficulty: Option<Difficulty>,
+        count: usize,
+        seed: Option<u64>,
+    },
+    /// Solve the puzzles read from the input, with a solver backend
+    Solve { solver: SolverBackend },
+    /// Grade the puzzles read from the input
+    Rate,
+    /// Draw the puzzles read from the input as grids,
+    /// with the candidates of their empty cells if asked
+    Print { candidates: bool },
+    /// Check every puzzle of a file, and report them as CSV
+    Batch { path: String },
+}
+
+impl Command {
+    /// Parses a command from the command line arguments.
+    ///
+    /// ## Parameters
+    ///
+    /// - `args: &[String]` - The arguments after the [`CLI_FLAG`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the command is unknown,
+    /// or if one of its arguments is invalid.
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let Some((name, rest)) = args.split_first() else {
+            return Err(Error::msg("Missing command"));
+        };
+        match name.as_str() {
+            "generate" => {
+                let mut difficulty = None;
+                let mut count = 1;
+                let mut seed = None;
+                let mut rest = rest.iter();
+                while let Some(arg) = rest.next() {
+                    match arg.as_str() {
+                        "--count" => count = option_value(arg, rest.next())?,
+                        "--seed" => seed = Some(option_value(arg, rest.next())?),
+                        _ => difficulty = Some(arg.parse()?),
+                    }
+                }
+                Ok(Self::Generate {
+                    difficulty,
+                    count,
+                    seed,
+                })
+            }
+            "solve" => match rest {
+                [] => Ok(Self::Solve {
+                    solver: SolverBackend::DEFAULT,
+                }),
+                [flag, solver] if flag == "--solver" => Ok(Self::Solve {
+                    solver: solver.parse()?,
+                }),
+                _ => Err(Error::msg(format!("Unexpected arguments to {name}"))),
+            },
+            "rate" if rest.is_empty() => Ok(Self::Rate),
+            "print" => match rest {
+                [] => Ok(Self::Print { candidates: false }),
+                [flag] if flag == "--candidates" => Ok(Self::Print { candidates: true }),
+                _ => Err(Error::msg(format!("Unexpected arguments to {name}"))),
+            },
+            "batch" => match rest {
+                [path] => Ok(Self::Batch { path: path.clone() }),
+                _ => Err(Error::msg("Expected the path of a puzzle file")),
+            },
+            "rate" => Err(Error::msg(format!("Unexpected arguments to {name}"))),
+            _ => Err(Error::msg(format!("Unknown command: {name}"))),
+        }
+    }
+
+    /// Runs the command.
+    ///
+    /// ## Parameters
+    ///
+    /// - `input: impl BufRead` - The puzzles to read, one per line,
+    ///   usually the standard input.
+    /// - `output: impl Write` - Where to write the results, one per line,
+    ///   usually the standard output.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the input or output fails,
+    /// or if a puzzle cannot be read or solved,
+    /// with the number of its line.
+    pub fn run(self, input: impl BufRead, mut output: impl Write) -> Result<()> {
+        match self {
+            Self::Generate {
+                difficulty,
+                count,
+                seed,
+            } => {
+                let mut rng = seed.map_or_else(GameRng::new, GameRng::seeded);
+                for _ in 0..count {
+                    let puzzle = match difficulty {
+                        Some(difficulty) => create_puzzle_with_rng(difficulty, &mut rng),
+                        None => create_sudoku_with_rng(&mut rng),
+                    };
+                    writeln!(output, "{}", to_line(&puzzle, '.'))?;
+                }
+                Ok(())
+            }
+            Self::Solve { solver } => for_each_puzzle(input, |puzzle| {
+                let solution = find_solution_with(&puzzle, solver)?;
+                Ok(writeln!(output, "{}", to_line(&solution, '.'))?)
+            }),
+            Self::Rate => for_each_puzzle(input, |puzzle| {
+                Ok(writeln!(output, "{}", grade_puzzle(&puzzle))?)
+            }),
+            Self::Print { candidates } => {
+                let mut first = true;
+                for_each_puzzle(input, |puzzle| {
+                    // the grids are separated by a blank line
+                    if !std::mem::take(&mut first) {
+                        writeln!(output)?;
+                    }
+                    Ok(writeln!(output, "{}", to_box_grid(&puzzle, candidates))?)
+                })
+            }
+            Self::Batch { path } => {
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|err| Error::msg(format!("Cannot read {path}: {err}")))?;
+                writeln!(output, "{BATCH_HEADER}")?;
+                for row in batch_report(&path, &contents) {
+                    writeln!(output, "{row}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Calls a function with every puzzle of the input, one per line.
+///
+/// Blank lines are skipped, and errors are prefixed with the line number.
+fn for_each_puzzle(
+    input: impl BufRead,
+    mut f: impl FnMut(SudokuState) -> Result<()>,
+) -> Result<()> {
+    for (number, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        parse_line(&line)
+            .and_then(&mut f)
+            .map_err(|err| Error::msg(format!("Line {}: {err}", number + 1)))?;
+    }
+    Ok(())
+}
+
+/// Checks every puzzle of a file, in parallel.
+///
+/// Files with one puzzle per line, such as `.sdm` files, are checked line by
+/// line, so an invalid line is reported instead of failing the whole file.
+/// Other files are parsed with [`parse_puzzle_file`].
+///
+/// ## Parameters
+///
+/// - `file_name: &str` - The name of the file, used to detect its format.
+/// - `contents: &str` - The contents of the file.
+///
+/// ## Returns
+///
+/// Returns a CSV row for every puzzle, in the order of the file,
+/// with the columns of the [`BATCH_HEADER`].
+///
+/// ## Panics
+///
+/// The function will panic if one of the threads checking the puzzles
+/// panics.
+#[must_use]
+pub fn batch_report(file_name: &str, contents: &str) -> Vec<String> {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase());
+    let entries: Vec<(usize, Result<SudokuState>)> =
+        if matches!(extension.as_deref(), Some("sdk" | "ss")) {
+            match parse_puzzle_file(file_name, contents) {
+                Ok(puzzles) => puzzles.into_iter().map(|puzzle| (1, Ok(puzzle))).collect(),
+                Err(err) => vec![(1, Err(err))],
+            }
+        } else {
+            contents
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(number, line)| (number + 1, parse_line(line)))
+                .collect()
+        };
+
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk = entries.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        // every thread is spawned before any is joined
+        #[allow(clippy::needless_collect)]
+        let handles: Vec<_> = entries
+            .chunks(chunk)
+            .map(|entries| {
+                scope.spawn(move || {
+                    entries
+                        .iter()
+                        .map(|(line, puzzle)| report_row(*line, puzzle))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("a batch thread panicked"))
+            .collect()
+    })
+}
+
+/// Checks a puzzle, and returns its CSV row.
+fn report_row(line: usize, puzzle: &Result<SudokuState>) -> String {
+    let mut row = format!("{line},");
+    match puzzle {
+        Ok(puzzle) => {
+            let solutions = SolverBackend::DEFAULT.count_solutions(puzzle, 2);
+            // only puzzles with a unique solution have a difficulty
+            let difficulty = if solutions == 1 {
+                grade_puzzle(puzzle).to_string()
+            } else {
+                String::new()
+            };
+            let _ = write!(
+                row,
+                "{},{},{},{difficulty},",
+                to_line(puzzle, '.'),
+                solutions > 0,
+                solutions == 1
+            );
+        }
+        Err(err) => {
+            let _ = write!(row, ",false,false,,{}", csv_field(&err.to_string()));
+        }
+    }
+    row
+}
+
+/// Quotes a CSV field if it has a comma, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Parses the value of a command line option.
+fn option_value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| Error::msg(format!("Invalid value for {option}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EASY: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    fn run(args: &[&str], input: &str) -> Result<String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        let mut output = vec![];
+        Command::parse(&args)?.run(input.as_bytes(), &mut output)?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = ["generate", "hard", "--count", "3", "--seed", "7"].map(String::from);
+        assert_eq!(
+            Command::parse(&args).expect("valid command"),
+            Command::Generate {
+                difficulty: Some(Difficulty::Hard),
+                count: 3,
+                seed: Some(7),
+            }
+        );
+        assert!(Command::parse(&["generate".into(), "--count".into()]).is_err());
+        assert!(Command::parse(&["solve".into(), "now".into()]).is_err());
+        assert!(Command::parse(&["play".into()]).is_err());
+        assert!(Command::parse(&[]).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let output = run(&["generate", "--count", "2", "--seed", "1"], "").expect("generate");
+        let puzzles: Vec<&str> = output.lines().collect();
+        assert_eq!(puzzles.len(), 2);
+        assert!(puzzles.iter().all(|line| parse_line(line).is_ok()));
+        assert_eq!(
+            output,
+            run(&["generate", "--count", "2", "--seed", "1"], "").expect("generate")
+        );
+    }
+
+    #[test]
+    fn test_batch_report() {
+        let mut conflicting = EASY.to_string();
+        conflicting.replace_range(2..3, "5");
+        let contents = format!("{EASY}\n\n{conflicting}\n{}\n12,3\n", ".".repeat(81));
+        let rows = batch_report("puzzles.sdm", &contents);
+        assert_eq!(
+            rows,
+            vec![
+                format!("1,{EASY},true,true,Easy,"),
+                format!("3,{conflicting},false,false,,"),
+                format!("4,{},true,false,,", ".".repeat(81)),
+                "5,,false,false,,\"Invalid cell character ','\"".to_string(),
+            ]
+        );
+        assert_eq!(
+            Command::parse(&["batch".into(), "puzzles.sdm".into()]).expect("valid command"),
+            Command::Batch {
+                path: "puzzles.sdm".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_solve_and_rate() {
+        let input = format!("{EASY}\n\n{EASY}\n");
+        let solved = run(&["solve"], &input).expect("solve");
+        assert_eq!(solved.lines().count(), 2);
+        assert!(!solved.contains('.'));
+        assert_eq!(
+            run(&["solve", "--solver", "dlx"], &input).expect("solve"),
+            solved
+        );
+        assert!(run(&["solve", "--solver", "guess"], &input).is_err());
+        assert_eq!(run(&["rate"], &input).expect("rate"), "Easy\nEasy\n");
+        let printed = run(&["print", "--candidates"], &input).expect("print");
+        assert_eq!(printed.lines().count(), 27);
+
+        let err = run(&["solve"], "123\n").expect_err("invalid puzzle");
+        assert!(err.to_string().starts_with("Line 1:"));
+    }
+}
@@ -0,0 +1,159 @@
+//! # Analytics Module
+//!
+//! The `analytics` module contains the [`use_track`] hook, which records
+//! gameplay events to the sink picked in the settings,
+//! and the [`AnalyticsSettings`] component to pick it.
+//!
+//! Every event is also kept in the [`SessionEvents`],
+//! listed by the [`SessionLogPanel`] that [`SessionLogButton`] toggles.
+//!
+//! See also: [`analytics`](crate::analytics).
+
+use dioxus::prelude::*;
+
+use crate::analytics::{track, AnalyticsSink, Event, SessionLog};
+use crate::browser::now;
+use crate::components::buttons::ActionButton;
+use crate::components::modal::Modal;
+use crate::components::settings::UserSettings;
+use crate::utils::format_duration;
+
+/// Shared State for the events of the session
+///
+/// Represents globally across the app the [`SessionLog`] of the notable
+/// events since the app was opened.
+#[derive(Debug, Clone)]
+pub struct SessionEvents(pub SessionLog);
+
+/// Shared State for the session log panel
+///
+/// Represents globally across the app if the [`SessionLogPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct SessionLogOpen(pub bool);
+
+/// Hook that returns a function recording a gameplay [`Event`].
+///
+/// Events are recorded to the [`AnalyticsSink`] of the current settings,
+/// so nothing is recorded unless the user opted in.
+/// They are always kept in the [`SessionEvents`],
+/// which never leave the app.
+pub fn use_track() -> impl FnMut(Event) + Copy {
+    // Unpack shared states
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut session = use_context::<Signal<SessionEvents>>();
+
+    move |event: Event| {
+        session.write().0.push(now(), event.clone());
+        track(&settings.peek().0.analytics, event);
+    }
+}
+
+/// Component to render the analytics settings
+///
+/// This component renders a picker of the [`AnalyticsSink`],
+/// with the URL of the endpoint if events are sent over HTTP.
+#[component]
+pub fn AnalyticsSettings() -> Element {
+    // Unpack shared states
+    let mut settings = use_context::<Signal<UserSettings>>();
+
+    let sink = settings.read().0.analytics.clone();
+
+    rsx!(
+        label {
+            "Anonymous analytics"
+            select {
+                onchange: move |event| {
+                    settings.write().0.analytics = match event.value().as_str() {
+                        "log" => AnalyticsSink::Log,
+                        "http" => AnalyticsSink::Http { url: String::new() },
+                        _ => AnalyticsSink::Off,
+                    };
+                },
+                option { value: "off", selected: sink == AnalyticsSink::Off, "Off" }
+                option { value: "log", selected: sink == AnalyticsSink::Log, "Local log" }
+                option {
+                    value: "http",
+                    selected: matches!(sink, AnalyticsSink::Http { .. }),
+                    "HTTP endpoint"
+                }
+            }
+        }
+        if let AnalyticsSink::Http { url } = &sink {
+            label {
+                "Endpoint URL"
+                input {
+                    r#type: "url",
+                    value: "{url}",
+                    onchange: move |event| {
+                        settings.write().0.analytics = AnalyticsSink::Http {
+                            url: event.value().trim().to_string(),
+                        };
+                    },
+                }
+            }
+        }
+    )
+}
+
+/// Component to render a session log button
+///
+/// This component renders a "Session log" button that opens and closes the
+/// [`SessionLogPanel`].
+#[component]
+pub fn SessionLogButton() -> Element {
+    // Unpack shared states
+    let mut session_log_open = use_context::<Signal<SessionLogOpen>>();
+
+    rsx!(ActionButton {
+        icon: "session-log",
+        label: "Session log",
+        tooltip: "Review what happened since the app was opened",
+        onclick: move |()| {
+            let open = session_log_open.read().0;
+            session_log_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the session log panel
+///
+/// This component renders, in a [`Modal`], the [`SessionEvents`],
+/// the latest first, with the time since the session started.
+#[component]
+pub fn SessionLogPanel() -> Element {
+    // Unpack shared states
+    let mut session_log_open = use_context::<Signal<SessionLogOpen>>();
+    let session = use_context::<Signal<SessionEvents>>();
+
+    if !session_log_open.read().0 {
+        return None;
+    }
+    let log = &session.read().0;
+
+    rsx!(Modal {
+        class: "session-log",
+        label: "Session log",
+        onclose: move |()| session_log_open.write().0 = false,
+        span { "Session log" }
+        if log.events.is_empty() {
+            span { "Nothing happened yet" }
+        } else {
+            ol {
+                reversed: true,
+                for (index, entry) in log.events.iter().enumerate().rev() {
+                    li {
+                        key: "{index}",
+                        time { "{format_duration(entry.at.saturating_sub(log.started_at))}" }
+                        " {entry.event}"
+                    }
+                }
+            }
+        }
+        button {
+            class: "input",
+            onclick: move |_| session_log_open.write().0 = false,
+            "Close"
+        }
+    })
+}
@@ -0,0 +1,140 @@
+//! # Auth Module
+//!
+//! The `auth` module contains the shared state of the signed in account and
+//! the [`AccountSettings`] component, which lives in the settings panel.
+//!
+//! See also: [`auth`](crate::auth).
+
+use dioxus::prelude::*;
+
+use crate::app::Hydrated;
+use crate::auth::{request_magic_link, store_session, verify_magic_link, Session, LOGIN_PARAM};
+use crate::browser::{page_url, query_param, remove_query_param};
+use crate::components::profiles::ActiveProfile;
+use crate::components::settings::UserSettings;
+
+/// Shared State for the signed in account
+///
+/// Represents globally across the app the [`Session`] of the active
+/// profile, or `None` if it is signed out.
+#[derive(Debug, Clone)]
+pub struct UserSession(pub Option<Session>);
+
+/// Shared State for the status of the sign in
+///
+/// Represents globally across the app the outcome of the last sign in step,
+/// as a message for the user.
+#[derive(Debug, Clone)]
+pub struct AuthStatus(pub Option<String>);
+
+/// Hook that signs in with the magic link the app was opened from, if any.
+///
+/// The magic link token is removed from the URL,
+/// and the session is kept for the active profile.
+/// The URL is read once the settings are loaded, see [`Hydrated`].
+pub fn use_magic_link_login() {
+    // Unpack shared states
+    let hydrated = use_context::<Signal<Hydrated>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut session = use_context::<Signal<UserSession>>();
+    let mut status = use_context::<Signal<AuthStatus>>();
+
+    use_effect(move || {
+        if !hydrated.read().0 {
+            return;
+        }
+        let Some(token) = query_param(LOGIN_PARAM) else {
+            return;
+        };
+        remove_query_param(LOGIN_PARAM);
+        let server = settings.peek().0.server_url.clone();
+        if server.is_empty() {
+            status.write().0 = Some("Set the server to sign in".to_string());
+            return;
+        }
+        spawn(async move {
+            match verify_magic_link(&server, &token).await {
+                Ok(account) => {
+                    store_session(profile.peek().0, Some(&account));
+                    status.write().0 = Some(format!("Signed in as {}", account.email));
+                    session.write().0 = Some(account);
+                }
+                Err(err) => status.write().0 = Some(format!("Sign in failed: {err}")),
+            }
+        });
+    });
+}
+
+/// Component to render the account settings
+///
+/// This component renders the URL of the server and,
+/// once it is set, the signed in account with a button to sign out,
+/// or a form to request a magic link to sign in.
+#[component]
+pub fn AccountSettings() -> Element {
+    // Unpack shared states
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut settings = use_context::<Signal<UserSettings>>();
+    let mut session = use_context::<Signal<UserSession>>();
+    let mut status = use_context::<Signal<AuthStatus>>();
+    let mut email = use_signal(String::new);
+
+    let server = settings.read().0.server_url.clone();
+    let account = session.read().0.clone();
+
+    rsx!(
+        label {
+            "Server URL"
+            input {
+                r#type: "url",
+                placeholder: "None",
+                value: "{server}",
+                onchange: move |event| settings.write().0.server_url = event.value().trim().to_string(),
+            }
+        }
+        if let Some(account) = account {
+            label {
+                "Signed in as {account.email}"
+                button {
+                    class: "input",
+                    onclick: move |_| {
+                        store_session(profile.peek().0, None);
+                        session.write().0 = None;
+                        status.write().0 = None;
+                    },
+                    "Sign out"
+                }
+            }
+        } else if !server.is_empty() {
+            label {
+                input {
+                    r#type: "email",
+                    placeholder: "Email",
+                    value: "{email}",
+                    oninput: move |event| email.set(event.value()),
+                }
+                button {
+                    class: "input",
+                    onclick: move |_| {
+                        let server = settings.peek().0.server_url.clone();
+                        let address = email.read().trim().to_string();
+                        let redirect = page_url().unwrap_or_default();
+                        spawn(async move {
+                            status.write().0 = Some(
+                                match request_magic_link(&server, &address, &redirect).await {
+                                    Ok(()) => format!("Check {address} for a sign in link"),
+                                    Err(err) => format!("Sign in failed: {err}"),
+                                },
+                            );
+                        });
+                    },
+                    "Sign in"
+                }
+            }
+        }
+        if let Some(status) = status.read().0.clone() {
+            span { "{status}" }
+        }
+    )
+}
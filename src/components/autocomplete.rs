@@ -0,0 +1,108 @@
+//! # Autocomplete Module
+//!
+//! The `autocomplete` module contains the [`AutoComplete`] offer,
+//! which finishes the endgame for the player once only naked singles are
+//! left, when the [`auto_complete`](crate::settings::Settings::auto_complete)
+//! setting is on.
+
+use dioxus::prelude::*;
+
+use crate::app::SudokuState;
+use crate::components::board::use_dispatch;
+use crate::components::generator::WAIT;
+use crate::components::settings::UserSettings;
+use crate::game::{GameAction, GameState};
+use crate::logic::{solve_logically, Technique};
+use crate::utils::get_all_conflicting_cells;
+
+/// Milliseconds waited between the cells filled when finishing the board
+const FILL_DELAY: u32 = 120;
+
+/// Returns the cells left to fill, and their values,
+/// if the board can be finished with naked singles alone.
+fn singles_left(board: &SudokuState) -> Vec<(u8, u8)> {
+    if !board.contains(&0) || !get_all_conflicting_cells(board).is_empty() {
+        return vec![];
+    }
+    let solution = solve_logically(board, &[Technique::NakedSingle]);
+    if !solution.solved {
+        return vec![];
+    }
+    solution
+        .deductions
+        .iter()
+        .filter_map(|deduction| deduction.placement)
+        .collect()
+}
+
+/// Component to render the offer to finish the board
+///
+/// This component offers, once the board can be finished with naked
+/// singles alone, to fill them automatically.
+/// The cells are filled one after the other, each as its own move,
+/// with a short delay between them unless the motion is reduced.
+/// Filling stops if the board is changed meanwhile.
+///
+/// The offer can be dismissed, and is not made again for the same puzzle.
+#[component]
+pub fn AutoComplete() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut dispatch = use_dispatch();
+    // the puzzle the offer was dismissed for
+    let mut dismissed = use_signal(|| None::<SudokuState>);
+    let mut finishing = use_signal(|| false);
+
+    let placements = use_memo(move || singles_left(&game.read().current()));
+
+    let initial = game.read().initial;
+    if !settings.read().0.auto_complete
+        || finishing()
+        || dismissed() == Some(initial)
+        || placements.read().is_empty()
+    {
+        return None;
+    }
+
+    let finish = move |_| {
+        finishing.set(true);
+        let delay = if settings.peek().0.reduce_motion {
+            0
+        } else {
+            FILL_DELAY
+        };
+        let placements = placements.peek().clone();
+        spawn(async move {
+            let mut expected = game.peek().current();
+            for (index, value) in placements {
+                if delay > 0 {
+                    let mut wait = eval(WAIT);
+                    if wait.send(delay.into()).is_err() || wait.recv().await.is_err() {
+                        break;
+                    }
+                }
+                // the board was changed meanwhile
+                if game.peek().current() != expected {
+                    break;
+                }
+                dispatch(GameAction::Place { index, value });
+                expected[index as usize] = value;
+            }
+            finishing.set(false);
+        });
+    };
+
+    rsx!(div {
+        class: "auto-complete",
+        role: "status",
+        span { "Only naked singles are left." }
+        button { class: "input", onclick: finish, "Finish" }
+        button {
+            class: "input",
+            aria_label: "Dismiss the offer to finish",
+            onclick: move |_| dismissed.set(Some(initial)),
+            "×"
+        }
+    })
+}
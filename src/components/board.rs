@@ -10,300 +10,343 @@
 //! which is the central element of the Sudoku game interface,
 //! displaying the puzzle to the user and allowing interaction
 //!  with individual cells.
+//!
+//! The game is a single [`GameState`] shared state,
+//! only changed with a [`GameAction`] through [`use_dispatch`].
 
 use dioxus::prelude::*;
 
-use crate::app::SudokuState;
+use crate::animation::{reveal, transitions, CellTransition};
+use crate::bitboard::BitBoard;
+use crate::components::analytics::{SessionLogOpen, SessionLogPanel};
+use crate::components::autocomplete::AutoComplete;
+use crate::components::bookmarks::{Bookmarks, BookmarksOpen, BookmarksPanel};
 use crate::components::cell::Cell;
-use crate::utils::{
-    create_sudoku, find_changed_cell, get_all_conflicting_cells, get_class, get_hint,
-    get_related_cells, remove_conflicting_cells,
+use crate::components::controls::{ControlBarConfig, ControlPanel, ReplacedGames};
+use crate::components::daily::{DailyOpen, DailyPanel};
+use crate::components::embed::Embedded;
+use crate::components::export::{ExportOpen, ExportPanel};
+use crate::components::favorites::{FavoritesOpen, FavoritesPanel};
+use crate::components::games::GamesPanel;
+use crate::components::hotseat::{use_hot_seat, HotSeatBar, HotSeatGame};
+use crate::components::import::{
+    FileImport, ImportedPuzzles, PasteImport, PhotoImport, RecognizedPuzzle,
 };
-
-/// Shared State for clicked [`Cell`]
-///
-/// Represents globally across the app which cell is clicked by id.
-#[derive(Debug, Clone)]
-pub struct Clicked(pub u8);
-
-/// Shared State for mutable clicked [`Cell`]
-///
-/// Represents globally across the app if the clicke cell is mutable.
-/// Imutable cells are the one created by the app at the initial puzzle creation.
-#[derive(Debug, Clone)]
-pub struct Mutable(pub bool);
-
-/// Shared State for clicked [`Cell`]'s related [`Cell`]s
+use crate::components::keybindings::{use_keybindings, ShortcutsOpen, ShortcutsPanel};
+use crate::components::kids::use_kids_guide;
+use crate::components::library::{LibraryOpen, LibraryPanel};
+use crate::components::menu::{AboutOpen, AboutPanel, Menu};
+use crate::components::packs::{PacksOpen, PacksPanel};
+use crate::components::settings::{SettingsOpen, SettingsPanel, UserSettings};
+use crate::components::speedrun::{use_personal_bests, use_progress_log, GhostRace};
+use crate::components::splits::use_split_times;
+use crate::components::stats::{StatsOpen, StatsPanel};
+use crate::components::status::StatusBar;
+use crate::components::techniques::{
+    use_explanation, Explanation, TechniqueCaption, TechniqueOverlay,
+};
+use crate::components::theme::use_pinch_zoom;
+use crate::components::toast::ToastList;
+use crate::components::undo_tree::{UndoTreeOpen, UndoTreePanel};
+use crate::components::victory::{use_victory, VictoryPanel};
+use crate::components::weekly::{WeeklyOpen, WeeklyPanel};
+use crate::game::{GameAction, GameState};
+use crate::hotseat::Player;
+use crate::interop::use_js_api;
+use crate::techniques::TechniqueStep;
+use crate::utils::{find_solution, get_all_conflicting_cells, get_class, get_conflicting_cells};
+use crate::validation::{explain_conflict, validate_board};
+
+/// Derived State for clicked [`Cell`]'s related [`Cell`]s
 ///
 /// Represents globally across the app which cells, by id,
 /// are related to the clicked [`Cell`].
+/// It is a `Memo` derived from the clicked cell of the [`GameState`],
+/// so it cannot be out of date.
 ///
 /// [`Cell`]s are related if they share the same row, column, or sub-grid in
 /// a Sudoku board.
 ///
-/// See also: [`get_related_cells`].
-#[derive(Debug, Clone)]
+/// See also: [`get_related_cells`](crate::utils::get_related_cells).
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Related(pub Vec<u8>);
 
-/// Shared State for clicked [`Cell`]'s conficts
+/// Derived State for the conflicting [`Cell`]s
 ///
 /// Represents globally across the app which cells, by id,
-/// are in conflict to the clicked [`Cell`].
+/// are in conflict with another cell.
+/// It is a `Memo` derived from the current board of the [`GameState`],
+/// so it cannot be out of date.
 ///
 /// [`Cell`]s are in conflict if they share the same row, column, or sub-grid in
 /// a Sudoku board and have the same value.
 ///
-/// See also: [`get_related_cells`]
-/// and [`get_conflicting_cells`](crate::utils::get_conflicting_cells).
-#[derive(Debug, Clone)]
+/// See also: [`get_all_conflicting_cells`].
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Conflicting(pub Vec<u8>);
 
-/// Shared State for the initial [`SudokuBoard`] puzzle
+/// Shared State for the digit picked on the number pad
+///
+/// Represents globally across the app which digit is stamped in the cells
+/// the user clicks, in the number-first
+/// [`InputMode`](crate::settings::InputMode),
+/// or the last digit entered in the cell-first one.
+/// The digit 0 erases the cells.
+///
+/// The digit stays highlighted on the number pad and on the board until
+/// another one is chosen.
 #[derive(Debug, Clone)]
-pub struct InitialSudokuPuzzle(pub SudokuState);
+pub struct ActiveDigit(pub Option<u8>);
 
-impl InitialSudokuPuzzle {
-    #[must_use]
-    pub fn new() -> Self {
-        Self(create_sudoku())
-    }
-}
-impl Default for InitialSudokuPuzzle {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+/// Shared State for the cell taking notes
+///
+/// Represents globally across the app which cell, by id,
+/// gets the next digit entered as a note instead of a value.
+/// A cell is switched to note entry by double-clicking it.
+#[derive(Debug, Clone)]
+pub struct NoteCell(pub Option<u8>);
 
-/// Shared State for the current [`SudokuBoard`] puzzle
+/// Shared State for the heatmap overlay
+///
+/// Represents globally across the app if the empty cells are colored by
+/// their number of candidates, so the most constrained ones stand out.
 #[derive(Debug, Clone)]
-pub struct SudokuPuzzle(pub SudokuState);
+pub struct Heatmap(pub bool);
 
-/// Shared State for the all the [`SudokuState`] across user moves
+/// Shared State for the generation of a new puzzle
+///
+/// Represents globally across the app if a new puzzle is being generated,
+/// which can take a while for the hardest difficulties.
+/// Meanwhile, the board is covered by a loading indicator.
 #[derive(Debug, Clone)]
-pub struct SudokuPuzzleMoves(pub Vec<SudokuState>);
+pub struct Generating(pub bool);
 
-/// Component Props for [`NumberButton`]
+/// Shared State for the number of hints used in the current game
 ///
-/// - `number: u8`: the value to be rendered in the button and also the value
-///   that dictates what the button will write to a mutable cell.
-#[derive(Props, Copy, Clone, PartialEq, Eq)]
-struct NumberButtonProps {
-    number: u8,
-}
+/// Hints are limited by the
+/// [`hint_budget`](crate::settings::Settings::hint_budget) setting.
+#[derive(Debug, Clone)]
+pub struct HintsUsed(pub u32);
 
-/// Component to render a number button
+/// Hook that returns a function applying a [`GameAction`] to the game.
 ///
-/// This component renders buttons that are used to interact with the board.
-/// They work by assigning value to a mutable cell.
+/// The game is the [`GameState`] shared state:
+/// the puzzle, the moves on every branch, the notes, the clicked cell and
+/// the trial under way.
+/// The action is applied with [`GameState::apply`] to a copy of the game,
+/// which replaces it only if it changed,
+/// so what reads the game is only rendered again after a change.
+/// Every change to the game goes through this function,
+/// and the [`Related`] and [`Conflicting`] cells are derived from it.
+/// During a [`HotSeatGame`], the actions it does not allow are ignored,
+/// see [`HotSeat::allows`](crate::hotseat::HotSeat::allows).
 ///
-/// The number 0 is a special button that renders as a delete icon.
-/// It adds the number 0 to a mutable cell which represents an empty cell.
-#[component]
-fn NumberButton(props: NumberButtonProps) -> Element {
-    let number = props.number;
-    let class: &str = match number {
-        0 => "input icon delete",
-        _ => "input number",
-    };
-
+/// The function returns whether the action was applied.
+pub fn use_dispatch() -> impl FnMut(GameAction) -> bool + Copy {
     // Unpack shared states
-    let mut moves = use_context::<Signal<SudokuPuzzleMoves>>();
-    let mut sudoku = use_context::<Signal<SudokuPuzzle>>();
-    let mut conflicting = use_context::<Signal<Conflicting>>();
-    let clicked = use_context::<Signal<Clicked>>().read().0;
-    let mutable = use_context::<Signal<Mutable>>().read().0;
-
-    rsx!(
-        button {
-            class: "{class}",
-            onclick: move |_| {
-                // if the value is the same
-                if sudoku.read().0[clicked as usize] == number {
-                }
-                // if the cell is mutable
-                 else if mutable {
-                    // chaging the clicked cell value to the button number
-                    sudoku.write().0[clicked as usize] = number;
-                    let current_sudoku = sudoku.read().0;
-                    moves.write().0.push(current_sudoku);
-
-                    // conflicting logic
-                    let new_conflicting = get_all_conflicting_cells(&current_sudoku);
-                    conflicting.write().0 = new_conflicting;
-                }
-            },
-            "{number}"
+    let mut game = use_context::<Signal<GameState>>();
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+
+    move |action: GameAction| {
+        if hot_seat
+            .peek()
+            .0
+            .as_ref()
+            .is_some_and(|game| !game.allows(&action))
+        {
+            return false;
         }
-    )
+        let mut next = game.peek().clone();
+        let applied = next.apply(action);
+        if *game.peek() != next {
+            game.set(next);
+        }
+        applied
+    }
 }
 
-/// Component to render a new button
+/// The highlights of a [`Cell`]
 ///
-/// This component renders a "New Game" button.
-/// When activated, all current state is dropped and the board is drawn with a
-/// fresh new puzzle for the user.
-#[component]
-fn NewButton() -> Element {
-    // Unpack shared states
-    let mut initial_sudoku = use_context::<Signal<InitialSudokuPuzzle>>();
-    let mut moves = use_context::<Signal<SudokuPuzzleMoves>>();
-    let mut sudoku = use_context::<Signal<SudokuPuzzle>>();
-    let mut clicked = use_context::<Signal<Clicked>>();
-    let mut mutable = use_context::<Signal<Mutable>>();
-    let mut related = use_context::<Signal<Related>>();
-    let mut conflicting = use_context::<Signal<Conflicting>>();
-
-    rsx!(button {
-        class: "input icon new",
-        onclick: move |_| {
-            // resetting the board with a new puzzle
-            initial_sudoku.write().0 = create_sudoku();
-            moves.write().0 = vec![initial_sudoku.read().0];
-            sudoku.write().0 = initial_sudoku.read().0;
-            // resetting the clicked cell
-            clicked.write().0 = 90;
-            // resetting the mutable cell
-            mutable.write().0 = true;
-            // resetting the related list
-            related.write().0 = vec![];
-            // resetting the conflicting list
-            conflicting.write().0 = vec![];
-        }
-    })
+/// - `selected: bool`: if the cell is the clicked cell.
+/// - `related: bool`: if the cell is one of the [`Related`] cells.
+/// - `conflicting: bool`: if the cell is one of the [`Conflicting`] cells.
+/// - `same_digit: bool`: if the cell has the [`ActiveDigit`].
+/// - `noting: bool`: if the cell is the [`NoteCell`].
+/// - `heat: u8`: the number of candidates of the cell, if it is empty and
+///   the [`Heatmap`] is on, otherwise 0.
+/// - `trial: bool`: if the cell was changed since the trial started,
+///   see [`GameState::trial_cells`].
+/// - `owner: Option<Player>`: the player who filled the cell,
+///   in the pass-and-play mode.
+/// - `transition: Option<CellTransition>`: how the value of the cell just
+///   changed, from the [`CellTransitions`], if it is being animated.
+/// - `focusable: bool`: if the cell is in the tab order,
+///   i.e. it is the clicked cell, or the first one if none is clicked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct CellHighlight {
+    pub selected: bool,
+    pub related: bool,
+    pub conflicting: bool,
+    pub same_digit: bool,
+    pub noting: bool,
+    pub heat: u8,
+    pub trial: bool,
+    pub owner: Option<Player>,
+    pub transition: Option<CellTransition>,
+    pub focusable: bool,
 }
 
-/// Component to render an undo button
+impl CellHighlight {
+    /// Returns the CSS classes of the highlights, colored by the palette.
+    #[must_use]
+    pub fn css_classes(&self) -> String {
+        // Conditionally have a state class
+        let state = if self.selected {
+            "selected"
+        } else if self.conflicting {
+            "conflicting"
+        } else if self.related {
+            "highlight"
+        } else {
+            ""
+        };
+
+        // Highlight the occurrences of the active digit
+        let same_digit = if self.same_digit { "same-digit" } else { "" };
+
+        // Mark the cell taking notes
+        let noting = if self.noting { "noting" } else { "" };
+
+        // Tint the entries of the trial
+        let trial = if self.trial { "trial" } else { "" };
+
+        // Color the entries by player in the pass-and-play mode
+        let owner = self.owner.map_or("", Player::css_name);
+
+        // Animate the value that just changed
+        let transition = self.transition.map_or("", CellTransition::css_name);
+
+        // Color the empty cells by their candidates, from 1 to 4 or more
+        let heat = match self.heat {
+            0 => String::new(),
+            count => format!("heat-{}", count.min(4)),
+        };
+
+        [state, same_digit, noting, trial, owner, transition, &heat].join(" ")
+    }
+}
+
+/// Shared State for the animations of the cells
 ///
-/// This component renders a "Undo" button.
-/// When activated, all current state is dropped and the board is drawn with a
-/// fresh new puzzle for the user.
-#[component]
-fn UndoButton() -> Element {
+/// Represents globally across the app how the value of every cell just
+/// changed, if it is being animated.
+/// A [`Cell`] clears its transition once its animation ends.
+#[derive(Debug, Clone)]
+pub struct CellTransitions(pub [Option<CellTransition>; 81]);
+
+/// Hook that provides the [`CellTransitions`] shared state.
+///
+/// Every change of the board, by the player, a hint, an undo or a redo,
+/// sets the transitions of the cells it changed.
+/// A change that makes a mistake shakes the offending cells,
+/// though the values that are not the ones of the solution are not told
+/// apart in the [`kids`](crate::settings::Settings::kids) mode,
+/// which hides the mistakes.
+/// A new puzzle, and the first one, reveals its clues box by box instead.
+/// Only the clues are revealed, so the cascade never gets in the way of the
+/// input, and every clue is left alone once it has appeared.
+pub fn use_cell_transitions() {
     // Unpack shared states
-    let initial_sudoku = use_context::<Signal<InitialSudokuPuzzle>>().read().0;
-    let mut moves = use_context::<Signal<SudokuPuzzleMoves>>();
-    let current_sudoku = *moves
-        .read()
-        .0
-        .last()
-        .expect("failed to get the current sudoku state");
-    let mut sudoku = use_context::<Signal<SudokuPuzzle>>();
-    let mut clicked = use_context::<Signal<Clicked>>();
-    let mut related = use_context::<Signal<Related>>();
-    let mut conflicting = use_context::<Signal<Conflicting>>();
-
-    rsx!(button {
-        class: "input icon undo",
-        onclick: move |_| {
-            if current_sudoku == initial_sudoku {
-                let new_conflicting = conflicting.read().0.clone();
-                conflicting.write().0 = new_conflicting;
-            } else {
-                // pop the last element of moves
-                let last_state = moves
-                    .write()
-                    .0
-                    .pop()
-                    .expect("cannot pop the last element of the sudoku moves shared state");
-
-                let new_sudoku = *moves
-                    .read()
-                    .0
-                    .last()
-                    .expect("failed to get sudoku moves shared state");
-                // resetting the board with a new puzzle
-                sudoku.write().0 = new_sudoku;
-
-                // update clicked, related
-                let last_clicked = find_changed_cell(&last_state, &new_sudoku)
-                    .expect("cannot find changed index between the two previous state");
-                clicked.write().0 = last_clicked;
-                related.write().0 = get_related_cells(last_clicked);
-
-                // conflicting logic
-                let new_conflicting = get_all_conflicting_cells(&new_sudoku);
-                conflicting.write().0 = new_conflicting;
+    let game = use_context::<Signal<GameState>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut cell_transitions =
+        use_context_provider(|| Signal::new(CellTransitions(reveal(&game.peek().initial))));
+
+    // the puzzle and the board, which only change with a move
+    let initial = use_memo(move || game.read().initial);
+    let sudoku = use_memo(move || game.read().current());
+
+    // the solution is only searched when the puzzle changes
+    let solution = use_memo(move || find_solution(&initial()).ok());
+
+    // the puzzle and the board of the last change
+    let mut previous = use_signal(|| (initial(), sudoku()));
+
+    use_effect(move || {
+        let puzzle = initial();
+        let board = sudoku();
+        let (old_puzzle, old_board) = *previous.peek();
+        if (puzzle, board) == (old_puzzle, old_board) {
+            return;
+        }
+        previous.set((puzzle, board));
+        if puzzle != old_puzzle {
+            cell_transitions.set(CellTransitions(reveal(&puzzle)));
+            return;
+        }
+        let solution = solution.read();
+        let solution = solution.as_ref().filter(|_| !settings.peek().0.kids);
+        let changes = transitions(&old_board, &board, solution);
+        let cells = &mut cell_transitions.write().0;
+        for (cell, change) in cells.iter_mut().zip(changes) {
+            if change.is_some() {
+                *cell = change;
             }
         }
-    })
+    });
 }
 
-/// Component to render a hint button
+/// Hook that derives the [`CellHighlight`] of every cell from the shared
+/// states.
 ///
-/// This component renders a "Hint" button.
-/// When activated, the button will give the user a hint by filling a cell with a value.
-/// It also handles the UI updates for the clicked, related and conflicting cells.
-///
-/// ## Panics
-///
-/// The component will panic if cannot find the changed cell between the two previous states.
-#[component]
-pub fn HintButton() -> Element {
+/// The highlights are memoized, so what reads them is only rendered again
+/// when the highlight of a cell changes,
+/// and every [`Cell`] only when its own highlight changes.
+#[must_use]
+pub fn use_cell_highlights() -> Memo<[CellHighlight; 81]> {
     // Unpack shared states
-    let mut moves = use_context::<Signal<SudokuPuzzleMoves>>();
-    let current_sudoku = *moves
-        .read()
-        .0
-        .last()
-        .expect("failed to get the current sudoku state");
-    let mut sudoku = use_context::<Signal<SudokuPuzzle>>();
-    let mut clicked = use_context::<Signal<Clicked>>();
-    let mut related = use_context::<Signal<Related>>();
-    let mut conflicting = use_context::<Signal<Conflicting>>();
-
-    rsx!(button {
-        class: "input icon hint",
-        onclick: move |_| {
-            #[cfg(debug_assertions)]
-            log::info!("entering hint button onclick event handler");
-
-            // If there are conflicting cells, remove all of them
-            if !conflicting.read().0.is_empty() {
-                #[cfg(debug_assertions)]
-                log::info!("conflicting cells found, removing them");
-
-                let mut current_sudoku = current_sudoku;
-                let conficting_cells = get_all_conflicting_cells(&current_sudoku);
-                remove_conflicting_cells(&mut current_sudoku, &conficting_cells);
-
-                // update the moves state with new sudoku
-                moves.write().0.push(current_sudoku);
-
-                // update the conflicting state
-                conflicting.write().0 = vec![];
-
-                // update the sudoku state
-                sudoku.write().0 = current_sudoku;
-            }
-
-            // Try to get a hint
-            let new_sudoku = get_hint(&sudoku.read().0).unwrap_or_else(|_| {
-                // If no hint found, try again after removing conflicts
-                let mut current_sudoku = sudoku.read().0;
-                let conficting_cells = get_all_conflicting_cells(&current_sudoku);
-                remove_conflicting_cells(&mut current_sudoku, &conficting_cells);
-
-                get_hint(&current_sudoku).expect("no hint found even after removing conflicts")
-            });
-
-            // If the sudoku is not complete, then update the states
-            if sudoku.read().0.iter().any(|&val| val == 0) {
-                #[cfg(debug_assertions)]
-                log::info!("sudoku is not complete, updating states");
-
-                // get the last clicked cell
-                let last_clicked = find_changed_cell(&sudoku.read().0, &new_sudoku)
-                    .expect("cannot find changed index between the two previous state");
-
-                // update all states
-                sudoku.write().0 = new_sudoku;
-
-                moves.write().0.push(new_sudoku);
-                clicked.write().0 = last_clicked;
-                related.write().0 = get_related_cells(last_clicked);
-                conflicting.write().0 = get_all_conflicting_cells(&new_sudoku);
-            }
+    let game = use_context::<Signal<GameState>>();
+    let related = use_context::<Memo<Related>>();
+    let conflicting = use_context::<Memo<Conflicting>>();
+    let active_digit = use_context::<Signal<ActiveDigit>>();
+    let note_cell = use_context::<Signal<NoteCell>>();
+    let heatmap = use_context::<Signal<Heatmap>>();
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+    let cell_transitions = use_context::<Signal<CellTransitions>>();
+
+    use_memo(move || {
+        let game = game.read();
+        let clicked = game.clicked;
+        let related = &related.read().0;
+        let conflicting = &conflicting.read().0;
+        let active_digit = active_digit.read().0;
+        let note_cell = note_cell.read().0;
+        let current = game.current();
+        let candidates = heatmap.read().0.then(|| BitBoard::new(current));
+        let trial_cells = game.trial_cells();
+        let owners = hot_seat.read().0.as_ref().map(|game| game.owners);
+        let changes = cell_transitions.read().0;
+
+        let mut cells = [CellHighlight::default(); 81];
+        for (id, cell) in (0..81_u8).zip(cells.iter_mut()) {
+            let value = current[id as usize];
+            *cell = CellHighlight {
+                selected: clicked == id,
+                related: related.contains(&id),
+                conflicting: conflicting.contains(&id),
+                same_digit: value != 0 && active_digit == Some(value),
+                noting: note_cell == Some(id),
+                heat: candidates.as_ref().map_or(0, |board| {
+                    u8::try_from(board.candidates(id).count_ones()).unwrap_or(0)
+                }),
+                trial: trial_cells.contains(&id),
+                owner: owners.and_then(|owners| owners[id as usize]),
+                transition: changes[id as usize],
+                focusable: clicked == id || (clicked >= 81 && id == 0),
+            };
         }
+        cells
     })
 }
 
@@ -315,6 +358,14 @@ pub fn HintButton() -> Element {
 /// Cells that were generated by the initial puzzle are not mutable,
 /// i.e cannot have the values changed by the user.
 ///
+/// The board is rendered next to the [`ControlPanel`],
+/// on the side set in the settings.
+/// If the game is [`Embedded`], only the board and the number pad are
+/// rendered.
+/// The board can be scaled with a pinch on touch screens,
+/// and the game can be played with the
+/// [`keybindings`](crate::keybindings).
+///
 /// ## Panics
 ///
 /// The component will panic if cannot convert any of the Sudoku's board cells
@@ -324,56 +375,235 @@ pub fn HintButton() -> Element {
 #[component]
 pub fn SudokuBoard() -> Element {
     // Initialize all shared states
-    use_context_provider(|| Signal::new(Clicked(90))); // this will never imply in a highlighted cell at initial state
-    use_context_provider(|| Signal::new(Mutable(false)));
-    use_context_provider(|| Signal::new(Related(vec![])));
-    use_context_provider(|| Signal::new(Conflicting(vec![])));
+    let game = use_context::<Signal<GameState>>();
+    use_context_provider(|| Signal::new(ActiveDigit(None)));
+    use_context_provider(|| Signal::new(NoteCell(None)));
+    use_context_provider(|| Signal::new(Heatmap(false)));
+    use_context_provider(|| Signal::new(ReplacedGames::default()));
+    use_context_provider(|| Signal::new(Generating(false)));
+    use_context_provider(|| Signal::new(ShortcutsOpen(false)));
+    use_context_provider(|| Signal::new(RecognizedPuzzle(None)));
+    use_context_provider(|| Signal::new(ExportOpen(false)));
+    use_context_provider(|| Signal::new(SettingsOpen(false)));
+    use_context_provider(|| Signal::new(StatsOpen(false)));
+    use_context_provider(|| Signal::new(DailyOpen(false)));
+    use_context_provider(|| Signal::new(WeeklyOpen(false)));
+    use_context_provider(|| Signal::new(PacksOpen(false)));
+    use_context_provider(|| Signal::new(LibraryOpen(false)));
+    use_context_provider(|| Signal::new(FavoritesOpen(false)));
+    use_context_provider(|| Signal::new(AboutOpen(false)));
+    use_context_provider(|| Signal::new(Bookmarks(vec![])));
+    use_context_provider(|| Signal::new(BookmarksOpen(false)));
+    use_context_provider(|| Signal::new(UndoTreeOpen(false)));
+    use_context_provider(|| Signal::new(SessionLogOpen(false)));
+    use_explanation();
+
+    // Animate the cells whose values change
+    use_cell_transitions();
+
+    // Time the digits and boxes completed, and celebrate the solved board
+    use_split_times();
+    use_victory();
+
+    // Keep the times of the speedrun mode as personal bests,
+    // with their progress to race
+    use_progress_log();
+    use_personal_bests();
+
+    // Take turns on the board in the pass-and-play mode
+    use_hot_seat();
+
+    // Guide the idle player in the kids mode
+    use_kids_guide();
+
+    // Derive the related and conflicting cells
+    let related = use_memo(move || Related(game.read().related()));
+    use_context_provider(|| related);
+    let current = use_memo(move || game.read().current());
+    let conflicting = use_memo(move || Conflicting(get_all_conflicting_cells(&current())));
+    use_context_provider(|| conflicting);
+
+    // Expose the game to JavaScript on `window.sudoku`
+    use_js_api();
+
+    // Play with the keyboard shortcuts
+    use_keybindings();
+    use_context_provider(|| Signal::new(ImportedPuzzles(None)));
 
     // Unpack shared states
-    let initial_sudoku = use_context::<Signal<InitialSudokuPuzzle>>().read().0;
-    let moves = use_context::<Signal<SudokuPuzzleMoves>>();
+    let initial_sudoku = game.read().initial;
+    let last_sudoku = &current();
+    let notes = game.read().notes;
+
+    // Derive the highlights of every cell, so only the cells whose
+    // highlights change are rendered again
+    let cells = use_cell_highlights();
+    let cells = cells.read();
+
+    // Report the violations of the rules, to explain the conflicts
+    let violations = use_memo(move || validate_board(&current()));
+    let violations = violations.read();
+
+    // Cross out the candidates the explained step eliminates
+    let eliminated = use_context::<Signal<Explanation>>()
+        .read()
+        .0
+        .as_ref()
+        .map_or([0; 81], TechniqueStep::eliminated);
+
+    let generating = use_context::<Signal<Generating>>().read().0;
+
+    // Only the board and the number pad are rendered when embedded
+    let embedded = use_context::<Signal<Embedded>>().read().0;
+
+    // Place the number pad as set in the settings
+    let settings = use_context::<Signal<UserSettings>>();
+    let placement = settings.read().0.pad_placement.css_name();
+
+    // Only the number pad is rendered when embedded,
+    // and the hint button is hidden when no hints are allowed
+    let controls = if embedded {
+        ControlBarConfig::NUMBER_PAD
+    } else {
+        ControlBarConfig {
+            hint: settings.read().0.hint_budget != Some(0),
+            explain: settings.read().0.hint_budget != Some(0),
+            ..ControlBarConfig::FULL
+        }
+    };
+
+    // Scale the board with a pinch on touch screens
+    let (pinch_start, pinch_zoom) = use_pinch_zoom();
+
+    rsx!(
+        if !embedded {
+            // Render the application menu
+            Menu {}
+
+            // Render the paste-to-import prompt
+            PasteImport {}
+
+            // Render the photo import correction
+            PhotoImport {}
+
+            // Render the file import puzzle picker
+            FileImport {}
+
+            // Render the export panel
+            ExportPanel {}
+
+            // Render the settings panel
+            SettingsPanel {}
+
+            // Render the "My games" panel
+            GamesPanel {}
+
+            // Render the statistics panel
+            StatsPanel {}
+
+            // Render the session log
+            SessionLogPanel {}
+
+            // Render the daily archive
+            DailyPanel {}
 
-    let sudoku = &moves.read().0;
-    let last_sudoku = sudoku
-        .last()
-        .expect("failed to get the last element of the sudoku moves shared state");
+            // Render the weekly challenge
+            WeeklyPanel {}
 
-    let clicked = use_context::<Signal<Clicked>>();
+            // Render the pack browser
+            PacksPanel {}
 
-    rsx!(div {
+            // Render the puzzle library
+            LibraryPanel {}
+
+            // Render the favorite puzzles
+            FavoritesPanel {}
+
+            // Render the undo tree
+            UndoTreePanel {}
+
+            // Render the bookmarks of the board
+            BookmarksPanel {}
+
+            // Render the about panel
+            AboutPanel {}
+
+            // Render the keyboard shortcuts overlay
+            ShortcutsPanel {}
+
+            // Render the victory panel, with the splits
+            VictoryPanel {}
+
+            // Render the difficulty, timer, mistakes and hints left
+            StatusBar {}
+
+            // Render the turn and the scores of the pass-and-play mode
+            HotSeatBar {}
+
+            // Render the race against the personal best
+            GhostRace {}
+
+            // Render the explanation of the next step
+            TechniqueCaption {}
+
+            // Render the offer to finish the board
+            AutoComplete {}
+
+            // Render the toasts
+            ToastList {}
+        }
+
+        div {
+        class: "board pad-{placement}",
+
+        div {
         id: "container",
+        class: if generating { "loading" } else { "" },
+        aria_busy: "{generating}",
+        ontouchstart: pinch_start,
+        ontouchmove: pinch_zoom,
+
+        // Render the loading indicator over the board
+        if generating {
+            span {
+                class: "generating",
+                role: "status",
+                span { class: "spinner" }
+                "Generating a new puzzle…"
+            }
+        }
+
+        // Render the next step over the board
+        TechniqueOverlay {}
 
         // Render Cells
         for (index, &value) in last_sudoku.iter().enumerate() {
             Cell {
                 index: u8::try_from(index).expect("cannot convert from u8"),
                 value: value,
-                    selected: clicked.read().0 == u8::try_from(index).expect("cannot convert from u8"),
-                    highlighted: false,
-                    class: get_class(u8::try_from(index).expect("cannot convert from u8"), initial_sudoku[index] == 0),
-                    mutable: initial_sudoku[index] == 0,
-                }
-            }
-
-        // Render NumberButtons
-        for i in 1..=9 {
-            NumberButton {
-                number: i
+                highlight: cells[index],
+                class: get_class(u8::try_from(index).expect("cannot convert from u8"), initial_sudoku[index] == 0),
+                mutable: initial_sudoku[index] == 0,
+                notes: notes[index],
+                eliminated: eliminated[index],
+                conflict: if cells[index].conflicting {
+                    explain_conflict(&violations, u8::try_from(index).expect("cannot convert from u8"))
+                } else {
+                    None
+                },
+                clashes: if cells[index].conflicting {
+                    get_conflicting_cells(last_sudoku, u8::try_from(index).expect("cannot convert from u8"))
+                } else {
+                    vec![]
+                },
             }
         }
-
-        // Render "DeleteButton", a.k.a number is 0
-        NumberButton {
-            number: 0,
         }
 
-        // Render HintButton
-        HintButton{}
-
-        // Render UndoButton
-        UndoButton{}
-
-        // Render NewButton
-        NewButton{}
-    })
+        // Render the number pad and the other controls
+        ControlPanel {
+            config: controls,
+        }
+        }
+    )
 }
@@ -0,0 +1,193 @@
+//! # Bookmarks Module
+//!
+//! The `bookmarks` module contains the named snapshots of the board taken
+//! while playing, so a risky line can be tried and abandoned.
+//!
+//! [`BookmarksButton`] toggles the [`BookmarksPanel`],
+//! which saves the current board and its notes as a [`Bookmark`],
+//! and restores a saved one with [`GameAction::Restore`].
+//! Restoring is a move like any other, so it can be undone,
+//! and the moves made since the snapshot stay in the history.
+//!
+//! The bookmarks are only kept for the puzzle being played.
+
+use dioxus::prelude::*;
+
+use crate::app::{SudokuNotes, SudokuState};
+use crate::components::board::use_dispatch;
+use crate::components::buttons::ActionButton;
+use crate::components::games::Thumbnail;
+use crate::components::modal::Modal;
+use crate::components::toast::use_toast;
+use crate::game::{GameAction, GameState};
+use crate::toast::ToastKind;
+
+/// A named snapshot of the board
+///
+/// - `name: String`: the name given by the user.
+/// - `puzzle: SudokuState`: the puzzle of the game it was taken in.
+/// - `board: SudokuState`: the board when it was taken.
+/// - `notes: SudokuNotes`: the notes when it was taken.
+/// - `moves: usize`: the number of moves made when it was taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bookmark {
+    pub name: String,
+    pub puzzle: SudokuState,
+    pub board: SudokuState,
+    pub notes: SudokuNotes,
+    pub moves: usize,
+}
+
+/// Shared State for the bookmarks
+///
+/// Represents globally across the app the snapshots of the board,
+/// the oldest first.
+#[derive(Debug, Clone)]
+pub struct Bookmarks(pub Vec<Bookmark>);
+
+/// Shared State for the bookmarks panel
+///
+/// Represents globally across the app if the [`BookmarksPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct BookmarksOpen(pub bool);
+
+/// Component to render the bookmarks button
+///
+/// This component renders a "Bookmarks" button that opens and closes the
+/// [`BookmarksPanel`].
+#[component]
+pub fn BookmarksButton() -> Element {
+    // Unpack shared states
+    let mut bookmarks_open = use_context::<Signal<BookmarksOpen>>();
+
+    rsx!(ActionButton {
+        icon: "bookmarks",
+        label: "Bookmarks",
+        tooltip: "Save the board, to come back to it later",
+        onclick: move |()| {
+            let open = bookmarks_open.read().0;
+            bookmarks_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the bookmarks panel
+///
+/// This component saves, in a [`Modal`], the current board under a name,
+/// "Snapshot 1" if none is given,
+/// and lists the [`Bookmarks`] of the puzzle with their thumbnail.
+///
+/// Restoring a bookmark brings back its board and its notes,
+/// and closes the panel.
+#[component]
+pub fn BookmarksPanel() -> Element {
+    // Unpack shared states
+    let mut bookmarks_open = use_context::<Signal<BookmarksOpen>>();
+    let mut bookmarks = use_context::<Signal<Bookmarks>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut dispatch = use_dispatch();
+    let mut toast = use_toast();
+    let mut name = use_signal(String::new);
+
+    if !bookmarks_open.read().0 {
+        return None;
+    }
+
+    let initial = game.read().initial;
+    let shown: Vec<(usize, Bookmark)> = bookmarks
+        .read()
+        .0
+        .iter()
+        .filter(|bookmark| bookmark.puzzle == initial)
+        .cloned()
+        .enumerate()
+        .collect();
+    let count = shown.len();
+
+    let mut save = move || {
+        let typed = name.peek().trim().to_string();
+        let bookmark = Bookmark {
+            name: if typed.is_empty() {
+                format!("Snapshot {}", count + 1)
+            } else {
+                typed
+            },
+            puzzle: initial,
+            board: game.peek().current(),
+            notes: game.peek().notes,
+            moves: game.peek().moves().len() - 1,
+        };
+        toast(ToastKind::Success, format!("Saved {}", bookmark.name));
+        let mut bookmarks = bookmarks.write();
+        // the bookmarks of the other puzzles are dropped
+        bookmarks.0.retain(|bookmark| bookmark.puzzle == initial);
+        bookmarks.0.push(bookmark);
+        name.set(String::new());
+    };
+
+    let mut restore = move |bookmark: Bookmark| {
+        let moved = dispatch(GameAction::Restore(bookmark.board));
+        let noted = dispatch(GameAction::SetNotes(bookmark.notes));
+        if !moved && !noted {
+            toast(ToastKind::Info, format!("Already at {}", bookmark.name));
+            return;
+        }
+        bookmarks_open.write().0 = false;
+        toast(ToastKind::Success, format!("Restored {}", bookmark.name));
+    };
+
+    rsx!(Modal {
+        class: "bookmarks",
+        label: "Bookmarks",
+        onclose: move |()| bookmarks_open.write().0 = false,
+        span { "Bookmarks" }
+        label {
+            input {
+                r#type: "text",
+                placeholder: "Snapshot {count + 1}",
+                value: "{name}",
+                oninput: move |event| name.set(event.value()),
+            }
+            button { class: "input", onclick: move |_| save(), "Save" }
+        }
+        if shown.is_empty() {
+            span { "No bookmarks yet. Save the board before trying a risky line." }
+        }
+        span {
+            class: "games-list",
+            for (key, bookmark) in shown {
+                span {
+                    key: "{key}",
+                    class: "game",
+                    Thumbnail { initial: initial, current: bookmark.board }
+                    span { "{bookmark.name} · at move {bookmark.moves}" }
+                    button {
+                        class: "input",
+                        onclick: {
+                            let bookmark = bookmark.clone();
+                            move |_| restore(bookmark.clone())
+                        },
+                        "Restore"
+                    }
+                    button {
+                        class: "input",
+                        onclick: move |_| {
+                            let mut bookmarks = bookmarks.write();
+                            if let Some(position) =
+                                bookmarks.0.iter().position(|other| other == &bookmark)
+                            {
+                                bookmarks.0.remove(position);
+                            }
+                        },
+                        "Remove"
+                    }
+                }
+            }
+        }
+        button {
+            class: "input",
+            onclick: move |_| bookmarks_open.write().0 = false,
+            "Close"
+        }
+    })
+}
@@ -0,0 +1,59 @@
+//! # Buttons Module
+//!
+//! The `buttons` module contains the [`ActionButton`],
+//! the icon button shared by the controls and the menu.
+//!
+//! Every action button has a label, read by screen readers and shown in
+//! the menu, and a tooltip with its keyboard shortcut, if any.
+//! Buttons whose action would do nothing, such as undoing at the start of
+//! the game, are disabled.
+
+use dioxus::prelude::*;
+
+use crate::keybindings::{shortcut_label, Action};
+
+/// Component Props for [`ActionButton`]
+///
+/// - `icon: String`: the CSS class of the icon of the button.
+/// - `label: String`: the name of the button.
+/// - `disabled: bool`: whether the button cannot be activated,
+///   `false` by default.
+/// - `tooltip: Option<String>`: what the button does, shown on hover,
+///   or the label if `None`.
+/// - `shortcut: Option<Action>`: the action of the keyboard shortcut of the
+///   button, shown in the tooltip.
+/// - `onclick: EventHandler`: called when the button is activated.
+#[derive(Props, Clone, PartialEq)]
+pub struct ActionButtonProps {
+    icon: String,
+    label: String,
+    #[props(default)]
+    disabled: bool,
+    tooltip: Option<String>,
+    shortcut: Option<Action>,
+    onclick: EventHandler,
+}
+
+/// Component to render an action button
+///
+/// This component renders an icon button with a label and a tooltip.
+/// The tooltip ends with the keys of the keyboard shortcut,
+/// as listed in the [`KEYBINDINGS`](crate::keybindings::KEYBINDINGS).
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn ActionButton(props: ActionButtonProps) -> Element {
+    let onclick = props.onclick;
+    let tooltip = props.tooltip.unwrap_or_else(|| props.label.clone());
+    let title = match props.shortcut.and_then(shortcut_label) {
+        Some(keys) => format!("{tooltip} ({keys})"),
+        None => tooltip,
+    };
+
+    rsx!(button {
+        class: "input icon {props.icon}",
+        title: "{title}",
+        disabled: props.disabled,
+        onclick: move |_| onclick.call(()),
+        "{props.label}"
+    })
+}
@@ -13,11 +13,23 @@
 
 use std::borrow::Cow;
 
-use crate::components::board::Clicked;
-use crate::utils::get_related_cells;
+use crate::animation::reveal_order;
+use crate::browser::now;
+use crate::components::board::{use_dispatch, CellHighlight, CellTransitions};
+use crate::components::controls::{use_erase_cell, use_place_value};
+use crate::components::settings::UserSettings;
+use crate::components::toast::use_toast;
+use crate::game::GameAction;
+use crate::settings::InputMode;
+use crate::toast::ToastKind;
+use crate::validation::cell_name;
 use dioxus::prelude::*;
 
-use super::board::{Conflicting, Mutable, Related};
+use super::board::{ActiveDigit, NoteCell};
+
+/// How long a touch must be held to erase a cell,
+/// or every value of a digit from the number pad, in milliseconds
+pub const LONG_PRESS_MILLIS: u64 = 500;
 
 /// Component Props for [`Cell`]
 ///
@@ -26,9 +38,8 @@ use super::board::{Conflicting, Mutable, Related};
 ///   The index is used to calculate the cell's position in the grid
 ///   and to derive its CSS class.
 /// - `value: u8`: The current value of the cell.
-/// - `selected: bool`: If the cell is current clicked by the user.
-///   Cells that are clicked, should be highlighted.
-/// - `highlighted: bool`: If the cell is related to the clicked cells.
+/// - `highlight: CellHighlight`: If the cell is clicked by the user,
+///   related to the clicked cell, in conflict, and so on.
 ///   Cells are related if the share the same row, column, or sub-grid
 ///   in a Sudoku board.
 /// - `class: &str`: The cell's CSS class.
@@ -36,15 +47,28 @@ use super::board::{Conflicting, Mutable, Related};
 /// - `mutable: bool`: If the cell's value can be changed by the user.
 ///   Mutable cells are the ones that are blank when the Sudoku board is
 ///   generated.
+/// - `notes: u16`: The notes of the cell as a bitmask.
+///   Notes are only displayed when the cell is empty.
+///   See also: [`SudokuNotes`](crate::app::SudokuNotes).
+/// - `eliminated: u16`: The candidates crossed out by the explained step,
+///   as a bitmask like the notes.
+///   See also: [`Explanation`](crate::components::techniques::Explanation).
+/// - `conflict: Option<String>`: Why the cell is in conflict, if it is.
+///   See also: [`explain_conflict`](crate::validation::explain_conflict).
+/// - `clashes: Vec<u8>`: The indices of the cells it is in conflict with.
+///   See also: [`get_conflicting_cells`](crate::utils::get_conflicting_cells).
 #[allow(clippy::module_name_repetitions)]
 #[derive(Props, Clone, PartialEq, Eq)]
 pub struct CellProps {
     index: u8,
     value: u8,
-    selected: bool,
-    highlighted: bool,
+    highlight: CellHighlight,
     class: Cow<'static, str>,
     mutable: bool,
+    notes: u16,
+    eliminated: u16,
+    conflict: Option<String>,
+    clashes: Vec<u8>,
 }
 
 /// Represents a cell in a Sudoku puzzle.
@@ -58,9 +82,8 @@ pub struct CellProps {
 ///   The index is used to calculate the cell's position in the grid
 ///   and to derive its CSS class.
 /// - `value: u8`: The current value of the cell.
-/// - `selected: bool`: If the cell is current clicked by the user.
-///   Cells that are clicked, should be highlighted.
-/// - `highlighted: bool`: If the cell is related to the clicked cells.
+/// - `highlight: CellHighlight`: If the cell is clicked by the user,
+///   related to the clicked cell, in conflict, and so on.
 ///   Cells are related if the share the same row, column, or sub-grid
 ///   in a Sudoku board.
 /// - `class: &str`: The cell's CSS class.
@@ -68,55 +91,193 @@ pub struct CellProps {
 /// - `mutable: bool`: If the cell's value can be changed by the user.
 ///   Mutable cells are the ones that are blank when the Sudoku board is
 ///   generated.
+/// - `notes: u16`: The notes of the cell as a bitmask.
+///   Notes are only displayed when the cell is empty.
+///   See also: [`SudokuNotes`](crate::app::SudokuNotes).
+/// - `eliminated: u16`: The candidates crossed out by the explained step,
+///   as a bitmask like the notes.
+///   See also: [`Explanation`](crate::components::techniques::Explanation).
+/// - `conflict: Option<String>`: Why the cell is in conflict, if it is.
+///   See also: [`explain_conflict`](crate::validation::explain_conflict).
+/// - `clashes: Vec<u8>`: The indices of the cells it is in conflict with.
+///   See also: [`get_conflicting_cells`](crate::utils::get_conflicting_cells).
+///
+/// A cell in conflict has an info badge, which shows why on hover,
+/// and in a toast when it is tapped,
+/// and a tooltip with the coordinates of the cells it clashes with.
+///
+/// In the number-first [`InputMode`], clicking a mutable cell also stamps
+/// the [`ActiveDigit`] in it.
+/// Cells with the [`ActiveDigit`] are highlighted.
+///
+/// A value that just changed is animated, see [`CellTransitions`],
+/// and the clues of a new puzzle appear box by box.
+/// The value and the notes are shown in the
+/// [`SymbolSet`](crate::symbols::SymbolSet) of the settings.
 ///
-/// ## Panics
+/// A mutable cell is erased with a context-click on desktop,
+/// or a long-press on touch screens:
+/// its value if it has one, or else its notes.
+/// In the cell-first input mode, double-clicking an empty mutable cell
+/// makes it the [`NoteCell`], so the next digit entered is a note.
 ///
-/// The component can panic if it cannot read the App's shared state on
-/// the clicked cell and it's related cells.
+/// Only the clicked cell is in the tab order, and it is activated with
+/// Enter or Space as a click.
+/// The arrow [`keybindings`](crate::keybindings) move between the cells.
+///
+/// The highlights of the cell are derived by the
+/// [`SudokuBoard`](crate::components::board::SudokuBoard) and passed as
+/// props, and the shared states are only read by the event handlers,
+/// so a cell is only rendered again when its own props change.
 #[allow(clippy::module_name_repetitions)]
 #[allow(clippy::needless_pass_by_value)]
 #[must_use]
 pub fn Cell(props: CellProps) -> Element {
     let value = props.value;
     let is_mutable = props.mutable;
+    let notes = props.notes;
+    let eliminated = props.eliminated;
 
     // Unpack all props and share states
     let id = props.index;
-    let mut clicked = use_context::<Signal<Clicked>>();
-    let mut mutable = use_context::<Signal<Mutable>>();
-    let mut related = use_context::<Signal<Related>>();
-    let conflicting = use_context::<Signal<Conflicting>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let active_digit = use_context::<Signal<ActiveDigit>>();
+    let mut note_cell = use_context::<Signal<NoteCell>>();
+    let mut cell_transitions = use_context::<Signal<CellTransitions>>();
+    let mut place_value = use_place_value();
+    let mut erase_cell = use_erase_cell();
+    let mut dispatch = use_dispatch();
+
+    // when the current single-finger touch started, if any
+    let mut touch_start = use_signal(|| None::<u64>);
 
-    // Conditionally display the value or an empty string
+    // Display the value in the symbols of the settings,
+    // or an empty string for an empty cell
     let free = value != 0;
-    let mut value = value.to_string();
-    if !free {
-        value = String::new();
-    };
-
-    // Conditionally have style
-    let mut style = String::new();
-    if related.read().0.contains(&id) {
-        style = "background-color: #c2ddf8;".to_string();
-    };
-    if conflicting.read().0.contains(&id) {
-        style = "background-color: #d5656f;".to_string();
-    };
-    if clicked.read().0 == id {
-        style = "background-color: #e4ebf2;".to_string();
-    }
+    let symbols = settings.read().0.symbols;
+    let value = symbols.symbol(value);
+
+    // Only one cell is in the tab order, the clicked one or the first one,
+    // and the arrows move between them
+    let highlight = props.highlight;
+    let tabindex = if highlight.focusable { 0 } else { -1 };
 
     rsx!(
         div {
             onclick: move |_| {
-                clicked.write().0 = id;
-                mutable.write().0 = is_mutable;
-                related.write().0 = get_related_cells(id);
+                dispatch(GameAction::Select(id));
+
+                // other cells go back to value entry
+                if note_cell.peek().0.is_some_and(|cell| cell != id) {
+                    note_cell.set(NoteCell(None));
+                }
+
+                // stamp the picked digit in the number-first input mode
+                if settings.peek().0.input_mode == InputMode::NumberFirst && is_mutable {
+                    match active_digit.peek().0 {
+                        Some(0) => erase_cell(id),
+                        Some(digit) => place_value(id, digit),
+                        None => {}
+                    }
+                }
+            },
+            // toggle note entry with a double-click, in the cell-first input mode
+            ondoubleclick: move |_| {
+                if settings.peek().0.input_mode == InputMode::CellFirst && is_mutable && !free {
+                    let cell = &mut note_cell.write().0;
+                    *cell = if *cell == Some(id) { None } else { Some(id) };
+                }
+            },
+            // erase the cell with a context-click or a long-press
+            prevent_default: "oncontextmenu",
+            oncontextmenu: move |_| {
+                if is_mutable {
+                    erase_cell(id);
+                }
             },
-            class: "{props.class}",
+            ontouchstart: move |event| touch_start.set((event.touches().len() == 1).then(now)),
+            ontouchmove: move |_| touch_start.set(None),
+            ontouchend: move |_| {
+                let held = touch_start.take().map(|start| now().saturating_sub(start));
+                if is_mutable && held.is_some_and(|held| held >= LONG_PRESS_MILLIS) {
+                    erase_cell(id);
+                }
+            },
+            // the transition is over once its animation ends
+            onanimationend: move |_| {
+                if cell_transitions.peek().0[usize::from(id)].is_some() {
+                    cell_transitions.write().0[usize::from(id)] = None;
+                }
+            },
+            class: "{props.class} {highlight.css_classes()}",
+            style: "--reveal-order: {reveal_order(id)};",
             id: "{id}",
-            style: "{style}",
-            "{&value}"
+            tabindex: tabindex,
+            role: "button",
+            if let Some(explanation) = props.conflict {
+                ConflictInfo { explanation, clashes: props.clashes }
+            }
+            if free {
+                span { class: "value", "{value}" }
+            } else if notes | eliminated != 0 {
+                span {
+                    class: "notes",
+                    for note in 1..=9_u8 {
+                        span {
+                            class: if eliminated & (1 << (note - 1)) != 0 { "eliminated" } else { "" },
+                            if (notes | eliminated) & (1 << (note - 1)) != 0 {
+                                "{symbols.symbol(note)}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// Component Props for [`ConflictInfo`]
+///
+/// - `explanation: String`: why the cell is in conflict.
+/// - `clashes: Vec<u8>`: the indices of the cells it is in conflict with.
+#[derive(Props, Clone, PartialEq, Eq)]
+struct ConflictInfoProps {
+    explanation: String,
+    clashes: Vec<u8>,
+}
+
+/// Component to render the info badge of a cell in conflict
+///
+/// The explanation is shown on hover, and in a toast when it is tapped.
+/// The tooltip, shown while the cell is hovered or focused,
+/// lists the cells it clashes with by their [`cell_name`].
+#[allow(clippy::needless_pass_by_value)]
+#[component]
+fn ConflictInfo(props: ConflictInfoProps) -> Element {
+    let mut toast = use_toast();
+    let explanation = props.explanation;
+    let clashes: Vec<String> = props.clashes.into_iter().map(cell_name).collect();
+    let clashes = clashes.join(", ");
+
+    rsx!(
+        span {
+            class: "conflict-info",
+            role: "button",
+            title: "{explanation}",
+            aria_label: "{explanation}",
+            onclick: move |event| {
+                // the cell is not selected by tapping its badge
+                event.stop_propagation();
+                toast(ToastKind::Error, explanation.clone());
+            },
+            "i"
+        }
+        if !clashes.is_empty() {
+            span {
+                class: "conflict-tooltip",
+                role: "tooltip",
+                "Clashes with {clashes}"
+            }
         }
     )
 }
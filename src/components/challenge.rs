@@ -0,0 +1,170 @@
+//! # Challenge Module
+//!
+//! The `challenge` module contains the [`ChallengeButton`],
+//! which shares a link challenging a friend to beat the time of a solved
+//! puzzle, and the [`ChallengeResult`] of the head-to-head,
+//! shown once the puzzle of a challenge is solved.
+//!
+//! See also: [`challenge`](crate::challenge).
+
+use dioxus::prelude::*;
+
+use crate::challenge::{Challenge, Outcome};
+use crate::components::buttons::ActionButton;
+use crate::components::profiles::{ActiveProfile, Profiles};
+use crate::components::settings::UserSettings;
+use crate::components::timer::GameTimer;
+use crate::components::toast::use_toast;
+use crate::formats::to_line;
+use crate::game::GameState;
+use crate::links::{CHALLENGER_PARAM, CHALLENGE_PARAM, PUZZLE_PARAM};
+use crate::toast::ToastKind;
+use crate::utils::{format_duration, format_duration_millis, is_solved};
+
+/// Shared State for the challenge
+///
+/// Represents globally across the app the [`Challenge`] the app was opened
+/// with, if any.
+#[derive(Debug, Clone)]
+pub struct ActiveChallenge(pub Option<Challenge>);
+
+/// JavaScript that shares a challenge link, with the query parameters
+/// received, natively or through the clipboard
+const SHARE_CHALLENGE: &str = r#"
+    const { text, params } = await dioxus.recv();
+    const url = `${location.origin}${location.pathname}?${new URLSearchParams(params)}`;
+    if (navigator.share) {
+        await navigator.share({ title: "Sudoku", text, url }).catch(() => {});
+        dioxus.send(false);
+    } else {
+        dioxus.send(await navigator.clipboard.writeText(`${text} ${url}`).then(() => true, () => false));
+    }
+"#;
+
+/// Component to render a challenge button
+///
+/// This component renders a "Challenge" button, enabled once the board is
+/// solved.
+/// When activated, a link to the puzzle with the time it was solved in and
+/// the name of the active profile is shared,
+/// so a friend can play the same board and compare their time.
+/// The native share sheet is used where supported,
+/// otherwise the link is copied to the clipboard.
+#[component]
+pub fn ChallengeButton() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let profiles = use_context::<Signal<Profiles>>();
+    let mut toast = use_toast();
+
+    rsx!(ActionButton {
+        icon: "challenge",
+        label: "Challenge",
+        tooltip: "Challenge a friend to beat your time",
+        disabled: !is_solved(&game.read().current()),
+        onclick: move |()| {
+            let millis = timer.read().0;
+            // the seconds only, unless the milliseconds were shown
+            let (millis, time) = if settings.read().0.speedrun {
+                (millis, format_duration_millis(millis))
+            } else {
+                (millis / 1000 * 1000, format_duration(millis))
+            };
+            let id = profile.read().0;
+            let name = profiles
+                .read()
+                .0
+                .iter()
+                .find(|profile| profile.id == id)
+                .map(|profile| profile.name.clone())
+                .unwrap_or_default();
+            let params = serde_json::json!({
+                PUZZLE_PARAM: to_line(&game.read().initial, '.'),
+                CHALLENGE_PARAM: millis.to_string(),
+                CHALLENGER_PARAM: name,
+            });
+            let text = format!("I solved this Sudoku puzzle in {time}. Can you beat me?");
+
+            let mut share = eval(SHARE_CHALLENGE);
+            if let Err(err) = share.send(serde_json::json!({ "text": text, "params": params })) {
+                log::error!("failed to share the challenge: {err:?}");
+                return;
+            }
+            spawn(async move {
+                if share
+                    .recv()
+                    .await
+                    .is_ok_and(|copied| copied.as_bool() == Some(true))
+                {
+                    toast(
+                        ToastKind::Success,
+                        "Challenge link copied to the clipboard".to_string(),
+                    );
+                }
+            });
+        },
+    })
+}
+
+/// Component to render the result of a challenge
+///
+/// This component renders, once the puzzle of the [`ActiveChallenge`] is
+/// solved, the time of the player next to the time of the challenger,
+/// and who won by how much.
+#[component]
+pub fn ChallengeResult() -> Element {
+    // Unpack shared states
+    let challenge = use_context::<Signal<ActiveChallenge>>();
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+
+    let challenge = challenge.read();
+    let challenge = challenge
+        .0
+        .as_ref()
+        .filter(|challenge| challenge.puzzle == game.read().initial)?;
+    let millis = timer.read().0;
+    let outcome = challenge.outcome(millis);
+    // the milliseconds are shown if both times have them
+    let precise = settings.read().0.speedrun && !challenge.millis.is_multiple_of(1000);
+    let format = |millis| {
+        if precise {
+            format_duration_millis(millis)
+        } else {
+            format_duration(millis)
+        }
+    };
+    let verdict = match outcome {
+        Outcome::Won(margin) => format!("You beat {} by {}!", challenge.name, format(margin)),
+        Outcome::Lost(margin) => format!("{} was faster by {}", challenge.name, format(margin)),
+        Outcome::Tied => format!("A tie with {}!", challenge.name),
+    };
+    let (own, theirs) = match outcome {
+        Outcome::Won(_) => ("winner", ""),
+        Outcome::Lost(_) => ("", "winner"),
+        Outcome::Tied => ("", ""),
+    };
+
+    rsx!(
+        span { class: "challenge-verdict", "{verdict}" }
+        table {
+            class: "challenge",
+            tbody {
+                tr {
+                    class: own,
+                    td { "You" }
+                    td { "{format(millis)}" }
+                }
+                tr {
+                    class: theirs,
+                    td { "{challenge.name}" }
+                    td { "{format(challenge.millis)}" }
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,180 @@
+//! # Charts Module
+//!
+//! The `charts` module contains the SVG charts of the statistics:
+//! the [`LineChart`] of a trend, the [`BarChart`] of a value per
+//! category, and the [`GridHeatmap`] of a value per cell of the board.
+//!
+//! See also: [`charts`](crate::charts).
+
+use dioxus::prelude::*;
+
+use crate::charts::{bars, line_points, CHART_HEIGHT, CHART_WIDTH};
+use crate::validation::cell_name;
+
+/// Component Props for [`LineChart`]
+///
+/// - `title: String`: the title of the chart, also read by screen readers.
+/// - `values: Vec<u64>`: the values, from the left to the right.
+/// - `summary: String`: the values in words, for screen readers.
+#[derive(Props, Clone, PartialEq, Eq)]
+pub struct LineChartProps {
+    title: String,
+    values: Vec<u64>,
+    summary: String,
+}
+
+/// Component to render a line chart
+///
+/// This component renders the title of the chart, and the values as a
+/// line, with a dot for every value, in an SVG.
+/// Nothing is drawn if there are no values.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn LineChart(props: LineChartProps) -> Element {
+    let LineChartProps {
+        title,
+        values,
+        summary,
+    } = props;
+    if values.is_empty() {
+        return None;
+    }
+    let points = line_points(&values);
+
+    rsx!(figure {
+        class: "chart",
+        figcaption { "{title}" }
+        svg {
+            view_box: "0 0 {CHART_WIDTH} {CHART_HEIGHT}",
+            role: "img",
+            "aria-label": "{title}: {summary}",
+            polyline { class: "line", points: "{points}" }
+            for (index, point) in points.split(' ').enumerate() {
+                if let Some((x, y)) = point.split_once(',') {
+                    circle { key: "{index}", class: "dot", cx: x, cy: y, r: "2.5" }
+                }
+            }
+        }
+    })
+}
+
+/// Component Props for [`BarChart`]
+///
+/// - `title: String`: the title of the chart, also read by screen readers.
+/// - `bars: Vec<(String, u64)>`: the label and the value of every bar,
+///   from the left to the right.
+#[derive(Props, Clone, PartialEq, Eq)]
+pub struct BarChartProps {
+    title: String,
+    bars: Vec<(String, u64)>,
+}
+
+/// Component to render a bar chart
+///
+/// This component renders the title of the chart, and a bar for every
+/// value, with its label and its value, in an SVG.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn BarChart(props: BarChartProps) -> Element {
+    let BarChartProps {
+        title,
+        bars: labeled,
+    } = props;
+    let values: Vec<u64> = labeled.iter().map(|&(_, value)| value).collect();
+    let summary = labeled
+        .iter()
+        .map(|(label, value)| format!("{label} {value}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    rsx!(figure {
+        class: "chart",
+        figcaption { "{title}" }
+        svg {
+            // room for the labels below the bars
+            view_box: "0 -14 {CHART_WIDTH} {CHART_HEIGHT + 30}",
+            role: "img",
+            "aria-label": "{title}: {summary}",
+            for (bar, (label, value)) in bars(&values).into_iter().zip(labeled) {
+                g {
+                    key: "{label}",
+                    rect {
+                        class: "bar",
+                        x: "{bar.x}",
+                        y: "{bar.y}",
+                        width: "{bar.width}",
+                        height: "{bar.height}",
+                    }
+                    text {
+                        class: "value",
+                        x: "{bar.x + bar.width / 2}",
+                        y: "{bar.y.saturating_sub(4)}",
+                        "{value}"
+                    }
+                    text {
+                        class: "label",
+                        x: "{bar.x + bar.width / 2}",
+                        y: "{CHART_HEIGHT + 12}",
+                        "{label}"
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Component Props for [`GridHeatmap`]
+///
+/// - `title: String`: the title of the chart, also read by screen readers.
+/// - `cells: Vec<u32>`: the value of every cell of the board, row by row.
+#[derive(Props, Clone, PartialEq, Eq)]
+pub struct GridHeatmapProps {
+    title: String,
+    cells: Vec<u32>,
+}
+
+/// Component to render a heatmap of the board
+///
+/// This component renders the title of the chart, and the 9x9 board in an
+/// SVG, where every cell is tinted by its value,
+/// from clear for 0 to solid for the highest value.
+/// Every cell has its value in a tooltip.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn GridHeatmap(props: GridHeatmapProps) -> Element {
+    let GridHeatmapProps { title, cells } = props;
+    let max = cells.iter().copied().max().unwrap_or(0).max(1);
+    let hottest = (0..81_u8)
+        .zip(&cells)
+        .filter(|&(_, &value)| value == max)
+        .map(|(index, _)| cell_name(index))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    rsx!(figure {
+        class: "chart grid-heatmap",
+        figcaption { "{title}" }
+        svg {
+            view_box: "0 0 9 9",
+            role: "img",
+            "aria-label": "{title}: most in {hottest}",
+            for (index, &value) in (0..81_u8).zip(&cells) {
+                rect {
+                    key: "{index}",
+                    class: "heat",
+                    x: "{index % 9}",
+                    y: "{index / 9}",
+                    width: "1",
+                    height: "1",
+                    fill_opacity: "{f64::from(value) / f64::from(max)}",
+                    title { "{cell_name(index)}: {value}" }
+                }
+            }
+            for line in [3, 6] {
+                line { key: "v{line}", class: "box", x1: "{line}", y1: "0", x2: "{line}", y2: "9" }
+                line { key: "h{line}", class: "box", x1: "0", y1: "{line}", x2: "9", y2: "{line}" }
+            }
+            rect { class: "frame", x: "0", y: "0", width: "9", height: "9" }
+        }
+    })
+}
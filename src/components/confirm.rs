@@ -0,0 +1,80 @@
+//! # Confirm Module
+//!
+//! The `confirm` module contains the [`ConfirmDialog`],
+//! which asks the user to confirm a destructive action,
+//! such as discarding a game with progress.
+//!
+//! The dialog can be turned off with its "Don't ask again" checkbox,
+//! or in the [`Settings`](crate::settings::Settings).
+
+use dioxus::prelude::*;
+
+use crate::components::modal::Modal;
+use crate::components::settings::UserSettings;
+
+/// Component Props for [`ConfirmDialog`]
+///
+/// - `message: String`: the question asked to the user.
+/// - `action: String`: the label of the button that confirms the action.
+/// - `onconfirm: EventHandler`: called when the action is confirmed.
+/// - `oncancel: EventHandler`: called when the action is cancelled.
+#[derive(Props, Clone, PartialEq)]
+pub struct ConfirmDialogProps {
+    message: String,
+    action: String,
+    onconfirm: EventHandler,
+    oncancel: EventHandler,
+}
+
+/// Component to render a confirmation dialog
+///
+/// This component renders a question in a [`Modal`], with buttons to
+/// confirm or cancel the action.
+/// Closing the modal cancels the action.
+/// If "Don't ask again" is checked when confirming,
+/// [`confirm_discard`](crate::settings::Settings::confirm_discard) is
+/// turned off.
+///
+/// The dialog is rendered by the component of the action,
+/// while it waits for the confirmation.
+#[allow(clippy::module_name_repetitions)]
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn ConfirmDialog(props: ConfirmDialogProps) -> Element {
+    // Unpack shared states
+    let mut settings = use_context::<Signal<UserSettings>>();
+    let mut dont_ask = use_signal(|| false);
+
+    let onconfirm = props.onconfirm;
+    let oncancel = props.oncancel;
+
+    rsx!(Modal {
+        class: "confirm",
+        label: "{props.message}",
+        onclose: move |()| oncancel.call(()),
+        span { "{props.message}" }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: dont_ask(),
+                onchange: move |event| dont_ask.set(event.checked()),
+            }
+            "Don't ask again"
+        }
+        button {
+            class: "input",
+            onclick: move |_| {
+                if dont_ask() {
+                    settings.write().0.confirm_discard = false;
+                }
+                onconfirm.call(());
+            },
+            "{props.action}"
+        }
+        button {
+            class: "input",
+            onclick: move |_| oncancel.call(()),
+            "Cancel"
+        }
+    })
+}
@@ -0,0 +1,1097 @@
+//! # Controls Module
+//!
+//! The `controls` module contains the [`ControlPanel`],
+//! the cluster of controls next to the Sudoku board:
+//! the number pad and the buttons to play the game.
+//!
+//! The placement of the number pad and the size of the buttons are
+//! configurable in the [`Settings`](crate::settings::Settings),
+//! so the controls can be reached with one hand.
+//! So is the [`InputMode`], which decides if the number pad enters a digit
+//! in the selected cell or picks the digit to stamp in the cells.
+
+use dioxus::prelude::*;
+
+use crate::analytics::Event;
+use crate::app::{SudokuNotes, SudokuState};
+use crate::browser::now;
+use crate::components::analytics::use_track;
+use crate::components::board::{
+    use_dispatch, ActiveDigit, Generating, Heatmap, HintsUsed, NoteCell,
+};
+use crate::components::bookmarks::BookmarksButton;
+use crate::components::buttons::ActionButton;
+use crate::components::cell::LONG_PRESS_MILLIS;
+use crate::components::confirm::ConfirmDialog;
+use crate::components::games::{CurrentSlot, SaveSlots};
+use crate::components::haptics::use_haptics;
+use crate::components::hotseat::HotSeatGame;
+use crate::components::settings::UserSettings;
+use crate::components::techniques::ExplainButton;
+use crate::components::timer::GameTimer;
+use crate::components::toast::{use_action_toast, use_toast};
+use crate::components::undo_tree::UndoTreeButton;
+use crate::difficulty::{grade_puzzle, Difficulty};
+use crate::encoding::{encode_game, GameSnapshot};
+use crate::game::{GameAction, GameState};
+use crate::generator::generate;
+use crate::haptics::Haptic;
+use crate::keybindings::Action;
+use crate::save::next_slot_id;
+use crate::settings::{InputMode, PadLayout};
+use crate::toast::{ToastAction, ToastKind};
+use crate::undo_tree::UndoTree;
+use crate::utils::{create_sudoku, format_duration, is_solved};
+
+/// Hook that returns a function placing a value in a cell.
+///
+/// The function takes the index of the cell and the value,
+/// where 0 erases the cell.
+/// Nothing happens if the cell is a clue or already has the value.
+/// Otherwise the move is recorded with [`GameAction::Place`],
+/// and placing a value gives [`Haptic`] feedback.
+/// A value that conflicts with another one is tracked as an
+/// [`Event::Conflict`].
+pub fn use_place_value() -> impl FnMut(u8, u8) + Copy {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut dispatch = use_dispatch();
+    let mut haptics = use_haptics();
+    let mut track = use_track();
+
+    move |index: u8, value: u8| {
+        if dispatch(GameAction::Place { index, value }) && value != 0 {
+            let board = game.peek().current();
+            let haptic = Haptic::of_placement(&board);
+            if haptic == Haptic::Conflict {
+                track(Event::Conflict {
+                    empty: 81 - board.iter().filter(|&&value| value != 0).count(),
+                });
+            }
+            haptics(haptic);
+        }
+    }
+}
+
+/// Hook that returns a function erasing a cell.
+///
+/// The function takes the index of the cell.
+/// A cell with a value is emptied with [`use_place_value`],
+/// which shows again the notes the value was hiding.
+/// An empty cell has its notes cleared instead,
+/// with [`GameAction::ToggleNote`].
+/// Nothing happens if the cell is a clue.
+pub fn use_erase_cell() -> impl FnMut(u8) + Copy {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut place_value = use_place_value();
+    let mut dispatch = use_dispatch();
+
+    move |index: u8| {
+        let cell = index as usize;
+        if game.peek().current()[cell] != 0 {
+            place_value(index, 0);
+        } else {
+            dispatch(GameAction::ToggleNote { index, digit: 0 });
+        }
+    }
+}
+
+/// Hook that returns a function entering a number, as the number pad does.
+///
+/// In the number-first [`InputMode`], the function picks the number as the
+/// [`ActiveDigit`] instead, or drops it if it was already picked.
+/// In the cell-first input mode, the number is placed in the clicked cell,
+/// if mutable, and becomes the [`ActiveDigit`],
+/// or the cell is erased with [`use_erase_cell`] by the number 0.
+/// If the clicked cell is the [`NoteCell`], the number is toggled in its
+/// notes instead, or its notes are cleared by the number 0,
+/// with [`GameAction::ToggleNote`], and the cell goes back to value entry.
+pub fn use_enter_number() -> impl FnMut(u8) + Copy {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut active_digit = use_context::<Signal<ActiveDigit>>();
+    let mut note_cell = use_context::<Signal<NoteCell>>();
+    let mut place_value = use_place_value();
+    let mut erase_cell = use_erase_cell();
+    let mut dispatch = use_dispatch();
+
+    move |number: u8| {
+        let (clicked, mutable) = (game.peek().clicked, game.peek().mutable());
+
+        if settings.peek().0.input_mode == InputMode::NumberFirst {
+            let digit = &mut active_digit.write().0;
+            *digit = if *digit == Some(number) {
+                None
+            } else {
+                Some(number)
+            };
+        }
+        // if the cell takes notes, only for this digit
+        else if mutable && note_cell.peek().0 == Some(clicked) {
+            dispatch(GameAction::ToggleNote {
+                index: clicked,
+                digit: number,
+            });
+            note_cell.set(NoteCell(None));
+        }
+        // if the cell is mutable, and erased
+        else if mutable && number == 0 {
+            erase_cell(clicked);
+        }
+        // if the cell is mutable
+        else if mutable {
+            place_value(clicked, number);
+            // the last digit entered stays highlighted
+            active_digit.write().0 = Some(number);
+        }
+    }
+}
+
+/// Hook that returns a function undoing the last move,
+/// with [`GameAction::Undo`].
+///
+/// The undone move can be redone with [`use_redo`],
+/// and after a new move is made,
+/// it stays on its own branch of the
+/// [`UndoTreePanel`](crate::components::undo_tree::UndoTreePanel).
+/// The cell changed by the move is clicked.
+pub fn use_undo() -> impl FnMut() + Copy {
+    let mut dispatch = use_dispatch();
+
+    move || {
+        dispatch(GameAction::Undo);
+    }
+}
+
+/// Hook that returns a function undoing the moves back to the last state
+/// without mistakes, with [`GameAction::UndoMistakes`].
+///
+/// The undone moves can be redone with [`use_redo`].
+/// If the board has no mistakes, a toast says so.
+pub fn use_undo_mistakes() -> impl FnMut() + Copy {
+    let mut dispatch = use_dispatch();
+    let mut toast = use_toast();
+
+    move || {
+        if !dispatch(GameAction::UndoMistakes) {
+            toast(ToastKind::Info, "No mistakes to undo".to_string());
+        }
+    }
+}
+
+/// Hook that returns a function redoing the last move undone with
+/// [`use_undo`], with [`GameAction::Redo`].
+///
+/// The cell changed by the move is clicked.
+pub fn use_redo() -> impl FnMut() + Copy {
+    let mut dispatch = use_dispatch();
+
+    move || {
+        dispatch(GameAction::Redo);
+    }
+}
+
+/// Hook that returns a function giving the user a hint,
+/// with [`GameAction::Hint`].
+///
+/// The function fills a cell with a value,
+/// after removing all the conflicting cells, if any,
+/// and clicks the cell.
+/// Nothing happens once the hints of the
+/// [`hint_budget`](crate::settings::Settings::hint_budget) are used.
+pub fn use_hint() -> impl FnMut() + Copy {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut hints_used = use_context::<Signal<HintsUsed>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut dispatch = use_dispatch();
+    let mut track = use_track();
+
+    move || {
+        // no hints are left
+        if settings
+            .peek()
+            .0
+            .hint_budget
+            .is_some_and(|budget| hints_used.peek().0 >= budget)
+        {
+            return;
+        }
+
+        if dispatch(GameAction::Hint) {
+            hints_used.write().0 += 1;
+            track(Event::HintUsed {
+                empty: 81
+                    - game
+                        .peek()
+                        .current()
+                        .iter()
+                        .filter(|&&value| value != 0)
+                        .count(),
+            });
+        }
+    }
+}
+
+/// Component Props for [`NumberButton`]
+///
+/// - `number: u8`: the value to be rendered in the button and also the value
+///   that dictates what the button will write to a mutable cell.
+#[derive(Props, Copy, Clone, PartialEq, Eq)]
+struct NumberButtonProps {
+    number: u8,
+}
+
+/// Component to render a number button
+///
+/// This component renders buttons that are used to interact with the board.
+/// They work by assigning value to a mutable cell,
+/// with [`use_enter_number`].
+///
+/// The number 0 is a special button that renders as a delete icon.
+/// It adds the number 0 to a mutable cell which represents an empty cell.
+///
+/// A digit button erases every value of its digit that is not a clue,
+/// in a single move, with [`GameAction::EraseDigit`],
+/// on a context-click on desktop, or a long-press on touch screens.
+///
+/// The button of the [`ActiveDigit`] is highlighted.
+/// The digits are shown in the [`SymbolSet`](crate::symbols::SymbolSet)
+/// of the settings.
+#[component]
+fn NumberButton(props: NumberButtonProps) -> Element {
+    let number = props.number;
+    let class: &str = match number {
+        0 => "input icon delete",
+        _ => "input number",
+    };
+
+    // Unpack shared states
+    let settings = use_context::<Signal<UserSettings>>();
+    let active_digit = use_context::<Signal<ActiveDigit>>();
+    let mut enter_number = use_enter_number();
+    let mut dispatch = use_dispatch();
+    let mut toast = use_toast();
+    // when the touch started, if it can become a long-press
+    let mut touch_start = use_signal(|| None::<u64>);
+    // whether the touch that just ended erased the digit, so it is no click
+    let mut long_pressed = use_signal(|| false);
+
+    // the delete button keeps its number, hidden by its icon
+    let symbol = match number {
+        0 => "0",
+        _ => settings.read().0.symbols.symbol(number),
+    };
+
+    let mut erase_digit = move || {
+        if number != 0 && dispatch(GameAction::EraseDigit(number)) {
+            toast(
+                ToastKind::Info,
+                format!("Erased every {symbol} you entered"),
+            );
+        }
+    };
+
+    let active = if active_digit.read().0 == Some(number) {
+        "active"
+    } else {
+        ""
+    };
+
+    rsx!(
+        button {
+            class: "{class} {active}",
+            onclick: move |_| {
+                if !long_pressed.take() {
+                    enter_number(number);
+                }
+            },
+            prevent_default: "oncontextmenu",
+            oncontextmenu: move |_| erase_digit(),
+            ontouchstart: move |event| {
+                long_pressed.set(false);
+                touch_start.set((event.touches().len() == 1).then(now));
+            },
+            ontouchmove: move |_| touch_start.set(None),
+            ontouchend: move |_| {
+                let held = touch_start.take().map(|start| now().saturating_sub(start));
+                if number != 0 && held.is_some_and(|held| held >= LONG_PRESS_MILLIS) {
+                    long_pressed.set(true);
+                    erase_digit();
+                }
+            },
+            "{symbol}"
+        }
+    )
+}
+
+/// Component Props for [`NumberPad`]
+///
+/// - `layout: PadLayout`: how the number buttons are laid out.
+#[derive(Props, Copy, Clone, PartialEq, Eq)]
+pub struct NumberPadProps {
+    layout: PadLayout,
+}
+
+/// Component to render the number pad
+///
+/// This component renders a number button for every digit and the
+/// delete button, in the order and the shape of the [`PadLayout`].
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn NumberPad(props: NumberPadProps) -> Element {
+    let layout = props.layout;
+
+    rsx!(div {
+        class: "number-pad {layout.css_name()}",
+        for number in layout.numbers() {
+            NumberButton {
+                number: number
+            }
+        }
+    })
+}
+
+/// Hook that returns a function resetting the board to a puzzle,
+/// with [`GameAction::NewGame`].
+///
+/// The function takes the initial puzzle to play.
+/// The moves, notes, timer and hints used are dropped,
+/// and no cell is clicked anymore.
+pub fn use_reset_board() -> impl FnMut(SudokuState) + Copy {
+    // Unpack shared states
+    let mut timer = use_context::<Signal<GameTimer>>();
+    let mut note_cell = use_context::<Signal<NoteCell>>();
+    let mut hints_used = use_context::<Signal<HintsUsed>>();
+    let mut dispatch = use_dispatch();
+
+    move |puzzle: SudokuState| {
+        // resetting the board and the notes with the puzzle
+        dispatch(GameAction::NewGame(puzzle));
+        note_cell.set(NoteCell(None));
+        // resetting the timer and the hints
+        timer.write().0 = 0;
+        hints_used.write().0 = 0;
+    }
+}
+
+/// How many games replaced by a new one are kept, older ones are dropped
+const MAX_REPLACED_GAMES: usize = 5;
+
+/// A game replaced by a new one, kept so the new game can be undone
+///
+/// - `history: UndoTree`: its moves, on every branch.
+/// - `notes: SudokuNotes`: its notes.
+/// - `timer: u64`: its [`GameTimer`].
+/// - `hints_used: u32`: its [`HintsUsed`].
+/// - `slot: u32`: its save slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplacedGame {
+    pub history: UndoTree,
+    pub notes: SudokuNotes,
+    pub timer: u64,
+    pub hints_used: u32,
+    pub slot: u32,
+}
+
+/// Shared State for the games replaced by a new one
+///
+/// Represents globally across the app the games with progress that a new
+/// game replaced, the last one at the end,
+/// so an accidental new game can be undone from its toast.
+#[derive(Debug, Clone, Default)]
+pub struct ReplacedGames(pub Vec<ReplacedGame>);
+
+/// Hook that returns a function starting a new game with a puzzle.
+///
+/// The function takes the initial puzzle of the new game.
+/// The current game is tracked as abandoned, if it has progress,
+/// and stays in its save slot while the new one gets its own.
+/// A game with progress is also kept in the [`ReplacedGames`],
+/// and a toast offers to bring it back with [`use_undo_new_game`].
+pub fn use_new_game() -> impl FnMut(SudokuState) + Copy {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let hints_used = use_context::<Signal<HintsUsed>>();
+    let slots = use_context::<Signal<SaveSlots>>();
+    let mut current_slot = use_context::<Signal<CurrentSlot>>();
+    let mut replaced = use_context::<Signal<ReplacedGames>>();
+    let mut track = use_track();
+    let mut reset_board = use_reset_board();
+    let mut action_toast = use_action_toast();
+
+    move |puzzle: SudokuState| {
+        let game = game.peek().clone();
+        if let Some(event) = Event::abandoned(&game.initial, &game.current(), timer.peek().0) {
+            track(event);
+        }
+
+        // keeping the current game, if it has progress, to undo the new one
+        let progress = game.has_progress();
+        if progress {
+            let mut replaced = replaced.write();
+            replaced.0.push(ReplacedGame {
+                history: game.history,
+                notes: game.notes,
+                timer: timer.peek().0,
+                hints_used: hints_used.peek().0,
+                slot: current_slot.peek().0,
+            });
+            if replaced.0.len() > MAX_REPLACED_GAMES {
+                replaced.0.remove(0);
+            }
+        }
+
+        // resetting the board with the new puzzle
+        track(Event::PuzzleStarted {
+            difficulty: grade_puzzle(&puzzle),
+        });
+        reset_board(puzzle);
+        // playing in a new save slot
+        current_slot.write().0 = next_slot_id(&slots.peek().0);
+
+        if progress {
+            action_toast(
+                ToastKind::Info,
+                "Game replaced".to_string(),
+                ToastAction::UndoNewGame,
+            );
+        }
+    }
+}
+
+/// Hook that returns a function bringing back the last game replaced by a
+/// new one, with [`GameAction::Resume`].
+///
+/// The game is taken from the [`ReplacedGames`] with its moves, notes,
+/// timer and hints used, and is played again in its save slot.
+/// The new game stays in its own slot, if it has progress.
+pub fn use_undo_new_game() -> impl FnMut() + Copy {
+    // Unpack shared states
+    let mut replaced = use_context::<Signal<ReplacedGames>>();
+    let mut timer = use_context::<Signal<GameTimer>>();
+    let mut note_cell = use_context::<Signal<NoteCell>>();
+    let mut hints_used = use_context::<Signal<HintsUsed>>();
+    let mut current_slot = use_context::<Signal<CurrentSlot>>();
+    let mut dispatch = use_dispatch();
+
+    move || {
+        let Some(game) = replaced.write().0.pop() else {
+            return;
+        };
+        // the slot is set first, so the new game is not saved to it
+        current_slot.write().0 = game.slot;
+        dispatch(GameAction::Resume {
+            history: game.history,
+            notes: game.notes,
+        });
+        note_cell.set(NoteCell(None));
+        timer.write().0 = game.timer;
+        hints_used.write().0 = game.hints_used;
+    }
+}
+
+/// JavaScript that sends `true` to Rust once the page was painted,
+/// so the browser shows the loading indicator before a puzzle is generated
+const NEXT_FRAME: &str = r"
+    requestAnimationFrame(() => setTimeout(() => dioxus.send(true)));
+";
+
+/// Hook that returns a function generating a new puzzle and starting a new
+/// game with it, with [`use_new_game`].
+///
+/// The function takes the difficulty of the new puzzle,
+/// or `None` for a puzzle of any difficulty,
+/// which is easy in the kids mode, see [`Settings::new_game_difficulty`](crate::settings::Settings::new_game_difficulty).
+/// Puzzles of a difficulty are generated with [`generate`],
+/// which asks the server when the device is too slow to find one.
+/// [`Generating`] is set until the new game starts,
+/// so the board shows a loading indicator instead of appearing frozen.
+/// Nothing happens while another puzzle is being generated.
+pub fn use_generate_game() -> impl FnMut(Option<Difficulty>) + Copy {
+    // Unpack shared states
+    let mut generating = use_context::<Signal<Generating>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut new_game = use_new_game();
+
+    move |difficulty: Option<Difficulty>| {
+        if generating.peek().0 {
+            return;
+        }
+        let difficulty = settings.peek().0.new_game_difficulty(difficulty);
+        generating.write().0 = true;
+        spawn(async move {
+            // letting the loading indicator be painted first
+            let mut frame = eval(NEXT_FRAME);
+            if let Err(err) = frame.recv().await {
+                log::error!("failed to wait for the next frame: {err:?}");
+            }
+            let puzzle = match difficulty {
+                Some(difficulty) => generate(difficulty).await,
+                None => create_sudoku(),
+            };
+            new_game(puzzle);
+            generating.write().0 = false;
+        });
+    }
+}
+
+/// Hook that returns a function telling if discarding the current game
+/// must be confirmed with a [`ConfirmDialog`].
+///
+/// It must if the game has moves or notes,
+/// unless confirmations are turned off in the settings.
+pub fn use_needs_confirm() -> impl Fn() -> bool + Copy {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let settings = use_context::<Signal<UserSettings>>();
+
+    move || game.peek().has_progress() && settings.peek().0.confirm_discard
+}
+
+/// Component to render a new button
+///
+/// This component renders a "New Game" [`ActionButton`].
+/// When activated, all current state is dropped and the board is drawn with a
+/// fresh new puzzle for the user, with [`use_generate_game`].
+///
+/// It is disabled while a puzzle is being generated.
+///
+/// If the current game has progress, a [`ConfirmDialog`] asks first.
+#[component]
+fn NewButton() -> Element {
+    // Unpack shared states
+    let generating = use_context::<Signal<Generating>>();
+    let mut generate_game = use_generate_game();
+    let needs_confirm = use_needs_confirm();
+    let mut confirming = use_signal(|| false);
+
+    let mut start = move || {
+        confirming.set(false);
+        generate_game(None);
+    };
+
+    rsx!(
+        ActionButton {
+            icon: "new",
+            label: "New game",
+            tooltip: "Start a new puzzle",
+            disabled: generating.read().0,
+            onclick: move |()| {
+                if needs_confirm() {
+                    confirming.set(true);
+                } else {
+                    start();
+                }
+            },
+        }
+        if confirming() {
+            ConfirmDialog {
+                message: "Start a new game? The current one stays in My games.",
+                action: "New game",
+                onconfirm: move |()| start(),
+                oncancel: move |()| confirming.set(false),
+            }
+        }
+    )
+}
+
+/// Component to render a restart button
+///
+/// This component renders a "Restart" [`ActionButton`].
+/// When activated, the moves, notes and timer of the current puzzle are
+/// dropped, so it is played again from the start, in the same save slot.
+/// It is disabled until the game has moves or notes.
+///
+/// If the current game has progress, a [`ConfirmDialog`] asks first.
+#[component]
+fn RestartButton() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut reset_board = use_reset_board();
+    let needs_confirm = use_needs_confirm();
+    let mut confirming = use_signal(|| false);
+
+    let mut restart = move || {
+        confirming.set(false);
+        reset_board(game.peek().initial);
+    };
+    let progress = game.read().has_progress();
+
+    rsx!(
+        ActionButton {
+            icon: "restart",
+            label: "Restart",
+            tooltip: "Play this puzzle again from the start",
+            disabled: !progress,
+            onclick: move |()| {
+                if needs_confirm() {
+                    confirming.set(true);
+                } else {
+                    restart();
+                }
+            },
+        }
+        if confirming() {
+            ConfirmDialog {
+                message: "Restart this puzzle? All your moves and notes are lost.",
+                action: "Restart",
+                onconfirm: move |()| restart(),
+                oncancel: move |()| confirming.set(false),
+            }
+        }
+    )
+}
+
+/// Component to render an undo button
+///
+/// This component renders a "Undo" [`ActionButton`].
+/// When activated, the last move is undone with [`use_undo`].
+/// It is disabled at the start of the game and in the pass-and-play mode.
+#[component]
+fn UndoButton() -> Element {
+    // Unpack shared states
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut undo = use_undo();
+
+    rsx!(ActionButton {
+        icon: "undo",
+        label: "Undo",
+        tooltip: "Undo the last move",
+        shortcut: Action::Undo,
+        disabled: game.read().moves().len() < 2 || hot_seat.read().0.is_some(),
+        onclick: move |()| undo(),
+    })
+}
+
+/// Component to render an undo mistakes button
+///
+/// This component renders an "Undo mistakes" [`ActionButton`].
+/// When activated, the moves are undone back to the last state without
+/// mistakes, with [`use_undo_mistakes`].
+/// It is disabled at the start of the game and in the pass-and-play mode.
+#[component]
+fn UndoMistakesButton() -> Element {
+    // Unpack shared states
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut undo_mistakes = use_undo_mistakes();
+
+    rsx!(ActionButton {
+        icon: "undo-mistakes",
+        label: "Undo mistakes",
+        tooltip: "Undo the moves back to the last board without mistakes",
+        disabled: game.read().moves().len() < 2 || hot_seat.read().0.is_some(),
+        onclick: move |()| undo_mistakes(),
+    })
+}
+
+/// Component to render a redo button
+///
+/// This component renders a "Redo" [`ActionButton`].
+/// When activated, the last undone move is redone with [`use_redo`].
+/// It is disabled unless the current board has a move to redo,
+/// and in the pass-and-play mode.
+#[component]
+fn RedoButton() -> Element {
+    // Unpack shared states
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut redo = use_redo();
+
+    rsx!(ActionButton {
+        icon: "redo",
+        label: "Redo",
+        tooltip: "Redo the last undone move",
+        shortcut: Action::Redo,
+        disabled: !game.read().history.can_redo() || hot_seat.read().0.is_some(),
+        onclick: move |()| redo(),
+    })
+}
+
+/// Component to render a hint button
+///
+/// This component renders a "Hint" [`ActionButton`].
+/// When activated, the button will give the user a hint by filling a cell
+/// with a value, with [`use_hint`].
+/// It is disabled once the puzzle is filled or the hints of the
+/// [`hint_budget`](crate::settings::Settings::hint_budget) are used,
+/// and in the pass-and-play mode.
+#[component]
+pub fn HintButton() -> Element {
+    // Unpack shared states
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+    let game = use_context::<Signal<GameState>>();
+    let hints_used = use_context::<Signal<HintsUsed>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut hint = use_hint();
+
+    let exhausted = settings
+        .read()
+        .0
+        .hint_budget
+        .is_some_and(|budget| hints_used.read().0 >= budget);
+    let filled = !game.read().current().contains(&0);
+
+    rsx!(ActionButton {
+        icon: "hint",
+        label: "Hint",
+        tooltip: "Fill a cell",
+        shortcut: Action::Hint,
+        disabled: exhausted || filled || hot_seat.read().0.is_some(),
+        onclick: move |()| hint(),
+    })
+}
+
+/// Component to render a fill singles button
+///
+/// This component renders a "Fill singles" [`ActionButton`] that fills
+/// every empty cell with a single candidate, in a single move,
+/// with [`GameAction::FillSingles`],
+/// for the players who enjoy the hard deductions but not the cleanup.
+/// It is disabled once the puzzle is filled and in the pass-and-play mode.
+#[component]
+fn SinglesButton() -> Element {
+    // Unpack shared states
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut dispatch = use_dispatch();
+    let mut toast = use_toast();
+
+    let filled = !game.read().current().contains(&0);
+
+    rsx!(ActionButton {
+        icon: "singles",
+        label: "Fill singles",
+        tooltip: "Fill every cell where a single digit fits",
+        disabled: filled || hot_seat.read().0.is_some(),
+        onclick: move |()| {
+            if !dispatch(GameAction::FillSingles) {
+                toast(
+                    ToastKind::Info,
+                    "No cell has a single candidate".to_string(),
+                );
+            }
+        },
+    })
+}
+
+/// Component to render a heatmap button
+///
+/// This component renders a "Heatmap" [`ActionButton`] that shows or hides
+/// the [`Heatmap`] overlay,
+/// which colors the empty cells by their number of candidates.
+#[component]
+fn HeatmapButton() -> Element {
+    // Unpack shared states
+    let mut heatmap = use_context::<Signal<Heatmap>>();
+
+    let on = heatmap.read().0;
+
+    rsx!(ActionButton {
+        icon: if on { "heatmap on" } else { "heatmap" },
+        label: if on { "Hide heatmap" } else { "Heatmap" },
+        tooltip: "Color the empty cells by how many digits fit them",
+        shortcut: Action::ToggleHeatmap,
+        onclick: move |()| heatmap.write().0 = !on,
+    })
+}
+
+/// Component to render the trial buttons
+///
+/// This component renders a "Try a line" [`ActionButton`] that starts a
+/// trial, whose entries are tinted on the board.
+/// During the trial, it renders a "Keep trial" button that commits its
+/// moves to the history, and a "Discard trial" button that drops them all.
+#[component]
+fn TrialButtons() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut dispatch = use_dispatch();
+    let mut toast = use_toast();
+
+    if game.read().trial.is_none() {
+        return rsx!(ActionButton {
+            icon: "trial",
+            label: "Try a line",
+            tooltip: "Start a trial, whose moves can be kept or discarded at once",
+            onclick: move |()| {
+                if dispatch(GameAction::StartTrial) {
+                    toast(
+                        ToastKind::Info,
+                        "Trial started: the new entries are tinted".to_string(),
+                    );
+                }
+            },
+        });
+    }
+
+    rsx!(
+        ActionButton {
+            icon: "keep-trial",
+            label: "Keep trial",
+            tooltip: "Keep the moves of the trial",
+            onclick: move |()| {
+                dispatch(GameAction::CommitTrial);
+            },
+        }
+        ActionButton {
+            icon: "discard-trial",
+            label: "Discard trial",
+            tooltip: "Discard every move of the trial",
+            onclick: move |()| {
+                if dispatch(GameAction::DiscardTrial) {
+                    toast(ToastKind::Info, "Trial discarded".to_string());
+                }
+            },
+        }
+    )
+}
+
+/// JavaScript that shares a puzzle link with the Web Share API,
+/// falling back to copying it to the clipboard where unsupported
+///
+/// It sends to Rust whether the link was copied.
+const SHARE: &str = r#"
+    const { text, fragment } = await dioxus.recv();
+    const url = `${location.origin}${location.pathname}#${fragment}`;
+    if (navigator.share) {
+        await navigator.share({ title: "Sudoku", text, url }).catch(() => {});
+        dioxus.send(false);
+    } else {
+        dioxus.send(await navigator.clipboard.writeText(`${text} ${url}`).then(() => true, () => false));
+    }
+"#;
+
+/// Component to render a share button
+///
+/// This component renders a "Share" button.
+/// When activated, a link to the current puzzle is shared along with a
+/// summary of the user's result.
+/// The native share sheet is used where supported,
+/// otherwise the link is copied to the clipboard.
+///
+/// The shared link only contains the initial puzzle, not the user's
+/// progress.
+/// A toast tells the user when the link was copied.
+#[component]
+pub fn ShareButton() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let mut toast = use_toast();
+
+    rsx!(ActionButton {
+        icon: "share",
+        label: "Share",
+        tooltip: "Share a link to this puzzle",
+        onclick: move |()| {
+            let initial = game.read().initial;
+            let fragment = encode_game(&GameSnapshot {
+                initial,
+                current: initial,
+                notes: [0; 81],
+            });
+
+            let text = if is_solved(&game.read().current()) {
+                format!(
+                    "I solved this Sudoku puzzle in {}",
+                    format_duration(timer.read().0)
+                )
+            } else {
+                "Can you solve this Sudoku puzzle?".to_string()
+            };
+
+            let mut share = eval(SHARE);
+            if let Err(err) = share.send(serde_json::json!({ "text": text, "fragment": fragment }))
+            {
+                log::error!("failed to share the puzzle: {err:?}");
+                return;
+            }
+            spawn(async move {
+                if share
+                    .recv()
+                    .await
+                    .is_ok_and(|copied| copied.as_bool() == Some(true))
+                {
+                    toast(
+                        ToastKind::Success,
+                        "Puzzle link copied to the clipboard".to_string(),
+                    );
+                }
+            });
+        },
+    })
+}
+
+/// Which buttons of the [`ControlPanel`] are rendered, besides the number pad
+///
+/// - `hint: bool`: the [`HintButton`].
+/// - `explain: bool`: the [`ExplainButton`].
+/// - `singles: bool`: the fill singles button.
+/// - `heatmap: bool`: the heatmap button.
+/// - `undo: bool`: the undo button.
+/// - `undo_mistakes: bool`: the undo mistakes button.
+/// - `redo: bool`: the redo button.
+/// - `undo_tree: bool`: the undo tree button.
+/// - `trial: bool`: the trial buttons.
+/// - `bookmarks: bool`: the bookmarks button.
+/// - `new: bool`: the new game button.
+/// - `restart: bool`: the restart button.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ControlBarConfig {
+    pub hint: bool,
+    pub explain: bool,
+    pub singles: bool,
+    pub heatmap: bool,
+    pub undo: bool,
+    pub undo_mistakes: bool,
+    pub redo: bool,
+    pub undo_tree: bool,
+    pub trial: bool,
+    pub bookmarks: bool,
+    pub new: bool,
+    pub restart: bool,
+}
+
+impl ControlBarConfig {
+    /// All the buttons
+    pub const FULL: Self = Self {
+        hint: true,
+        explain: true,
+        singles: true,
+        heatmap: true,
+        undo: true,
+        undo_mistakes: true,
+        redo: true,
+        undo_tree: true,
+        trial: true,
+        bookmarks: true,
+        new: true,
+        restart: true,
+    };
+
+    /// The number pad only, for an
+    /// [`Embedded`](crate::components::embed::Embedded) game
+    pub const NUMBER_PAD: Self = Self {
+        hint: false,
+        explain: false,
+        singles: false,
+        heatmap: false,
+        undo: false,
+        undo_mistakes: false,
+        redo: false,
+        undo_tree: false,
+        trial: false,
+        bookmarks: false,
+        new: false,
+        restart: false,
+    };
+}
+
+impl Default for ControlBarConfig {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// Component Props for [`ControlPanel`]
+///
+/// - `config: ControlBarConfig`: the buttons to render,
+///   all of them by default.
+#[derive(Props, Copy, Clone, PartialEq, Eq)]
+pub struct ControlPanelProps {
+    #[props(default)]
+    config: ControlBarConfig,
+}
+
+/// Component to render the control panel
+///
+/// This component renders the [`NumberPad`], laid out as set in the settings,
+/// and the buttons to play the game enabled in the [`ControlBarConfig`].
+/// The buttons to share, import, export and configure games are in the
+/// [`Menu`](crate::components::menu::Menu).
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn ControlPanel(props: ControlPanelProps) -> Element {
+    let config = props.config;
+
+    // Unpack shared states
+    let layout = use_context::<Signal<UserSettings>>().read().0.pad_layout;
+
+    rsx!(
+        div {
+        class: "controls",
+
+        // Render the NumberButtons and the "DeleteButton", a.k.a number is 0
+        NumberPad {
+            layout: layout
+        }
+
+        if config.hint {
+            // Render HintButton
+            HintButton{}
+        }
+
+        if config.explain {
+            // Render ExplainButton
+            ExplainButton{}
+        }
+
+        if config.singles {
+            // Render SinglesButton
+            SinglesButton{}
+        }
+
+        if config.heatmap {
+            // Render HeatmapButton
+            HeatmapButton{}
+        }
+
+        if config.undo {
+            // Render UndoButton
+            UndoButton{}
+        }
+
+        if config.undo_mistakes {
+            // Render UndoMistakesButton
+            UndoMistakesButton{}
+        }
+
+        if config.redo {
+            // Render RedoButton
+            RedoButton{}
+        }
+
+        if config.undo_tree {
+            // Render UndoTreeButton
+            UndoTreeButton{}
+        }
+
+        if config.trial {
+            // Render TrialButtons
+            TrialButtons{}
+        }
+
+        if config.bookmarks {
+            // Render BookmarksButton
+            BookmarksButton{}
+        }
+
+        if config.new {
+            // Render NewButton
+            NewButton{}
+        }
+
+        if config.restart {
+            // Render RestartButton
+            RestartButton{}
+        }
+        }
+    )
+}
@@ -0,0 +1,207 @@
+//! # Daily Module
+//!
+//! The `daily` module contains the shared states of the daily puzzles and
+//! the archive of past daily puzzles.
+//!
+//! [`DailyButton`] toggles the [`DailyPanel`],
+//! a calendar of the daily puzzles of a month,
+//! where every completed day is marked as solved on the day or late.
+//! Any past day can be opened and completed late.
+//! The last standing on the daily leaderboard is shown below,
+//! from the cache, so it can be viewed offline.
+//!
+//! See also: [`daily`](crate::daily).
+
+use dioxus::prelude::*;
+
+use crate::browser::now;
+use crate::components::buttons::ActionButton;
+use crate::components::confirm::ConfirmDialog;
+use crate::components::controls::{use_needs_confirm, use_new_game};
+use crate::components::leaderboard::StandingTable;
+use crate::components::modal::Modal;
+use crate::components::profiles::ActiveProfile;
+use crate::daily::{
+    date_of, day_from_date, day_of, days_in_month, load_daily_log, weekday, MONTH_NAMES,
+};
+use crate::leaderboard::load_standings;
+use crate::utils::create_daily_sudoku;
+
+/// The initials of the days of the week, from Monday
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+/// Shared State for the daily puzzle
+///
+/// Represents globally across the app the day of the daily puzzle being
+/// played, in days since the Unix epoch,
+/// or `None` if it was not opened as a daily puzzle.
+#[derive(Debug, Clone)]
+pub struct DailyPuzzle(pub Option<u64>);
+
+/// Shared State for the daily archive
+///
+/// Represents globally across the app if the [`DailyPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct DailyOpen(pub bool);
+
+/// Component to render the daily archive button
+///
+/// This component renders a "Daily archive" button that opens and closes
+/// the [`DailyPanel`].
+#[component]
+pub fn DailyButton() -> Element {
+    // Unpack shared states
+    let mut daily_open = use_context::<Signal<DailyOpen>>();
+
+    rsx!(ActionButton {
+        icon: "daily",
+        label: "Daily archive",
+        tooltip: "Play the daily puzzles of past days",
+        onclick: move |()| {
+            let open = daily_open.read().0;
+            daily_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the daily archive
+///
+/// This component renders, in a [`Modal`], the calendar of a month,
+/// starting at the current one, with a button for every day.
+/// Days are marked as solved or late from the daily log of the active
+/// profile, loaded every time the calendar is rendered.
+/// Future days cannot be opened.
+/// The last standing submitted to the daily leaderboard is rendered in a
+/// [`StandingTable`].
+///
+/// Opening a day starts a new game with its puzzle.
+/// If the current game has progress, a [`ConfirmDialog`] asks first.
+#[component]
+pub fn DailyPanel() -> Element {
+    // Unpack shared states
+    let mut daily_open = use_context::<Signal<DailyOpen>>();
+    let mut daily = use_context::<Signal<DailyPuzzle>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut new_game = use_new_game();
+    let needs_confirm = use_needs_confirm();
+
+    let today = day_of(now());
+    let mut month = use_signal(|| {
+        let (year, month, _) = date_of(today);
+        (year, month)
+    });
+    // the day waiting for a confirmation
+    let mut pending = use_signal(|| None::<u64>);
+
+    if !daily_open.read().0 {
+        return None;
+    }
+
+    let mut open = move |day: u64| {
+        pending.set(None);
+        new_game(create_daily_sudoku(day));
+        daily.write().0 = Some(day);
+        daily_open.write().0 = false;
+    };
+
+    let (year, shown) = month();
+    let first = day_from_date(year, shown, 1);
+    let days = first..first + u64::from(days_in_month(year, shown));
+    let log = load_daily_log(profile.read().0);
+    let standing = load_standings(profile.read().0)
+        .0
+        .pop_last()
+        .map(|(_, standing)| standing);
+    let name = MONTH_NAMES[shown as usize - 1];
+    let current = (year, shown) == {
+        let (year, month, _) = date_of(today);
+        (year, month)
+    };
+
+    rsx!(
+        Modal {
+            class: "daily",
+            label: "Daily archive",
+            onclose: move |()| daily_open.write().0 = false,
+            span { "Daily archive" }
+            span {
+                class: "daily-month",
+                button {
+                    class: "input",
+                    aria_label: "Previous month",
+                    disabled: (year, shown) == (1970, 1),
+                    onclick: move |_| {
+                        month.set(if shown == 1 { (year - 1, 12) } else { (year, shown - 1) });
+                    },
+                    "‹"
+                }
+                span { "{name} {year}" }
+                button {
+                    class: "input",
+                    aria_label: "Next month",
+                    disabled: current,
+                    onclick: move |_| {
+                        month.set(if shown == 12 { (year + 1, 1) } else { (year, shown + 1) });
+                    },
+                    "›"
+                }
+            }
+            span {
+                class: "calendar",
+                for initials in WEEKDAYS {
+                    span { class: "weekday", "{initials}" }
+                }
+                for blank in 0..weekday(first) {
+                    span { key: "blank-{blank}" }
+                }
+                for day in days {
+                    button {
+                        key: "{day}",
+                        class: {
+                            let status = log.status(day).map_or("", |status| status.css_name());
+                            let today = if day == today { "today" } else { "" };
+                            format!("input {status} {today}")
+                        },
+                        disabled: day > today,
+                        aria_label: {
+                            let status = log
+                                .status(day)
+                                .map_or_else(String::new, |status| format!(", {}", status.label()));
+                            format!("{name} {}{status}", date_of(day).2)
+                        },
+                        onclick: move |_| {
+                            if needs_confirm() {
+                                pending.set(Some(day));
+                            } else {
+                                open(day);
+                            }
+                        },
+                        "{date_of(day).2}"
+                    }
+                }
+            }
+            if let Some(standing) = standing {
+                span {
+                    {
+                        let (_, month, day) = date_of(standing.day);
+                        format!("Leaderboard of {} {day}", MONTH_NAMES[month as usize - 1])
+                    }
+                }
+                StandingTable { standing: standing }
+            }
+            button {
+                class: "input",
+                onclick: move |_| daily_open.write().0 = false,
+                "Close"
+            }
+        }
+        if let Some(day) = pending() {
+            ConfirmDialog {
+                message: "Play the daily puzzle of {name} {date_of(day).2}? The current game stays in My games.",
+                action: "Play",
+                onconfirm: move |()| open(day),
+                oncancel: move |()| pending.set(None),
+            }
+        }
+    )
+}
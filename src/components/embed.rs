@@ -0,0 +1,57 @@
+//! # Embed Module
+//!
+//! The `embed` module contains the shared state of the embeddable widget
+//! mode and the [`use_completion_message`] hook, which tells the embedding
+//! page that the puzzle was solved.
+//!
+//! See also: [`embed`](crate::embed).
+
+use dioxus::prelude::*;
+
+use crate::browser::{now, post_to_parent};
+use crate::embed::EmbedMessage;
+use crate::formats::to_line;
+use crate::game::GameState;
+use crate::utils::is_solved;
+
+/// Shared State for the embed mode
+///
+/// Represents globally across the app if the game is embedded in another
+/// page, so only the board and the number pad are rendered.
+#[derive(Debug, Clone)]
+pub struct Embedded(pub bool);
+
+/// Hook that posts an [`EmbedMessage::Solved`] to the embedding page once
+/// the puzzle is solved.
+///
+/// The timer is not rendered in embed mode,
+/// so the time taken is measured from when the hook is first run.
+/// Nothing is posted unless the game is [`Embedded`].
+///
+/// ## Panics
+///
+/// The hook will panic if it cannot get the current Sudoku state.
+pub fn use_completion_message() {
+    // Unpack shared states
+    let embedded = use_context::<Signal<Embedded>>();
+    let game = use_context::<Signal<GameState>>();
+
+    let started_at = use_hook(now);
+    let mut posted = use_signal(|| false);
+
+    use_effect(move || {
+        let solved = is_solved(&game.read().current());
+        if !embedded.peek().0 || !solved || *posted.peek() {
+            return;
+        }
+        posted.set(true);
+        let message = EmbedMessage::Solved {
+            puzzle: to_line(&game.peek().initial, '.'),
+            millis: now().saturating_sub(started_at),
+        };
+        match serde_json::to_value(message) {
+            Ok(message) => post_to_parent(message),
+            Err(err) => log::error!("failed to serialize the message: {err:?}"),
+        }
+    });
+}
@@ -0,0 +1,146 @@
+//! # Export Module
+//!
+//! The `export` module contains the components used to export the current
+//! Sudoku position to other tools.
+//!
+//! [`ExportButton`] toggles the [`ExportPanel`], which shows the position in
+//! the line format and as a pencilmark grid with the candidates of every
+//! cell, ready to be copied or opened in the `SudokuWiki` solver.
+
+use dioxus::prelude::*;
+
+use crate::components::buttons::ActionButton;
+use crate::components::modal::Modal;
+use crate::components::settings::UserSettings;
+use crate::components::toast::use_toast;
+use crate::formats::{sudokuwiki_url, to_pencilmark_grid, to_symbol_line};
+use crate::game::GameState;
+use crate::toast::ToastKind;
+
+/// Shared State for the export panel
+///
+/// Represents globally across the app if the [`ExportPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct ExportOpen(pub bool);
+
+/// JavaScript that copies the text it receives to the clipboard,
+/// then sends whether it was copied to Rust
+const COPY: &str = r"
+    const text = await dioxus.recv();
+    dioxus.send(await navigator.clipboard.writeText(text).then(() => true, () => false));
+";
+
+/// Hook that returns a function copying a text to the clipboard.
+///
+/// A toast tells the user whether the text was copied.
+fn use_copy_to_clipboard() -> impl FnMut(String) + Copy {
+    let mut toast = use_toast();
+
+    move |text: String| {
+        let mut copy = eval(COPY);
+        if let Err(err) = copy.send(text.into()) {
+            log::error!("failed to copy to the clipboard: {err:?}");
+            return;
+        }
+        spawn(async move {
+            match copy.recv().await.map(|copied| copied.as_bool()) {
+                Ok(Some(true)) => toast(ToastKind::Success, "Copied to the clipboard".to_string()),
+                _ => toast(
+                    ToastKind::Error,
+                    "Could not copy to the clipboard".to_string(),
+                ),
+            }
+        });
+    }
+}
+
+/// Component to render the export button
+///
+/// This component renders an "Export" button that opens and closes the
+/// [`ExportPanel`].
+#[component]
+pub fn ExportButton() -> Element {
+    // Unpack shared states
+    let mut export_open = use_context::<Signal<ExportOpen>>();
+
+    rsx!(ActionButton {
+        icon: "export",
+        label: "Export",
+        tooltip: "Export the puzzle as text or an image",
+        onclick: move |()| {
+            let open = export_open.read().0;
+            export_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the export panel
+///
+/// This component renders, in a [`Modal`], the current position in the line format,
+/// with [`to_symbol_line`] in the symbols of the settings,
+/// and as a pencilmark grid, with [`to_pencilmark_grid`],
+/// together with buttons to copy them and a link to analyze the position
+/// in the `SudokuWiki` solver.
+/// The link carries the digits only, as the panel tells the user,
+/// so the notes are left out of it.
+///
+/// ## Panics
+///
+/// The component will panic if it cannot get the current Sudoku state.
+#[component]
+pub fn ExportPanel() -> Element {
+    // Unpack shared states
+    let mut export_open = use_context::<Signal<ExportOpen>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut copy_to_clipboard = use_copy_to_clipboard();
+
+    if !export_open.read().0 {
+        return None;
+    }
+
+    let current_sudoku = game.read().current();
+    let line = to_symbol_line(&current_sudoku, '.', settings.read().0.symbols);
+    let grid = to_pencilmark_grid(&current_sudoku, &game.read().notes);
+    let url = sudokuwiki_url(&current_sudoku);
+
+    rsx!(Modal {
+        class: "export",
+        label: "Export",
+        onclose: move |()| export_open.write().0 = false,
+        span { "Line format" }
+        textarea {
+            readonly: true,
+            rows: 1,
+            value: "{line}",
+        }
+        button {
+            class: "input",
+            onclick: move |_| copy_to_clipboard(line.clone()),
+            "Copy line"
+        }
+        span { "Pencilmarks" }
+        textarea {
+            readonly: true,
+            rows: 13,
+            value: "{grid}",
+        }
+        button {
+            class: "input",
+            onclick: move |_| copy_to_clipboard(grid.clone()),
+            "Copy pencilmarks"
+        }
+        a {
+            href: "{url}",
+            target: "_blank",
+            rel: "noopener noreferrer",
+            "Open in SudokuWiki"
+        }
+        span { class: "export-note", "SudokuWiki opens the digits only and works out its own candidates, not your notes." }
+        button {
+            class: "input",
+            onclick: move |_| export_open.write().0 = false,
+            "Close"
+        }
+    })
+}
@@ -0,0 +1,249 @@
+//! # Favorites Module
+//!
+//! The `favorites` module contains the shared state and the list of the
+//! favorite puzzles of the active profile.
+//!
+//! [`FavoriteButton`] stars, or unstars, the puzzle being played,
+//! and [`FavoritesButton`] toggles the [`FavoritesPanel`],
+//! which lists the starred puzzles to replay them later.
+//!
+//! The favorites are kept next to the history,
+//! see [`Favorite`].
+
+use dioxus::prelude::*;
+
+use crate::browser::now;
+use crate::components::buttons::ActionButton;
+use crate::components::confirm::ConfirmDialog;
+use crate::components::controls::{use_needs_confirm, use_new_game};
+use crate::components::games::Thumbnail;
+use crate::components::modal::Modal;
+use crate::components::profiles::ActiveProfile;
+use crate::components::toast::use_toast;
+use crate::daily::{date_of, day_of, MONTH_NAMES};
+use crate::difficulty::grade_puzzle;
+use crate::formats::to_line;
+use crate::game::GameState;
+use crate::storage::{add_favorite, load_favorites, remove_favorite, Favorite};
+use crate::toast::ToastKind;
+
+/// Shared State for the favorite puzzles
+///
+/// Represents globally across the app the favorites of the active profile,
+/// most recently starred first.
+#[derive(Debug, Clone)]
+pub struct Favorites(pub Vec<Favorite>);
+
+/// Shared State for the favorites panel
+///
+/// Represents globally across the app if the [`FavoritesPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct FavoritesOpen(pub bool);
+
+/// Hook that provides the [`Favorites`] shared state.
+///
+/// The favorites are loaded from the storage on start,
+/// and again whenever the active profile changes.
+pub fn use_favorites() {
+    // Unpack shared states
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut favorites = use_context_provider(|| Signal::new(Favorites(vec![])));
+
+    use_effect(move || {
+        let profile = profile.read().0;
+        spawn(async move {
+            match load_favorites(profile).await {
+                Ok(loaded) => favorites.set(Favorites(loaded)),
+                Err(err) => log::error!("failed to load the favorites: {err:?}"),
+            }
+        });
+    });
+}
+
+/// Component to render the star button
+///
+/// This component renders an "Add to favorites" button that stars the
+/// puzzle being played,
+/// or a "Remove from favorites" button if it is already starred.
+#[component]
+pub fn FavoriteButton() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut favorites = use_context::<Signal<Favorites>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut toast = use_toast();
+
+    let puzzle = game.read().initial;
+    let line = to_line(&puzzle, '.');
+    let starred = favorites
+        .read()
+        .0
+        .iter()
+        .any(|favorite| favorite.puzzle == line);
+
+    rsx!(ActionButton {
+        icon: if starred { "favorited" } else { "favorite" },
+        label: if starred {
+            "Remove from favorites"
+        } else {
+            "Add to favorites"
+        },
+        tooltip: if starred {
+            "Remove this puzzle from your favorites"
+        } else {
+            "Star this puzzle to replay it later"
+        },
+        onclick: move |()| {
+            let profile = profile.peek().0;
+            if starred {
+                favorites
+                    .write()
+                    .0
+                    .retain(|favorite| favorite.puzzle != line);
+                spawn(async move {
+                    if let Err(err) = remove_favorite(profile, &puzzle).await {
+                        log::error!("failed to remove the favorite: {err:?}");
+                    }
+                });
+            } else {
+                let favorite = Favorite::new(profile, &puzzle, grade_puzzle(&puzzle), now());
+                favorites.write().0.insert(0, favorite.clone());
+                spawn(async move {
+                    if let Err(err) = add_favorite(&favorite).await {
+                        log::error!("failed to add the favorite: {err:?}");
+                    }
+                });
+                toast(ToastKind::Success, "Added to favorites".to_string());
+            }
+        },
+    })
+}
+
+/// Component to render the favorites button
+///
+/// This component renders a "Favorites" button that opens and closes the
+/// [`FavoritesPanel`].
+#[component]
+pub fn FavoritesButton() -> Element {
+    // Unpack shared states
+    let mut favorites_open = use_context::<Signal<FavoritesOpen>>();
+
+    rsx!(ActionButton {
+        icon: "favorites",
+        label: "Favorites",
+        tooltip: "Replay the puzzles you starred",
+        onclick: move |()| {
+            let open = favorites_open.read().0;
+            favorites_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the favorites panel
+///
+/// This component lists, in a [`Modal`], the [`Favorites`] with their
+/// thumbnail, difficulty and the day they were starred.
+///
+/// Playing a favorite starts a new game with its puzzle.
+/// If the current game has progress, a [`ConfirmDialog`] asks first.
+#[component]
+pub fn FavoritesPanel() -> Element {
+    // Unpack shared states
+    let mut favorites_open = use_context::<Signal<FavoritesOpen>>();
+    let mut favorites = use_context::<Signal<Favorites>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut new_game = use_new_game();
+    let needs_confirm = use_needs_confirm();
+    // the puzzle waiting for a confirmation
+    let mut pending = use_signal(|| None::<Favorite>);
+
+    if !favorites_open.read().0 {
+        return None;
+    }
+
+    let mut play = move |favorite: Favorite| {
+        pending.set(None);
+        if let Some(puzzle) = favorite.puzzle() {
+            new_game(puzzle);
+            favorites_open.write().0 = false;
+        }
+    };
+
+    let current = to_line(&game.read().initial, '.');
+    let shown = favorites.read().0.clone();
+
+    rsx!(
+        Modal {
+            class: "favorites",
+            label: "Favorites",
+            onclose: move |()| favorites_open.write().0 = false,
+            span { "Favorites" }
+            if shown.is_empty() {
+                span { "No favorites yet. Star a puzzle to replay it later." }
+            }
+            span {
+                class: "games-list",
+                for favorite in shown {
+                    span {
+                        key: "{favorite.puzzle}",
+                        class: "game",
+                        if let Some(puzzle) = favorite.puzzle() {
+                            Thumbnail { initial: puzzle, current: puzzle }
+                        }
+                        span {
+                            {
+                                let (_, month, day) = date_of(day_of(favorite.added_at));
+                                let month = MONTH_NAMES[month as usize - 1];
+                                format!("{} · starred {month} {day}", favorite.difficulty)
+                            }
+                        }
+                        button {
+                            class: "input",
+                            disabled: favorite.puzzle == current,
+                            onclick: {
+                                let favorite = favorite.clone();
+                                move |_| {
+                                    if needs_confirm() {
+                                        pending.set(Some(favorite.clone()));
+                                    } else {
+                                        play(favorite.clone());
+                                    }
+                                }
+                            },
+                            "Play"
+                        }
+                        button {
+                            class: "input",
+                            onclick: move |_| {
+                                let profile = profile.peek().0;
+                                favorites.write().0.retain(|other| other.puzzle != favorite.puzzle);
+                                if let Some(puzzle) = favorite.puzzle() {
+                                    spawn(async move {
+                                        if let Err(err) = remove_favorite(profile, &puzzle).await {
+                                            log::error!("failed to remove the favorite: {err:?}");
+                                        }
+                                    });
+                                }
+                            },
+                            "Remove"
+                        }
+                    }
+                }
+            }
+            button {
+                class: "input",
+                onclick: move |_| favorites_open.write().0 = false,
+                "Close"
+            }
+        }
+        if let Some(favorite) = pending() {
+            ConfirmDialog {
+                message: "Play this {favorite.difficulty} puzzle? The current game stays in My games.",
+                action: "Play",
+                onconfirm: move |()| play(favorite.clone()),
+                oncancel: move |()| pending.set(None),
+            }
+        }
+    )
+}
@@ -0,0 +1,201 @@
+//! # Games Module
+//!
+//! The `games` module contains the shared state of the save slots and the
+//! "My games" list, which lets the user switch between several
+//! in-progress games.
+//!
+//! Every game with progress is kept in a [`SaveSlot`].
+//! [`GamesButton`] toggles the [`GamesPanel`],
+//! which lists the slots with a [`Thumbnail`] of the board,
+//! the difficulty and the progress of each game.
+//! The panel also opens when the app starts on a new puzzle while there are
+//! unfinished games, so any of them can be continued.
+
+use dioxus::prelude::*;
+
+use crate::app::SudokuState;
+use crate::browser::now;
+use crate::components::buttons::ActionButton;
+use crate::components::import::use_restore_game;
+use crate::components::modal::Modal;
+use crate::components::profiles::ActiveProfile;
+use crate::save::{
+    load_deleted_slots, store_deleted_slots, store_slots, DeletedSlot, SaveSlot, SavedGame,
+};
+use crate::utils::format_duration;
+
+/// Shared State for the save slots
+///
+/// Represents globally across the app the in-progress games,
+/// most recently played first.
+#[derive(Debug, Clone)]
+pub struct SaveSlots(pub Vec<SaveSlot>);
+
+/// Shared State for the save slot of the current game
+///
+/// Represents globally across the app the id of the [`SaveSlot`] the
+/// current game is saved to.
+#[derive(Debug, Clone)]
+pub struct CurrentSlot(pub u32);
+
+/// Shared State for the "My games" panel
+///
+/// Represents globally across the app if the [`GamesPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct GamesOpen(pub bool);
+
+/// Represents the properties of a [`Thumbnail`].
+///
+/// ## Fields
+///
+/// - `initial: SudokuState`: The initial puzzle, where non-zero cells are clues.
+/// - `current: SudokuState`: The current board.
+#[derive(Props, Clone, PartialEq, Eq)]
+pub struct ThumbnailProps {
+    initial: SudokuState,
+    current: SudokuState,
+}
+
+/// Component to render a thumbnail of a board
+///
+/// This component renders a miniature of the board as an SVG,
+/// with clues and the user's values in different colors.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn Thumbnail(props: ThumbnailProps) -> Element {
+    let ThumbnailProps { initial, current } = props;
+
+    rsx!(svg {
+        class: "thumbnail",
+        view_box: "0 0 9 9",
+        for (index, &value) in current.iter().enumerate() {
+            if value != 0 {
+                rect {
+                    key: "{index}",
+                    x: "{index % 9}",
+                    y: "{index / 9}",
+                    width: "1",
+                    height: "1",
+                    class: if initial[index] == 0 { "value" } else { "clue" },
+                }
+            }
+        }
+        path {
+            d: "M3 0v9M6 0v9M0 3h9M0 6h9",
+            stroke_width: "0.1",
+        }
+    })
+}
+
+/// Component to render the "My games" button
+///
+/// This component renders a "My games" button that opens and closes the
+/// [`GamesPanel`].
+#[component]
+pub fn GamesButton() -> Element {
+    // Unpack shared states
+    let mut games_open = use_context::<Signal<GamesOpen>>();
+
+    rsx!(ActionButton {
+        icon: "games",
+        label: "My games",
+        tooltip: "Switch between your games in progress",
+        onclick: move |()| {
+            let open = games_open.read().0;
+            games_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the "My games" panel
+///
+/// This component lists, in a [`Modal`], the [`SaveSlots`] with their thumbnail,
+/// difficulty, progress and time.
+/// Continuing a slot replaces the current game with it,
+/// keeping the current settings.
+/// Deleting a slot remembers it as a [`DeletedSlot`](crate::save::DeletedSlot),
+/// so a sync does not bring it back.
+/// The current game is already saved to its own slot,
+/// so it can be resumed later.
+#[component]
+pub fn GamesPanel() -> Element {
+    // Unpack shared states
+    let mut slots = use_context::<Signal<SaveSlots>>();
+    let mut current_slot = use_context::<Signal<CurrentSlot>>();
+    let mut games_open = use_context::<Signal<GamesOpen>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut restore_game = use_restore_game();
+
+    if !games_open.read().0 {
+        return None;
+    }
+
+    let current_id = current_slot.read().0;
+    let games = slots.read().0.clone();
+
+    rsx!(Modal {
+        class: "games",
+        label: "My games",
+        onclose: move |()| games_open.write().0 = false,
+        span { "My games" }
+        if games.is_empty() {
+            span { "No games in progress" }
+        }
+        span {
+            class: "games-list",
+            for slot in games {
+                span {
+                    key: "{slot.id}",
+                    class: "game",
+                    Thumbnail {
+                        initial: slot.game.initial,
+                        current: slot.game.current(),
+                    }
+                    span {
+                        "{slot.difficulty} · {progress(&slot.game)}% · {format_duration(slot.game.timer)}"
+                    }
+                    button {
+                        class: "input",
+                        disabled: slot.id == current_id,
+                        onclick: {
+                            let slot = slot.clone();
+                            move |_| {
+                                restore_game(slot.game.clone());
+                                current_slot.write().0 = slot.id;
+                                games_open.write().0 = false;
+                            }
+                        },
+                        "Continue"
+                    }
+                    button {
+                        class: "input",
+                        disabled: slot.id == current_id,
+                        onclick: move |_| {
+                            let profile = profile.peek().0;
+                            let mut slots = slots.write();
+                            slots.0.retain(|other| other.id != slot.id);
+                            store_slots(profile, &slots.0);
+                            // so a sync does not bring it back
+                            let mut deleted = load_deleted_slots(profile);
+                            deleted.retain(|other| !other.is_of(&slot.game.initial));
+                            deleted.push(DeletedSlot::new(&slot.game.initial, now()));
+                            store_deleted_slots(profile, &deleted);
+                        },
+                        "Delete"
+                    }
+                }
+            }
+        }
+        button {
+            class: "input",
+            onclick: move |_| games_open.write().0 = false,
+            "Close"
+        }
+    })
+}
+
+/// Returns the progress of a game as a percentage.
+fn progress(game: &SavedGame) -> usize {
+    let (filled, empty) = game.progress();
+    (filled * 100).checked_div(empty).unwrap_or(100)
+}
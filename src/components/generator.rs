@@ -0,0 +1,68 @@
+//! # Generator Module
+//!
+//! The `generator` module contains the [`use_puzzle_queue`] hook,
+//! which fills the [`PuzzleQueue`](crate::generator::PuzzleQueue) in the
+//! background, so new games can start at once, even offline.
+//!
+//! See also: [`generator`](crate::generator).
+
+use dioxus::prelude::*;
+
+use crate::components::embed::Embedded;
+use crate::generator::{fill_queue, QueueFill};
+
+/// Milliseconds waited before generating each puzzle of the queue,
+/// so the app is responsive while it starts
+const QUEUE_DELAY: u32 = 2000;
+
+/// Milliseconds waited before searching again a puzzle not found in time,
+/// so the app handles the input of the player in between
+const SEARCH_DELAY: u32 = 100;
+
+/// Number of searches of a puzzle before giving up on filling the queue
+const MAX_SEARCHES: u32 = 100;
+
+/// JavaScript that waits for the number of milliseconds it receives,
+/// then sends `true` to Rust
+pub const WAIT: &str = r"
+    const millis = await dioxus.recv();
+    setTimeout(() => dioxus.send(true), millis);
+";
+
+/// Hook that fills the puzzle queue in the background.
+///
+/// A puzzle is generated every few seconds,
+/// until the queue is full or a puzzle cannot be found in
+/// [`MAX_SEARCHES`] short searches, such as a hard one on a slow device.
+/// Nothing is generated if the game is [`Embedded`].
+pub fn use_puzzle_queue() {
+    // Unpack shared states
+    let embedded = use_context::<Signal<Embedded>>();
+
+    use_future(move || async move {
+        if embedded.peek().0 {
+            return;
+        }
+        let mut searches = 0;
+        loop {
+            let delay = if searches == 0 {
+                QUEUE_DELAY
+            } else {
+                SEARCH_DELAY
+            };
+            let mut wait = eval(WAIT);
+            if wait.send(delay.into()).is_err() || wait.recv().await.is_err() {
+                return;
+            }
+            match fill_queue() {
+                QueueFill::Added => searches = 0,
+                QueueFill::Searching if searches + 1 < MAX_SEARCHES => searches += 1,
+                QueueFill::Searching => {
+                    log::info!("stopped filling the puzzle queue: no puzzle found in time");
+                    return;
+                }
+                QueueFill::Full => return,
+            }
+        }
+    });
+}
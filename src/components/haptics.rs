@@ -0,0 +1,26 @@
+//! # Haptics Module
+//!
+//! The `haptics` module contains the [`use_haptics`] hook,
+//! which gives [`Haptic`] feedback unless it is turned off in the settings.
+//!
+//! See also: [`haptics`](crate::haptics).
+
+use dioxus::prelude::*;
+
+use crate::browser::vibrate;
+use crate::components::settings::UserSettings;
+use crate::haptics::Haptic;
+
+/// Hook that returns a function giving a [`Haptic`] feedback.
+///
+/// The device only vibrates if haptics are on in the current settings.
+pub fn use_haptics() -> impl FnMut(Haptic) + Copy {
+    // Unpack shared states
+    let settings = use_context::<Signal<UserSettings>>();
+
+    move |haptic: Haptic| {
+        if settings.peek().0.haptics {
+            vibrate(haptic.pattern());
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! # Hot Seat Module
+//!
+//! The `hotseat` module contains the pass-and-play mode,
+//! where two players take turns placing digits on the same board.
+//!
+//! [`HotSeatButton`] starts and ends the mode,
+//! the [`HotSeatBar`] shows whose turn it is and the scores,
+//! the cells are colored by the player who filled them,
+//! and the [`HotSeatResult`] tells who won once the board is solved.
+//!
+//! See also: [`hotseat`](crate::hotseat).
+
+use dioxus::prelude::*;
+
+use crate::components::buttons::ActionButton;
+use crate::game::GameState;
+use crate::hotseat::{HotSeat, Player};
+use crate::utils::find_solution;
+
+/// Shared State for the pass-and-play mode
+///
+/// Represents globally across the app the [`HotSeat`] game under way,
+/// or `None` if the board is played alone.
+#[derive(Debug, Clone)]
+pub struct HotSeatGame(pub Option<HotSeat>);
+
+/// Hook that records the moves of the pass-and-play mode.
+///
+/// Every change to the board is recorded in the [`HotSeatGame`],
+/// if one is under way, which passes the turn once a digit is placed.
+/// The moves it does not allow never reach the board,
+/// see [`use_dispatch`](crate::components::board::use_dispatch).
+pub fn use_hot_seat() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut hot_seat = use_context::<Signal<HotSeatGame>>();
+    // the boards alone, so selecting a cell records nothing
+    let boards = use_memo(move || (game.read().initial, game.read().current()));
+
+    use_effect(move || {
+        let (puzzle, board) = boards();
+        if let Some(game) = &mut hot_seat.write().0 {
+            game.record(&puzzle, &board);
+        }
+    });
+}
+
+/// Component to render the pass-and-play button
+///
+/// This component renders a "Two players" [`ActionButton`] that starts a
+/// [`HotSeatGame`] on the current board,
+/// or ends the one under way.
+#[component]
+pub fn HotSeatButton() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut hot_seat = use_context::<Signal<HotSeatGame>>();
+
+    let on = hot_seat.read().0.is_some();
+
+    rsx!(ActionButton {
+        icon: if on { "hot-seat on" } else { "hot-seat" },
+        label: if on { "One player" } else { "Two players" },
+        tooltip: "Take turns placing digits on this board",
+        onclick: move |()| {
+            hot_seat.write().0 = if on {
+                None
+            } else {
+                let game = game.peek();
+                Some(HotSeat::new(game.initial, game.current()))
+            };
+        },
+    })
+}
+
+/// Component to render the turn and the scores of the pass-and-play mode
+///
+/// This component renders, during a [`HotSeatGame`],
+/// the score of each player, colored like their cells,
+/// and whose turn it is.
+#[component]
+pub fn HotSeatBar() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+
+    let solution = use_memo(move || find_solution(&game.read().initial).ok());
+
+    let hot_seat = hot_seat.read();
+    let game = hot_seat.0.as_ref()?;
+    let solution = solution.read();
+    let score = |player| {
+        solution
+            .as_ref()
+            .map_or(0, |solution| game.score(player, solution))
+    };
+
+    rsx!(div {
+        class: "hot-seat",
+        role: "status",
+        for player in [Player::One, Player::Two] {
+            span {
+                key: "{player}",
+                class: if game.turn == player { "{player.css_name()} turn" } else { player.css_name() },
+                "{player}: {score(player)}"
+            }
+        }
+        span { "{game.turn}'s turn" }
+    })
+}
+
+/// Component to render the result of the pass-and-play mode
+///
+/// This component renders, once the board of a [`HotSeatGame`] is solved,
+/// the winner and the final scores.
+#[component]
+pub fn HotSeatResult() -> Element {
+    // Unpack shared states
+    let hot_seat = use_context::<Signal<HotSeatGame>>();
+
+    let hot_seat = hot_seat.read();
+    let game = hot_seat.0.as_ref()?;
+    // the board is solved, so it is the solution
+    let solution = game.board;
+    let one = game.score(Player::One, &solution);
+    let two = game.score(Player::Two, &solution);
+    let verdict = game.leader(&solution).map_or_else(
+        || format!("A tie, {one} to {two}!"),
+        |winner| format!("{winner} wins, {} to {}!", one.max(two), one.min(two)),
+    );
+
+    rsx!(span { class: "hot-seat-result", "{verdict}" })
+}
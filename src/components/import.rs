@@ -0,0 +1,472 @@
+//! # Import Module
+//!
+//! The `import` module contains the components used to import Sudoku games
+//! from outside of the app.
+//!
+//! [`PasteImport`] listens to paste events on the whole page and offers to
+//! start a new game from the pasted text, if it is a recognizable puzzle,
+//! or to restore it, if it is a save file.
+//!
+//! [`PhotoButton`] and [`PhotoImport`] read a puzzle from a photo of a
+//! printed grid and let the user correct it before starting a new game.
+//!
+//! [`FileButton`] and [`FileImport`] read the puzzles in a file, such as the
+//! `.sdk`, `.sdm` and `.ss` formats of desktop Sudoku programs,
+//! and let the user pick which one to play.
+//! [`FileButton`] also restores the JSON save files created by
+//! [`SaveButton`](crate::components::save::SaveButton).
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use dioxus::prelude::*;
+
+use crate::analytics::Event;
+use crate::app::SudokuState;
+use crate::components::analytics::use_track;
+use crate::components::board::{use_dispatch, HintsUsed};
+use crate::components::games::{CurrentSlot, SaveSlots};
+use crate::components::modal::Modal;
+use crate::components::settings::UserSettings;
+use crate::components::timer::GameTimer;
+use crate::components::toast::use_toast;
+use crate::difficulty::grade_puzzle;
+use crate::encoding::GameSnapshot;
+use crate::formats::{parse_puzzle_file, recognize_paste, PastedGame};
+use crate::game::{GameAction, GameState};
+use crate::ocr::{recognize_sudoku, GrayImage};
+use crate::save::{import_save, next_slot_id, SavedGame};
+use crate::toast::ToastKind;
+use crate::utils::validate_puzzle;
+
+/// Shared State for the puzzle recognized from a photo
+///
+/// Represents globally across the app the puzzle recognized by
+/// [`PhotoButton`] and waiting to be corrected in [`PhotoImport`].
+#[derive(Debug, Clone)]
+pub struct RecognizedPuzzle(pub Option<SudokuState>);
+
+/// Shared State for the puzzles imported from a file
+///
+/// Represents globally across the app the puzzles read by [`FileButton`]
+/// and waiting to be picked in [`FileImport`].
+#[derive(Debug, Clone)]
+pub struct ImportedPuzzles(pub Option<Vec<SudokuState>>);
+
+/// JavaScript that forwards the text of every paste event to Rust
+///
+/// Texts pasted in form fields or in a modal dialog are not imported.
+const PASTE_LISTENER: &str = r#"
+    document.addEventListener("paste", (event) => {
+        if (event.target.closest("input, textarea, select, [contenteditable], [aria-modal=true]")) {
+            return;
+        }
+        dioxus.send(event.clipboardData.getData("text"));
+    });
+"#;
+
+/// JavaScript that reads the chosen photo into RGBA pixels
+///
+/// The photo is downscaled so its largest side is at most 720 pixels and
+/// the pixels are sent to Rust as base64.
+const PHOTO_READER: &str = r#"
+    const input = document.getElementById("photo-import");
+    const file = input.files[0];
+    input.value = "";
+    if (file) {
+        const bitmap = await createImageBitmap(file);
+        const scale = Math.min(1, 720 / Math.max(bitmap.width, bitmap.height));
+        const canvas = document.createElement("canvas");
+        canvas.width = Math.round(bitmap.width * scale);
+        canvas.height = Math.round(bitmap.height * scale);
+        const context = canvas.getContext("2d");
+        context.drawImage(bitmap, 0, 0, canvas.width, canvas.height);
+        const { data } = context.getImageData(0, 0, canvas.width, canvas.height);
+        let binary = "";
+        for (let i = 0; i < data.length; i += 0x8000) {
+            binary += String.fromCharCode(...data.subarray(i, i + 0x8000));
+        }
+        dioxus.send({ width: canvas.width, height: canvas.height, data: btoa(binary) });
+    }
+"#;
+
+/// Hook that returns a function replacing the current game with a
+/// [`SavedGame`].
+///
+/// The moves of the saved game, with the notes on them,
+/// are resumed with its notes with [`GameAction::Resume`],
+/// as when a new game starts, and then its timer is restored.
+/// The settings of the saved game are ignored,
+/// so restoring a game never changes the server, analytics or theme of the
+/// player.
+/// Hints used are not saved, so the game gets its whole hint budget back.
+/// The game is played in a new save slot.
+pub fn use_restore_game() -> impl FnMut(SavedGame) + Copy {
+    // Unpack shared states
+    let mut timer = use_context::<Signal<GameTimer>>();
+    let slots = use_context::<Signal<SaveSlots>>();
+    let mut current_slot = use_context::<Signal<CurrentSlot>>();
+    let mut hints_used = use_context::<Signal<HintsUsed>>();
+    let mut dispatch = use_dispatch();
+
+    move |game: SavedGame| {
+        // replacing the board with the saved game
+        dispatch(GameAction::Resume {
+            history: game.undo_tree(),
+            notes: game.notes,
+        });
+        timer.write().0 = game.timer;
+        hints_used.write().0 = 0;
+        current_slot.write().0 = next_slot_id(&slots.peek().0);
+    }
+}
+
+/// Hook that returns a function replacing the current game with an
+/// imported [`GameSnapshot`].
+///
+/// All the shared states of the board are reset, as when a new game starts.
+/// The timer restarts and the settings are kept.
+/// A toast tells the user the puzzle was imported.
+pub fn use_import_game() -> impl FnMut(GameSnapshot) + Copy {
+    // Unpack shared states
+    let current_game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut restore_game = use_restore_game();
+    let mut track = use_track();
+    let mut toast = use_toast();
+
+    move |game: GameSnapshot| {
+        let (initial, current) = {
+            let current_game = current_game.peek();
+            (current_game.initial, current_game.current())
+        };
+        if let Some(event) = Event::abandoned(&initial, &current, timer.peek().0) {
+            track(event);
+        }
+        let difficulty = grade_puzzle(&game.initial);
+        track(Event::PuzzleImported { difficulty });
+        track(Event::PuzzleStarted { difficulty });
+
+        let moves = if game.current == game.initial {
+            vec![game.initial]
+        } else {
+            vec![game.initial, game.current]
+        };
+        restore_game(SavedGame {
+            initial: game.initial,
+            moves,
+            notes: game.notes,
+            timer: 0,
+            settings: settings.peek().0.clone(),
+            move_notes: vec![],
+            tree: None,
+        });
+        toast(ToastKind::Success, "Puzzle imported".to_string());
+    }
+}
+
+/// Component to render the paste-to-import prompt
+///
+/// This component installs a global paste handler,
+/// which ignores the texts pasted in form fields, such as the annotations.
+/// When the pasted text is recognized as a Sudoku game by
+/// [`recognize_paste`], the user is asked whether to import it as a new
+/// game, or to restore it if it is a save file.
+/// Invalid games are reported with a toast instead,
+/// leaving the current game untouched.
+#[component]
+pub fn PasteImport() -> Element {
+    // Pending import of a valid game
+    let mut pending = use_signal(|| None::<PastedGame>);
+    let mut import_game = use_import_game();
+    let mut restore_game = use_restore_game();
+    let mut toast = use_toast();
+
+    use_future(move || async move {
+        let mut listener = eval(PASTE_LISTENER);
+        while let Ok(text) = listener.recv().await {
+            if let Some(import) = text.as_str().and_then(recognize_paste) {
+                match import {
+                    Ok(game) => pending.set(Some(game)),
+                    Err(err) => toast(
+                        ToastKind::Error,
+                        format!("The pasted puzzle is invalid: {err}"),
+                    ),
+                }
+            }
+        }
+    });
+
+    let game = pending.read().clone()?;
+    let question = match game {
+        PastedGame::Save(_) => "Restore the pasted saved game?",
+        PastedGame::Snapshot(_) => "Import the pasted puzzle as a new game?",
+    };
+
+    rsx!(Modal {
+        label: "Import the pasted puzzle",
+        onclose: move |()| pending.set(None),
+        span { "{question}" }
+        button {
+            class: "input",
+            onclick: move |_| {
+                match pending.take() {
+                    Some(PastedGame::Save(game)) => {
+                        restore_game(*game);
+                        toast(ToastKind::Success, "Game restored".to_string());
+                    }
+                    Some(PastedGame::Snapshot(game)) => import_game(*game),
+                    None => {}
+                }
+            },
+            "Import"
+        }
+        button {
+            class: "input",
+            onclick: move |_| pending.set(None),
+            "Dismiss"
+        }
+    })
+}
+
+/// Component to render the photo import button
+///
+/// This component renders an experimental "Import photo" button.
+/// When a photo of a printed Sudoku grid is chosen,
+/// the puzzle is recognized with [`recognize_sudoku`] and stored in the
+/// [`RecognizedPuzzle`] shared state, to be corrected in [`PhotoImport`].
+#[component]
+pub fn PhotoButton() -> Element {
+    // Unpack shared states
+    let mut recognized = use_context::<Signal<RecognizedPuzzle>>();
+    let mut toast = use_toast();
+
+    rsx!(label {
+        class: "input icon photo",
+        r#for: "photo-import",
+        // reachable with the keyboard, unlike the hidden input
+        tabindex: 0,
+        role: "button",
+        input {
+            id: "photo-import",
+            r#type: "file",
+            accept: "image/*",
+            hidden: true,
+            onchange: move |_| {
+                let mut reader = eval(PHOTO_READER);
+                spawn(async move {
+                    let Ok(photo) = reader.recv().await else {
+                        return;
+                    };
+                    let width = photo["width"].as_u64().and_then(|w| usize::try_from(w).ok());
+                    let height = photo["height"].as_u64().and_then(|h| usize::try_from(h).ok());
+                    let pixels = photo["data"].as_str().and_then(|data| STANDARD.decode(data).ok());
+
+                    let result = match (width, height, pixels) {
+                        (Some(width), Some(height), Some(pixels)) => {
+                            GrayImage::from_rgba(width, height, &pixels)
+                                .and_then(|image| recognize_sudoku(&image))
+                                .map_err(|err| err.to_string())
+                        }
+                        _ => Err("The photo could not be read".to_string()),
+                    };
+                    match result {
+                        Ok(puzzle) => recognized.write().0 = Some(puzzle),
+                        Err(reason) => {
+                            toast(ToastKind::Error, format!("No puzzle recognized: {reason}"));
+                        }
+                    }
+                });
+            }
+        }
+        "Import photo"
+    })
+}
+
+/// Component to render the correction of a puzzle recognized from a photo
+///
+/// This component renders the [`RecognizedPuzzle`] as an editable grid,
+/// so the user can fix any misrecognized cell before starting a new game
+/// with it.
+/// The puzzle is validated with [`validate_puzzle`] before replacing the
+/// current game.
+#[component]
+pub fn PhotoImport() -> Element {
+    // Unpack shared states
+    let mut recognized = use_context::<Signal<RecognizedPuzzle>>();
+    let mut import_game = use_import_game();
+    let mut error = use_signal(String::new);
+
+    let puzzle = recognized.read().0?;
+
+    rsx!(Modal {
+        label: "Fix the recognized puzzle",
+        onclose: move |()| {
+            recognized.write().0 = None;
+            error.set(String::new());
+        },
+        span { "Check the recognized puzzle and fix any wrong cell" }
+        span {
+            class: "photo-grid",
+            for (index, &value) in puzzle.iter().enumerate() {
+                input {
+                    key: "{index}",
+                    class: "input",
+                    r#type: "text",
+                    inputmode: "numeric",
+                    maxlength: 1,
+                    value: if value == 0 { String::new() } else { value.to_string() },
+                    oninput: move |event| {
+                        let value = event
+                            .value()
+                            .chars()
+                            .find_map(|c| c.to_digit(10))
+                            .and_then(|digit| u8::try_from(digit).ok())
+                            .unwrap_or(0);
+                        if let Some(puzzle) = &mut recognized.write().0 {
+                            puzzle[index] = value;
+                        }
+                    },
+                }
+            }
+        }
+        if !error.read().is_empty() {
+            span { "{error}" }
+        }
+        button {
+            class: "input",
+            onclick: move |_| {
+                match validate_puzzle(&puzzle) {
+                    Ok(()) => {
+                        import_game(GameSnapshot {
+                            initial: puzzle,
+                            current: puzzle,
+                            notes: [0; 81],
+                        });
+                        recognized.write().0 = None;
+                        error.set(String::new());
+                    }
+                    Err(err) => error.set(format!("The puzzle is invalid: {err}")),
+                }
+            },
+            "Start game"
+        }
+        button {
+            class: "input",
+            onclick: move |_| {
+                recognized.write().0 = None;
+                error.set(String::new());
+            },
+            "Cancel"
+        }
+    })
+}
+
+/// Component to render the file import button
+///
+/// This component renders an "Import file" button.
+/// When a puzzle file is chosen, its puzzles are parsed with
+/// [`parse_puzzle_file`] and stored in the [`ImportedPuzzles`] shared state,
+/// to be picked in [`FileImport`].
+/// When a `.json` save file is chosen, the saved game is restored right away
+/// with [`import_save`].
+#[component]
+pub fn FileButton() -> Element {
+    // Unpack shared states
+    let mut imported = use_context::<Signal<ImportedPuzzles>>();
+    let mut restore_game = use_restore_game();
+    let mut toast = use_toast();
+
+    rsx!(label {
+        class: "input icon file",
+        r#for: "file-import",
+        // reachable with the keyboard, unlike the hidden input
+        tabindex: 0,
+        role: "button",
+        input {
+            id: "file-import",
+            r#type: "file",
+            accept: ".sdk,.sdm,.ss,.txt,.json",
+            hidden: true,
+            onchange: move |event| {
+                let Some(files) = event.files() else {
+                    return;
+                };
+                spawn(async move {
+                    let Some(file_name) = files.files().into_iter().next() else {
+                        return;
+                    };
+                    let Some(contents) = files.read_file_to_string(&file_name).await else {
+                        toast(ToastKind::Error, "The file could not be read".to_string());
+                        return;
+                    };
+                    if file_name.to_lowercase().ends_with(".json") {
+                        match import_save(&contents) {
+                            Ok(game) => {
+                                restore_game(game);
+                                toast(ToastKind::Success, "Game restored".to_string());
+                            }
+                            Err(err) => toast(
+                                ToastKind::Error,
+                                format!("The file could not be imported: {err}"),
+                            ),
+                        }
+                        return;
+                    }
+                    match parse_puzzle_file(&file_name, &contents) {
+                        Ok(puzzles) => imported.write().0 = Some(puzzles),
+                        Err(err) => toast(
+                            ToastKind::Error,
+                            format!("The file could not be imported: {err}"),
+                        ),
+                    }
+                });
+            }
+        }
+        "Import file"
+    })
+}
+
+/// Component to render the puzzle picker of an imported file
+///
+/// This component renders the [`ImportedPuzzles`] as a list,
+/// so the user can pick which puzzle of the file to play.
+/// Puzzles that fail [`validate_puzzle`] are listed but cannot be picked.
+#[component]
+pub fn FileImport() -> Element {
+    // Unpack shared states
+    let mut imported = use_context::<Signal<ImportedPuzzles>>();
+    let mut import_game = use_import_game();
+
+    let puzzles = imported.read().0.clone()?;
+
+    rsx!(Modal {
+        label: "Pick a puzzle",
+        onclose: move |()| imported.write().0 = None,
+        span { "Pick a puzzle to play" }
+        span {
+            class: "puzzle-list",
+            for (index, puzzle) in puzzles.into_iter().enumerate() {
+                button {
+                    key: "{index}",
+                    class: "input",
+                    disabled: validate_puzzle(&puzzle).is_err(),
+                    onclick: move |_| {
+                        import_game(GameSnapshot {
+                            initial: puzzle,
+                            current: puzzle,
+                            notes: [0; 81],
+                        });
+                        imported.write().0 = None;
+                    },
+                    "Puzzle {index + 1} ({puzzle.iter().filter(|&&value| value != 0).count()} clues)"
+                }
+            }
+        }
+        button {
+            class: "input",
+            onclick: move |_| imported.write().0 = None,
+            "Cancel"
+        }
+    })
+}
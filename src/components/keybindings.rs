@@ -0,0 +1,176 @@
+//! # Keybindings Module
+//!
+//! The `keybindings` module contains the [`use_keybindings`] hook,
+//! which plays the game with the keyboard shortcuts,
+//! and the [`ShortcutsPanel`] that lists them.
+//!
+//! See also: [`keybindings`](crate::keybindings).
+
+use dioxus::prelude::*;
+
+use crate::browser::focus_element;
+use crate::components::board::{use_dispatch, Heatmap, NoteCell};
+use crate::components::controls::{
+    use_enter_number, use_generate_game, use_hint, use_redo, use_undo,
+};
+use crate::components::embed::Embedded;
+use crate::components::modal::Modal;
+use crate::components::settings::UserSettings;
+use crate::difficulty::grade_puzzle;
+use crate::game::{GameAction, GameState};
+use crate::keybindings::{action_for_key, shortcut_keys, Action, KEYBINDINGS};
+use crate::settings::InputMode;
+
+/// Shared State for the keyboard shortcuts overlay
+///
+/// Represents globally across the app if the [`ShortcutsPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct ShortcutsOpen(pub bool);
+
+/// JavaScript that sends the shortcut keys pressed on the page to Rust
+///
+/// It receives the shortcut keys first, so their default action,
+/// such as scrolling with the arrows, is prevented.
+/// Keys typed in form fields or in a modal dialog are not shortcuts.
+///
+/// Enter and Space also activate the focused elements with the `button`
+/// role, such as the cells, as they do buttons.
+const KEYS: &str = r"
+    const keys = await dioxus.recv();
+    document.addEventListener('keydown', (event) => {
+        if (event.altKey || event.target.closest('input, textarea, select, [aria-modal=true]')) {
+            return;
+        }
+        if ((event.key === 'Enter' || event.key === ' ') && event.target.matches('[role=button]')) {
+            event.preventDefault();
+            event.target.click();
+            return;
+        }
+        const key = (event.ctrlKey || event.metaKey ? 'Ctrl+' : '') + event.key;
+        if (keys.includes(key)) {
+            event.preventDefault();
+            dioxus.send(key);
+        }
+    });
+";
+
+/// Hook that plays the game with the keyboard shortcuts of the
+/// [`KEYBINDINGS`].
+///
+/// Digits are entered as with the number pad,
+/// the arrows move the clicked cell, and the keyboard focus with it,
+/// and note entry is toggled on the clicked cell as with a double-click
+/// on it.
+/// In [`speedrun`](crate::settings::Settings::speedrun) mode,
+/// a new puzzle of the same difficulty starts at once,
+/// without asking to confirm.
+///
+/// If the game is [`Embedded`], only the shortcuts of the number pad and
+/// the board work.
+pub fn use_keybindings() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut note_cell = use_context::<Signal<NoteCell>>();
+    let mut shortcuts_open = use_context::<Signal<ShortcutsOpen>>();
+    let mut heatmap = use_context::<Signal<Heatmap>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let embedded = use_context::<Signal<Embedded>>();
+    let mut enter_number = use_enter_number();
+    let mut undo = use_undo();
+    let mut redo = use_redo();
+    let mut hint = use_hint();
+    let mut generate_game = use_generate_game();
+    let mut dispatch = use_dispatch();
+
+    use_future(move || async move {
+        let mut keys = eval(KEYS);
+        if let Err(err) = keys.send(serde_json::json!(shortcut_keys())) {
+            log::error!("failed to listen to the keyboard shortcuts: {err:?}");
+            return;
+        }
+        while let Ok(key) = keys.recv().await {
+            let Some(key) = key.as_str() else {
+                continue;
+            };
+            let Some(action) = action_for_key(key) else {
+                continue;
+            };
+            match action {
+                Action::Digit => {
+                    if let Ok(number) = key.parse() {
+                        enter_number(number);
+                    }
+                }
+                Action::Erase => enter_number(0),
+                Action::Move(direction) => {
+                    let id = direction.step(game.peek().clicked);
+                    dispatch(GameAction::Select(id));
+                    focus_element(&id.to_string());
+                    // other cells go back to value entry
+                    if note_cell.peek().0.is_some_and(|cell| cell != id) {
+                        note_cell.set(NoteCell(None));
+                    }
+                }
+                Action::ToggleNotes => {
+                    let (id, mutable) = (game.peek().clicked, game.peek().mutable());
+                    let empty = game.peek().current().get(id as usize) == Some(&0);
+                    if settings.peek().0.input_mode == InputMode::CellFirst && mutable && empty {
+                        let cell = &mut note_cell.write().0;
+                        *cell = if *cell == Some(id) { None } else { Some(id) };
+                    }
+                }
+                // the other controls are not rendered when embedded
+                _ if embedded.peek().0 => {}
+                Action::Undo => undo(),
+                Action::Redo => redo(),
+                Action::Hint => hint(),
+                Action::ToggleHeatmap => {
+                    let on = heatmap.peek().0;
+                    heatmap.write().0 = !on;
+                }
+                Action::QuickRestart => {
+                    if settings.peek().0.speedrun {
+                        generate_game(Some(grade_puzzle(&game.peek().initial)));
+                    }
+                }
+                Action::Shortcuts => {
+                    let open = shortcuts_open.peek().0;
+                    shortcuts_open.write().0 = !open;
+                }
+            }
+        }
+    });
+}
+
+/// Component to render the keyboard shortcuts overlay
+///
+/// This component renders all the [`KEYBINDINGS`],
+/// with their keys and what they do.
+/// It is opened with the "?" key, in a [`Modal`].
+#[component]
+pub fn ShortcutsPanel() -> Element {
+    // Unpack shared states
+    let mut shortcuts_open = use_context::<Signal<ShortcutsOpen>>();
+
+    if !shortcuts_open.read().0 {
+        return None;
+    }
+
+    rsx!(Modal {
+        class: "shortcuts",
+        label: "Keyboard shortcuts",
+        onclose: move |()| shortcuts_open.write().0 = false,
+        span { "Keyboard shortcuts" }
+        dl {
+            for binding in KEYBINDINGS {
+                dt { kbd { "{binding.label}" } }
+                dd { "{binding.action.description()}" }
+            }
+        }
+        button {
+            class: "input",
+            onclick: move |_| shortcuts_open.write().0 = false,
+            "Close"
+        }
+    })
+}
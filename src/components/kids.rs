@@ -0,0 +1,103 @@
+//! # Kids Module
+//!
+//! The `kids` module contains the beginner experience of the kids mode,
+//! see [`Settings::set_kids`](crate::settings::Settings::set_kids).
+//!
+//! The [`use_kids_guide`] hook shows the next step on the board once the
+//! player has not moved for [`GUIDE_MILLIS`],
+//! and the [`Celebration`] fills the victory panel with the symbols of the
+//! board.
+//! Mistakes are not counted in the kids mode,
+//! see [`StatusBar`](crate::components::status::StatusBar),
+//! and new games are easy,
+//! see [`Settings::new_game_difficulty`](crate::settings::Settings::new_game_difficulty).
+//! The kids mode plays the same 9x9 board as the rest of the app,
+//! since the board has no smaller sizes.
+
+use dioxus::prelude::*;
+
+use crate::components::generator::WAIT;
+use crate::components::settings::UserSettings;
+use crate::components::techniques::Explanation;
+use crate::game::GameState;
+use crate::techniques::next_step;
+
+/// How long the player can be idle before the next step is shown,
+/// in milliseconds
+pub const GUIDE_MILLIS: u64 = 30_000;
+
+/// How many symbols fly around in the [`Celebration`]
+const CELEBRATION_PIECES: u8 = 18;
+
+/// Hook that guides the player of the kids mode.
+///
+/// Once the board has not changed for [`GUIDE_MILLIS`],
+/// the next step is shown as an [`Explanation`],
+/// as if the explain button was pressed.
+/// Nothing is shown outside of the kids mode,
+/// or if an explanation is already shown.
+pub fn use_kids_guide() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut explanation = use_context::<Signal<Explanation>>();
+
+    // every move starts a new wait, and the older ones are dropped
+    let mut moves = use_signal(|| 0_u64);
+    let sudoku = use_memo(move || game.read().current());
+
+    use_effect(move || {
+        sudoku.read();
+        if !settings.read().0.kids {
+            return;
+        }
+        let waiting = *moves.peek() + 1;
+        moves.set(waiting);
+        spawn(async move {
+            let mut wait = eval(WAIT);
+            if wait.send(GUIDE_MILLIS.into()).is_err() || wait.recv().await.is_err() {
+                return;
+            }
+            if *moves.peek() != waiting || !settings.peek().0.kids || explanation.peek().0.is_some()
+            {
+                return;
+            }
+            if let Some(step) = next_step(&sudoku.peek(), &game.peek().notes) {
+                explanation.set(Explanation(Some(step)));
+            }
+        });
+    });
+}
+
+/// Component to render the celebration of a solved board
+///
+/// This component renders, in the kids mode,
+/// a cheer and the symbols of the board flying around it.
+/// The symbols are decorative and hidden from screen readers.
+#[component]
+pub fn Celebration() -> Element {
+    // Unpack shared states
+    let settings = use_context::<Signal<UserSettings>>();
+
+    let settings = &settings.read().0;
+    if !settings.kids {
+        return None;
+    }
+    let symbols = settings.symbols;
+
+    rsx!(div {
+        class: "celebration",
+        span { class: "cheer", "You did it!" }
+        div {
+            class: "pieces",
+            "aria-hidden": "true",
+            for piece in 0..CELEBRATION_PIECES {
+                span {
+                    key: "{piece}",
+                    style: "--piece: {piece};",
+                    "{symbols.symbol(piece % 9 + 1)}"
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,159 @@
+//! # Leaderboard Module
+//!
+//! The `leaderboard` module contains the [`DailySubmit`] offer,
+//! shown once the daily puzzle is solved,
+//! which submits its time to the daily leaderboard,
+//! and the [`StandingTable`] that shows the standing.
+//!
+//! See also: [`leaderboard`](crate::leaderboard).
+
+use dioxus::prelude::*;
+
+use crate::components::auth::UserSession;
+use crate::components::daily::DailyPuzzle;
+use crate::components::profiles::{ActiveProfile, Profiles};
+use crate::components::timer::GameTimer;
+use crate::game::GameState;
+use crate::leaderboard::{
+    load_standings, store_standings, submit_daily, DailyStanding, MAX_NAME_CHARS,
+};
+use crate::utils::{create_daily_sudoku, format_duration};
+
+/// Component Props for [`StandingTable`]
+///
+/// - `standing: DailyStanding`: the standing to render.
+#[derive(Props, Clone, PartialEq, Eq)]
+pub struct StandingTableProps {
+    standing: DailyStanding,
+}
+
+/// Component to render a standing on the daily leaderboard
+///
+/// This component renders the rank and percentile of the player,
+/// and the fastest times of the day.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn StandingTable(props: StandingTableProps) -> Element {
+    let StandingTableProps { standing } = props;
+
+    rsx!(
+        span {
+            "Rank {standing.rank} of {standing.total}, "
+            "faster than {standing.percentile()}% of the players"
+        }
+        table {
+            class: "leaderboard",
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Player" }
+                    th { "Time" }
+                }
+            }
+            tbody {
+                for (index, entry) in standing.top.iter().enumerate() {
+                    tr {
+                        key: "{index}",
+                        class: if index + 1 == standing.rank { "own" } else { "" },
+                        td { "{index + 1}" }
+                        td { "{entry.name}" }
+                        td { "{format_duration(entry.millis)}" }
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// Component to render the offer to submit the time of the daily puzzle
+///
+/// This component renders, once the daily puzzle of a day is solved,
+/// a name, the one of the active profile by default,
+/// and a button submitting the time and the solved board
+/// to the leaderboard of the day.
+/// Only signed in accounts can submit.
+/// The standing is then cached for the profile and rendered in a
+/// [`StandingTable`], even offline.
+/// Nothing is submitted unless the button is clicked.
+#[component]
+pub fn DailySubmit() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let session = use_context::<Signal<UserSession>>();
+    let daily = use_context::<Signal<DailyPuzzle>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let profiles = use_context::<Signal<Profiles>>();
+    let mut name = use_signal(|| {
+        let id = profile.peek().0;
+        profiles
+            .peek()
+            .0
+            .iter()
+            .find(|profile| profile.id == id)
+            .map(|profile| profile.name.clone())
+            .unwrap_or_default()
+    });
+    let mut submitting = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut submitted = use_signal(|| None::<DailyStanding>);
+
+    let day = daily
+        .read()
+        .0
+        .filter(|&day| create_daily_sudoku(day) == game.read().initial)?;
+    let standing = submitted()
+        .filter(|standing| standing.day == day)
+        .or_else(|| load_standings(profile.read().0).get(day).cloned());
+    if let Some(standing) = standing {
+        return rsx!(StandingTable { standing: standing });
+    }
+
+    let Some(account) = session.read().0.clone() else {
+        return rsx!(span { "Sign in to submit your time to the daily leaderboard" });
+    };
+
+    let submit = move |_| {
+        submitting.set(true);
+        error.set(None);
+        let account = account.clone();
+        spawn(async move {
+            let board = game.peek().current();
+            let standing =
+                submit_daily(day, account, name.peek().clone(), &board, timer.peek().0).await;
+            match standing {
+                Ok(standing) => {
+                    let profile = profile.peek().0;
+                    let mut standings = load_standings(profile);
+                    standings.insert(standing.clone());
+                    store_standings(profile, &standings);
+                    submitted.set(Some(standing));
+                }
+                Err(err) => error.set(Some(err.to_string())),
+            }
+            submitting.set(false);
+        });
+    };
+
+    rsx!(
+        label {
+            class: "daily-submit",
+            input {
+                r#type: "text",
+                placeholder: "Your name",
+                maxlength: "{MAX_NAME_CHARS}",
+                value: "{name}",
+                oninput: move |event| name.set(event.value()),
+            }
+            button {
+                class: "input",
+                disabled: submitting(),
+                onclick: submit,
+                "Submit to the daily leaderboard"
+            }
+        }
+        if let Some(error) = error() {
+            span { role: "status", "{error}" }
+        }
+    )
+}
@@ -0,0 +1,256 @@
+//! # Library Module
+//!
+//! The `library` module contains the puzzle browser.
+//!
+//! [`LibraryButton`] toggles the [`LibraryPanel`],
+//! which lists every puzzle of the library with a [`Thumbnail`],
+//! filtered by difficulty and completion, and plays the chosen one.
+//!
+//! See also: [`library`](crate::library).
+
+use dioxus::prelude::*;
+
+use crate::bank::bank_puzzles;
+use crate::components::buttons::ActionButton;
+use crate::components::confirm::ConfirmDialog;
+use crate::components::controls::{use_needs_confirm, use_new_game};
+use crate::components::games::{CurrentSlot, SaveSlots, Thumbnail};
+use crate::components::import::use_restore_game;
+use crate::components::modal::Modal;
+use crate::components::packs::PackPuzzle;
+use crate::components::profiles::ActiveProfile;
+use crate::difficulty::Difficulty;
+use crate::game::GameState;
+use crate::library::{build_library, Completion, LibraryEntry, LibraryFilter, PuzzleSource};
+use crate::packs::{load_pack_progress, load_packs, PuzzlePack};
+use crate::storage::load_records;
+
+/// Number of puzzles shown at first, and added by "Show more"
+const PAGE_SIZE: usize = 24;
+
+/// Shared State for the puzzle library
+///
+/// Represents globally across the app if the [`LibraryPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct LibraryOpen(pub bool);
+
+/// Component to render the puzzle library button
+///
+/// This component renders a "Puzzle library" button that opens and closes
+/// the [`LibraryPanel`].
+#[component]
+pub fn LibraryButton() -> Element {
+    // Unpack shared states
+    let mut library_open = use_context::<Signal<LibraryOpen>>();
+
+    rsx!(ActionButton {
+        icon: "library",
+        label: "Puzzle library",
+        tooltip: "Browse all the puzzles",
+        onclick: move |()| {
+            let open = library_open.read().0;
+            library_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the puzzle library
+///
+/// This component renders, in a [`Modal`], the filters and a page of the
+/// puzzles of the library that pass them,
+/// each with its thumbnail, difficulty, source and completion.
+/// The library is built with [`build_library`] every time the panel opens,
+/// from the bank, the packs, the save slots and the history of the active
+/// profile.
+///
+/// Playing a puzzle with a game in progress resumes its save slot,
+/// and playing any other puzzle starts a new game with it.
+/// If the current game has progress, a [`ConfirmDialog`] asks first.
+#[component]
+pub fn LibraryPanel() -> Element {
+    // Unpack shared states
+    let mut library_open = use_context::<Signal<LibraryOpen>>();
+    let mut pack_puzzle = use_context::<Signal<PackPuzzle>>();
+    let game = use_context::<Signal<GameState>>();
+    let slots = use_context::<Signal<SaveSlots>>();
+    let mut current_slot = use_context::<Signal<CurrentSlot>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut new_game = use_new_game();
+    let mut restore_game = use_restore_game();
+    let needs_confirm = use_needs_confirm();
+
+    let mut filter = use_signal(LibraryFilter::default);
+    let mut shown = use_signal(|| PAGE_SIZE);
+    // the entry waiting for a confirmation
+    let mut pending = use_signal(|| None::<LibraryEntry>);
+
+    let records = use_resource(move || async move {
+        if !library_open.read().0 {
+            return vec![];
+        }
+        load_records(profile.read().0).await.unwrap_or_else(|err| {
+            log::error!("failed to load the history: {err:?}");
+            vec![]
+        })
+    });
+    let library = use_memo(move || {
+        let records = records.read().clone().unwrap_or_default();
+        let profile = profile.read().0;
+        build_library(
+            bank_puzzles(),
+            &load_packs(),
+            &load_pack_progress(profile),
+            &slots.read().0,
+            &records,
+        )
+    });
+
+    if !library_open.read().0 {
+        return None;
+    }
+
+    let mut play = move |entry: LibraryEntry| {
+        pending.set(None);
+        let slot = slots
+            .peek()
+            .0
+            .iter()
+            .find(|slot| slot.game.initial == entry.puzzle)
+            .cloned();
+        if let Some(slot) = slot {
+            restore_game(slot.game);
+            current_slot.write().0 = slot.id;
+        } else {
+            new_game(entry.puzzle);
+            if let PuzzleSource::Pack(pack, index) = entry.source {
+                pack_puzzle.write().0 = Some((pack, index));
+            }
+        }
+        library_open.write().0 = false;
+    };
+
+    let current = game.read().initial;
+    let packs = load_packs();
+    let matching: Vec<LibraryEntry> = library
+        .read()
+        .iter()
+        .filter(|entry| filter.read().matches(entry))
+        .cloned()
+        .collect();
+    let total = matching.len();
+
+    rsx!(
+        Modal {
+            class: "library",
+            label: "Puzzle library",
+            onclose: move |()| library_open.write().0 = false,
+            span { "Puzzle library" }
+            span {
+                class: "library-filters",
+                label {
+                    "Difficulty"
+                    select {
+                        onchange: move |event| {
+                            let index = event.value().parse::<usize>().ok();
+                            filter.write().difficulty = index
+                                .and_then(|index| Difficulty::ALL.get(index))
+                                .copied();
+                            shown.set(PAGE_SIZE);
+                        },
+                        option { value: "", "All" }
+                        for (index, difficulty) in Difficulty::ALL.into_iter().enumerate() {
+                            option {
+                                value: "{index}",
+                                selected: filter.read().difficulty == Some(difficulty),
+                                "{difficulty}"
+                            }
+                        }
+                    }
+                }
+                label {
+                    "Status"
+                    select {
+                        onchange: move |event| {
+                            let index = event.value().parse::<usize>().ok();
+                            filter.write().completion = index
+                                .and_then(|index| Completion::ALL.get(index))
+                                .copied();
+                            shown.set(PAGE_SIZE);
+                        },
+                        option { value: "", "All" }
+                        for (index, completion) in Completion::ALL.into_iter().enumerate() {
+                            option {
+                                value: "{index}",
+                                selected: filter.read().completion == Some(completion),
+                                "{completion}"
+                            }
+                        }
+                    }
+                }
+            }
+            if total == 0 {
+                span { "No puzzles match the filters" }
+            }
+            span {
+                class: "library-list",
+                for (index, entry) in matching.into_iter().take(shown()).enumerate() {
+                    span {
+                        key: "{index}",
+                        class: "library-entry {entry.completion.css_name()}",
+                        Thumbnail {
+                            initial: entry.puzzle,
+                            current: entry.current,
+                        }
+                        span { "{entry.difficulty} · {source_label(entry.source, &packs)}" }
+                        span { class: "completion", "{entry.completion}" }
+                        button {
+                            class: "input",
+                            disabled: entry.puzzle == current,
+                            onclick: move |_| {
+                                if needs_confirm() {
+                                    pending.set(Some(entry.clone()));
+                                } else {
+                                    play(entry.clone());
+                                }
+                            },
+                            if entry.completion == Completion::InProgress { "Resume" } else { "Play" }
+                        }
+                    }
+                }
+            }
+            if shown() < total {
+                button {
+                    class: "input",
+                    onclick: move |_| shown += PAGE_SIZE,
+                    "Show more ({total - shown()} left)"
+                }
+            }
+            button {
+                class: "input",
+                onclick: move |_| library_open.write().0 = false,
+                "Close"
+            }
+        }
+        if let Some(entry) = pending() {
+            ConfirmDialog {
+                message: "Play this {entry.difficulty} puzzle? The current game stays in My games.",
+                action: "Play",
+                onconfirm: move |()| play(entry.clone()),
+                oncancel: move |()| pending.set(None),
+            }
+        }
+    )
+}
+
+/// Returns where a puzzle comes from, as shown to the user.
+fn source_label(source: PuzzleSource, packs: &[PuzzlePack]) -> String {
+    match source {
+        PuzzleSource::Bank => "Bank".to_string(),
+        PuzzleSource::Pack(id, index) => packs
+            .iter()
+            .find(|pack| pack.id == id)
+            .map_or_else(String::new, |pack| format!("{} #{}", pack.name, index + 1)),
+        PuzzleSource::Slot(_) => "My games".to_string(),
+        PuzzleSource::History => "History".to_string(),
+    }
+}
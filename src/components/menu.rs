@@ -0,0 +1,182 @@
+//! # Menu Module
+//!
+//! The `menu` module contains the [`Menu`] of the application,
+//! which gives access to everything but playing the board:
+//! new games of a chosen difficulty, the daily archive, the weekly
+//! challenge, the puzzle packs and library, the favorites,
+//! the imports and exports,
+//! the statistics, the settings and the [`AboutPanel`].
+//!
+//! The menu is toggled by a hamburger button above the board,
+//! so the number pad only keeps the buttons used while playing.
+
+use dioxus::prelude::*;
+
+use crate::components::analytics::SessionLogButton;
+use crate::components::buttons::ActionButton;
+use crate::components::challenge::ChallengeButton;
+use crate::components::confirm::ConfirmDialog;
+use crate::components::controls::{use_generate_game, use_needs_confirm, ShareButton};
+use crate::components::daily::DailyButton;
+use crate::components::export::ExportButton;
+use crate::components::favorites::{FavoriteButton, FavoritesButton};
+use crate::components::games::GamesButton;
+use crate::components::hotseat::HotSeatButton;
+use crate::components::import::{FileButton, PhotoButton};
+use crate::components::library::LibraryButton;
+use crate::components::modal::Modal;
+use crate::components::packs::PacksButton;
+use crate::components::save::SaveButton;
+use crate::components::settings::{SettingsButton, UserSettings};
+use crate::components::stats::StatsButton;
+use crate::components::weekly::WeeklyButton;
+use crate::difficulty::Difficulty;
+
+/// Shared State for the about panel
+///
+/// Represents globally across the app if the [`AboutPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct AboutOpen(pub bool);
+
+/// Component to render the application menu
+///
+/// This component renders a hamburger button that opens and closes the
+/// menu items.
+/// Activating an item closes the menu,
+/// except for the "New game" submenu, which lists the difficulties of the
+/// new game, only the easy one in the kids mode.
+///
+/// The items stay mounted while the menu is closed,
+/// so the file inputs of the imports outlive the menu.
+/// If the current game has progress,
+/// a [`ConfirmDialog`] asks before starting a new one.
+#[component]
+pub fn Menu() -> Element {
+    // Unpack shared states
+    let mut about_open = use_context::<Signal<AboutOpen>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let mut generate_game = use_generate_game();
+    let needs_confirm = use_needs_confirm();
+    let mut open = use_signal(|| false);
+    // the difficulty of the new game waiting for a confirmation
+    let mut pending = use_signal(|| None::<Difficulty>);
+
+    let settings = settings.read();
+    let difficulties = Difficulty::ALL
+        .into_iter()
+        .filter(|&difficulty| settings.0.new_game_difficulty(Some(difficulty)) == Some(difficulty));
+
+    let mut start = move |difficulty: Difficulty| {
+        pending.set(None);
+        generate_game(Some(difficulty));
+    };
+
+    rsx!(
+        div {
+            class: "menu",
+            button {
+                class: "input icon menu",
+                aria_expanded: "{open}",
+                aria_controls: "menu-items",
+                onclick: move |_| open.toggle(),
+                "Menu"
+            }
+            div {
+                id: "menu-items",
+                class: "menu-items",
+                hidden: !open(),
+                // activating an item closes the menu
+                onclick: move |_| open.set(false),
+                details {
+                    summary {
+                        class: "input",
+                        // the submenu opens without closing the menu
+                        onclick: move |event| event.stop_propagation(),
+                        "New game"
+                    }
+                    for difficulty in difficulties {
+                        button {
+                            class: "input",
+                            onclick: move |_| {
+                                if needs_confirm() {
+                                    pending.set(Some(difficulty));
+                                } else {
+                                    start(difficulty);
+                                }
+                            },
+                            "{difficulty}"
+                        }
+                    }
+                }
+                GamesButton {}
+                DailyButton {}
+                WeeklyButton {}
+                PacksButton {}
+                LibraryButton {}
+                FavoriteButton {}
+                FavoritesButton {}
+                PhotoButton {}
+                FileButton {}
+                ExportButton {}
+                SaveButton {}
+                ShareButton {}
+                ChallengeButton {}
+                HotSeatButton {}
+                StatsButton {}
+                SessionLogButton {}
+                SettingsButton {}
+                ActionButton {
+                    icon: "about",
+                    label: "About",
+                    tooltip: "About this game",
+                    onclick: move |()| about_open.write().0 = true,
+                }
+            }
+        }
+        if let Some(difficulty) = pending() {
+            ConfirmDialog {
+                message: "Start a new {difficulty} game? The current one stays in My games.",
+                action: "New game",
+                onconfirm: move |()| start(difficulty),
+                oncancel: move |()| pending.set(None),
+            }
+        }
+    )
+}
+
+/// Component to render the about panel
+///
+/// This component renders, in a [`Modal`], the version of the game and a
+/// link to its source code.
+#[component]
+pub fn AboutPanel() -> Element {
+    // Unpack shared states
+    let mut about_open = use_context::<Signal<AboutOpen>>();
+
+    if !about_open.read().0 {
+        return None;
+    }
+
+    let version = env!("CARGO_PKG_VERSION");
+    let description = env!("CARGO_PKG_DESCRIPTION");
+
+    rsx!(Modal {
+        class: "about",
+        label: "About",
+        onclose: move |()| about_open.write().0 = false,
+        span { "Sudoku {version}" }
+        span { "{description}" }
+        a {
+            class: "input",
+            href: "https://github.com/storopoli/sudoku",
+            target: "_blank",
+            rel: "noopener noreferrer",
+            "Source code on GitHub"
+        }
+        button {
+            class: "input",
+            onclick: move |_| about_open.write().0 = false,
+            "Close"
+        }
+    })
+}
@@ -8,5 +8,47 @@
 //! and presentation. They can be used to build the complete user interface of
 //! the Sudoku game.
 
+pub mod analytics;
+pub mod auth;
+pub mod autocomplete;
 pub mod board;
+pub mod bookmarks;
+pub mod buttons;
 pub mod cell;
+pub mod challenge;
+pub mod charts;
+pub mod confirm;
+pub mod controls;
+pub mod daily;
+pub mod embed;
+pub mod export;
+pub mod favorites;
+pub mod games;
+pub mod generator;
+pub mod haptics;
+pub mod hotseat;
+pub mod import;
+pub mod keybindings;
+pub mod kids;
+pub mod leaderboard;
+pub mod library;
+pub mod menu;
+pub mod modal;
+pub mod packs;
+pub mod profiles;
+pub mod recovery;
+pub mod replay;
+pub mod save;
+pub mod settings;
+pub mod speedrun;
+pub mod splits;
+pub mod stats;
+pub mod status;
+pub mod sync;
+pub mod techniques;
+pub mod theme;
+pub mod timer;
+pub mod toast;
+pub mod undo_tree;
+pub mod victory;
+pub mod weekly;
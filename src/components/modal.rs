@@ -0,0 +1,114 @@
+//! # Modal Module
+//!
+//! The `modal` module contains the [`Modal`] component,
+//! the dialog shown over the page by the panels and prompts of the game,
+//! such as the settings, the imports and the confirmations.
+//!
+//! Every modal dims the page behind a backdrop,
+//! keeps the keyboard focus inside while open,
+//! and closes with the Escape key or a click on the backdrop.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use dioxus::prelude::*;
+
+/// The number of modals created so far, to give each one a unique id
+static MODALS: AtomicU32 = AtomicU32::new(0);
+
+/// JavaScript that traps the keyboard focus in the modal whose id it
+/// receives, and sends to Rust when Escape is pressed
+///
+/// The first focusable element of the modal is focused,
+/// and Tab and Shift+Tab cycle through its focusable elements.
+const FOCUS_TRAP: &str = r#"
+    const id = await dioxus.recv();
+    const dialog = document.getElementById(id);
+    if (!dialog) {
+        return;
+    }
+    const focusable = () => [...dialog.querySelectorAll(
+        'button, [href], input, select, textarea, [tabindex]:not([tabindex="-1"])'
+    )].filter((element) => !element.disabled && !element.hidden);
+    focusable()[0]?.focus();
+    dialog.addEventListener('keydown', (event) => {
+        if (event.key === 'Escape') {
+            event.preventDefault();
+            dioxus.send(true);
+            return;
+        }
+        const elements = focusable();
+        if (event.key !== 'Tab' || elements.length === 0) {
+            return;
+        }
+        const first = elements[0];
+        const last = elements[elements.length - 1];
+        if (event.shiftKey && document.activeElement === first) {
+            event.preventDefault();
+            last.focus();
+        } else if (!event.shiftKey && document.activeElement === last) {
+            event.preventDefault();
+            first.focus();
+        }
+    });
+"#;
+
+/// Component Props for [`Modal`]
+///
+/// - `class: String`: the CSS class of the dialog, besides `prompt`.
+/// - `label: String`: the name of the dialog, read by screen readers.
+/// - `onclose: EventHandler`: called when the dialog is dismissed with the
+///   Escape key or a click on the backdrop.
+/// - `children: Element`: the content of the dialog.
+#[derive(Props, Clone, PartialEq)]
+pub struct ModalProps {
+    #[props(default)]
+    class: String,
+    label: String,
+    onclose: EventHandler,
+    children: Element,
+}
+
+/// Component to render a modal dialog
+///
+/// This component renders its children in a dialog over a backdrop that
+/// dims the page.
+/// The keyboard focus moves to the first focusable element of the dialog
+/// and stays inside until it closes,
+/// and the keyboard shortcuts of the board are off meanwhile.
+///
+/// The dialog is rendered by the component that owns it,
+/// which removes it in `onclose`.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn Modal(props: ModalProps) -> Element {
+    let id = use_hook(|| format!("modal-{}", MODALS.fetch_add(1, Ordering::Relaxed)));
+    let onclose = props.onclose;
+    let trap_id = id.clone();
+
+    rsx!(div {
+        class: "backdrop",
+        onclick: move |_| onclose.call(()),
+        div {
+            id: "{id}",
+            class: "prompt {props.class}",
+            role: "dialog",
+            aria_modal: "true",
+            aria_label: "{props.label}",
+            // clicks in the dialog do not reach the backdrop
+            onclick: move |event| event.stop_propagation(),
+            onmounted: move |_| {
+                let mut trap = eval(FOCUS_TRAP);
+                if let Err(err) = trap.send(trap_id.clone().into()) {
+                    log::error!("failed to trap the focus in the modal: {err:?}");
+                    return;
+                }
+                spawn(async move {
+                    while trap.recv().await.is_ok() {
+                        onclose.call(());
+                    }
+                });
+            },
+            {props.children}
+        }
+    })
+}
@@ -0,0 +1,263 @@
+//! # Packs Module
+//!
+//! The `packs` module contains the shared states and the browser of the
+//! puzzle packs.
+//!
+//! [`PacksButton`] toggles the [`PacksPanel`],
+//! which imports pack files, lists the imported packs with the progress of
+//! the active profile, and plays their puzzles in order.
+//!
+//! See also: [`packs`](crate::packs).
+
+use dioxus::prelude::*;
+
+use crate::components::buttons::ActionButton;
+use crate::components::confirm::ConfirmDialog;
+use crate::components::controls::{use_needs_confirm, use_new_game};
+use crate::components::modal::Modal;
+use crate::components::profiles::ActiveProfile;
+use crate::components::toast::use_toast;
+use crate::packs::{
+    load_pack_progress, load_packs, parse_pack, store_pack_progress, store_packs, PuzzlePack,
+};
+use crate::toast::ToastKind;
+
+/// Shared State for the pack puzzle
+///
+/// Represents globally across the app the id of the pack and the position
+/// of the puzzle being played,
+/// or `None` if it was not opened from a pack.
+#[derive(Debug, Clone)]
+pub struct PackPuzzle(pub Option<(u32, usize)>);
+
+/// Shared State for the pack browser
+///
+/// Represents globally across the app if the [`PacksPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct PacksOpen(pub bool);
+
+/// Component to render the puzzle packs button
+///
+/// This component renders a "Puzzle packs" button that opens and closes
+/// the [`PacksPanel`].
+#[component]
+pub fn PacksButton() -> Element {
+    // Unpack shared states
+    let mut packs_open = use_context::<Signal<PacksOpen>>();
+
+    rsx!(ActionButton {
+        icon: "packs",
+        label: "Puzzle packs",
+        tooltip: "Play collections of puzzles",
+        onclick: move |()| {
+            let open = packs_open.read().0;
+            packs_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the pack browser
+///
+/// This component renders, in a [`Modal`], every imported pack with its
+/// author, difficulty and the number of puzzles the active profile solved,
+/// and a button for each of its puzzles, marked when solved.
+/// "Play next" opens the first puzzle of the pack that was not solved yet.
+///
+/// A pack file is imported with [`parse_pack`],
+/// and deleting a pack also forgets its progress in the active profile.
+///
+/// Playing a puzzle starts a new game with it.
+/// If the current game has progress, a [`ConfirmDialog`] asks first.
+#[component]
+pub fn PacksPanel() -> Element {
+    // Unpack shared states
+    let mut packs_open = use_context::<Signal<PacksOpen>>();
+    let mut pack_puzzle = use_context::<Signal<PackPuzzle>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut new_game = use_new_game();
+    let needs_confirm = use_needs_confirm();
+    let mut toast = use_toast();
+
+    let mut packs = use_signal(load_packs);
+    // the pack whose puzzles are listed
+    let mut expanded = use_signal(|| None::<u32>);
+    // the pack puzzle waiting for a confirmation
+    let mut pending = use_signal(|| None::<(u32, usize)>);
+
+    if !packs_open.read().0 {
+        return None;
+    }
+
+    let mut play = move |pack: u32, index: usize| {
+        pending.set(None);
+        let puzzle = packs
+            .peek()
+            .iter()
+            .find(|found| found.id == pack)
+            .and_then(|found| found.puzzle(index));
+        if let Some(puzzle) = puzzle {
+            new_game(puzzle);
+            pack_puzzle.write().0 = Some((pack, index));
+            packs_open.write().0 = false;
+        }
+    };
+    let mut request = move |pack: u32, index: usize| {
+        if needs_confirm() {
+            pending.set(Some((pack, index)));
+        } else {
+            play(pack, index);
+        }
+    };
+    let mut delete = move |pack: u32| {
+        packs.write().retain(|found| found.id != pack);
+        store_packs(&packs.peek());
+        let profile = profile.peek().0;
+        let mut progress = load_pack_progress(profile);
+        progress.remove(pack);
+        store_pack_progress(profile, &progress);
+    };
+
+    let progress = load_pack_progress(profile.read().0);
+    let shown: Vec<PuzzlePack> = packs.read().clone();
+
+    rsx!(
+        Modal {
+            class: "packs",
+            label: "Puzzle packs",
+            onclose: move |()| packs_open.write().0 = false,
+            span { "Puzzle packs" }
+            if shown.is_empty() {
+                span { "No packs yet. Import a pack file to play its puzzles." }
+            }
+            for pack in shown {
+                div {
+                    key: "{pack.id}",
+                    class: "pack",
+                    span { class: "pack-name", "{pack.name}" }
+                    span {
+                        {
+                            let author = if pack.author.is_empty() {
+                                "Unknown author".to_string()
+                            } else {
+                                format!("By {}", pack.author)
+                            };
+                            let difficulty = pack
+                                .difficulty
+                                .map_or_else(String::new, |difficulty| format!(" · {difficulty}"));
+                            format!(
+                                "{author}{difficulty} · {}/{} solved",
+                                progress.solved(pack.id),
+                                pack.puzzles.len(),
+                            )
+                        }
+                    }
+                    span {
+                        class: "pack-actions",
+                        button {
+                            class: "input",
+                            disabled: progress.next(&pack).is_none(),
+                            onclick: {
+                                let next = progress.next(&pack);
+                                move |_| {
+                                    if let Some(index) = next {
+                                        request(pack.id, index);
+                                    }
+                                }
+                            },
+                            "Play next"
+                        }
+                        button {
+                            class: "input",
+                            aria_expanded: "{expanded() == Some(pack.id)}",
+                            onclick: move |_| {
+                                let open = expanded() == Some(pack.id);
+                                expanded.set(if open { None } else { Some(pack.id) });
+                            },
+                            "Puzzles"
+                        }
+                        button {
+                            class: "input",
+                            onclick: move |_| delete(pack.id),
+                            "Delete"
+                        }
+                    }
+                    if expanded() == Some(pack.id) {
+                        span {
+                            class: "pack-puzzles",
+                            for index in 0..pack.puzzles.len() {
+                                button {
+                                    key: "{index}",
+                                    class: if progress.is_solved(pack.id, index) {
+                                        "input solved"
+                                    } else {
+                                        "input"
+                                    },
+                                    aria_label: if progress.is_solved(pack.id, index) {
+                                        format!("Puzzle {}, solved", index + 1)
+                                    } else {
+                                        format!("Puzzle {}", index + 1)
+                                    },
+                                    onclick: move |_| request(pack.id, index),
+                                    "{index + 1}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            label {
+                class: "input",
+                r#for: "pack-import",
+                // reachable with the keyboard, unlike the hidden input
+                tabindex: 0,
+                role: "button",
+                input {
+                    id: "pack-import",
+                    r#type: "file",
+                    accept: ".json",
+                    hidden: true,
+                    onchange: move |event| {
+                        let Some(files) = event.files() else {
+                            return;
+                        };
+                        spawn(async move {
+                            let Some(file_name) = files.files().into_iter().next() else {
+                                return;
+                            };
+                            let Some(contents) = files.read_file_to_string(&file_name).await else {
+                                toast(ToastKind::Error, "The file could not be read".to_string());
+                                return;
+                            };
+                            let parsed = parse_pack(&contents, &packs.peek());
+                            match parsed {
+                                Ok(pack) => {
+                                    toast(ToastKind::Success, format!("Pack \"{}\" imported", pack.name));
+                                    packs.write().push(pack);
+                                    store_packs(&packs.peek());
+                                }
+                                Err(err) => toast(
+                                    ToastKind::Error,
+                                    format!("The pack could not be imported: {err}"),
+                                ),
+                            }
+                        });
+                    }
+                }
+                "Import pack"
+            }
+            button {
+                class: "input",
+                onclick: move |_| packs_open.write().0 = false,
+                "Close"
+            }
+        }
+        if let Some((pack, index)) = pending() {
+            ConfirmDialog {
+                message: "Play puzzle {index + 1} of the pack? The current game stays in My games.",
+                action: "Play",
+                onconfirm: move |()| play(pack, index),
+                oncancel: move |()| pending.set(None),
+            }
+        }
+    )
+}
@@ -0,0 +1,132 @@
+//! # Profiles Module
+//!
+//! The `profiles` module contains the shared state of the local player
+//! profiles and the [`ProfileSwitcher`] component,
+//! which lives in the settings panel.
+//!
+//! Switching profiles loads the settings and save slots of the other
+//! profile and resumes its most recent game.
+
+use dioxus::prelude::*;
+
+use crate::auth::load_session;
+use crate::components::auth::UserSession;
+use crate::components::games::{CurrentSlot, SaveSlots};
+use crate::components::import::use_restore_game;
+use crate::components::settings::UserSettings;
+use crate::profiles::{new_profile, store_active_profile, store_profiles, Profile};
+use crate::save::{load_slots, SavedGame};
+use crate::settings::load_settings;
+use crate::utils::create_sudoku;
+
+/// Shared State for the player profiles
+///
+/// Represents globally across the app the local player profiles.
+#[derive(Debug, Clone)]
+pub struct Profiles(pub Vec<Profile>);
+
+/// Shared State for the active player profile
+///
+/// Represents globally across the app the id of the active [`Profile`].
+#[derive(Debug, Clone)]
+pub struct ActiveProfile(pub u32);
+
+/// Hook that returns a function switching to another player profile.
+///
+/// The settings, session and save slots of the profile are loaded,
+/// and its most recent game is resumed.
+/// If the profile has no games, a new one starts.
+pub fn use_switch_profile() -> impl FnMut(u32) + Copy {
+    // Unpack shared states
+    let mut profile = use_context::<Signal<ActiveProfile>>();
+    let mut settings = use_context::<Signal<UserSettings>>();
+    let mut session = use_context::<Signal<UserSession>>();
+    let mut slots = use_context::<Signal<SaveSlots>>();
+    let mut current_slot = use_context::<Signal<CurrentSlot>>();
+    let mut restore_game = use_restore_game();
+
+    move |id: u32| {
+        profile.write().0 = id;
+        store_active_profile(id);
+        let profile_settings = load_settings(id);
+        settings.write().0 = profile_settings.clone();
+        session.write().0 = load_session(id);
+        let profile_slots = load_slots(id);
+        let latest = profile_slots.first().cloned();
+        slots.write().0 = profile_slots;
+
+        if let Some(slot) = latest {
+            restore_game(slot.game);
+            current_slot.write().0 = slot.id;
+        } else {
+            let initial = create_sudoku();
+            restore_game(SavedGame {
+                initial,
+                moves: vec![initial],
+                notes: [0; 81],
+                timer: 0,
+                settings: profile_settings,
+                move_notes: vec![],
+                tree: None,
+            });
+        }
+    }
+}
+
+/// Component to render the profile switcher
+///
+/// This component renders a picker of the [`Profiles`] to switch to,
+/// and a form to create a new profile, which becomes the active one.
+#[component]
+pub fn ProfileSwitcher() -> Element {
+    // Unpack shared states
+    let mut profiles = use_context::<Signal<Profiles>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut switch_profile = use_switch_profile();
+    let mut name = use_signal(String::new);
+
+    let active = profile.read().0;
+
+    rsx!(
+        label {
+            "Profile"
+            select {
+                onchange: move |event| {
+                    if let Ok(id) = event.value().parse() {
+                        switch_profile(id);
+                    }
+                },
+                for profile in profiles.read().0.iter() {
+                    option {
+                        key: "{profile.id}",
+                        value: "{profile.id}",
+                        selected: profile.id == active,
+                        "{profile.name}"
+                    }
+                }
+            }
+        }
+        label {
+            input {
+                r#type: "text",
+                placeholder: "New player",
+                value: "{name}",
+                oninput: move |event| name.set(event.value()),
+            }
+            button {
+                class: "input",
+                onclick: move |_| {
+                    let Some(profile) = new_profile(&profiles.read().0, &name.read()) else {
+                        return;
+                    };
+                    let id = profile.id;
+                    profiles.write().0.push(profile);
+                    store_profiles(&profiles.read().0);
+                    name.set(String::new());
+                    switch_profile(id);
+                },
+                "Add"
+            }
+        }
+    )
+}
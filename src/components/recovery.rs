@@ -0,0 +1,108 @@
+//! # Recovery Module
+//!
+//! The `recovery` module contains the [`RecoveryCard`],
+//! rendered by the error boundary around the [`SudokuBoard`](crate::components::board::SudokuBoard) when one of
+//! its components fails, instead of a blank page.
+//!
+//! The card recovers the game from its last autosave in the save slots,
+//! and the board is rendered again from scratch,
+//! as the [`Recoveries`] are counted.
+//!
+//! Errors thrown by the components are caught everywhere,
+//! but panics are only caught outside of the browser,
+//! since WebAssembly cannot unwind them.
+
+use dioxus::prelude::*;
+
+use crate::components::board::{use_dispatch, HintsUsed};
+use crate::components::games::{CurrentSlot, SaveSlots};
+use crate::components::profiles::ActiveProfile;
+use crate::components::timer::GameTimer;
+use crate::game::{GameAction, GameState};
+use crate::save::load_slots;
+use crate::undo_tree::UndoTree;
+
+/// Shared State for the recoveries of the board
+///
+/// Represents globally across the app how many times the board was
+/// recovered with the [`RecoveryCard`].
+/// The app reads it, so every recovery renders the error boundary again,
+/// which mounts a new board in place of the card.
+#[derive(Debug, Clone)]
+pub struct Recoveries(pub u32);
+
+/// Component Props for [`RecoveryCard`]
+///
+/// - `error: String`: what went wrong, for the details of the card.
+#[derive(Props, Clone, PartialEq, Eq)]
+pub struct RecoveryCardProps {
+    error: String,
+}
+
+/// Component to render the recovery card
+///
+/// This component tells the user that something went wrong and offers to
+/// recover the game.
+/// The game is restored from its save slot, as last stored in the local
+/// storage, with its moves and the notes on them, notes and timer,
+/// with [`GameAction::Resume`], which clears the clicked cell and the
+/// trial.
+/// If it has none, such as before the first move,
+/// the puzzle is restarted from the start instead.
+/// Hints used are not saved, so the game gets its whole hint budget back.
+///
+/// Restoring the game bumps the [`Recoveries`],
+/// which renders the [`SudokuBoard`](crate::components::board::SudokuBoard) again,
+/// with the shared states of the board reset.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn RecoveryCard(props: RecoveryCardProps) -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut timer = use_context::<Signal<GameTimer>>();
+    let mut hints_used = use_context::<Signal<HintsUsed>>();
+    let mut recoveries = use_context::<Signal<Recoveries>>();
+    let mut slots = use_context::<Signal<SaveSlots>>();
+    let current_slot = use_context::<Signal<CurrentSlot>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut dispatch = use_dispatch();
+
+    let recover = move |_| {
+        let stored = load_slots(profile.peek().0);
+        let initial = game.peek().initial;
+        let (history, notes, game_timer) = stored
+            .iter()
+            .find(|slot| slot.id == current_slot.peek().0)
+            .map_or_else(
+                || (UndoTree::new(initial), [0; 81], 0),
+                |slot| {
+                    let game = &slot.game;
+                    (game.undo_tree(), game.notes, game.timer)
+                },
+            );
+        slots.write().0 = stored;
+
+        // replacing the game with its autosave
+        dispatch(GameAction::Resume { history, notes });
+        timer.write().0 = game_timer;
+        hints_used.write().0 = 0;
+        // rendering the board again in place of the card
+        recoveries.write().0 += 1;
+    };
+
+    rsx!(div {
+        class: "prompt recovery",
+        role: "alert",
+        span { "Something went wrong" }
+        span { "Your game was saved as you played and can be recovered." }
+        button {
+            class: "input",
+            onclick: recover,
+            "Recover game"
+        }
+        details {
+            summary { "Details" }
+            pre { "{props.error}" }
+        }
+    })
+}
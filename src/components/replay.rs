@@ -0,0 +1,112 @@
+//! # Replay Module
+//!
+//! The `replay` module contains the replay of the completed games.
+//!
+//! [`GameReplay`] lives in the [`StatsPanel`](crate::components::stats::StatsPanel),
+//! and steps through the moves of one of the last completed games,
+//! with the notes left on them in the
+//! [`UndoTreePanel`](crate::components::undo_tree::UndoTreePanel).
+
+use dioxus::prelude::*;
+
+use crate::components::games::Thumbnail;
+use crate::daily::{date_of, day_of, MONTH_NAMES};
+use crate::storage::GameRecord;
+
+/// How many of the last completed games can be replayed
+const REPLAY_GAMES: usize = 10;
+
+/// Represents the properties of a [`GameReplay`].
+///
+/// ## Fields
+///
+/// - `records: Vec<GameRecord>`: The history of completed games.
+#[derive(Props, Clone, PartialEq, Eq)]
+pub struct GameReplayProps {
+    records: Vec<GameRecord>,
+}
+
+/// Component to render the replay of a completed game
+///
+/// This component renders a picker of the last completed games,
+/// the board after the chosen move, a slider to step through the moves,
+/// and the note on the move, if any.
+/// The moves with a note are listed, so they can be jumped to.
+/// A game is replayed from its last move.
+#[allow(clippy::needless_pass_by_value)]
+#[must_use]
+pub fn GameReplay(props: GameReplayProps) -> Element {
+    // the chosen game, from the latest, and its move, or `None` for the last
+    let mut chosen = use_signal(|| 0_usize);
+    let mut step = use_signal(|| None::<usize>);
+
+    let mut games: Vec<&GameRecord> = props.records.iter().collect();
+    games.sort_by_key(|record| std::cmp::Reverse(record.finished_at));
+    games.truncate(REPLAY_GAMES);
+    let record = games.get(chosen()).or_else(|| games.first())?;
+    let game = &record.game;
+    let last = game.moves.len() - 1;
+    let shown = step().map_or(last, |step| step.min(last));
+    let annotated: Vec<(usize, String)> = (1..=last)
+        .filter_map(|index| Some((index, game.move_note(index)?.to_string())))
+        .collect();
+    let name = |record: &GameRecord| {
+        let (_, month, day) = date_of(day_of(record.finished_at));
+        let month = MONTH_NAMES[month as usize - 1];
+        format!("{} · {month} {day}", record.difficulty)
+    };
+
+    rsx!(div {
+        class: "replay",
+        span { "Replay a game" }
+        label {
+            "Game"
+            select {
+                onchange: move |event| {
+                    if let Ok(index) = event.value().parse() {
+                        chosen.set(index);
+                        step.set(None);
+                    }
+                },
+                for (index, record) in games.iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: index == chosen(),
+                        "{name(record)}"
+                    }
+                }
+            }
+        }
+        Thumbnail { initial: game.initial, current: game.moves[shown] }
+        label {
+            "Move {shown} of {last}"
+            input {
+                r#type: "range",
+                min: "0",
+                max: "{last}",
+                value: "{shown}",
+                oninput: move |event| {
+                    if let Ok(index) = event.value().parse() {
+                        step.set(Some(index));
+                    }
+                },
+            }
+        }
+        if let Some(note) = game.move_note(shown) {
+            span { class: "move-note", "{note}" }
+        }
+        if !annotated.is_empty() {
+            span {
+                class: "replay-notes",
+                for (index, note) in annotated {
+                    button {
+                        key: "{index}",
+                        class: "input",
+                        onclick: move |_| step.set(Some(index)),
+                        "Move {index}: {note}"
+                    }
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,74 @@
+//! # Save Module
+//!
+//! The `save` module contains the [`SaveButton`] component,
+//! which downloads the current game as a JSON save file.
+//!
+//! Save files are loaded back with the
+//! [`FileButton`](crate::components::import::FileButton).
+
+use dioxus::prelude::*;
+
+use crate::components::buttons::ActionButton;
+use crate::components::settings::UserSettings;
+use crate::components::timer::GameTimer;
+use crate::game::GameState;
+use crate::save::{export_save, SavedGame};
+
+/// JavaScript that downloads the file it receives,
+/// a JSON file unless another type is given
+pub const DOWNLOAD: &str = r#"
+    const { name, contents, type = "application/json" } = await dioxus.recv();
+    const url = URL.createObjectURL(new Blob([contents], { type }));
+    const link = document.createElement("a");
+    link.href = url;
+    link.download = name;
+    link.click();
+    URL.revokeObjectURL(url);
+"#;
+
+/// Component to render the save button
+///
+/// This component renders a "Save" button that downloads the current game,
+/// with its moves and the notes on them, notes, timer and settings,
+/// as a JSON save file created by [`export_save`].
+#[component]
+pub fn SaveButton() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+
+    rsx!(ActionButton {
+        icon: "save",
+        label: "Save file",
+        tooltip: "Download the game as a save file",
+        onclick: move |()| {
+            let game = game.read();
+            let history = &game.history;
+            let game = SavedGame {
+                initial: game.initial,
+                moves: history.moves(),
+                notes: game.notes,
+                timer: timer.read().0,
+                settings: settings.read().0.clone(),
+                move_notes: history.move_notes(),
+                tree: Some(history.clone()),
+            };
+            let contents = match export_save(&game) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    log::error!("failed to export the game: {err:?}");
+                    return;
+                }
+            };
+
+            let download = eval(DOWNLOAD);
+            if let Err(err) = download.send(serde_json::json!({
+                "name": "sudoku-save.json",
+                "contents": contents,
+            })) {
+                log::error!("failed to download the save file: {err:?}");
+            }
+        },
+    })
+}
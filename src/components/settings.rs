@@ -0,0 +1,332 @@
+//! # Settings Module
+//!
+//! The `settings` module contains the shared state of the user's
+//! [`Settings`] and the components to change them.
+//!
+//! [`SettingsButton`] toggles the [`SettingsPanel`],
+//! which lists every setting and the player profile switcher.
+
+use dioxus::prelude::*;
+
+use crate::components::analytics::AnalyticsSettings;
+use crate::components::auth::AccountSettings;
+use crate::components::buttons::ActionButton;
+use crate::components::modal::Modal;
+use crate::components::profiles::ProfileSwitcher;
+use crate::components::sync::SyncSettings;
+use crate::components::theme::ThemeSettings;
+use crate::settings::{
+    ControlSize, InputMode, PadLayout, PadPlacement, Palette, Settings, BOARD_SCALE, DIGIT_SCALE,
+    HINT_BUDGETS,
+};
+use crate::symbols::SymbolSet;
+
+/// Shared State for the user's [`Settings`]
+///
+/// Represents globally across the app the user's preferences.
+#[derive(Debug, Clone)]
+pub struct UserSettings(pub Settings);
+
+/// Shared State for the settings panel
+///
+/// Represents globally across the app if the [`SettingsPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct SettingsOpen(pub bool);
+
+/// Component to render the settings button
+///
+/// This component renders a "Settings" button that opens and closes the
+/// [`SettingsPanel`].
+#[component]
+pub fn SettingsButton() -> Element {
+    // Unpack shared states
+    let mut settings_open = use_context::<Signal<SettingsOpen>>();
+
+    rsx!(ActionButton {
+        icon: "settings",
+        label: "Settings",
+        tooltip: "Change your preferences",
+        onclick: move |()| {
+            let open = settings_open.read().0;
+            settings_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the settings panel
+///
+/// This component renders, in a [`Modal`], a control for every [`Settings`] field,
+/// with the custom [`ThemeSettings`],
+/// the [`AccountSettings`], cloud [`SyncSettings`] and
+/// [`AnalyticsSettings`],
+/// and the [`ProfileSwitcher`], since settings belong to a profile.
+/// Changes are applied immediately.
+#[component]
+pub fn SettingsPanel() -> Element {
+    // Unpack shared states
+    let mut settings = use_context::<Signal<UserSettings>>();
+    let mut settings_open = use_context::<Signal<SettingsOpen>>();
+
+    if !settings_open.read().0 {
+        return None;
+    }
+
+    rsx!(Modal {
+        class: "settings",
+        label: "Settings",
+        onclose: move |()| settings_open.write().0 = false,
+        ProfileSwitcher {}
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.kids,
+                onchange: move |event| settings.write().0.set_kids(event.checked()),
+            }
+            "Kids mode, with pictures, big cells and no timer"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.large_print,
+                onchange: move |event| settings.write().0.large_print = event.checked(),
+            }
+            "Large print, with the biggest digits and a fixed number pad"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.show_timer,
+                onchange: move |event| settings.write().0.show_timer = event.checked(),
+            }
+            "Show timer"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.show_track,
+                onchange: move |event| settings.write().0.show_track = event.checked(),
+            }
+            "Show if the entries match the solution"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.auto_complete,
+                onchange: move |event| settings.write().0.auto_complete = event.checked(),
+            }
+            "Offer to finish the board when only naked singles are left"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.haptics,
+                onchange: move |event| settings.write().0.haptics = event.checked(),
+            }
+            "Haptic feedback"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.reduce_motion,
+                onchange: move |event| settings.write().0.reduce_motion = event.checked(),
+            }
+            "Reduce motion"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.confirm_discard,
+                onchange: move |event| settings.write().0.confirm_discard = event.checked(),
+            }
+            "Confirm before discarding a game"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.speedrun,
+                onchange: move |event| settings.write().0.speedrun = event.checked(),
+            }
+            "Speedrun mode"
+        }
+        label {
+            input {
+                r#type: "checkbox",
+                checked: settings.read().0.ghost,
+                onchange: move |event| settings.write().0.ghost = event.checked(),
+            }
+            "Race the personal best"
+        }
+        label {
+            "Board size"
+            input {
+                r#type: "range",
+                min: "{BOARD_SCALE.start()}",
+                max: "{BOARD_SCALE.end()}",
+                step: "10",
+                value: "{settings.read().0.board_scale}",
+                oninput: move |event| {
+                    if let Ok(percent) = event.value().parse() {
+                        settings.write().0.set_board_scale(percent);
+                    }
+                },
+            }
+        }
+        label {
+            "Digit size"
+            input {
+                r#type: "range",
+                min: "{DIGIT_SCALE.start()}",
+                max: "{DIGIT_SCALE.end()}",
+                step: "10",
+                value: "{settings.read().0.digit_scale}",
+                oninput: move |event| {
+                    if let Ok(percent) = event.value().parse() {
+                        settings.write().0.set_digit_scale(percent);
+                    }
+                },
+            }
+        }
+        label {
+            "Colors"
+            select {
+                onchange: move |event| {
+                    let index = event.value().parse::<usize>().ok();
+                    if let Some(&palette) = index.and_then(|index| Palette::ALL.get(index)) {
+                        let settings = &mut settings.write().0;
+                        settings.palette = palette;
+                        // picking a palette leaves the custom theme
+                        settings.custom_theme = None;
+                    }
+                },
+                for (index, palette) in Palette::ALL.into_iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: settings.read().0.palette == palette,
+                        "{palette}"
+                    }
+                }
+            }
+        }
+        ThemeSettings {}
+        label {
+            "Symbols"
+            select {
+                onchange: move |event| {
+                    let index = event.value().parse::<usize>().ok();
+                    if let Some(&symbols) = index.and_then(|index| SymbolSet::ALL.get(index)) {
+                        settings.write().0.symbols = symbols;
+                    }
+                },
+                for (index, symbols) in SymbolSet::ALL.into_iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: settings.read().0.symbols == symbols,
+                        "{symbols}"
+                    }
+                }
+            }
+        }
+        label {
+            "Input"
+            select {
+                onchange: move |event| {
+                    let index = event.value().parse::<usize>().ok();
+                    if let Some(&mode) = index.and_then(|index| InputMode::ALL.get(index)) {
+                        settings.write().0.input_mode = mode;
+                    }
+                },
+                for (index, mode) in InputMode::ALL.into_iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: settings.read().0.input_mode == mode,
+                        "{mode}"
+                    }
+                }
+            }
+        }
+        label {
+            "Hints"
+            select {
+                onchange: move |event| {
+                    let index = event.value().parse::<usize>().ok();
+                    if let Some(&budget) = index.and_then(|index| HINT_BUDGETS.get(index)) {
+                        settings.write().0.hint_budget = budget;
+                    }
+                },
+                for (index, budget) in HINT_BUDGETS.into_iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: settings.read().0.hint_budget == budget,
+                        match budget {
+                            None => "Unlimited".to_string(),
+                            Some(0) => "None".to_string(),
+                            Some(hints) => format!("{hints} per game"),
+                        }
+                    }
+                }
+            }
+        }
+        label {
+            "Number pad"
+            select {
+                onchange: move |event| {
+                    let index = event.value().parse::<usize>().ok();
+                    if let Some(&placement) = index.and_then(|index| PadPlacement::ALL.get(index)) {
+                        settings.write().0.pad_placement = placement;
+                    }
+                },
+                for (index, placement) in PadPlacement::ALL.into_iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: settings.read().0.pad_placement == placement,
+                        "{placement}"
+                    }
+                }
+            }
+        }
+        label {
+            "Number pad layout"
+            select {
+                onchange: move |event| {
+                    let index = event.value().parse::<usize>().ok();
+                    if let Some(&layout) = index.and_then(|index| PadLayout::ALL.get(index)) {
+                        settings.write().0.pad_layout = layout;
+                    }
+                },
+                for (index, layout) in PadLayout::ALL.into_iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: settings.read().0.pad_layout == layout,
+                        "{layout}"
+                    }
+                }
+            }
+        }
+        label {
+            "Button size"
+            select {
+                onchange: move |event| {
+                    let index = event.value().parse::<usize>().ok();
+                    if let Some(&size) = index.and_then(|index| ControlSize::ALL.get(index)) {
+                        settings.write().0.control_size = size;
+                    }
+                },
+                for (index, size) in ControlSize::ALL.into_iter().enumerate() {
+                    option {
+                        value: "{index}",
+                        selected: settings.read().0.control_size == size,
+                        "{size}"
+                    }
+                }
+            }
+        }
+        AccountSettings {}
+        SyncSettings {}
+        AnalyticsSettings {}
+        button {
+            class: "input",
+            onclick: move |_| settings_open.write().0 = false,
+            "Close"
+        }
+    })
+}
@@ -0,0 +1,179 @@
+//! # Speedrun Module
+//!
+//! The `speedrun` module contains the [`use_personal_bests`] hook,
+//! which submits the times of the speedrun mode to the
+//! [`PersonalBests`](crate::speedrun::PersonalBests),
+//! and the [`PersonalBest`] shown once a puzzle is solved.
+//!
+//! The [`Progress`] of the game is recorded with [`use_progress_log`],
+//! kept as the [`Ghost`] of a new personal best,
+//! and raced in the [`GhostRace`] by the next puzzle of its difficulty.
+//!
+//! See also: [`speedrun`](crate::speedrun).
+
+use dioxus::prelude::*;
+
+use crate::app::SudokuState;
+use crate::components::profiles::ActiveProfile;
+use crate::components::settings::UserSettings;
+use crate::components::timer::GameTimer;
+use crate::difficulty::grade_puzzle;
+use crate::game::GameState;
+use crate::speedrun::{
+    filled_cells, load_ghosts, load_personal_bests, percent, store_ghosts, store_personal_bests,
+    Ghost, ProgressLog,
+};
+use crate::utils::{format_duration_millis, is_solved};
+
+/// Shared State for the progress of the game
+///
+/// Represents globally across the app how many cells of the current game
+/// were filled over time.
+#[derive(Debug, Clone)]
+pub struct Progress(pub ProgressLog);
+
+/// Hook that records the progress of the game.
+///
+/// It provides the [`Progress`] shared state,
+/// and records the cells filled after every move,
+/// at the time of the [`GameTimer`].
+pub fn use_progress_log() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let mut progress =
+        use_context_provider(|| Signal::new(Progress(ProgressLog::new(game.peek().initial))));
+    // the boards alone, so selecting a cell records nothing
+    let boards = use_memo(move || (game.read().initial, game.read().current()));
+
+    use_effect(move || {
+        let (puzzle, board) = boards();
+        progress.write().0.record(&puzzle, &board, timer.peek().0);
+    });
+}
+
+/// Hook that submits the time of the game to the personal bests of its
+/// difficulty once it is solved, in speedrun mode.
+///
+/// Only a puzzle played unsolved is submitted,
+/// not one that was already solved when loaded.
+/// The time is submitted again if the timer changes once solved,
+/// as when the time since its last tick is counted,
+/// so the exact time is kept.
+/// A new personal best keeps the [`Progress`] of the game as its
+/// [`Ghost`].
+pub fn use_personal_bests() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let progress = use_context::<Signal<Progress>>();
+    // the puzzle last seen unsolved
+    let mut played = use_signal(|| None::<SudokuState>);
+
+    use_effect(move || {
+        let millis = timer.read().0;
+        let initial = game.read().initial;
+        if !is_solved(&game.read().current()) {
+            if *played.peek() != Some(initial) {
+                played.set(Some(initial));
+            }
+            return;
+        }
+        if !settings.peek().0.speedrun || *played.peek() != Some(initial) {
+            return;
+        }
+        let profile = profile.peek().0;
+        let difficulty = grade_puzzle(&initial);
+        let mut bests = load_personal_bests(profile);
+        if bests.submit(difficulty, millis) {
+            store_personal_bests(profile, &bests);
+            // the board is full at the time of the best
+            let mut ghost = progress.peek().0.ghost.clone();
+            ghost.record(ghost.cells, millis);
+            let mut ghosts = load_ghosts(profile);
+            ghosts.0.insert(difficulty, ghost);
+            store_ghosts(profile, &ghosts);
+        }
+    });
+}
+
+/// Component to render the personal best of the difficulty of the puzzle
+///
+/// This component renders, in speedrun mode,
+/// the personal best of the difficulty of the puzzle,
+/// and whether the time of the game is a new one.
+#[component]
+pub fn PersonalBest() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+
+    if !settings.read().0.speedrun {
+        return None;
+    }
+    let difficulty = grade_puzzle(&game.read().initial);
+    let best = load_personal_bests(profile.read().0).best(difficulty)?;
+
+    rsx!(span {
+        class: "personal-best",
+        if best == timer.read().0 {
+            "New personal best for {difficulty}!"
+        } else {
+            "Personal best for {difficulty}: {format_duration_millis(best)}"
+        }
+    })
+}
+
+/// Component to render the race against the ghost of the personal best
+///
+/// This component renders, with the
+/// [`ghost`](crate::settings::Settings::ghost) setting,
+/// the progress of the game next to the progress of the personal best of
+/// the difficulty of the puzzle at the same time, until the board is
+/// solved.
+/// Nothing is rendered if the difficulty has no personal best yet.
+#[component]
+pub fn GhostRace() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+
+    let puzzle = use_memo(move || game.read().initial);
+    // the ghost is only loaded when the puzzle or the profile changes
+    let ghost = use_memo(move || {
+        let difficulty = grade_puzzle(&puzzle.read());
+        load_ghosts(profile.read().0).0.remove(&difficulty)
+    });
+
+    let board = game.read().current();
+    if !settings.read().0.ghost || is_solved(&board) {
+        return None;
+    }
+    let ghost: Ghost = ghost.read().clone()?;
+    let millis = timer.read().0;
+    let initial = game.read().initial;
+    let own = percent(
+        filled_cells(&initial, &board),
+        filled_cells(&initial, &[1; 81]),
+    );
+    let best = ghost.progress(millis);
+
+    rsx!(div {
+        class: "ghost-race",
+        label {
+            "You"
+            progress { max: "100", value: "{own}", "{own}%" }
+        }
+        label {
+            class: "ghost",
+            "Best"
+            progress { max: "100", value: "{best}", "{best}%" }
+        }
+    })
+}
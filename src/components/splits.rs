@@ -0,0 +1,78 @@
+//! # Splits Module
+//!
+//! The `splits` module contains the shared state of the [`SplitTimes`] of
+//! the game, the [`use_split_times`] hook that records them,
+//! and the [`SplitsTable`] that lists them.
+//!
+//! See also: [`splits`](crate::splits).
+
+use dioxus::prelude::*;
+
+use crate::components::timer::GameTimer;
+use crate::game::GameState;
+use crate::splits::SplitTimes;
+use crate::utils::format_duration;
+
+/// Shared State for the split times
+///
+/// Represents globally across the app the time every digit and box of the
+/// current game was completed at.
+#[derive(Debug, Clone)]
+pub struct Splits(pub SplitTimes);
+
+/// Hook that records the split times of the game.
+///
+/// It provides the [`Splits`] shared state,
+/// and records the splits reached after every move,
+/// at the time of the [`GameTimer`].
+pub fn use_split_times() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let mut splits =
+        use_context_provider(|| Signal::new(Splits(SplitTimes::new(game.peek().initial))));
+    // the boards alone, so selecting a cell records nothing
+    let boards = use_memo(move || (game.read().initial, game.read().current()));
+
+    use_effect(move || {
+        let (puzzle, board) = boards();
+        splits.write().0.record(&puzzle, &board, timer.peek().0);
+    });
+}
+
+/// Component to render the splits table
+///
+/// This component renders a row for every split reached,
+/// in the order they were reached,
+/// with the time it was reached at and the time since the previous one.
+#[component]
+pub fn SplitsTable() -> Element {
+    // Unpack shared states
+    let splits = use_context::<Signal<Splits>>();
+
+    let laps = splits.read().0.laps();
+    if laps.is_empty() {
+        return None;
+    }
+
+    rsx!(table {
+        class: "splits",
+        thead {
+            tr {
+                th { "Split" }
+                th { "Time" }
+                th { "Lap" }
+            }
+        }
+        tbody {
+            for (split, millis, lap) in laps {
+                tr {
+                    key: "{split}",
+                    td { "{split}" }
+                    td { "{format_duration(millis)}" }
+                    td { "+{format_duration(lap)}" }
+                }
+            }
+        }
+    })
+}
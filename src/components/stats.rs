@@ -0,0 +1,210 @@
+//! # Stats Module
+//!
+//! The `stats` module contains the statistics panel of the active profile.
+//!
+//! [`StatsButton`] toggles the [`StatsPanel`],
+//! which lists the games solved, the best and average times and the hints
+//! used of every difficulty,
+//! with [`charts`](crate::components::charts) of the times and the accuracy
+//! of the last games, the games solved and the hints used.
+//! A heatmap of the board shows where the wrong digits are placed most
+//! often.
+//! The games and the statistics can be downloaded as CSV files,
+//! and the last games can be replayed with their notes.
+//!
+//! See also: [`stats`](crate::stats).
+
+use dioxus::prelude::*;
+
+use crate::components::buttons::ActionButton;
+use crate::components::charts::{BarChart, GridHeatmap, LineChart};
+use crate::components::modal::Modal;
+use crate::components::profiles::ActiveProfile;
+use crate::components::replay::GameReplay;
+use crate::components::save::DOWNLOAD;
+use crate::stats::{accuracy, compute_stats, games_csv, mistake_map, stats_csv, trend, TREND_SIZE};
+use crate::storage::load_records;
+use crate::utils::format_duration;
+
+/// Shared State for the statistics panel
+///
+/// Represents globally across the app if the [`StatsPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct StatsOpen(pub bool);
+
+/// Component to render a statistics button
+///
+/// This component renders a "Statistics" button that opens and closes the
+/// [`StatsPanel`].
+#[component]
+pub fn StatsButton() -> Element {
+    // Unpack shared states
+    let mut stats_open = use_context::<Signal<StatsOpen>>();
+
+    rsx!(ActionButton {
+        icon: "stats",
+        label: "Statistics",
+        tooltip: "See your solved games and best times",
+        onclick: move |()| {
+            let open = stats_open.read().0;
+            stats_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the statistics panel
+///
+/// This component renders, in a [`Modal`], the statistics of the
+/// active profile.
+#[component]
+pub fn StatsPanel() -> Element {
+    // Unpack shared states
+    let mut stats_open = use_context::<Signal<StatsOpen>>();
+
+    if !stats_open.read().0 {
+        return None;
+    }
+
+    rsx!(Modal {
+        class: "stats",
+        label: "Statistics",
+        onclose: move |()| stats_open.write().0 = false,
+        span { "Statistics" }
+        StatsTable {}
+        button {
+            class: "input",
+            onclick: move |_| stats_open.write().0 = false,
+            "Close"
+        }
+    })
+}
+
+/// Component to render the statistics table
+///
+/// This component renders a row for every difficulty,
+/// with the games solved, the best and average times and the hints used,
+/// followed by the charts of the history and the heatmap of the wrong
+/// digits, the [`GameReplay`] of the last games,
+/// and the buttons downloading the games and the statistics as CSV files.
+/// The history of completed games is loaded from the
+/// [`storage`](crate::storage) every time the table is rendered,
+/// so it is up to date.
+#[component]
+fn StatsTable() -> Element {
+    // Unpack shared states
+    let profile = use_context::<Signal<ActiveProfile>>();
+
+    let records = use_resource(move || async move {
+        load_records(profile.read().0).await.unwrap_or_else(|err| {
+            log::error!("failed to load the history: {err:?}");
+            vec![]
+        })
+    });
+
+    let Some(records) = &*records.read() else {
+        return rsx!(span { "Loading…" });
+    };
+    let stats = compute_stats(records);
+    let time = |millis: Option<u64>| millis.map_or_else(|| "–".to_string(), format_duration);
+
+    // the times in seconds, so the chart reads in whole units
+    let times = trend(records, |record| Some(record.game.timer / 1000));
+    let times_summary = times
+        .iter()
+        .map(|&seconds| format_duration(seconds * 1000))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let accuracies = trend(records, |record| accuracy(&record.game));
+    let accuracies_summary = accuracies
+        .iter()
+        .map(|percent| format!("{percent}%"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let solved = stats
+        .iter()
+        .map(|row| (row.difficulty.to_string(), row.solved as u64))
+        .collect::<Vec<_>>();
+    let hints = stats
+        .iter()
+        .map(|row| (row.difficulty.to_string(), u64::from(row.hints)))
+        .collect::<Vec<_>>();
+
+    let mistakes = mistake_map(records);
+    let wrong_digits = (1..=9)
+        .zip(mistakes.digits)
+        .map(|(digit, count)| (digit.to_string(), u64::from(count)))
+        .collect::<Vec<_>>();
+
+    let games_file = games_csv(records);
+    let stats_file = stats_csv(&stats);
+    let download = |name: &'static str, contents: String| {
+        move |_| {
+            let download = eval(DOWNLOAD);
+            if let Err(err) = download.send(serde_json::json!({
+                "name": name,
+                "contents": contents,
+                "type": "text/csv",
+            })) {
+                log::error!("failed to download the CSV file: {err:?}");
+            }
+        }
+    };
+
+    rsx!(
+        table {
+            thead {
+                tr {
+                    th { "Difficulty" }
+                    th { "Solved" }
+                    th { "Best" }
+                    th { "Average" }
+                    th { "Hints" }
+                }
+            }
+            tbody {
+                for row in stats {
+                    tr {
+                        td { "{row.difficulty}" }
+                        td { "{row.solved}" }
+                        td { "{time(row.best)}" }
+                        td { "{time(row.average)}" }
+                        td { "{row.hints}" }
+                    }
+                }
+            }
+        }
+        div {
+            class: "charts",
+            LineChart {
+                title: "Solve time of the last {TREND_SIZE} games",
+                values: times,
+                summary: times_summary,
+            }
+            LineChart {
+                title: "Accuracy of the last {TREND_SIZE} games",
+                values: accuracies,
+                summary: accuracies_summary,
+            }
+            BarChart { title: "Games solved", bars: solved }
+            BarChart { title: "Hints used", bars: hints }
+            if mistakes.max_cell() > 0 {
+                GridHeatmap { title: "Wrong digits by cell", cells: mistakes.cells.to_vec() }
+                BarChart { title: "Wrong digits by answer", bars: wrong_digits }
+            }
+        }
+        GameReplay { records: records.clone() }
+        div {
+            class: "csv-export",
+            button {
+                class: "input",
+                onclick: download("sudoku-games.csv", games_file),
+                "Download games (CSV)"
+            }
+            button {
+                class: "input",
+                onclick: download("sudoku-stats.csv", stats_file),
+                "Download statistics (CSV)"
+            }
+        }
+    )
+}
@@ -0,0 +1,90 @@
+//! # Status Module
+//!
+//! The `status` module contains the [`StatusBar`],
+//! the row above the board that sums up the current game:
+//! its difficulty, the game [`Timer`], the mistakes made,
+//! and the hints left,
+//! with a warning when the board can no longer be solved.
+
+use dioxus::prelude::*;
+
+use crate::components::board::HintsUsed;
+use crate::components::settings::UserSettings;
+use crate::components::timer::Timer;
+use crate::difficulty::grade_puzzle;
+use crate::game::GameState;
+use crate::utils::{count_mistakes, find_solution, get_all_conflicting_cells, is_solvable};
+
+/// Component to render the status bar
+///
+/// This component renders, in one row, the difficulty of the puzzle,
+/// graded with [`grade_puzzle`], the [`Timer`],
+/// the number of filled cells that do not match the solution,
+/// counted with [`count_mistakes`],
+/// and the hints left in the
+/// [`hint_budget`](crate::settings::Settings::hint_budget).
+/// With [`show_track`](crate::settings::Settings::show_track),
+/// it also shows whether all the entries still match the solution.
+/// The mistakes are not counted in the
+/// [`kids`](crate::settings::Settings::kids) mode.
+///
+/// If the board has no conflicting cells, but no solution either,
+/// checked with [`is_solvable`] after every move,
+/// it warns that the position is unsolvable,
+/// so the player can undo instead of digging deeper into a dead end.
+///
+/// The difficulty and the solution are only computed when the puzzle
+/// changes.
+#[component]
+pub fn StatusBar() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let hints_used = use_context::<Signal<HintsUsed>>();
+    let settings = use_context::<Signal<UserSettings>>();
+
+    let difficulty = use_memo(move || grade_puzzle(&game.read().initial));
+    let solution = use_memo(move || find_solution(&game.read().initial).ok());
+    // conflicting cells are already shown on the board
+    let unsolvable = use_memo(move || {
+        let board = game.read().current();
+        get_all_conflicting_cells(&board).is_empty() && !is_solvable(&board)
+    });
+
+    let mistakes = solution.read().map_or(0, |solution| {
+        count_mistakes(&game.read().current(), &solution)
+    });
+    let kids = settings.read().0.kids;
+    let show_track = settings.read().0.show_track && solution.read().is_some() && !kids;
+    let hints_left = settings.read().0.hint_budget.map_or_else(
+        || "∞".to_string(),
+        |budget| budget.saturating_sub(hints_used.read().0).to_string(),
+    );
+
+    rsx!(div {
+        class: "status-bar",
+        span { "{difficulty}" }
+        Timer {}
+        if !kids {
+            span { "Mistakes: {mistakes}" }
+        }
+        span { "Hints: {hints_left}" }
+        if show_track {
+            span {
+                class: if mistakes == 0 { "track on" } else { "track off" },
+                if mistakes == 0 {
+                    "On track"
+                } else {
+                    "Off track"
+                }
+            }
+        }
+        if unsolvable() {
+            span {
+                class: "unsolvable",
+                role: "status",
+                title: "No solution is left from this position: undo some moves",
+                "Unsolvable position"
+            }
+        }
+    })
+}
@@ -0,0 +1,145 @@
+//! # Sync Module
+//!
+//! The `sync` module contains the shared state and components of the
+//! optional cloud [`sync`](crate::sync).
+//!
+//! [`SyncSettings`] lives in the settings panel and lets the user sync on
+//! demand.
+//! Games are also synced when the app starts, if sync is on.
+
+use dioxus::prelude::*;
+
+use crate::components::auth::UserSession;
+use crate::components::games::SaveSlots;
+use crate::components::profiles::ActiveProfile;
+use crate::components::settings::UserSettings;
+use crate::save::{load_deleted_slots, store_deleted_slots, store_slots, SaveSlot};
+use crate::settings::{load_settings_modified_at, store_synced_settings, Settings};
+use crate::storage::{add_record, load_records, GameRecord};
+use crate::sync::{is_same_record, merge, sync, SyncDocument};
+
+/// Shared State for the status of the cloud sync
+///
+/// Represents globally across the app the outcome of the last sync:
+/// `None` if no sync happened, `Some(Ok(()))` if it succeeded,
+/// or `Some(Err(reason))` if it failed.
+#[derive(Debug, Clone)]
+pub struct SyncStatus(pub Option<Result<(), String>>);
+
+/// Returns the document of the active profile on this device.
+fn local_document(
+    profile: u32,
+    slots: &[SaveSlot],
+    settings: &Settings,
+    history: Vec<GameRecord>,
+) -> SyncDocument {
+    let settings_modified_at = load_settings_modified_at(profile);
+    SyncDocument {
+        modified_at: slots
+            .iter()
+            .map(|slot| slot.modified_at)
+            .chain(history.iter().map(|record| record.finished_at))
+            .chain([settings_modified_at])
+            .max()
+            .unwrap_or(0),
+        settings: settings.clone(),
+        settings_modified_at,
+        slots: slots.to_vec(),
+        history,
+        deleted: load_deleted_slots(profile),
+    }
+}
+
+/// Hook that returns a function syncing the games of the active profile.
+///
+/// The local save slots, history, deleted slots and settings are merged
+/// with the remote ones.
+/// Once the server answers, the merged data is merged again with the local
+/// data, so the games saved and the settings changed while syncing are kept.
+/// Nothing is done if sync is off, that is if there is no server or the
+/// profile is signed out.
+pub fn use_sync() -> impl FnMut() + Copy {
+    // Unpack shared states
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut settings = use_context::<Signal<UserSettings>>();
+    let mut slots = use_context::<Signal<SaveSlots>>();
+    let session = use_context::<Signal<UserSession>>();
+    let mut status = use_context::<Signal<SyncStatus>>();
+
+    move || {
+        let server = settings.peek().0.server_url.clone();
+        let Some(session) = session.peek().0.clone().filter(|_| !server.is_empty()) else {
+            return;
+        };
+        spawn(async move {
+            let profile = profile.peek().0;
+            let history = load_records(profile).await.unwrap_or_default();
+            let local = local_document(
+                profile,
+                &slots.peek().0,
+                &settings.peek().0,
+                history.clone(),
+            );
+
+            let synced = match sync(&server, &session, local).await {
+                Ok(synced) => synced,
+                Err(err) => {
+                    status.write().0 = Some(Err(err.to_string()));
+                    return;
+                }
+            };
+            // keeping what changed on this device while syncing
+            let current = local_document(profile, &slots.peek().0, &settings.peek().0, vec![]);
+            let merged = merge(current, synced);
+
+            // keeping the completed games that are new to this device
+            for mut record in merged.history {
+                if !history.iter().any(|other| is_same_record(other, &record)) {
+                    record.profile = profile;
+                    if let Err(err) = add_record(&record).await {
+                        log::error!("failed to add the game to the history: {err:?}");
+                    }
+                }
+            }
+            let mut merged_settings = merged.settings;
+            merged_settings.server_url = server;
+            store_synced_settings(profile, &merged_settings, merged.settings_modified_at);
+            store_slots(profile, &merged.slots);
+            store_deleted_slots(profile, &merged.deleted);
+            slots.write().0 = merged.slots;
+            settings.write().0 = merged_settings;
+            status.write().0 = Some(Ok(()));
+        });
+    }
+}
+
+/// Component to render the cloud sync settings
+///
+/// This component renders a button to sync now and the [`SyncStatus`].
+/// Syncing needs a server and a signed in account.
+#[component]
+pub fn SyncSettings() -> Element {
+    // Unpack shared states
+    let settings = use_context::<Signal<UserSettings>>();
+    let session = use_context::<Signal<UserSession>>();
+    let status = use_context::<Signal<SyncStatus>>();
+    let mut sync_games = use_sync();
+
+    let status = match &status.read().0 {
+        None => String::new(),
+        Some(Ok(())) => "Synced".to_string(),
+        Some(Err(reason)) => format!("Sync failed: {reason}"),
+    };
+
+    rsx!(
+        button {
+            class: "input",
+            disabled: settings.read().0.server_url.is_empty() || session.read().0.is_none(),
+            onclick: move |_| sync_games(),
+            "Sync now"
+        }
+        if !status.is_empty() {
+            span { "{status}" }
+        }
+    )
+}
@@ -0,0 +1,187 @@
+//! # Techniques Module
+//!
+//! The `techniques` module contains the shared state and the components of
+//! the explained hints.
+//!
+//! [`ExplainButton`] finds the next step with [`next_step`],
+//! [`TechniqueCaption`] explains it in words,
+//! and [`TechniqueOverlay`] draws it over the board:
+//! the cells of its pattern and the implication links between them.
+//! The candidates the step eliminates are crossed out in the notes of the
+//! cells, see [`Cell`](crate::components::cell::Cell).
+//!
+//! See also: [`techniques`](crate::techniques).
+
+use dioxus::prelude::*;
+
+use crate::components::board::use_dispatch;
+use crate::components::buttons::ActionButton;
+use crate::components::toast::use_toast;
+use crate::game::{GameAction, GameState};
+use crate::techniques::{next_step, TechniqueStep};
+use crate::toast::ToastKind;
+
+/// Shared State for the explained hint
+///
+/// Represents globally across the app the step being explained,
+/// or `None` if there is none.
+/// It is cleared whenever the board changes.
+#[derive(Debug, Clone)]
+pub struct Explanation(pub Option<TechniqueStep>);
+
+/// Hook that provides the [`Explanation`] shared state.
+///
+/// The explanation is cleared whenever the board changes,
+/// since the step may no longer apply.
+pub fn use_explanation() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut explanation = use_context_provider(|| Signal::new(Explanation(None)));
+    // the board alone, so selecting a cell keeps the explanation
+    let sudoku = use_memo(move || game.read().current());
+
+    use_effect(move || {
+        sudoku.read();
+        if explanation.peek().0.is_some() {
+            explanation.set(Explanation(None));
+        }
+    });
+}
+
+/// Component to render the explain button
+///
+/// This component renders an "Explain" [`ActionButton`] that shows the
+/// next step towards the solution, and why it works,
+/// or hides it if it is shown.
+/// Unlike a hint, it does not fill any cell.
+#[component]
+pub fn ExplainButton() -> Element {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut explanation = use_context::<Signal<Explanation>>();
+    let mut toast = use_toast();
+
+    let shown = explanation.read().0.is_some();
+    let filled = !game.read().current().contains(&0);
+
+    rsx!(ActionButton {
+        icon: if shown { "explain on" } else { "explain" },
+        label: if shown { "Hide explanation" } else { "Explain" },
+        tooltip: "Show the next step and why it works",
+        disabled: filled,
+        onclick: move |()| {
+            if shown {
+                explanation.set(Explanation(None));
+                return;
+            }
+            match next_step(&game.peek().current(), &game.peek().notes) {
+                Some(step) => explanation.set(Explanation(Some(step))),
+                None => toast(
+                    ToastKind::Info,
+                    "No technique applies to this board".to_string(),
+                ),
+            }
+        },
+    })
+}
+
+/// Component to render the explanation of a step
+///
+/// This component renders the description of the [`Explanation`],
+/// if there is one, with a button to dismiss it.
+/// A step that eliminates candidates, instead of placing a digit,
+/// has a "Cross out" button that removes them from the notes.
+#[component]
+pub fn TechniqueCaption() -> Element {
+    // Unpack shared states
+    let mut explanation = use_context::<Signal<Explanation>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut dispatch = use_dispatch();
+
+    let step = explanation.read().0.clone()?;
+
+    rsx!(div {
+        class: "technique-caption",
+        role: "status",
+        span { "{step.description}" }
+        if !step.eliminations.is_empty() {
+            button {
+                class: "input",
+                onclick: move |_| {
+                    let crossed = {
+                        let game = game.peek();
+                        step.apply_eliminations(&game.current(), &game.notes)
+                    };
+                    dispatch(GameAction::SetNotes(crossed));
+                    explanation.set(Explanation(None));
+                },
+                "Cross out"
+            }
+        }
+        button {
+            class: "input",
+            aria_label: "Dismiss the explanation",
+            onclick: move |_| explanation.set(Explanation(None)),
+            "×"
+        }
+    })
+}
+
+/// Component to render a step over the board
+///
+/// This component draws, as an SVG over the board, the cells of the
+/// pattern of the [`Explanation`] and the implication links between them,
+/// as arrows from the center of a cell to the center of the next.
+/// The overlay lets clicks through to the cells.
+#[component]
+pub fn TechniqueOverlay() -> Element {
+    // Unpack shared states
+    let explanation = use_context::<Signal<Explanation>>();
+
+    let step = explanation.read().0.clone()?;
+    // the center of a cell, in cells
+    let center = |index: u8| (f32::from(index % 9) + 0.5, f32::from(index / 9) + 0.5);
+
+    rsx!(svg {
+        class: "technique-overlay",
+        view_box: "0 0 9 9",
+        "aria-hidden": "true",
+        defs {
+            marker {
+                id: "technique-arrow",
+                view_box: "0 0 10 10",
+                ref_x: "10",
+                ref_y: "5",
+                marker_width: "4",
+                marker_height: "4",
+                orient: "auto",
+                path { d: "M0 0L10 5L0 10z" }
+            }
+        }
+        for &cell in &step.cells {
+            rect {
+                key: "cell-{cell}",
+                x: "{cell % 9}",
+                y: "{cell / 9}",
+                width: "1",
+                height: "1",
+            }
+        }
+        for &(from, to) in &step.links {
+            {
+                let ((x1, y1), (x2, y2)) = (center(from), center(to));
+                // stop short of the center of the cell, to leave its digit visible
+                let (dx, dy) = (x2 - x1, y2 - y1);
+                let shorten = 0.3 / dx.hypot(dy);
+                rsx!(line {
+                    key: "link-{from}-{to}",
+                    x1: "{dx.mul_add(shorten, x1)}",
+                    y1: "{dy.mul_add(shorten, y1)}",
+                    x2: "{dx.mul_add(-shorten, x2)}",
+                    y2: "{dy.mul_add(-shorten, y2)}",
+                    marker_end: "url(#technique-arrow)",
+                })
+            }
+        }
+    })
+}
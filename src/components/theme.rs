@@ -0,0 +1,255 @@
+//! # Theme Module
+//!
+//! The `theme` module contains the [`use_theme`] hook, the central place
+//! where the settings that change the look of the whole app are applied.
+//!
+//! The theme is applied as attributes of the root element of the page,
+//! which the stylesheet matches:
+//!
+//! - `data-symbols`: the name of the [`SymbolSet`] of the settings,
+//!   if its symbols are wider than a digit,
+//!   which sets the `--symbol-scale` CSS custom property the sizes of the
+//!   digits are multiplied by.
+//! - `data-print="large"`: the board is laid out for low vision,
+//!   if the `large_print` setting is on.
+//! - `data-motion="reduced"`: animations and transitions are turned off,
+//!   if the operating system asks for reduced motion or the
+//!   `reduce_motion` setting is on.
+//!   Animations must be written in the stylesheet, so they are turned off
+//!   with all the others.
+//! - `dir`: the [`Direction`] of the language of the browser,
+//!   so the chrome around the board mirrors for right-to-left languages.
+//! - `style`: the colors of the [`Theme`] of the settings,
+//!   see [`Theme::css_variables`],
+//!   and the `--board-scale`, `--digit-scale` and `--control-scale`
+//!   CSS custom properties, which the sizes of the board, its digits and
+//!   the control buttons are multiplied by.
+//!   Components must use the properties of the theme instead of their own
+//!   colors, so they follow the palette.
+//!
+//! The module also contains the [`ThemeSettings`],
+//! which import, preview and export custom themes,
+//! and the [`use_pinch_zoom`] hook,
+//! which scales the board with a pinch on touch screens.
+
+use dioxus::prelude::*;
+
+use crate::browser::{browser_language, media_matches, set_root_attribute};
+use crate::components::save::DOWNLOAD;
+use crate::components::settings::UserSettings;
+use crate::components::toast::use_toast;
+use crate::locale::Direction;
+use crate::settings::BOARD_SCALE;
+#[cfg(doc)]
+use crate::symbols::SymbolSet;
+use crate::theme::Theme;
+use crate::toast::ToastKind;
+
+/// Media query of the operating system preference for reduced motion
+const REDUCED_MOTION_QUERY: &str = "(prefers-reduced-motion: reduce)";
+
+/// Name of the file a theme is exported to
+const THEME_FILE: &str = "sudoku-theme.json";
+
+/// Shared State for the preview of a custom theme
+///
+/// Represents globally across the app the imported [`Theme`] shown in
+/// place of the one of the settings, until it is kept or discarded,
+/// or `None` if there is none.
+#[derive(Debug, Clone)]
+pub struct ThemePreview(pub Option<Theme>);
+
+/// Hook that keeps the theme of the page in sync with the settings.
+///
+/// The [`ThemePreview`], if any, is shown in place of the [`Theme`] of
+/// the settings.
+pub fn use_theme() {
+    // Unpack shared states
+    let settings = use_context::<Signal<UserSettings>>();
+    let preview = use_context_provider(|| Signal::new(ThemePreview(None)));
+
+    use_hook(|| {
+        if let Some(language) = browser_language() {
+            set_root_attribute("dir", Some(Direction::of_language(&language).html_name()));
+        }
+    });
+    use_effect(move || {
+        set_root_attribute(
+            "data-print",
+            settings.read().0.large_print.then_some("large"),
+        );
+    });
+    use_effect(move || {
+        set_root_attribute("data-symbols", settings.read().0.symbols.css_name());
+    });
+    use_effect(move || {
+        let reduce_motion = settings.read().0.reduce_motion || media_matches(REDUCED_MOTION_QUERY);
+        set_root_attribute("data-motion", reduce_motion.then_some("reduced"));
+    });
+    use_effect(move || {
+        let settings = &settings.read().0;
+        let theme = preview
+            .read()
+            .0
+            .clone()
+            .unwrap_or_else(|| Theme::of_settings(settings));
+        let style = format!(
+            "{} --board-scale: {}; --digit-scale: {}; --control-scale: {};",
+            theme.css_variables(),
+            f64::from(settings.board_scale) / 100.0,
+            f64::from(settings.digit_scale) / 100.0,
+            settings.control_size.scale(),
+        );
+        set_root_attribute("style", Some(&style));
+    });
+}
+
+/// Component to render the custom theme settings
+///
+/// This component renders a swatch of the colors of the current theme,
+/// and buttons to import a custom [`Theme`] from a JSON file,
+/// to export the current one, and to go back to the palette.
+/// An imported theme is previewed live, as the [`ThemePreview`],
+/// until it is kept or discarded.
+#[component]
+pub fn ThemeSettings() -> Element {
+    // Unpack shared states
+    let mut settings = use_context::<Signal<UserSettings>>();
+    let mut preview = use_context::<Signal<ThemePreview>>();
+    let mut toast = use_toast();
+
+    let previewing = preview.read().0.is_some();
+    let custom = settings.read().0.custom_theme.is_some();
+
+    rsx!(
+        span { "Theme" }
+        span {
+            class: "theme-swatches",
+            "aria-hidden": "true",
+            for color in ["background", "text", "accent", "selected", "highlight", "conflict"] {
+                span { key: "{color}", style: "background-color: var(--{color});" }
+            }
+        }
+        if previewing {
+            span {
+                class: "theme-actions",
+                button {
+                    class: "input",
+                    onclick: move |_| {
+                        let theme = preview.write().0.take();
+                        settings.write().0.custom_theme = theme;
+                    },
+                    "Keep theme"
+                }
+                button {
+                    class: "input",
+                    onclick: move |_| preview.set(ThemePreview(None)),
+                    "Discard"
+                }
+            }
+        } else {
+            span {
+                class: "theme-actions",
+                label {
+                    class: "input",
+                    r#for: "theme-import",
+                    // reachable with the keyboard, unlike the hidden input
+                    tabindex: 0,
+                    role: "button",
+                    input {
+                        id: "theme-import",
+                        r#type: "file",
+                        accept: ".json",
+                        hidden: true,
+                        onchange: move |event| {
+                            let Some(files) = event.files() else {
+                                return;
+                            };
+                            spawn(async move {
+                                let Some(file_name) = files.files().into_iter().next() else {
+                                    return;
+                                };
+                                let Some(contents) = files.read_file_to_string(&file_name).await else {
+                                    toast(ToastKind::Error, "The file could not be read".to_string());
+                                    return;
+                                };
+                                match Theme::parse(&contents) {
+                                    Ok(theme) => preview.set(ThemePreview(Some(theme))),
+                                    Err(err) => toast(ToastKind::Error, err.to_string()),
+                                }
+                            });
+                        },
+                    }
+                    "Import theme"
+                }
+                button {
+                    class: "input",
+                    onclick: move |_| {
+                        let theme = Theme::of_settings(&settings.peek().0);
+                        let download = eval(DOWNLOAD);
+                        if let Err(err) = download.send(serde_json::json!({
+                            "name": THEME_FILE,
+                            "contents": theme.to_json(),
+                            "type": "application/json",
+                        })) {
+                            log::error!("failed to download the theme: {err:?}");
+                        }
+                    },
+                    "Export theme"
+                }
+                if custom {
+                    button {
+                        class: "input",
+                        onclick: move |_| settings.write().0.custom_theme = None,
+                        "Use the palette"
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// Returns the distance between the first two touches of a touch event.
+fn pinch_distance(event: &TouchEvent) -> Option<f64> {
+    let touches = event.touches();
+    let [first, second, ..] = touches.as_slice() else {
+        return None;
+    };
+    let first = first.client_coordinates();
+    let second = second.client_coordinates();
+    Some((first - second).length())
+}
+
+/// Hook that returns the `touchstart` and `touchmove` handlers scaling the
+/// board with a two-finger pinch.
+///
+/// The board scale is stored in the settings,
+/// within [`BOARD_SCALE`].
+pub fn use_pinch_zoom() -> (impl FnMut(TouchEvent) + Copy, impl FnMut(TouchEvent) + Copy) {
+    // Unpack shared states
+    let mut settings = use_context::<Signal<UserSettings>>();
+
+    // the distance between the fingers and the scale when the pinch started
+    let mut pinch = use_signal(|| None::<(f64, u32)>);
+
+    let start = move |event: TouchEvent| {
+        let scale = settings.peek().0.board_scale;
+        pinch.set(pinch_distance(&event).map(|distance| (distance, scale)));
+    };
+    let zoom = move |event: TouchEvent| {
+        let (Some((start, scale)), Some(distance)) = (*pinch.peek(), pinch_distance(&event)) else {
+            return;
+        };
+        if start <= 0.0 {
+            return;
+        }
+        let percent = (f64::from(scale) * distance / start).round().clamp(
+            f64::from(*BOARD_SCALE.start()),
+            f64::from(*BOARD_SCALE.end()),
+        );
+        // the percent is clamped to the board scale range, so it fits
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        settings.write().0.set_board_scale(percent as u32);
+    };
+    (start, zoom)
+}
@@ -0,0 +1,146 @@
+//! # Timer Module
+//!
+//! The `timer` module contains the game timer shared state and the
+//! [`Timer`] component that keeps it ticking and displays it.
+//!
+//! The timer stops as soon as the board is solved, pauses while the page is
+//! hidden, and is reset whenever a new game starts.
+//! In [`speedrun`](crate::settings::Settings::speedrun) mode,
+//! it ticks faster and shows the milliseconds.
+
+use dioxus::prelude::*;
+
+use crate::browser::now;
+use crate::components::settings::UserSettings;
+use crate::game::GameState;
+use crate::utils::{format_duration, format_duration_millis, is_solved};
+
+/// Shared State for the game timer
+///
+/// Represents globally across the app the elapsed time,
+/// in milliseconds, of the current game.
+#[derive(Debug, Clone)]
+pub struct GameTimer(pub u64);
+
+/// Milliseconds between two ticks of the timer
+const TICK_MILLIS: u64 = 1000;
+
+/// Milliseconds between two ticks of the timer in speedrun mode,
+/// not a round number so every digit of the milliseconds shown changes
+const SPEEDRUN_TICK_MILLIS: u64 = 47;
+
+/// JavaScript that sends the current timestamp to Rust at every tick
+///
+/// The milliseconds between two ticks are received from Rust,
+/// first and whenever they change.
+/// No timestamps are sent while the page is hidden.
+/// When the page gets hidden, a last timestamp is sent followed by `null`,
+/// so the time until then is counted but not the time away.
+const TICKER: &str = r"
+    const tick = () => {
+        if (!document.hidden) {
+            dioxus.send(Date.now());
+        }
+    };
+    let interval;
+    tick();
+    document.addEventListener('visibilitychange', () => {
+        if (document.hidden) {
+            dioxus.send(Date.now());
+            dioxus.send(null);
+        } else {
+            tick();
+        }
+    });
+    while (true) {
+        const millis = await dioxus.recv();
+        clearInterval(interval);
+        interval = setInterval(tick, millis);
+    }
+";
+
+/// Component to render the game timer
+///
+/// This component keeps the [`GameTimer`] ticking while the board is not
+/// solved and renders the elapsed time, unless the timer is hidden in the
+/// settings.
+///
+/// The elapsed time is measured from timestamps, instead of counting ticks,
+/// so it stays accurate even if the browser throttles the ticks.
+/// The time since the last tick is counted as soon as the board is solved,
+/// so the final time is exact to the millisecond.
+/// The timer pauses while the page is hidden, such as when switching tabs
+/// or locking the phone, and resumes on return.
+///
+/// ## Panics
+///
+/// The component will panic if it cannot get the current Sudoku state.
+#[component]
+pub fn Timer() -> Element {
+    // Unpack shared states
+    let mut timer = use_context::<Signal<GameTimer>>();
+    let game = use_context::<Signal<GameState>>();
+    let settings = use_context::<Signal<UserSettings>>();
+    let ticker = use_hook(|| eval(TICKER));
+    // the timestamp of the last tick counted, if the timer is running
+    let mut last = use_signal(|| None::<u64>);
+
+    // the board alone, so selecting a cell does not count the time
+    let board = use_memo(move || game.read().current());
+    let solved = move || is_solved(&board.peek());
+
+    use_future(move || async move {
+        let mut ticker = ticker;
+        while let Ok(now) = ticker.recv().await {
+            // the page was hidden, so the time away is not counted
+            let Some(now) = now.as_u64() else {
+                last.set(None);
+                continue;
+            };
+            let since = last.peek().map(|last| now.saturating_sub(last));
+            if let Some(since) = since.filter(|_| !solved()) {
+                timer.write().0 += since;
+            }
+            last.set(Some(now));
+        }
+    });
+
+    // tick faster in speedrun mode
+    use_effect(move || {
+        let millis = if settings.read().0.speedrun {
+            SPEEDRUN_TICK_MILLIS
+        } else {
+            TICK_MILLIS
+        };
+        if let Err(err) = ticker.send(millis.into()) {
+            log::error!("failed to set the timer ticks: {err:?}");
+        }
+    });
+
+    // count the time since the last tick once the board is solved
+    use_effect(move || {
+        board.read();
+        if !solved() {
+            return;
+        }
+        let since = last.peek().map(|last| now().saturating_sub(last));
+        if let Some(since) = since {
+            timer.write().0 += since;
+            last.set(None);
+        }
+    });
+
+    if !settings.read().0.show_timer {
+        return None;
+    }
+
+    let millis = timer.read().0;
+    rsx!(div {
+        class: "timer",
+        if settings.read().0.speedrun {
+            "{format_duration_millis(millis)}"
+        } else {
+            "{format_duration(millis)}"
+        }
+    })
+}
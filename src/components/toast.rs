@@ -0,0 +1,133 @@
+//! # Toast Module
+//!
+//! The `toast` module contains the shared state of the [`ToastQueue`],
+//! the [`use_toast`] and [`use_action_toast`] hooks that push messages to
+//! it, and the [`ToastList`] that renders them.
+//!
+//! See also: [`toast`](crate::toast).
+
+use dioxus::prelude::*;
+
+use crate::components::controls::use_undo_new_game;
+use crate::toast::{Toast, ToastAction, ToastKind, ToastQueue};
+
+/// Shared State for the toasts
+///
+/// Represents globally across the app the [`ToastQueue`] rendered by
+/// [`ToastList`].
+#[derive(Debug, Clone, Default)]
+pub struct Toasts(pub ToastQueue);
+
+/// JavaScript that waits for the number of milliseconds it receives,
+/// then sends `true` to Rust
+const WAIT: &str = r"
+    const millis = await dioxus.recv();
+    setTimeout(() => dioxus.send(true), millis);
+";
+
+/// Hook that returns a function showing a toast.
+///
+/// The function takes the [`ToastKind`] and the message of the toast,
+/// which is dismissed after [`TOAST_MILLIS`](crate::toast::TOAST_MILLIS).
+pub fn use_toast() -> impl FnMut(ToastKind, String) + Copy {
+    // Unpack shared states
+    let mut toasts = use_context::<Signal<Toasts>>();
+
+    move |kind: ToastKind, message: String| {
+        toasts.write().0.push(kind, message);
+    }
+}
+
+/// Hook that returns a function showing a toast with an action.
+///
+/// The function takes the [`ToastKind`], the message and the
+/// [`ToastAction`] of the toast,
+/// which is dismissed after
+/// [`ACTION_TOAST_MILLIS`](crate::toast::ACTION_TOAST_MILLIS),
+/// or once its action is used.
+pub fn use_action_toast() -> impl FnMut(ToastKind, String, ToastAction) + Copy {
+    // Unpack shared states
+    let mut toasts = use_context::<Signal<Toasts>>();
+
+    move |kind: ToastKind, message: String, action: ToastAction| {
+        toasts.write().0.push_action(kind, message, action);
+    }
+}
+
+/// Component Props for [`ToastItem`]
+///
+/// - `toast: Toast`: the toast to render.
+#[derive(Props, Clone, PartialEq, Eq)]
+struct ToastItemProps {
+    toast: Toast,
+}
+
+/// Component to render a toast
+///
+/// This component renders the message of a toast,
+/// and the button of its action, if any.
+/// The toast is dismissed after [`Toast::millis`], or when clicked.
+#[allow(clippy::needless_pass_by_value)]
+fn ToastItem(props: ToastItemProps) -> Element {
+    let millis = props.toast.millis();
+    let Toast {
+        id,
+        kind,
+        message,
+        action,
+    } = props.toast;
+
+    // Unpack shared states
+    let mut toasts = use_context::<Signal<Toasts>>();
+    let mut undo_new_game = use_undo_new_game();
+
+    use_future(move || async move {
+        let mut wait = eval(WAIT);
+        if wait.send(millis.into()).is_ok() && wait.recv().await.is_ok() {
+            toasts.write().0.dismiss(id);
+        }
+    });
+
+    rsx!(div {
+        class: "toast {kind.css_name()}",
+        onclick: move |_| toasts.write().0.dismiss(id),
+        "{message}"
+        if let Some(action) = action {
+            button {
+                class: "input",
+                onclick: move |event| {
+                    event.stop_propagation();
+                    toasts.write().0.dismiss(id);
+                    match action {
+                        ToastAction::UndoNewGame => undo_new_game(),
+                    }
+                },
+                "{action.label()}"
+            }
+        }
+    })
+}
+
+/// Component to render the toasts
+///
+/// This component renders the [`Toasts`] in a corner of the page,
+/// oldest first.
+/// They are announced by screen readers as they are added.
+#[allow(clippy::module_name_repetitions)]
+#[component]
+pub fn ToastList() -> Element {
+    // Unpack shared states
+    let toasts = use_context::<Signal<Toasts>>();
+
+    rsx!(div {
+        class: "toasts",
+        role: "status",
+        aria_live: "polite",
+        for toast in toasts.read().0.iter().cloned() {
+            ToastItem {
+                key: "{toast.id}",
+                toast: toast,
+            }
+        }
+    })
+}
@@ -0,0 +1,189 @@
+//! # Undo Tree Module
+//!
+//! The `undo_tree` module draws the [`UndoTree`] of the game,
+//! to go back to the moves of another branch.
+//!
+//! [`UndoTreeButton`] toggles the [`UndoTreePanel`],
+//! which draws every board reached as a dot,
+//! one column per move and one row per branch,
+//! and goes to a board when its dot is clicked,
+//! with [`GameAction::GoTo`].
+//! The current move can be given a note, with [`GameAction::Annotate`],
+//! which is shown with the name of its dot.
+
+use dioxus::prelude::*;
+
+use crate::components::board::use_dispatch;
+use crate::components::buttons::ActionButton;
+use crate::components::modal::Modal;
+use crate::game::{GameAction, GameState};
+use crate::undo_tree::{UndoTree, MAX_NOTE_CHARS};
+use crate::validation::cell_name;
+
+/// The space between two dots, in the units of the drawing
+const SPACING: usize = 24;
+
+/// Names the move that reached a node of the tree,
+/// by the cell it changed, such as "Move 3: R4C9 = 5".
+fn move_name(tree: &UndoTree, node: usize) -> String {
+    if tree.parent(node).is_none() {
+        return "Puzzle".to_string();
+    }
+    let depth = tree.depth(node);
+    match tree.changes(node).and_then(<[_]>::first) {
+        Some(change) if change.new == 0 => {
+            format!("Move {depth}: {} erased", cell_name(change.index))
+        }
+        Some(change) => format!("Move {depth}: {} = {}", cell_name(change.index), change.new),
+        None => format!("Move {depth}"),
+    }
+}
+
+/// Names the move that reached a node of the tree with its note, if any,
+/// such as "Move 3: R4C9 = 5 · guessing 5 here".
+fn annotated_move_name(tree: &UndoTree, node: usize) -> String {
+    let name = move_name(tree, node);
+    match tree.note(node) {
+        Some(text) => format!("{name} · {text}"),
+        None => name,
+    }
+}
+
+/// Shared State for the undo tree panel
+///
+/// Represents globally across the app if the [`UndoTreePanel`] is open.
+#[derive(Debug, Clone)]
+pub struct UndoTreeOpen(pub bool);
+
+/// Component to render the undo tree button
+///
+/// This component renders an "Undo tree" [`ActionButton`] that opens and
+/// closes the [`UndoTreePanel`].
+/// It is disabled until a move is made.
+#[component]
+pub fn UndoTreeButton() -> Element {
+    // Unpack shared states
+    let mut undo_tree_open = use_context::<Signal<UndoTreeOpen>>();
+    let game = use_context::<Signal<GameState>>();
+
+    rsx!(ActionButton {
+        icon: "undo-tree",
+        label: "Undo tree",
+        tooltip: "Go back to the moves of any branch",
+        disabled: game.read().history.node_count() < 2,
+        onclick: move |()| {
+            let open = undo_tree_open.read().0;
+            undo_tree_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the undo tree panel
+///
+/// This component draws, in a [`Modal`], the boards of the
+/// history of the [`GameState`] from the puzzle on the left,
+/// with the current one filled.
+/// Every dot is named by the cell its move changed, and by its note,
+/// and the dots of the moves with a note are ringed.
+///
+/// Clicking a dot goes to its board,
+/// whose branch is then the one redone.
+/// The note of the current move is edited below the drawing.
+#[component]
+pub fn UndoTreePanel() -> Element {
+    // Unpack shared states
+    let mut undo_tree_open = use_context::<Signal<UndoTreeOpen>>();
+    let game = use_context::<Signal<GameState>>();
+    let mut dispatch = use_dispatch();
+
+    if !undo_tree_open.read().0 {
+        return None;
+    }
+
+    let game = game.read();
+    let tree = &game.history;
+    let layout = tree.layout();
+    let current = tree.current();
+    let current_name = move_name(tree, current);
+    let current_note = tree.note(current).unwrap_or_default().to_string();
+    let width = layout.iter().map(|node| node.depth + 1).max().unwrap_or(1) * SPACING;
+    let height = layout.iter().map(|node| node.lane + 1).max().unwrap_or(1) * SPACING;
+    // where every node is drawn, by index
+    let mut positions = vec![(0, 0); tree.node_count()];
+    for node in &layout {
+        positions[node.node] = (
+            node.depth * SPACING + SPACING / 2,
+            node.lane * SPACING + SPACING / 2,
+        );
+    }
+    let edges = layout.iter().filter_map(|node| {
+        let (x, y) = positions[node.node];
+        let (px, py) = positions[node.parent?];
+        Some((node.node, format!("M{px} {py}V{y}H{x}")))
+    });
+    let dots = layout.iter().map(|node| {
+        (
+            node.node,
+            positions[node.node],
+            annotated_move_name(tree, node.node),
+            tree.note(node.node).is_some(),
+        )
+    });
+
+    rsx!(Modal {
+        class: "undo-tree",
+        label: "Undo tree",
+        onclose: move |()| undo_tree_open.write().0 = false,
+        span { "Undo tree" }
+        div {
+            class: "tree-drawing",
+            svg {
+                width: "{width}",
+                height: "{height}",
+                view_box: "0 0 {width} {height}",
+                for (node, d) in edges {
+                    path { key: "edge-{node}", d: d }
+                }
+                for (node, (x, y), name, annotated) in dots {
+                    circle {
+                        key: "{node}",
+                        class: format!(
+                            "{} {}",
+                            if node == current { "current" } else { "" },
+                            if annotated { "annotated" } else { "" },
+                        ),
+                        cx: "{x}",
+                        cy: "{y}",
+                        r: "6",
+                        role: "button",
+                        "aria-label": "{name}",
+                        onclick: move |_| {
+                            dispatch(GameAction::GoTo(node));
+                        },
+                        title { "{name}" }
+                    }
+                }
+            }
+        }
+        if current > 0 {
+            label {
+                class: "move-note",
+                "{current_name}"
+                input {
+                    r#type: "text",
+                    placeholder: "Note on this move",
+                    maxlength: "{MAX_NOTE_CHARS}",
+                    value: "{current_note}",
+                    onchange: move |event| {
+                        dispatch(GameAction::Annotate(event.value()));
+                    },
+                }
+            }
+        }
+        button {
+            class: "input",
+            onclick: move |_| undo_tree_open.write().0 = false,
+            "Close"
+        }
+    })
+}
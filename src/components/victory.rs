@@ -0,0 +1,93 @@
+//! # Victory Module
+//!
+//! The `victory` module contains the [`VictoryPanel`],
+//! shown once the board is solved,
+//! with the time of the game, its [`SplitsTable`],
+//! the [`PersonalBest`] in speedrun mode,
+//! the [`ChallengeResult`] of a challenge link,
+//! the [`HotSeatResult`] of the pass-and-play mode,
+//! the [`Celebration`] of the kids mode,
+//! and the [`DailySubmit`] offer for a daily puzzle.
+
+use dioxus::prelude::*;
+
+use crate::components::challenge::ChallengeResult;
+use crate::components::hotseat::HotSeatResult;
+use crate::components::kids::Celebration;
+use crate::components::leaderboard::DailySubmit;
+use crate::components::modal::Modal;
+use crate::components::settings::UserSettings;
+use crate::components::speedrun::PersonalBest;
+use crate::components::splits::SplitsTable;
+use crate::components::timer::GameTimer;
+use crate::game::GameState;
+use crate::utils::{format_duration, format_duration_millis, is_solved};
+
+/// Shared State for the victory panel
+///
+/// Represents globally across the app if the [`VictoryPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct VictoryOpen(pub bool);
+
+/// Hook that opens the [`VictoryPanel`] when the board gets solved.
+///
+/// A board that was already solved when loaded does not open it.
+pub fn use_victory() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut victory_open = use_context_provider(|| Signal::new(VictoryOpen(false)));
+    let mut was_solved = use_signal(|| is_solved(&game.peek().current()));
+
+    use_effect(move || {
+        let solved = is_solved(&game.read().current());
+        if solved && !*was_solved.peek() {
+            victory_open.write().0 = true;
+        }
+        was_solved.set(solved);
+    });
+}
+
+/// Component to render the victory panel
+///
+/// This component renders, in a [`Modal`], the time the board was solved
+/// in and the [`SplitsTable`] of the game,
+/// with the milliseconds and the [`PersonalBest`] in speedrun mode,
+/// compares it with the time of a challenger,
+/// tells the winner of the pass-and-play mode,
+/// celebrates in the kids mode,
+/// and offers to submit the time of a daily puzzle to its leaderboard.
+#[component]
+pub fn VictoryPanel() -> Element {
+    // Unpack shared states
+    let mut victory_open = use_context::<Signal<VictoryOpen>>();
+    let timer = use_context::<Signal<GameTimer>>();
+    let settings = use_context::<Signal<UserSettings>>();
+
+    if !victory_open.read().0 {
+        return None;
+    }
+    let millis = timer.read().0;
+    let time = if settings.read().0.speedrun {
+        format_duration_millis(millis)
+    } else {
+        format_duration(millis)
+    };
+
+    rsx!(Modal {
+        class: "victory",
+        label: "Solved",
+        onclose: move |()| victory_open.write().0 = false,
+        Celebration {}
+        span { "Solved in {time}" }
+        PersonalBest {}
+        ChallengeResult {}
+        HotSeatResult {}
+        DailySubmit {}
+        SplitsTable {}
+        button {
+            class: "input",
+            onclick: move |_| victory_open.write().0 = false,
+            "Close"
+        }
+    })
+}
@@ -0,0 +1,166 @@
+//! # Weekly Module
+//!
+//! The `weekly` module contains the shared states and the panel of the
+//! weekly challenge.
+//!
+//! [`WeeklyButton`] toggles the [`WeeklyPanel`],
+//! which starts the challenge of the current week,
+//! shows whether and how fast it was completed,
+//! and counts down to the next one.
+//!
+//! See also: [`weekly`](crate::weekly).
+
+use dioxus::prelude::*;
+
+use crate::browser::now;
+use crate::components::buttons::ActionButton;
+use crate::components::confirm::ConfirmDialog;
+use crate::components::controls::{use_needs_confirm, use_new_game};
+use crate::components::modal::Modal;
+use crate::components::profiles::ActiveProfile;
+use crate::daily::{date_of, MONTH_NAMES};
+use crate::utils::format_duration;
+use crate::weekly::{
+    create_weekly_sudoku, first_day_of, format_countdown, load_weekly_log, next_challenge_in,
+    week_of, CHALLENGE_DIFFICULTY,
+};
+
+/// JavaScript that sends the current time to Rust every second
+const TICK: &str = r"
+    setInterval(() => dioxus.send(Date.now()), 1000);
+";
+
+/// Shared State for the weekly challenge
+///
+/// Represents globally across the app the week of the challenge being
+/// played, or `None` if it was not opened as a weekly challenge.
+#[derive(Debug, Clone)]
+pub struct WeeklyChallenge(pub Option<u64>);
+
+/// Shared State for the weekly challenge panel
+///
+/// Represents globally across the app if the [`WeeklyPanel`] is open.
+#[derive(Debug, Clone)]
+pub struct WeeklyOpen(pub bool);
+
+/// Component to render the weekly challenge button
+///
+/// This component renders a "Weekly challenge" button that opens and
+/// closes the [`WeeklyPanel`].
+#[component]
+pub fn WeeklyButton() -> Element {
+    // Unpack shared states
+    let mut weekly_open = use_context::<Signal<WeeklyOpen>>();
+
+    rsx!(ActionButton {
+        icon: "weekly",
+        label: "Weekly challenge",
+        tooltip: "Play the hard puzzle of the week",
+        onclick: move |()| {
+            let open = weekly_open.read().0;
+            weekly_open.write().0 = !open;
+        },
+    })
+}
+
+/// Component to render the weekly challenge panel
+///
+/// This component renders, in a [`Modal`], the week of the current
+/// challenge, its best time if the active profile completed it,
+/// the number of completed challenges, and the countdown to the next one.
+///
+/// Playing the challenge starts a new game with its puzzle.
+/// If the current game has progress, a [`ConfirmDialog`] asks first.
+#[component]
+pub fn WeeklyPanel() -> Element {
+    // Unpack shared states
+    let mut weekly_open = use_context::<Signal<WeeklyOpen>>();
+    let mut weekly = use_context::<Signal<WeeklyChallenge>>();
+    let profile = use_context::<Signal<ActiveProfile>>();
+    let mut new_game = use_new_game();
+    let needs_confirm = use_needs_confirm();
+    let mut confirming = use_signal(|| false);
+
+    if !weekly_open.read().0 {
+        return None;
+    }
+
+    let week = week_of(now());
+    let mut play = move || {
+        confirming.set(false);
+        new_game(create_weekly_sudoku(week));
+        weekly.write().0 = Some(week);
+        weekly_open.write().0 = false;
+    };
+
+    let (_, month, day) = date_of(first_day_of(week));
+    let month = MONTH_NAMES[month as usize - 1];
+    let log = load_weekly_log(profile.read().0);
+    let completed = log.completed();
+    let status = log.best(week).map_or_else(
+        || "Not completed yet".to_string(),
+        |best| format!("Completed in {}", format_duration(best)),
+    );
+
+    rsx!(
+        Modal {
+            class: "weekly",
+            label: "Weekly challenge",
+            onclose: move |()| weekly_open.write().0 = false,
+            span { "Weekly challenge" }
+            span { "Week of {month} {day} · {CHALLENGE_DIFFICULTY}" }
+            span { "{status}" }
+            span { "Challenges completed: {completed}" }
+            Countdown {}
+            button {
+                class: "input",
+                onclick: move |_| {
+                    if needs_confirm() {
+                        confirming.set(true);
+                    } else {
+                        play();
+                    }
+                },
+                "Play"
+            }
+            button {
+                class: "input",
+                onclick: move |_| weekly_open.write().0 = false,
+                "Close"
+            }
+        }
+        if confirming() {
+            ConfirmDialog {
+                message: "Play the weekly challenge? The current game stays in My games.",
+                action: "Play",
+                onconfirm: move |()| play(),
+                oncancel: move |()| confirming.set(false),
+            }
+        }
+    )
+}
+
+/// Component to render the countdown to the next challenge
+///
+/// This component renders the time left until the next challenge,
+/// updated every second while it is rendered.
+#[component]
+fn Countdown() -> Element {
+    let mut current = use_signal(now);
+
+    use_future(move || async move {
+        let mut tick = eval(TICK);
+        while let Ok(time) = tick.recv().await {
+            if let Some(time) = time.as_u64() {
+                current.set(time);
+            }
+        }
+    });
+
+    let left = format_countdown(next_challenge_in(current()));
+
+    rsx!(span {
+        class: "countdown",
+        "Next challenge in {left}"
+    })
+}
@@ -0,0 +1,279 @@
+//! # Daily Module
+//!
+//! The `daily` module keeps track of the daily puzzles,
+//! the same puzzle for everyone on a day,
+//! generated with [`create_daily_sudoku`](crate::utils::create_daily_sudoku).
+//!
+//! Past daily puzzles can be opened from the archive and completed late.
+//! Every completed day is kept in the [`DailyLog`] of the profile,
+//! in the local storage, as solved on the day or late.
+//!
+//! Days are counted since the Unix epoch, in UTC,
+//! and shown in the archive as dates of the Gregorian calendar.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::browser::{local_storage_get, local_storage_set};
+use crate::profiles::profile_key;
+
+/// Milliseconds in a day
+pub const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// Local storage key of the [`DailyLog`]
+const DAILY_KEY: &str = "daily";
+
+/// The names of the months, from January
+pub const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Returns the day of a time.
+///
+/// ## Parameters
+///
+/// - `millis: u64` - The time, in milliseconds since the Unix epoch.
+///
+/// ## Returns
+///
+/// Returns the number of days since the Unix epoch.
+#[must_use]
+pub const fn day_of(millis: u64) -> u64 {
+    millis / DAY_MILLIS
+}
+
+/// How a daily puzzle was completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DailyStatus {
+    /// Solved on its day
+    Solved,
+    /// Solved on a later day, from the archive
+    Late,
+}
+
+impl DailyStatus {
+    /// Returns the name of the CSS class of the status.
+    #[must_use]
+    pub const fn css_name(self) -> &'static str {
+        match self {
+            Self::Solved => "solved",
+            Self::Late => "late",
+        }
+    }
+
+    /// Returns the description of the status shown to the user.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Solved => "solved",
+            Self::Late => "late solve",
+        }
+    }
+}
+
+/// The completed daily puzzles of a profile, by day
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyLog(pub BTreeMap<u64, DailyStatus>);
+
+impl DailyLog {
+    /// Returns how the puzzle of a day was completed,
+    /// or `None` if it was not.
+    ///
+    /// ## Parameters
+    ///
+    /// - `day: u64` - The day of the puzzle, in days since the Unix epoch.
+    #[must_use]
+    pub fn status(&self, day: u64) -> Option<DailyStatus> {
+        self.0.get(&day).copied()
+    }
+
+    /// Records the puzzle of a day as completed.
+    ///
+    /// A puzzle solved on its day stays so, even if it is solved again late.
+    ///
+    /// ## Parameters
+    ///
+    /// - `day: u64` - The day of the puzzle, in days since the Unix epoch.
+    /// - `today: u64` - The day it was completed.
+    ///
+    /// ## Returns
+    ///
+    /// Returns how the puzzle is recorded.
+    pub fn complete(&mut self, day: u64, today: u64) -> DailyStatus {
+        let status = if day < today {
+            DailyStatus::Late
+        } else {
+            DailyStatus::Solved
+        };
+        *self
+            .0
+            .entry(day)
+            .and_modify(|recorded| {
+                if *recorded == DailyStatus::Late {
+                    *recorded = status;
+                }
+            })
+            .or_insert(status)
+    }
+}
+
+/// Loads the daily log of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`DailyLog`],
+/// or an empty one if none is stored or it cannot be read.
+#[must_use]
+pub fn load_daily_log(profile: u32) -> DailyLog {
+    local_storage_get(&profile_key(profile, DAILY_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the daily log of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `log: &DailyLog` - The log to store.
+pub fn store_daily_log(profile: u32, log: &DailyLog) {
+    match serde_json::to_string(log) {
+        Ok(json) => local_storage_set(&profile_key(profile, DAILY_KEY), &json),
+        Err(err) => log::error!("failed to store the daily log: {err:?}"),
+    }
+}
+
+/// Returns the date of a day.
+///
+/// ## Parameters
+///
+/// - `day: u64` - The number of days since the Unix epoch.
+///
+/// ## Returns
+///
+/// Returns the year, the month from 1 to 12, and the day of the month
+/// from 1 to 31.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn date_of(day: u64) -> (u64, u32, u32) {
+    // the days are counted from March 1st, 0000,
+    // so leap days are at the end of the 400-year eras
+    let days = day + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as u64;
+    (year, month as u32, day_of_month as u32)
+}
+
+/// Returns the day of a date.
+///
+/// ## Parameters
+///
+/// - `year: u64` - The year, from 1970.
+/// - `month: u32` - The month, from 1 to 12.
+/// - `day_of_month: u32` - The day of the month, from 1 to 31.
+///
+/// ## Returns
+///
+/// Returns the number of days since the Unix epoch.
+#[must_use]
+pub const fn day_from_date(year: u64, month: u32, day_of_month: u32) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 } as u64;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day_of_month as u64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the number of days of a month.
+///
+/// ## Parameters
+///
+/// - `year: u64` - The year.
+/// - `month: u32` - The month, from 1 to 12.
+#[must_use]
+pub const fn days_in_month(year: u64, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the day of the week of a day,
+/// from 0 for Monday to 6 for Sunday.
+///
+/// ## Parameters
+///
+/// - `day: u64` - The number of days since the Unix epoch.
+#[must_use]
+pub const fn weekday(day: u64) -> u64 {
+    // the Unix epoch was a Thursday
+    (day + 3) % 7
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(date_of(0), (1970, 1, 1));
+        assert_eq!(date_of(11_016), (2000, 2, 29));
+        assert_eq!(date_of(20_000), (2024, 10, 4));
+        for day in [0, 59, 11_016, 11_017, 20_000, 40_000] {
+            let (year, month, day_of_month) = date_of(day);
+            assert_eq!(day_from_date(year, month, day_of_month), day);
+        }
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+        assert_eq!(weekday(0), 3);
+        assert_eq!(weekday(20_000), 4);
+    }
+
+    #[test]
+    fn test_daily_log() {
+        let mut log = DailyLog::default();
+        assert_eq!(log.status(100), None);
+        assert_eq!(log.complete(100, 100), DailyStatus::Solved);
+        assert_eq!(log.complete(100, 105), DailyStatus::Solved);
+        assert_eq!(log.complete(90, 105), DailyStatus::Late);
+        assert_eq!(log.status(90), Some(DailyStatus::Late));
+
+        let json = serde_json::to_string(&log).expect("serialize");
+        assert_eq!(json, r#"{"90":"late","100":"solved"}"#);
+        assert_eq!(serde_json::from_str::<DailyLog>(&json).ok(), Some(log));
+    }
+}
@@ -0,0 +1,252 @@
+//! # Difficulty Module
+//!
+//! The `difficulty` module grades Sudoku puzzles by the solving techniques
+//! a human needs to solve them.
+//!
+//! Puzzles are graded with the logical solver of the [`logic`](crate::logic)
+//! module: the [`Difficulty`] of a puzzle is the easiest level whose
+//! techniques are enough to solve it.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Error;
+
+use serde::{Deserialize, Serialize};
+use sudoku::Sudoku;
+
+use crate::app::SudokuState;
+use crate::logic::{solve_logically, Technique};
+use crate::rng::GameRng;
+use crate::utils::create_sudoku_with_rng;
+
+/// Maximum number of puzzles generated to find one of a given difficulty
+const MAX_ATTEMPTS: usize = 1000;
+
+/// The difficulty of a Sudoku puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// Solvable with singles only
+    Easy,
+    /// Needs locked candidates, pairs or triples
+    Medium,
+    /// Needs fish, wings or simple chains
+    Hard,
+    /// Needs techniques beyond the ones above
+    Expert,
+}
+
+impl Difficulty {
+    /// All difficulties, from the easiest to the hardest
+    pub const ALL: [Self; 4] = [Self::Easy, Self::Medium, Self::Hard, Self::Expert];
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Easy => "Easy",
+            Self::Medium => "Medium",
+            Self::Hard => "Hard",
+            Self::Expert => "Expert",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::msg(format!("Unknown difficulty: {s}")))
+    }
+}
+
+/// Grades a Sudoku puzzle.
+///
+/// ## Parameters
+///
+/// - `sudoku: &SudokuState` - A reference to the puzzle to grade.
+///
+/// ## Returns
+///
+/// Returns the easiest [`Difficulty`] whose techniques solve the puzzle.
+/// Puzzles that cannot be read are graded [`Difficulty::Expert`].
+#[must_use]
+pub fn grade_puzzle(sudoku: &SudokuState) -> Difficulty {
+    // puzzles that cannot be read cannot be solved either
+    if Sudoku::from_bytes(*sudoku).is_err() {
+        return Difficulty::Expert;
+    }
+    let solution = solve_logically(sudoku, &Technique::ALL);
+    if solution.solved {
+        solution.difficulty()
+    } else {
+        Difficulty::Expert
+    }
+}
+
+/// Generates a new Sudoku puzzle of a given difficulty.
+///
+/// Puzzles are generated and graded until one has the difficulty.
+/// Since hard puzzles are rare, the search gives up after a number of
+/// attempts and returns the hardest puzzle found that is not harder than
+/// the difficulty.
+///
+/// ## Parameters
+///
+/// - `difficulty: Difficulty` - The difficulty of the puzzle.
+///
+/// ## Returns
+///
+/// Returns a `SudokuState`, which represents a 9x9 Sudoku puzzle.
+#[must_use]
+pub fn create_puzzle(difficulty: Difficulty) -> SudokuState {
+    create_puzzle_with_rng(difficulty, &mut GameRng::new())
+}
+
+/// Generates a new Sudoku puzzle of a given difficulty,
+/// from a random number generator.
+///
+/// The puzzle is searched like [`create_puzzle`],
+/// but all the puzzles are generated from the given generator,
+/// so a seeded generator always gives the same puzzle.
+///
+/// ## Parameters
+///
+/// - `difficulty: Difficulty` - The difficulty of the puzzle.
+/// - `rng: &mut GameRng` - The random number generator.
+///
+/// ## Returns
+///
+/// Returns a `SudokuState`, which represents a 9x9 Sudoku puzzle.
+#[must_use]
+pub fn create_puzzle_with_rng(difficulty: Difficulty, rng: &mut GameRng) -> SudokuState {
+    let mut best = None;
+    for _ in 0..MAX_ATTEMPTS {
+        let puzzle = create_sudoku_with_rng(rng);
+        let grade = grade_puzzle(&puzzle);
+        if grade == difficulty {
+            return puzzle;
+        }
+        if grade < difficulty && best.is_none_or(|(best, _)| grade > best) {
+            best = Some((grade, puzzle));
+        }
+    }
+    best.map_or_else(|| create_sudoku_with_rng(rng), |(_, puzzle)| puzzle)
+}
+
+/// Searches a Sudoku puzzle of a given difficulty until a deadline.
+///
+/// The puzzle is searched like [`create_puzzle_with_rng`],
+/// but the search stops as soon as the deadline has expired,
+/// so slow devices can hand the search over to the server.
+///
+/// ## Parameters
+///
+/// - `difficulty: Difficulty` - The difficulty of the puzzle.
+/// - `rng: &mut GameRng` - The random number generator.
+/// - `expired: impl FnMut() -> bool` - Whether the deadline has expired,
+///   asked after every generated puzzle.
+///
+/// ## Returns
+///
+/// Returns the puzzle, or `None` if none of the puzzles generated before
+/// the deadline has the difficulty.
+pub fn create_puzzle_until(
+    difficulty: Difficulty,
+    rng: &mut GameRng,
+    mut expired: impl FnMut() -> bool,
+) -> Option<SudokuState> {
+    for _ in 0..MAX_ATTEMPTS {
+        let puzzle = create_sudoku_with_rng(rng);
+        if grade_puzzle(&puzzle) == difficulty {
+            return Some(puzzle);
+        }
+        if expired() {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::parse_line;
+
+    #[test]
+    fn test_grade_puzzle() {
+        // solvable with singles only
+        let easy = parse_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .expect("valid puzzle");
+        assert_eq!(grade_puzzle(&easy), Difficulty::Easy);
+
+        // one of the hardest known puzzles
+        let expert = parse_line(
+            "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+        )
+        .expect("valid puzzle");
+        assert_eq!(grade_puzzle(&expert), Difficulty::Expert);
+    }
+
+    #[test]
+    fn test_create_puzzle() {
+        let puzzle = create_puzzle(Difficulty::Easy);
+        assert_eq!(grade_puzzle(&puzzle), Difficulty::Easy);
+    }
+
+    #[test]
+    fn test_create_puzzle_with_rng() {
+        let puzzle = create_puzzle_with_rng(Difficulty::Easy, &mut GameRng::seeded(1));
+        assert_eq!(
+            puzzle,
+            create_puzzle_with_rng(Difficulty::Easy, &mut GameRng::seeded(1))
+        );
+    }
+
+    #[test]
+    fn test_create_puzzle_until() {
+        let puzzle = create_puzzle_until(Difficulty::Easy, &mut GameRng::seeded(1), || false);
+        assert_eq!(
+            puzzle,
+            Some(create_puzzle_with_rng(
+                Difficulty::Easy,
+                &mut GameRng::seeded(1)
+            ))
+        );
+
+        // an expired deadline only tries the first puzzle
+        let first = create_sudoku_with_rng(&mut GameRng::seeded(2));
+        let puzzle = create_puzzle_until(Difficulty::Expert, &mut GameRng::seeded(2), || true);
+        assert_eq!(
+            puzzle,
+            (grade_puzzle(&first) == Difficulty::Expert).then_some(first)
+        );
+    }
+
+    #[test]
+    fn test_difficulty_from_str() {
+        assert_eq!("hard".parse::<Difficulty>().ok(), Some(Difficulty::Hard));
+        assert_eq!(
+            "Expert".parse::<Difficulty>().ok(),
+            Some(Difficulty::Expert)
+        );
+        assert!("impossible".parse::<Difficulty>().is_err());
+    }
+
+    #[test]
+    fn test_difficulty_serde() {
+        let json = serde_json::to_string(&Difficulty::Hard).expect("serialize");
+        assert_eq!(json, "\"hard\"");
+        assert_eq!(
+            serde_json::from_str::<Difficulty>(&json).expect("deserialize"),
+            Difficulty::Hard
+        );
+    }
+}
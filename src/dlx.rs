@@ -0,0 +1,276 @@
+//! # DLX Module
+//!
+//! The `dlx` module solves Sudoku puzzles as an exact cover problem,
+//! with the dancing links of Knuth's Algorithm X.
+//!
+//! Every candidate of a cell is a row of the problem,
+//! which covers four constraints: its cell, and its digit in its row,
+//! its column and its box.
+//! A solution is a set of rows that covers every constraint exactly once.
+//! The search always branches on the constraint with the fewest rows left,
+//! which keeps the worst cases fast,
+//! such as counting the solutions to check that a puzzle is unique.
+//!
+//! The dancing links are a backend of
+//! [`SolverBackend`](crate::utils::SolverBackend),
+//! the default one with the `dlx` feature.
+
+use crate::app::SudokuState;
+
+/// Number of constraints of a Sudoku board:
+/// 81 cells, and 9 digits in each of the 9 rows, columns and boxes
+const CONSTRAINTS: usize = 4 * 81;
+
+/// A sparse matrix of an exact cover problem, as dancing links
+///
+/// Node 0 is the root, nodes 1 to the number of columns are the column
+/// headers, and the rest are the ones of the rows.
+/// Every node is linked to its neighbours in four directions,
+/// so a column can be unlinked and linked back in place.
+#[derive(Debug, Clone)]
+struct DancingLinks {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of every node
+    column: Vec<usize>,
+    /// The row of every node
+    row: Vec<usize>,
+    /// The number of nodes of every column, by its header
+    size: Vec<usize>,
+}
+
+impl DancingLinks {
+    /// Creates a matrix with a number of columns, and no rows.
+    fn new(columns: usize) -> Self {
+        let nodes = columns + 1;
+        Self {
+            left: (0..nodes).map(|node| (node + columns) % nodes).collect(),
+            right: (0..nodes).map(|node| (node + 1) % nodes).collect(),
+            up: (0..nodes).collect(),
+            down: (0..nodes).collect(),
+            column: (0..nodes).collect(),
+            row: vec![usize::MAX; nodes],
+            size: vec![0; nodes],
+        }
+    }
+
+    /// Adds a row, with the columns it covers.
+    fn add_row(&mut self, row: usize, columns: &[usize]) {
+        let first = self.left.len();
+        let last = first + columns.len() - 1;
+        for (node, &column) in (first..).zip(columns) {
+            let header = column + 1;
+            self.left.push(if node == first { last } else { node - 1 });
+            self.right.push(if node == last { first } else { node + 1 });
+            // at the bottom of the column
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let bottom = self.up[header];
+            self.down[bottom] = node;
+            self.up[header] = node;
+            self.column.push(header);
+            self.row.push(row);
+            self.size[header] += 1;
+        }
+    }
+
+    /// Removes a column, and the rows that cover it from the other columns.
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    /// Puts back a column removed by [`DancingLinks::cover`].
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                let (up, down) = (self.up[node], self.down[node]);
+                self.down[up] = node;
+                self.up[down] = node;
+                self.size[self.column[node]] += 1;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+
+    /// Searches the solutions, up to a limit.
+    ///
+    /// The rows of the first solution found are kept in `first`.
+    /// Returns the number of solutions found.
+    fn search(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        limit: usize,
+        first: &mut Option<Vec<usize>>,
+    ) -> usize {
+        if self.right[0] == 0 {
+            first.get_or_insert_with(|| chosen.clone());
+            return 1;
+        }
+
+        // the column with the fewest rows left
+        let mut header = self.right[0];
+        let mut column = self.right[header];
+        while column != 0 {
+            if self.size[column] < self.size[header] {
+                header = column;
+            }
+            column = self.right[column];
+        }
+
+        self.cover(header);
+        let mut count = 0;
+        let mut row = self.down[header];
+        while row != header && count < limit {
+            chosen.push(self.row[row]);
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+            count += self.search(chosen, limit - count, first);
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            chosen.pop();
+            row = self.down[row];
+        }
+        self.uncover(header);
+        count
+    }
+}
+
+/// Searches the solutions of a board, up to a limit.
+///
+/// Returns the number of solutions found, and the first one.
+fn search(board: &SudokuState, limit: usize) -> (usize, Option<SudokuState>) {
+    if limit == 0 || board.iter().any(|&value| value > 9) {
+        return (0, None);
+    }
+
+    let mut links = DancingLinks::new(CONSTRAINTS);
+    for (index, &value) in board.iter().enumerate() {
+        let (row, col) = (index / 9, index % 9);
+        let square = row / 3 * 3 + col / 3;
+        // a filled cell has a single candidate, its value
+        let digits = if value == 0 {
+            0..9
+        } else {
+            value as usize - 1..value as usize
+        };
+        for digit in digits {
+            links.add_row(
+                index * 9 + digit,
+                &[
+                    index,
+                    81 + row * 9 + digit,
+                    2 * 81 + col * 9 + digit,
+                    3 * 81 + square * 9 + digit,
+                ],
+            );
+        }
+    }
+
+    let mut first = None;
+    let count = links.search(&mut vec![], limit, &mut first);
+    let solution = first.map(|rows| {
+        let mut solution = [0; 81];
+        for row in rows {
+            solution[row / 9] = u8::try_from(row % 9 + 1).expect("a digit fits in a u8");
+        }
+        solution
+    });
+    (count, solution)
+}
+
+/// Solves a Sudoku board.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - A reference to the board to solve.
+///
+/// ## Returns
+///
+/// Returns a solution of the board,
+/// or `None` if it has none, or has conflicting cells.
+#[must_use]
+pub fn solve(board: &SudokuState) -> Option<SudokuState> {
+    search(board, 1).1
+}
+
+/// Counts the solutions of a Sudoku board, up to a limit.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - A reference to the board.
+/// - `limit: usize` - The number of solutions to stop at,
+///   such as 2 to check that a puzzle has a unique solution.
+///
+/// ## Returns
+///
+/// Returns the number of solutions, at most `limit`.
+#[must_use]
+pub fn count_solutions(board: &SudokuState, limit: usize) -> usize {
+    search(board, limit).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::parse_line;
+    use crate::utils::is_solved;
+
+    #[test]
+    fn test_solve() {
+        // one of the hardest known puzzles
+        let puzzle = parse_line(
+            "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..",
+        )
+        .expect("valid puzzle");
+        let solution = solve(&puzzle).expect("failed to solve");
+        assert!(is_solved(&solution));
+        for (&given, &value) in puzzle.iter().zip(&solution) {
+            assert!(given == 0 || given == value);
+        }
+        assert_eq!(count_solutions(&puzzle, 2), 1);
+
+        // conflicting givens have no solution
+        let mut conflicting = puzzle;
+        conflicting[1] = 8;
+        assert_eq!(solve(&conflicting), None);
+        assert_eq!(count_solutions(&conflicting, 2), 0);
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        let empty: SudokuState = [0; 81];
+        assert_eq!(count_solutions(&empty, 2), 2);
+        assert_eq!(count_solutions(&empty, 5), 5);
+        assert_eq!(count_solutions(&empty, 0), 0);
+        assert!(solve(&empty).is_some_and(|solution| is_solved(&solution)));
+        assert_eq!(count_solutions(&[10; 81], 1), 0);
+    }
+}
@@ -0,0 +1,77 @@
+//! # Embed Module
+//!
+//! The `embed` module contains the logic of the embeddable widget mode,
+//! which lets blogs embed a playable puzzle in an `iframe`:
+//!
+//! ```html
+//! <iframe src="https://sudoku.example.com/?embed=1&p=53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79"></iframe>
+//! ```
+//!
+//! In embed mode only the board and the number pad are rendered,
+//! and nothing is saved, synced or written to the URL.
+//! The puzzle is read from the URL query, like any of the
+//! [`links`](crate::links), and a new puzzle is created if there is none.
+//!
+//! Once the puzzle is solved, an [`EmbedMessage`] is posted to the
+//! embedding page, which can listen to it with:
+//!
+//! ```js
+//! window.addEventListener("message", (event) => {
+//!     if (event.data.type === "sudoku:solved") {
+//!         console.log(`Solved in ${event.data.millis} ms`);
+//!     }
+//! });
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Query parameter turning the embed mode on
+pub const EMBED_PARAM: &str = "embed";
+
+/// A message posted to the embedding page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum EmbedMessage {
+    /// The puzzle was solved
+    #[serde(rename = "sudoku:solved")]
+    Solved {
+        /// The puzzle, in the line format
+        puzzle: String,
+        /// The time taken, in milliseconds
+        millis: u64,
+    },
+}
+
+/// Returns whether the value of the [`EMBED_PARAM`] turns the embed mode on.
+///
+/// ## Parameters
+///
+/// - `value: Option<&str>` - The value of the query parameter, if any.
+#[must_use]
+pub fn is_embedded(value: Option<&str>) -> bool {
+    matches!(value, Some("1" | "true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_embedded() {
+        assert!(is_embedded(Some("1")));
+        assert!(is_embedded(Some("true")));
+        assert!(!is_embedded(Some("0")));
+        assert!(!is_embedded(None));
+    }
+
+    #[test]
+    fn test_message_format() {
+        let message = EmbedMessage::Solved {
+            puzzle: ".".repeat(81),
+            millis: 1000,
+        };
+        let json = serde_json::to_value(&message).expect("serialize");
+        assert_eq!(json["type"], "sudoku:solved");
+        assert_eq!(json["millis"], 1000);
+    }
+}
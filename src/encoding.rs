@@ -0,0 +1,313 @@
+//! # Encoding Module
+//!
+//! The `encoding` module provides a compact, URL-safe representation of a
+//! full Sudoku game: the initial puzzle, the user's progress and the notes.
+//!
+//! The game is bit-packed and then encoded as unpadded base64url so that it
+//! fits comfortably in a URL fragment.
+//! This powers deep links and "continue on another device".
+//!
+//! ## Format
+//!
+//! All fields are written most significant bit first:
+//!
+//! 1. An 8-bit format version.
+//! 2. An 81-bit mask of the cells that are clues in the initial puzzle.
+//! 3. The value of every clue cell in 4 bits.
+//! 4. The value of every non-clue cell in 4 bits (0 being empty).
+//! 5. For every empty non-clue cell: a 1-bit flag telling whether it has
+//!    notes, followed by the 9-bit notes mask if it does.
+
+use anyhow::{Error, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::app::{SudokuNotes, SudokuState};
+
+/// Current version of the encoding format
+const VERSION: u8 = 1;
+
+/// A full snapshot of a Sudoku game
+///
+/// - `initial: SudokuState`: the initial puzzle, where non-zero cells are clues.
+/// - `current: SudokuState`: the current board with the user's progress.
+/// - `notes: SudokuNotes`: the notes of every cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameSnapshot {
+    pub initial: SudokuState,
+    pub current: SudokuState,
+    pub notes: SudokuNotes,
+}
+
+/// Writes values bit by bit into a byte buffer.
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    const fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Writes the `width` least significant bits of `value`.
+    fn write(&mut self, value: u16, width: u8) {
+        for shift in (0..width).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> shift) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+/// Reads values bit by bit from a byte buffer.
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    /// Reads `width` bits into the least significant bits of the result.
+    fn read(&mut self, width: u8) -> Result<u16> {
+        let mut value = 0;
+        for _ in 0..width {
+            let byte = self
+                .bytes
+                .get(self.position / 8)
+                .ok_or_else(|| Error::msg("Encoded game is truncated"))?;
+            let bit = (byte >> (7 - self.position % 8)) & 1;
+            value = (value << 1) | u16::from(bit);
+            self.position += 1;
+        }
+        Ok(value)
+    }
+
+    /// Checks that only the zero padding of the last byte is left unread.
+    fn finish(&self) -> Result<()> {
+        let padding = self
+            .bytes
+            .get(self.position / 8)
+            .map_or(0, |byte| byte << (self.position % 8));
+        if self.bytes.len() > self.position.div_ceil(8) || padding != 0 {
+            return Err(Error::msg("Encoded game has trailing data"));
+        }
+        Ok(())
+    }
+}
+
+/// Encodes a [`GameSnapshot`] into a compact URL-safe string.
+///
+/// ## Parameters
+///
+/// - `game: &GameSnapshot` - A reference to the game to encode.
+///
+/// ## Returns
+///
+/// Returns an unpadded base64url `String` suitable for a URL fragment.
+#[must_use]
+pub fn encode_game(game: &GameSnapshot) -> String {
+    let mut writer = BitWriter::new();
+    writer.write(u16::from(VERSION), 8);
+
+    // Clues mask
+    for &value in &game.initial {
+        writer.write(u16::from(value != 0), 1);
+    }
+
+    // Clues values
+    for &value in game.initial.iter().filter(|&&value| value != 0) {
+        writer.write(u16::from(value), 4);
+    }
+
+    // Progress values
+    for (&initial, &current) in game.initial.iter().zip(game.current.iter()) {
+        if initial == 0 {
+            writer.write(u16::from(current), 4);
+        }
+    }
+
+    // Notes of empty cells
+    for (index, &notes) in game.notes.iter().enumerate() {
+        if game.initial[index] == 0 && game.current[index] == 0 {
+            writer.write(u16::from(notes != 0), 1);
+            if notes != 0 {
+                writer.write(notes, 9);
+            }
+        }
+    }
+
+    URL_SAFE_NO_PAD.encode(writer.bytes)
+}
+
+/// Decodes a string created by [`encode_game`] back into a [`GameSnapshot`].
+///
+/// ## Parameters
+///
+/// - `encoded: &str` - The encoded game, with or without a leading `#`.
+///
+/// ## Returns
+///
+/// Returns a Result containing the decoded [`GameSnapshot`].
+///
+/// ## Errors
+///
+/// Returns an error if the string is not valid base64url, is truncated,
+/// has trailing data, has an unknown version or contains out of range values.
+pub fn decode_game(encoded: &str) -> Result<GameSnapshot> {
+    let bytes = URL_SAFE_NO_PAD.decode(encoded.trim_start_matches('#'))?;
+    let mut reader = BitReader::new(&bytes);
+
+    if reader.read(8)? != u16::from(VERSION) {
+        return Err(Error::msg("Unknown encoded game version"));
+    }
+
+    // Clues mask
+    let mut clues = [false; 81];
+    for clue in &mut clues {
+        *clue = reader.read(1)? == 1;
+    }
+
+    // Clues values
+    let mut initial: SudokuState = [0; 81];
+    for (value, _) in initial.iter_mut().zip(clues).filter(|(_, clue)| *clue) {
+        *value = read_value(&mut reader)?;
+        if *value == 0 {
+            return Err(Error::msg("Encoded game has an empty clue"));
+        }
+    }
+
+    // Progress values
+    let mut current = initial;
+    for (value, _) in current.iter_mut().zip(clues).filter(|(_, clue)| !*clue) {
+        *value = read_value(&mut reader)?;
+    }
+
+    // Notes of empty cells
+    let mut notes: SudokuNotes = [0; 81];
+    for (index, cell_notes) in notes.iter_mut().enumerate() {
+        if current[index] == 0 && reader.read(1)? == 1 {
+            *cell_notes = reader.read(9)?;
+        }
+    }
+    reader.finish()?;
+
+    Ok(GameSnapshot {
+        initial,
+        current,
+        notes,
+    })
+}
+
+/// Reads a 4-bit cell value and checks that it is in the range 0 to 9.
+fn read_value(reader: &mut BitReader) -> Result<u8> {
+    let value = u8::try_from(reader.read(4)?)?;
+    if value > 9 {
+        return Err(Error::msg("Encoded game has an invalid cell value"));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_sudoku;
+
+    #[test]
+    fn test_round_trip_initial_puzzle() {
+        for _ in 0..20 {
+            let initial = create_sudoku();
+            let game = GameSnapshot {
+                initial,
+                current: initial,
+                notes: [0; 81],
+            };
+            assert_eq!(decode_game(&encode_game(&game)).expect("decode"), game);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_progress_and_notes() {
+        let initial = create_sudoku();
+        let mut current = initial;
+        let mut notes: SudokuNotes = [0; 81];
+        let empty: Vec<usize> = (0..81).filter(|&idx| initial[idx] == 0).collect();
+        current[empty[0]] = 5;
+        current[empty[1]] = 9;
+        notes[empty[2]] = 0b1_0000_0001;
+        notes[empty[3]] = 0b0_0101_0100;
+
+        let game = GameSnapshot {
+            initial,
+            current,
+            notes,
+        };
+        assert_eq!(decode_game(&encode_game(&game)).expect("decode"), game);
+    }
+
+    #[test]
+    fn test_encoded_game_fits_in_url() {
+        let initial = create_sudoku();
+        let game = GameSnapshot {
+            initial,
+            current: initial,
+            notes: [0x1FF; 81],
+        };
+        let encoded = encode_game(&game);
+        assert!(encoded.len() < 200);
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    }
+
+    #[test]
+    fn test_decode_accepts_leading_hash() {
+        let initial = create_sudoku();
+        let game = GameSnapshot {
+            initial,
+            current: initial,
+            notes: [0; 81],
+        };
+        let encoded = format!("#{}", encode_game(&game));
+        assert_eq!(decode_game(&encoded).expect("decode"), game);
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_input() {
+        assert!(decode_game("not base64!").is_err());
+        assert!(decode_game("AQ").is_err()); // truncated
+        assert!(decode_game(&URL_SAFE_NO_PAD.encode([2; 64])).is_err()); // version
+    }
+
+    #[test]
+    fn test_decode_rejects_trailing_data() {
+        let initial = create_sudoku();
+        let game = GameSnapshot {
+            initial,
+            current: initial,
+            notes: [0; 81],
+        };
+        let mut bytes = URL_SAFE_NO_PAD
+            .decode(encode_game(&game))
+            .expect("valid base64url");
+        bytes.push(0);
+        assert!(decode_game(&URL_SAFE_NO_PAD.encode(&bytes)).is_err());
+        // set bits in the padding of the last byte
+        bytes.pop();
+        if let Some(last) = bytes.last_mut() {
+            *last |= 1;
+        }
+        assert!(decode_game(&URL_SAFE_NO_PAD.encode(&bytes)).is_err());
+    }
+}
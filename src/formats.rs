@@ -0,0 +1,695 @@
+//! # Formats Module
+//!
+//! The `formats` module provides parsers and exporters for the textual
+//! formats that Sudoku puzzles are commonly shared in.
+//!
+//! The line format is the most common: the 81 cells of the board,
+//! row by row from top-left to bottom-right,
+//! with `0`, `.` or `-` standing for an empty cell.
+//! The letters from `A` to `I` are read as the digits from 1 to 9,
+//! so puzzles exported in the letters [`SymbolSet`] can be imported back.
+//!
+//! The file formats of common desktop Sudoku programs are supported too:
+//!
+//! - `SadMan` Sudoku `.sdk`: a single puzzle as a 9-line block,
+//!   with optional `#` metadata lines and `[Puzzle]` sections.
+//! - `SadMan` Sudoku `.sdm`: many puzzles, one per line, in the line format.
+//! - Simple Sudoku `.ss`: a single puzzle as a 9-line block with `|` and
+//!   `-` box separators.
+//!
+//! To analyze a position in external tools, such as
+//! [SudokuWiki](https://www.sudokuwiki.org/sudoku.htm),
+//! boards can be exported to the line format and to a pencilmark grid with
+//! the candidates of every cell.
+//! The same grids can be drawn with box-drawing characters for terminals.
+
+use anyhow::{Error, Result};
+
+use crate::app::{SudokuNotes, SudokuState};
+use crate::bitboard::BitBoard;
+use crate::encoding::{decode_game, GameSnapshot};
+use crate::save::{import_save, SavedGame};
+use crate::symbols::SymbolSet;
+use crate::utils::validate_puzzle;
+
+/// Returns the value of a cell written in the line format.
+///
+/// Digits map to their value, letters from `A` to `I` to the digit they
+/// stand for, while `0`, `.` and `-` map to an empty cell.
+/// Any other character returns `None`.
+fn cell_value(c: char) -> Option<u8> {
+    match c {
+        '1'..='9' => c.to_digit(10).and_then(|digit| u8::try_from(digit).ok()),
+        '0' | '.' | '-' => Some(0),
+        _ => SymbolSet::letter_digit(c),
+    }
+}
+
+/// Parses a puzzle written in the line format.
+///
+/// Whitespace is ignored,
+/// so both a single line and a 9-line block are accepted.
+///
+/// ## Parameters
+///
+/// - `line: &str` - The puzzle in the line format.
+///
+/// ## Returns
+///
+/// Returns a Result containing the puzzle as a [`SudokuState`].
+///
+/// ## Errors
+///
+/// Returns an error if the puzzle does not have exactly 81 cells or has an
+/// unknown character.
+pub fn parse_line(line: &str) -> Result<SudokuState> {
+    let cells = line
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| cell_value(c).ok_or_else(|| Error::msg(format!("Invalid cell character '{c}'"))))
+        .collect::<Result<Vec<u8>>>()?;
+
+    SudokuState::try_from(cells.as_slice())
+        .map_err(|_| Error::msg(format!("Expected 81 cells, found {}", cells.len())))
+}
+
+/// Recognizes an imported text as a Sudoku game.
+///
+/// The text can either be a game link, or its fragment,
+/// created by [`encode_game`](crate::encoding::encode_game),
+/// or a puzzle in the line format.
+/// Recognized games are validated with [`validate_puzzle`].
+///
+/// ## Parameters
+///
+/// - `text: &str` - The text to import, e.g. the content of the clipboard.
+///
+/// ## Returns
+///
+/// Returns `None` if the text does not look like a Sudoku game at all.
+/// Otherwise returns `Some` with the result of parsing and validating
+/// the game.
+#[must_use]
+pub fn recognize_import(text: &str) -> Option<Result<GameSnapshot>> {
+    let text = text.trim();
+
+    // Game links
+    if let Some((_, fragment)) = text.rsplit_once('#') {
+        return decode_game(fragment).ok().map(|game| {
+            validate_puzzle(&game.initial)?;
+            Ok(game)
+        });
+    }
+
+    // Line format
+    let cells: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if cells.len() != 81 || !cells.iter().all(|&c| cell_value(c).is_some()) {
+        return None;
+    }
+    Some(parse_line(text).and_then(|initial| {
+        validate_puzzle(&initial)?;
+        Ok(GameSnapshot {
+            initial,
+            current: initial,
+            notes: [0; 81],
+        })
+    }))
+}
+
+/// A game recognized in a pasted text by [`recognize_paste`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PastedGame {
+    /// A JSON save file, restored with its moves, timer and notes
+    Save(Box<SavedGame>),
+    /// A game link or a puzzle in the line format
+    Snapshot(Box<GameSnapshot>),
+}
+
+/// Recognizes a pasted text as a Sudoku game.
+///
+/// The text is first tried as a JSON save file with [`import_save`],
+/// then recognized with [`recognize_import`].
+/// Save files are recognized before game links,
+/// since the colors of their themes contain a `#` too.
+///
+/// ## Parameters
+///
+/// - `text: &str` - The pasted text.
+///
+/// ## Returns
+///
+/// Returns `None` if the text does not look like a Sudoku game at all.
+/// Otherwise returns `Some` with the result of parsing and validating
+/// the game.
+#[must_use]
+pub fn recognize_paste(text: &str) -> Option<Result<PastedGame>> {
+    match import_save(text) {
+        Ok(game) => Some(Ok(PastedGame::Save(Box::new(game)))),
+        // a JSON object that is not a valid save file
+        Err(err) if text.trim_start().starts_with('{') => Some(Err(err)),
+        Err(_) => {
+            recognize_import(text).map(|game| game.map(|game| PastedGame::Snapshot(Box::new(game))))
+        }
+    }
+}
+
+/// Parses a puzzle in the `SadMan` Sudoku `.sdk` format.
+///
+/// Lines starting with `#` are metadata and are ignored.
+/// If the file has sections, only the `[Puzzle]` section is read.
+///
+/// ## Errors
+///
+/// Returns an error if the puzzle is not a valid 9-line block.
+pub fn parse_sdk(contents: &str) -> Result<SudokuState> {
+    let has_sections = contents.lines().any(|line| line.trim().starts_with('['));
+    let mut in_puzzle = !has_sections;
+    let mut block = String::new();
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_puzzle = line.eq_ignore_ascii_case("[puzzle]");
+        } else if in_puzzle && !line.starts_with('#') {
+            block.push_str(line);
+        }
+    }
+    parse_line(&block)
+}
+
+/// Parses the puzzles in the `SadMan` Sudoku `.sdm` format.
+///
+/// Blank lines are ignored.
+///
+/// ## Errors
+///
+/// Returns an error, with its line number, if any puzzle is invalid.
+pub fn parse_sdm(contents: &str) -> Result<Vec<SudokuState>> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            parse_line(line).map_err(|err| Error::msg(format!("Line {}: {err}", number + 1)))
+        })
+        .collect()
+}
+
+/// Parses a puzzle in the Simple Sudoku `.ss` format.
+///
+/// Box separators, `|` within rows and lines of `-`, are ignored.
+///
+/// ## Errors
+///
+/// Returns an error if the puzzle is not a valid 9-line block.
+pub fn parse_ss(contents: &str) -> Result<SudokuState> {
+    let block: String = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('-') && !line.starts_with('*'))
+        .flat_map(|line| line.chars().filter(|&c| c != '|' && c != '!'))
+        .collect();
+    parse_line(&block)
+}
+
+/// Parses the puzzles in a file, based on its extension.
+///
+/// Files with an unknown extension are parsed as one puzzle per line,
+/// like `.sdm` files.
+///
+/// ## Parameters
+///
+/// - `file_name: &str` - The name of the file, used to detect its format.
+/// - `contents: &str` - The contents of the file.
+///
+/// ## Returns
+///
+/// Returns a Result containing all the puzzles in the file.
+///
+/// ## Errors
+///
+/// Returns an error if the file is not valid in its format or has no
+/// puzzle.
+pub fn parse_puzzle_file(file_name: &str, contents: &str) -> Result<Vec<SudokuState>> {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase());
+    let puzzles = match extension.as_deref() {
+        Some("sdk") => vec![parse_sdk(contents)?],
+        Some("ss") => vec![parse_ss(contents)?],
+        _ => parse_sdm(contents)?,
+    };
+    if puzzles.is_empty() {
+        return Err(Error::msg("The file has no puzzle"));
+    }
+    Ok(puzzles)
+}
+
+/// Exports a board to the line format.
+///
+/// ## Parameters
+///
+/// - `sudoku: &SudokuState` - A reference to the board to export.
+/// - `empty: char` - The character standing for an empty cell,
+///   usually `.` or `0`.
+///
+/// ## Returns
+///
+/// Returns the 81 cells of the board as a `String`.
+#[must_use]
+pub fn to_line(sudoku: &SudokuState, empty: char) -> String {
+    to_symbol_line(sudoku, empty, SymbolSet::Digits)
+}
+
+/// Exports a board to the line format, in a [`SymbolSet`].
+///
+/// Sets whose symbols are longer than a single character,
+/// such as the Roman numerals, are exported as digits,
+/// so the line can always be read back with [`parse_line`].
+///
+/// ## Parameters
+///
+/// - `sudoku: &SudokuState` - A reference to the board to export.
+/// - `empty: char` - The character standing for an empty cell,
+///   usually `.` or `0`.
+/// - `symbols: SymbolSet` - The symbols the digits are written in.
+///
+/// ## Returns
+///
+/// Returns the 81 cells of the board as a `String`.
+#[must_use]
+pub fn to_symbol_line(sudoku: &SudokuState, empty: char, symbols: SymbolSet) -> String {
+    sudoku
+        .iter()
+        .map(|&value| {
+            if value == 0 {
+                empty
+            } else {
+                symbols
+                    .line_char(value)
+                    .unwrap_or_else(|| char::from(b'0' + value))
+            }
+        })
+        .collect()
+}
+
+/// The characters drawing the borders of a grid
+///
+/// - `horizontal: char`: the horizontal lines.
+/// - `vertical: char`: the vertical lines.
+/// - `top: [char; 3]`: the left corner, the joints between the boxes,
+///   and the right corner of the top border.
+/// - `middle: [char; 3]`: the same, for the borders between the boxes.
+/// - `bottom: [char; 3]`: the same, for the bottom border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridStyle {
+    pub horizontal: char,
+    pub vertical: char,
+    pub top: [char; 3],
+    pub middle: [char; 3],
+    pub bottom: [char; 3],
+}
+
+impl GridStyle {
+    /// ASCII borders, as in the pencilmark format
+    pub const ASCII: Self = Self {
+        horizontal: '-',
+        vertical: '|',
+        top: ['.', '.', '.'],
+        middle: [':', '+', ':'],
+        bottom: ['\'', '\'', '\''],
+    };
+
+    /// Unicode box-drawing borders, for terminals
+    pub const UNICODE: Self = Self {
+        horizontal: '─',
+        vertical: '│',
+        top: ['┌', '┬', '┐'],
+        middle: ['├', '┼', '┤'],
+        bottom: ['└', '┴', '┘'],
+    };
+}
+
+/// Returns the text of every cell of a board, with the candidates of its
+/// empty cells.
+///
+/// The candidates of an empty cell are its notes, if any,
+/// otherwise all the values that are not in its related cells.
+fn pencilmark_cells(sudoku: &SudokuState, notes: &SudokuNotes) -> Vec<String> {
+    let board = BitBoard::new(*sudoku);
+    (0..81)
+        .map(|index| {
+            if sudoku[index] != 0 {
+                return sudoku[index].to_string();
+            }
+            let candidates = if notes[index] == 0 {
+                board.candidates(u8::try_from(index).expect("cannot convert from u8"))
+            } else {
+                notes[index]
+            };
+            (1..=9_u8)
+                .filter(|value| candidates & (1 << (value - 1)) != 0)
+                .map(|value| value.to_string())
+                .collect()
+        })
+        .collect()
+}
+
+/// Draws the text of the 81 cells of a board as a grid,
+/// where every column is as wide as its widest cell.
+fn draw_grid(cells: &[String], style: GridStyle) -> String {
+    let widths: Vec<usize> = (0..9)
+        .map(|column| {
+            (0..9)
+                .map(|row| cells[row * 9 + column].chars().count())
+                .max()
+                .unwrap_or(1)
+        })
+        .collect();
+    let box_widths: Vec<usize> = widths
+        .chunks(3)
+        .map(|box_widths| box_widths.iter().sum::<usize>() + 4)
+        .collect();
+    let border = |[left, joint, right]: [char; 3]| -> String {
+        let segments: Vec<String> = box_widths
+            .iter()
+            .map(|&width| style.horizontal.to_string().repeat(width))
+            .collect();
+        format!("{left}{}{right}", segments.join(&joint.to_string()))
+    };
+
+    let vertical = style.vertical;
+    let mut lines = vec![border(style.top)];
+    for row in 0..9 {
+        if row == 3 || row == 6 {
+            lines.push(border(style.middle));
+        }
+        let boxes: Vec<String> = (0..3)
+            .map(|stack| {
+                (stack * 3..stack * 3 + 3)
+                    .map(|column| format!("{:<1$}", cells[row * 9 + column], widths[column]))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        lines.push(format!(
+            "{vertical} {} {vertical}",
+            boxes.join(&format!(" {vertical} "))
+        ));
+    }
+    lines.push(border(style.bottom));
+    lines.join("\n")
+}
+
+/// Exports a board, with the candidates of its empty cells,
+/// to a pencilmark grid.
+///
+/// The candidates of an empty cell are its notes, if any,
+/// otherwise all the values that are not in its related cells.
+/// Filled cells are exported as their value.
+///
+/// The grid is the pencilmark format understood by `SudokuWiki`'s importer
+/// and other Sudoku tools, such as `HoDoKu`:
+///
+/// ```text
+/// .----------------.----------------.----------------.
+/// | 5   3    12    | 26   7    2468 | 1489 1249 248  |
+/// ...
+/// '----------------'----------------'----------------'
+/// ```
+///
+/// ## Parameters
+///
+/// - `sudoku: &SudokuState` - A reference to the board to export.
+/// - `notes: &SudokuNotes` - A reference to the notes of the board.
+///
+/// ## Returns
+///
+/// Returns the pencilmark grid as a multi-line `String`.
+///
+/// ## Panics
+///
+/// The function will panic if cannot convert any of the Sudoku's board cells
+/// indexes from `usize` into a `u8`
+#[must_use]
+pub fn to_pencilmark_grid(sudoku: &SudokuState, notes: &SudokuNotes) -> String {
+    draw_grid(&pencilmark_cells(sudoku, notes), GridStyle::ASCII)
+}
+
+/// Draws a board as a grid with box-drawing borders, for terminals.
+///
+/// ```text
+/// ┌───────┬───────┬───────┐
+/// │ 5 3 . │ . 7 . │ . . . │
+/// ...
+/// └───────┴───────┴───────┘
+/// ```
+///
+/// ## Parameters
+///
+/// - `sudoku: &SudokuState` - A reference to the board to draw.
+/// - `candidates: bool` - Whether empty cells show their candidates,
+///   as in [`to_pencilmark_grid`], instead of a `.`.
+///
+/// ## Returns
+///
+/// Returns the grid as a multi-line `String`.
+///
+/// ## Panics
+///
+/// The function will panic if cannot convert any of the Sudoku's board cells
+/// indexes from `usize` into a `u8`
+#[must_use]
+pub fn to_box_grid(sudoku: &SudokuState, candidates: bool) -> String {
+    let cells: Vec<String> = if candidates {
+        pencilmark_cells(sudoku, &[0; 81])
+    } else {
+        to_line(sudoku, '.').chars().map(String::from).collect()
+    };
+    draw_grid(&cells, GridStyle::UNICODE)
+}
+
+/// Creates a link that opens a board in the `SudokuWiki` solver.
+///
+/// Only the digits of the board are linked, in the line format:
+/// the solver works out the candidates of the empty cells itself,
+/// so the notes of the player are not carried over.
+///
+/// ## Parameters
+///
+/// - `sudoku: &SudokuState` - A reference to the board to open.
+///
+/// ## Returns
+///
+/// Returns the link as a `String`.
+#[must_use]
+pub fn sudokuwiki_url(sudoku: &SudokuState) -> String {
+    format!(
+        "https://www.sudokuwiki.org/sudoku.htm?bd={}",
+        to_line(sudoku, '0')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::encode_game;
+    use crate::utils::create_sudoku;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn test_parse_line() {
+        let sudoku = parse_line(PUZZLE).expect("valid puzzle");
+        assert_eq!(sudoku[0], 5);
+        assert_eq!(sudoku[2], 0);
+        assert_eq!(sudoku[80], 9);
+    }
+
+    #[test]
+    fn test_parse_line_block() {
+        let block = PUZZLE
+            .as_bytes()
+            .chunks(9)
+            .map(String::from_utf8_lossy)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            parse_line(&block).expect("valid block"),
+            parse_line(PUZZLE).expect("valid puzzle")
+        );
+    }
+
+    #[test]
+    fn test_parse_line_invalid() {
+        assert!(parse_line(&PUZZLE[1..]).is_err());
+        assert!(parse_line(&PUZZLE.replace('5', "x")).is_err());
+    }
+
+    #[test]
+    fn test_recognize_import_line() {
+        let game = recognize_import(PUZZLE)
+            .expect("recognized")
+            .expect("valid");
+        assert_eq!(game.initial, game.current);
+    }
+
+    #[test]
+    fn test_recognize_import_link() {
+        let initial = create_sudoku();
+        let game = GameSnapshot {
+            initial,
+            current: initial,
+            notes: [0; 81],
+        };
+        let link = format!("https://example.com/#{}", encode_game(&game));
+        let imported = recognize_import(&link).expect("recognized").expect("valid");
+        assert_eq!(imported, game);
+    }
+
+    #[test]
+    fn test_recognize_import_ignores_other_text() {
+        assert!(recognize_import("hello world").is_none());
+        assert!(recognize_import("").is_none());
+    }
+
+    #[test]
+    fn test_recognize_import_rejects_conflicts() {
+        let conflicting = format!("11{}", &PUZZLE[2..]);
+        assert!(recognize_import(&conflicting).expect("recognized").is_err());
+    }
+
+    #[test]
+    fn test_recognize_paste() {
+        let initial = parse_line(PUZZLE).expect("valid puzzle");
+        let game = SavedGame {
+            initial,
+            moves: vec![initial],
+            notes: [0; 81],
+            timer: 5_000,
+            settings: crate::settings::Settings::default(),
+            move_notes: vec![],
+            tree: None,
+        };
+        let json = crate::save::export_save(&game).expect("export");
+        assert_eq!(
+            recognize_paste(&json).expect("recognized").expect("valid"),
+            PastedGame::Save(Box::new(game))
+        );
+        assert!(recognize_paste(r#"{ "version": 99 }"#)
+            .expect("recognized")
+            .is_err());
+        assert!(matches!(
+            recognize_paste(PUZZLE),
+            Some(Ok(PastedGame::Snapshot(_)))
+        ));
+        assert!(recognize_paste("hello world").is_none());
+    }
+
+    #[test]
+    fn test_parse_sdk() {
+        let sdk = "#ASadMan\n#D A description\n53..7....\n6..195...\n.98....6.\n8...6...3\n4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..79\n";
+        assert_eq!(
+            parse_sdk(sdk).expect("valid sdk"),
+            parse_line(PUZZLE).expect("valid puzzle")
+        );
+    }
+
+    #[test]
+    fn test_parse_sdk_sections() {
+        let sdk = "[Puzzle]\n53..7....\n6..195...\n.98....6.\n8...6...3\n4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..79\n[State]\n534678912\n";
+        assert_eq!(
+            parse_sdk(sdk).expect("valid sdk"),
+            parse_line(PUZZLE).expect("valid puzzle")
+        );
+    }
+
+    #[test]
+    fn test_parse_sdm() {
+        let sdm = format!("{PUZZLE}\n\n{PUZZLE}\n");
+        assert_eq!(parse_sdm(&sdm).expect("valid sdm").len(), 2);
+        assert!(parse_sdm(&format!("{PUZZLE}\n123\n")).is_err());
+    }
+
+    #[test]
+    fn test_parse_ss() {
+        let ss = "53.|.7.|...\n6..|195|...\n.98|...|.6.\n-----------\n8..|.6.|..3\n4..|8.3|..1\n7..|.2.|..6\n-----------\n.6.|...|28.\n...|419|..5\n...|.8.|.79\n";
+        assert_eq!(
+            parse_ss(ss).expect("valid ss"),
+            parse_line(PUZZLE).expect("valid puzzle")
+        );
+    }
+
+    #[test]
+    fn test_parse_puzzle_file() {
+        assert_eq!(
+            parse_puzzle_file("puzzles.SDM", PUZZLE)
+                .expect("valid file")
+                .len(),
+            1
+        );
+        assert!(parse_puzzle_file("empty.sdm", "\n").is_err());
+        assert!(parse_puzzle_file("bad.ss", "123").is_err());
+    }
+
+    #[test]
+    fn test_to_line_round_trip() {
+        let sudoku = parse_line(PUZZLE).expect("valid puzzle");
+        assert_eq!(to_line(&sudoku, '.'), PUZZLE);
+        assert_eq!(parse_line(&to_line(&sudoku, '0')).expect("valid"), sudoku);
+    }
+
+    #[test]
+    fn test_to_symbol_line_round_trip() {
+        let sudoku = parse_line(PUZZLE).expect("valid puzzle");
+        let letters = to_symbol_line(&sudoku, '.', SymbolSet::Letters);
+        assert!(letters.starts_with("EC..G"));
+        assert_eq!(parse_line(&letters).expect("valid"), sudoku);
+        assert_eq!(parse_line(&letters.to_lowercase()).expect("valid"), sudoku);
+        // the Roman numerals do not fit in a character
+        assert_eq!(to_symbol_line(&sudoku, '.', SymbolSet::Roman), PUZZLE);
+    }
+
+    #[test]
+    fn test_to_pencilmark_grid() {
+        let sudoku = parse_line(PUZZLE).expect("valid puzzle");
+        let mut notes: SudokuNotes = [0; 81];
+        notes[2] = 0b11; // candidates 1 and 2
+
+        let grid = to_pencilmark_grid(&sudoku, &notes);
+        let lines: Vec<&str> = grid.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert!(lines[0].starts_with(".-"));
+        assert!(lines[1].starts_with("| 5 "));
+        assert!(lines[1].contains(" 12 "));
+        assert!(lines[4].starts_with(":-"));
+        assert!(lines[12].starts_with("'-"));
+        assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+    }
+
+    #[test]
+    fn test_to_box_grid() {
+        let sudoku = parse_line(PUZZLE).expect("valid puzzle");
+        let grid = to_box_grid(&sudoku, false);
+        let lines: Vec<&str> = grid.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "┌───────┬───────┬───────┐");
+        assert_eq!(lines[1], "│ 5 3 . │ . 7 . │ . . . │");
+        assert_eq!(lines[4], "├───────┼───────┼───────┤");
+        assert_eq!(lines[12], "└───────┴───────┴───────┘");
+
+        let with_candidates = to_box_grid(&sudoku, true);
+        let lines: Vec<&str> = with_candidates.lines().collect();
+        assert!(lines[1].starts_with("│ 5 "));
+        assert!(!with_candidates.contains('.'));
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+    }
+
+    #[test]
+    fn test_sudokuwiki_url() {
+        let sudoku = parse_line(PUZZLE).expect("valid puzzle");
+        assert!(sudokuwiki_url(&sudoku).ends_with(
+            "?bd=530070000600195000098000060800060003400803001700020006060000280000419005000080079"
+        ));
+    }
+}
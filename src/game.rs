@@ -0,0 +1,882 @@
+//! # Game Module
+//!
+//! The `game` module contains the [`GameState`] of a Sudoku game and its
+//! transition function, [`GameState::apply`].
+//!
+//! Every change to the board goes through a [`GameAction`],
+//! such as placing a value, undoing a move, taking a note or starting a new
+//! game,
+//! so the puzzle, the moves, the notes and the clicked cell never disagree.
+//! The moves are kept in an [`UndoTree`],
+//! so a move made after undoing does not drop the moves undone.
+//! The values shown on the board, such as the current puzzle, the cells
+//! related to the clicked one and the conflicting cells,
+//! are derived from the state instead of being kept apart.
+
+use crate::app::{SudokuNotes, SudokuState};
+use crate::bitboard::BitBoard;
+use crate::undo_tree::UndoTree;
+use crate::utils::{
+    count_mistakes, find_changed_cell, find_solution, get_all_conflicting_cells, get_hint,
+    get_related_cells, remove_conflicting_cells, SolverBackend,
+};
+
+/// A change to a [`GameState`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameAction {
+    /// Click a cell, or no cell if the index is not on the board
+    Select(u8),
+    /// Place a value in a cell, where 0 erases the cell
+    Place { index: u8, value: u8 },
+    /// Erase a cell
+    Erase(u8),
+    /// Erase every value of a digit that is not a clue, in one move
+    EraseDigit(u8),
+    /// Fill every empty cell with a single candidate, in one move
+    FillSingles,
+    /// Undo the last move
+    Undo,
+    /// Redo the last undone move
+    Redo,
+    /// Undo the moves back to the last state without mistakes
+    UndoMistakes,
+    /// Fill a cell with a hint, after erasing the conflicting cells, if any
+    Hint,
+    /// Restore a snapshot of the board, as a new move
+    Restore(SudokuState),
+    /// Start a trial, whose moves can be kept or discarded at once
+    StartTrial,
+    /// Keep the moves of the trial, which ends it
+    CommitTrial,
+    /// Discard the moves of the trial, which ends it
+    DiscardTrial,
+    /// Go to a node of the undo tree, on any branch
+    GoTo(usize),
+    /// Set the note on the current move, where an empty note removes it
+    Annotate(String),
+    /// Toggle a digit in the notes of a cell, where 0 clears them
+    ToggleNote { index: u8, digit: u8 },
+    /// Replace the notes of every cell
+    SetNotes(SudokuNotes),
+    /// Start a new game with a puzzle
+    NewGame(SudokuState),
+    /// Load a game with its moves, the first of which is the puzzle,
+    /// and its notes
+    Load {
+        moves: Vec<SudokuState>,
+        notes: SudokuNotes,
+    },
+    /// Resume a game with its undo tree, whose root is the puzzle,
+    /// and its notes
+    Resume {
+        history: UndoTree,
+        notes: SudokuNotes,
+    },
+}
+
+/// The state of a Sudoku game
+///
+/// - `initial: SudokuState`: the puzzle, whose clues cannot be changed.
+/// - `history: UndoTree`: the states of the board after every move,
+///   the puzzle being the root, including the moves undone.
+/// - `notes: SudokuNotes`: the candidates noted in every cell,
+///   one bit per digit.
+/// - `clicked: u8`: the index of the clicked cell,
+///   or 90 if no cell is clicked.
+/// - `trial: Option<TrialStart>`: where the trial started,
+///   if one is under way.
+///   The moves of a trial cannot be undone past its start.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameState {
+    pub initial: SudokuState,
+    pub history: UndoTree,
+    pub notes: SudokuNotes,
+    pub clicked: u8,
+    pub trial: Option<TrialStart>,
+}
+
+/// Where a trial started in the [`UndoTree`]
+///
+/// - `node: usize`: the node of the board the trial started from.
+/// - `nodes: usize`: the number of nodes when it started,
+///   so the nodes added since are the moves of the trial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrialStart {
+    pub node: usize,
+    pub nodes: usize,
+}
+
+impl GameState {
+    /// Creates the state of a new game, with no moves, no notes and no cell
+    /// clicked.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: SudokuState` - The puzzle of the game.
+    #[must_use]
+    pub fn new(puzzle: SudokuState) -> Self {
+        Self {
+            initial: puzzle,
+            history: UndoTree::new(puzzle),
+            notes: [0; 81],
+            clicked: 90,
+            trial: None,
+        }
+    }
+
+    /// Returns the current state of the board, after the last move.
+    #[must_use]
+    pub const fn current(&self) -> SudokuState {
+        self.history.current_board()
+    }
+
+    /// Returns the states of the board after every move of the current
+    /// branch, starting with the puzzle.
+    ///
+    /// See also: [`UndoTree::moves`].
+    #[must_use]
+    pub fn moves(&self) -> Vec<SudokuState> {
+        self.history.moves()
+    }
+
+    /// Returns whether the game has progress, i.e. moves or notes.
+    #[must_use]
+    pub fn has_progress(&self) -> bool {
+        self.history.depth(self.history.current()) > 0 || self.notes != [0; 81]
+    }
+
+    /// Returns whether the clicked cell can be changed,
+    /// i.e. it is on the board and is not a clue.
+    #[must_use]
+    pub fn mutable(&self) -> bool {
+        self.initial.get(self.clicked as usize) == Some(&0)
+    }
+
+    /// Returns the cells related to the clicked cell,
+    /// or none if no cell is clicked.
+    ///
+    /// See also: [`get_related_cells`].
+    #[must_use]
+    pub fn related(&self) -> Vec<u8> {
+        if self.clicked < 81 {
+            get_related_cells(self.clicked)
+        } else {
+            vec![]
+        }
+    }
+
+    /// Returns the cells changed since the trial started,
+    /// or none if no trial is under way.
+    #[must_use]
+    pub fn trial_cells(&self) -> Vec<u8> {
+        let Some(base) = self.trial.and_then(|start| self.history.board(start.node)) else {
+            return vec![];
+        };
+        let current = self.current();
+        (0..81)
+            .filter(|&index| current[index as usize] != base[index as usize])
+            .collect()
+    }
+
+    /// Returns the cells of the current board in conflict with another.
+    ///
+    /// See also: [`get_all_conflicting_cells`].
+    #[must_use]
+    pub fn conflicting(&self) -> Vec<u8> {
+        get_all_conflicting_cells(&self.current())
+    }
+
+    /// Applies an action to the game.
+    ///
+    /// ## Parameters
+    ///
+    /// - `action: GameAction` - The action to apply.
+    ///
+    /// ## Returns
+    ///
+    /// Returns whether the action was applied.
+    /// Placing a value in a clue or undoing the puzzle, for instance,
+    /// is not, and neither is a hint on a filled board.
+    /// Neither is erasing a digit that is only in the clues,
+    /// filling the singles of a board without any,
+    /// or undoing the mistakes of a board without any.
+    /// Restoring a snapshot is not if it changes a clue or the board is
+    /// already at it.
+    /// Undoing past the start of a trial is not either,
+    /// nor starting a trial during another one, or ending one without any,
+    /// nor going to a node out of the trial during one.
+    /// Annotating the puzzle, which is not a move, is not,
+    /// and neither is annotating a move with the note it already has.
+    /// Notes are not taken in the clues, nor changed if they already are
+    /// the ones set.
+    /// Loading or resuming a game without moves is not either.
+    /// Undoing and redoing click the cell changed by the move,
+    /// and so do undoing the mistakes, going to a node, restoring a
+    /// snapshot and a hint.
+    pub fn apply(&mut self, action: GameAction) -> bool {
+        match action {
+            GameAction::Select(index) => {
+                self.clicked = index;
+                true
+            }
+            GameAction::Place { index, value } => self.place(index, value),
+            GameAction::Erase(index) => self.place(index, 0),
+            GameAction::EraseDigit(digit) => self.erase_digit(digit),
+            GameAction::FillSingles => self.fill_singles(),
+            GameAction::Undo => {
+                // the board a trial started from cannot be undone
+                let last = self.current();
+                if self
+                    .trial
+                    .is_some_and(|start| start.node == self.history.current())
+                    || !self.history.undo()
+                {
+                    return false;
+                }
+                self.click_changed(&last);
+                true
+            }
+            GameAction::Redo => {
+                let last = self.current();
+                if !self.history.redo() {
+                    return false;
+                }
+                self.click_changed(&last);
+                true
+            }
+            GameAction::GoTo(node) => self.go_to(node),
+            GameAction::Annotate(note) => self.history.annotate(self.history.current(), &note),
+            GameAction::ToggleNote { index, digit } => self.toggle_note(index, digit),
+            GameAction::SetNotes(notes) => {
+                if self.notes == notes {
+                    return false;
+                }
+                self.notes = notes;
+                true
+            }
+            GameAction::UndoMistakes => self.undo_mistakes(),
+            GameAction::Hint => self.hint(),
+            GameAction::Restore(snapshot) => self.restore(snapshot),
+            GameAction::StartTrial => {
+                if self.trial.is_some() {
+                    return false;
+                }
+                self.trial = Some(TrialStart {
+                    node: self.history.current(),
+                    nodes: self.history.node_count(),
+                });
+                // only the moves of the trial can be redone
+                self.history.forget_redo();
+                true
+            }
+            GameAction::CommitTrial => self.trial.take().is_some(),
+            GameAction::DiscardTrial => self.discard_trial(),
+            GameAction::NewGame(puzzle) => {
+                *self = Self::new(puzzle);
+                true
+            }
+            GameAction::Resume { history, notes } => {
+                let Some(initial) = history.board(0) else {
+                    return false;
+                };
+                *self = Self {
+                    history,
+                    notes,
+                    ..Self::new(initial)
+                };
+                true
+            }
+            GameAction::Load { moves, notes } => {
+                let (Some(&initial), Some(history)) = (moves.first(), UndoTree::from_moves(&moves))
+                else {
+                    return false;
+                };
+                *self = Self {
+                    history,
+                    notes,
+                    ..Self::new(initial)
+                };
+                true
+            }
+        }
+    }
+
+    /// Places a value in a cell, unless it is a clue or already has it.
+    fn place(&mut self, index: u8, value: u8) -> bool {
+        let mut current = self.current();
+        let index = index as usize;
+        if self.initial.get(index) != Some(&0) || current[index] == value {
+            return false;
+        }
+        current[index] = value;
+        self.push(current);
+        true
+    }
+
+    /// Toggles a digit in the notes of a cell, or clears them for 0,
+    /// unless it is a clue or the notes would not change.
+    fn toggle_note(&mut self, index: u8, digit: u8) -> bool {
+        let cell = index as usize;
+        if self.initial.get(cell) != Some(&0) || digit > 9 {
+            return false;
+        }
+        let notes = match digit {
+            0 => 0,
+            _ => self.notes[cell] ^ (1 << (digit - 1)),
+        };
+        if notes == self.notes[cell] {
+            return false;
+        }
+        self.notes[cell] = notes;
+        true
+    }
+
+    /// Goes to a node of the undo tree,
+    /// unless it is out of the trial under way.
+    fn go_to(&mut self, node: usize) -> bool {
+        // a trial stays on its moves
+        if self
+            .trial
+            .is_some_and(|start| node != start.node && node < start.nodes)
+        {
+            return false;
+        }
+        let last = self.current();
+        if !self.history.goto(node) {
+            return false;
+        }
+        self.click_changed(&last);
+        true
+    }
+
+    /// Restores a snapshot of the board as a new move,
+    /// unless it changes a clue or the board is already at it.
+    fn restore(&mut self, snapshot: SudokuState) -> bool {
+        let keeps_clues = self
+            .initial
+            .iter()
+            .zip(&snapshot)
+            .all(|(&clue, &value)| clue == 0 || clue == value);
+        let last = self.current();
+        if !keeps_clues || snapshot == last {
+            return false;
+        }
+        self.push(snapshot);
+        self.click_changed(&last);
+        true
+    }
+
+    /// Ends the trial under way, going back to the board it started from
+    /// and dropping its moves.
+    fn discard_trial(&mut self) -> bool {
+        let Some(start) = self.trial.take() else {
+            return false;
+        };
+        let last = self.current();
+        self.history.goto(start.node);
+        self.history.truncate(start.nodes);
+        self.click_changed(&last);
+        true
+    }
+
+    /// Undoes the moves back to the last state without mistakes.
+    ///
+    /// A state has no mistakes if all its entries match the solution,
+    /// or, for a puzzle without a unique solution,
+    /// if none of its cells are conflicting.
+    /// The undone moves can be redone, as if undone one at a time.
+    /// During a trial, the moves are not undone past its start.
+    fn undo_mistakes(&mut self) -> bool {
+        let solution = (SolverBackend::DEFAULT.count_solutions(&self.initial, 2) == 1)
+            .then(|| find_solution(&self.initial).ok())
+            .flatten();
+        let correct = |state: &SudokuState| {
+            solution.map_or_else(
+                || get_all_conflicting_cells(state).is_empty(),
+                |solution| count_mistakes(state, &solution) == 0,
+            )
+        };
+        let moves = self.moves();
+        let Some(last_correct) = moves.iter().rposition(correct) else {
+            return false;
+        };
+        let last_correct =
+            last_correct.max(self.trial.map_or(0, |start| self.history.depth(start.node)));
+        if last_correct + 1 == moves.len() {
+            return false;
+        }
+        let last = self.current();
+        for _ in last_correct + 1..moves.len() {
+            self.history.undo();
+        }
+        self.click_changed(&last);
+        true
+    }
+
+    /// Erases every value of a digit that is not a clue, in one move.
+    fn erase_digit(&mut self, digit: u8) -> bool {
+        let mut current = self.current();
+        let mut erased = false;
+        for (cell, &clue) in current.iter_mut().zip(&self.initial) {
+            if digit != 0 && clue == 0 && *cell == digit {
+                *cell = 0;
+                erased = true;
+            }
+        }
+        if erased {
+            self.push(current);
+        }
+        erased
+    }
+
+    /// Fills every empty cell with a single candidate, in one move.
+    ///
+    /// Only the cells that are singles before the move are filled,
+    /// and a cell whose digit was already placed in a related single,
+    /// on a board without a solution, is skipped.
+    fn fill_singles(&mut self) -> bool {
+        let mut board = BitBoard::new(self.current());
+        let singles: Vec<(u8, u16)> = (0..81)
+            .map(|index| (index, board.candidates(index)))
+            .filter(|(_, candidates)| candidates.is_power_of_two())
+            .collect();
+        let mut filled = false;
+        for (index, candidates) in singles {
+            if board.candidates(index) == candidates {
+                let digit = (1..=9).find(|digit| candidates & (1 << (digit - 1)) != 0);
+                board.set(index, digit.unwrap_or_default());
+                filled = true;
+            }
+        }
+        if filled {
+            self.push(*board.cells());
+        }
+        filled
+    }
+
+    /// Fills a cell with a hint, after erasing the conflicting cells.
+    ///
+    /// The hint is found on a copy of the board without the conflicting
+    /// cells, so nothing is recorded unless there is a hint.
+    fn hint(&mut self) -> bool {
+        let conflicting = self.conflicting();
+        let mut cleared = self.current();
+        remove_conflicting_cells(&mut cleared, &conflicting);
+        if !cleared.contains(&0) {
+            return false;
+        }
+        let Ok(hinted) = get_hint(&cleared) else {
+            return false;
+        };
+        if !conflicting.is_empty() {
+            self.push(cleared);
+        }
+        self.push(hinted);
+        self.click_changed(&cleared);
+        true
+    }
+
+    /// Records a move, on a new branch if moves were undone.
+    fn push(&mut self, state: SudokuState) {
+        self.history.push(state);
+    }
+
+    /// Clicks the cell that differs between a state and the current one.
+    fn click_changed(&mut self, other: &SudokuState) {
+        if let Some(index) = find_changed_cell(other, &self.current()) {
+            self.clicked = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku, find_solution};
+
+    fn empty_cell(game: &GameState) -> u8 {
+        let index = game
+            .initial
+            .iter()
+            .position(|&value| value == 0)
+            .expect("the puzzle has an empty cell");
+        u8::try_from(index).expect("index fits in u8")
+    }
+
+    #[test]
+    fn test_place_undo_redo() {
+        let mut game = GameState::new(create_sudoku());
+        let index = empty_cell(&game);
+
+        assert!(game.apply(GameAction::Place { index, value: 5 }));
+        assert_eq!(game.current()[index as usize], 5);
+        assert!(!game.apply(GameAction::Place { index, value: 5 }));
+
+        assert!(game.apply(GameAction::Undo));
+        assert_eq!(game.current(), game.initial);
+        assert_eq!(game.clicked, index);
+        assert!(!game.apply(GameAction::Undo));
+
+        assert!(game.apply(GameAction::Redo));
+        assert_eq!(game.current()[index as usize], 5);
+        assert!(!game.apply(GameAction::Redo));
+
+        // a new move keeps the undone ones on another branch
+        assert!(game.apply(GameAction::Undo));
+        assert!(!game.apply(GameAction::Erase(index)));
+        assert!(game.apply(GameAction::Place { index, value: 3 }));
+        assert!(!game.apply(GameAction::Redo));
+        assert_eq!(game.history.node_count(), 3);
+        assert!(game.apply(GameAction::GoTo(1)));
+        assert_eq!(game.current()[index as usize], 5);
+        assert!(!game.apply(GameAction::GoTo(1)));
+    }
+
+    #[test]
+    fn test_clues_are_not_placed() {
+        let mut game = GameState::new(create_sudoku());
+        let clue = game
+            .initial
+            .iter()
+            .position(|&value| value != 0)
+            .expect("the puzzle has a clue");
+        let index = u8::try_from(clue).expect("index fits in u8");
+
+        assert!(!game.apply(GameAction::Erase(index)));
+        assert!(!game.apply(GameAction::Place {
+            index: 90,
+            value: 1
+        }));
+        assert_eq!(game.moves().len(), 1);
+    }
+
+    #[test]
+    fn test_erase_digit() {
+        let mut game = GameState::new(create_sudoku());
+        let empty: Vec<u8> = (0..81)
+            .filter(|&index| game.initial[index as usize] == 0)
+            .collect();
+        assert!(!game.apply(GameAction::EraseDigit(4)));
+
+        game.apply(GameAction::Place {
+            index: empty[0],
+            value: 4,
+        });
+        game.apply(GameAction::Place {
+            index: empty[1],
+            value: 4,
+        });
+        game.apply(GameAction::Place {
+            index: empty[2],
+            value: 7,
+        });
+        assert!(game.apply(GameAction::EraseDigit(4)));
+        let current = game.current();
+        assert_eq!(current[empty[0] as usize], 0);
+        assert_eq!(current[empty[1] as usize], 0);
+        assert_eq!(current[empty[2] as usize], 7);
+        // the clues keep the digit
+        for (&clue, &value) in game.initial.iter().zip(&current) {
+            assert!(clue == 0 || clue == value);
+        }
+
+        // a single move is undone
+        assert!(game.apply(GameAction::Undo));
+        assert_eq!(game.current()[empty[1] as usize], 4);
+        assert!(!game.apply(GameAction::EraseDigit(0)));
+    }
+
+    #[test]
+    fn test_undo_mistakes() {
+        let puzzle = create_sudoku();
+        let solution = find_solution(&puzzle).expect("solvable");
+        let mut game = GameState::new(puzzle);
+        let empty: Vec<u8> = (0..81)
+            .filter(|&index| puzzle[index as usize] == 0)
+            .collect();
+        let right = |index: u8| solution[index as usize];
+        let wrong = |index: u8| right(index) % 9 + 1;
+        assert!(!game.apply(GameAction::UndoMistakes));
+
+        game.apply(GameAction::Place {
+            index: empty[0],
+            value: right(empty[0]),
+        });
+        game.apply(GameAction::Place {
+            index: empty[1],
+            value: wrong(empty[1]),
+        });
+        game.apply(GameAction::Place {
+            index: empty[2],
+            value: right(empty[2]),
+        });
+        assert!(game.apply(GameAction::UndoMistakes));
+        assert_eq!(game.moves().len(), 2);
+        assert_eq!(game.current()[empty[0] as usize], right(empty[0]));
+        assert_eq!(game.clicked, empty[1]);
+        assert!(!game.apply(GameAction::UndoMistakes));
+
+        // the undone moves are redone one at a time
+        assert!(game.apply(GameAction::Redo));
+        assert_eq!(game.current()[empty[1] as usize], wrong(empty[1]));
+        assert!(game.apply(GameAction::Redo));
+        assert!(!game.apply(GameAction::Redo));
+    }
+
+    #[test]
+    fn test_restore() {
+        let puzzle = create_sudoku();
+        let mut game = GameState::new(puzzle);
+        let index = empty_cell(&game);
+        game.apply(GameAction::Place { index, value: 1 });
+        let snapshot = game.current();
+        game.apply(GameAction::Erase(index));
+        game.apply(GameAction::Undo);
+        assert!(!game.apply(GameAction::Restore(snapshot)));
+
+        game.apply(GameAction::Place { index, value: 2 });
+        game.clicked = 90;
+        assert!(game.apply(GameAction::Restore(snapshot)));
+        assert_eq!(game.current(), snapshot);
+        assert_eq!(game.clicked, index);
+        assert_eq!(game.moves().len(), 4);
+        // restoring is a move, which can be undone
+        assert!(game.apply(GameAction::Undo));
+        assert_eq!(game.current()[index as usize], 2);
+
+        // a snapshot of another puzzle is not restored
+        let clue = (0..81).find(|&index| puzzle[index] != 0).expect("a clue");
+        let mut other = snapshot;
+        other[clue] = 0;
+        assert!(!game.apply(GameAction::Restore(other)));
+    }
+
+    #[test]
+    fn test_trial() {
+        let mut game = GameState::new(create_sudoku());
+        let index = empty_cell(&game);
+        assert!(!game.apply(GameAction::CommitTrial));
+        assert!(!game.apply(GameAction::DiscardTrial));
+
+        game.apply(GameAction::Place { index, value: 1 });
+        let before = game.current();
+        assert!(game.apply(GameAction::StartTrial));
+        assert!(!game.apply(GameAction::StartTrial));
+        assert!(game.trial_cells().is_empty());
+        // the board the trial started from cannot be undone
+        assert!(!game.apply(GameAction::Undo));
+
+        game.apply(GameAction::Place { index, value: 2 });
+        game.apply(GameAction::Place { index, value: 3 });
+        assert!(game.trial_cells().contains(&index));
+        // nor can the moves before the trial be visited
+        assert!(!game.apply(GameAction::GoTo(0)));
+        game.clicked = 90;
+        assert!(game.apply(GameAction::DiscardTrial));
+        assert_eq!(game.current(), before);
+        assert_eq!(game.moves().len(), 2);
+        assert!(!game.history.can_redo());
+        assert_eq!(game.history.node_count(), 2);
+        assert_eq!(game.trial, None);
+        assert_ne!(game.clicked, 90);
+
+        // the kept moves stay in the history
+        game.apply(GameAction::StartTrial);
+        game.apply(GameAction::Place { index, value: 4 });
+        assert!(game.apply(GameAction::CommitTrial));
+        assert!(game.trial_cells().is_empty());
+        assert!(game.apply(GameAction::Undo));
+        assert_eq!(game.current(), before);
+    }
+
+    #[test]
+    fn test_fill_singles() {
+        let mut solution = find_solution(&create_sudoku()).expect("solvable");
+        solution[0] = 0;
+        solution[40] = 0;
+        solution[80] = 0;
+        let mut game = GameState::new(solution);
+        assert!(game.apply(GameAction::FillSingles));
+        assert!(!game.current().contains(&0));
+        assert_eq!(game.moves().len(), 2);
+        assert!(!game.apply(GameAction::FillSingles));
+
+        // an empty board has no singles
+        let mut game = GameState::new([0; 81]);
+        assert!(!game.apply(GameAction::FillSingles));
+        assert_eq!(game.moves().len(), 1);
+    }
+
+    #[test]
+    fn test_select() {
+        let mut game = GameState::new(create_sudoku());
+        assert!(!game.mutable());
+        assert!(game.related().is_empty());
+
+        let index = empty_cell(&game);
+        assert!(game.apply(GameAction::Select(index)));
+        assert!(game.mutable());
+        assert_eq!(game.related(), get_related_cells(index));
+    }
+
+    #[test]
+    fn test_annotate() {
+        let mut game = GameState::new(create_sudoku());
+        assert!(!game.apply(GameAction::Annotate("the puzzle".to_string())));
+
+        let index = empty_cell(&game);
+        game.apply(GameAction::Place { index, value: 5 });
+        assert!(game.apply(GameAction::Annotate("guessing 5 here".to_string())));
+        assert!(!game.apply(GameAction::Annotate("guessing 5 here".to_string())));
+        assert_eq!(
+            game.history.note(game.history.current()),
+            Some("guessing 5 here")
+        );
+    }
+
+    #[test]
+    fn test_hint_removes_conflicts() {
+        let mut game = GameState::new(create_sudoku());
+        let index = empty_cell(&game);
+        let related = get_related_cells(index);
+        let clue = related
+            .iter()
+            .map(|&cell| game.initial[cell as usize])
+            .find(|&value| value != 0)
+            .expect("a related cell is a clue");
+        assert!(game.apply(GameAction::Place { index, value: clue }));
+        assert!(!game.conflicting().is_empty());
+
+        assert!(game.apply(GameAction::Hint));
+        assert!(game.conflicting().is_empty());
+        assert_ne!(game.current()[game.clicked as usize], 0);
+    }
+
+    #[test]
+    fn test_failed_hint_keeps_the_board() {
+        let puzzle = create_sudoku();
+        let solution = find_solution(&puzzle).expect("a solution");
+        let empty: Vec<u8> = (0..81)
+            .filter(|&index| puzzle[index as usize] == 0)
+            .collect();
+        let fits = |board: &SudokuState, index: u8, value: u8| {
+            crate::utils::get_candidates(board, index) & (1 << (value - 1)) != 0
+        };
+
+        // a wrong digit that conflicts with nothing, so there is no solution
+        let mut board = puzzle;
+        let (wrong, value) = empty
+            .iter()
+            .find_map(|&index| {
+                (1..=9)
+                    .find(|&value| value != solution[index as usize] && fits(&puzzle, index, value))
+                    .map(|value| (index, value))
+            })
+            .expect("a cell with two candidates");
+        board[wrong as usize] = value;
+        // and two equal digits in a row, which conflict with each other
+        let (first, second, value) = empty
+            .iter()
+            .flat_map(|&first| empty.iter().map(move |&second| (first, second)))
+            .filter(|&(first, second)| {
+                first < second && first / 9 == second / 9 && first != wrong && second != wrong
+            })
+            .find_map(|(first, second)| {
+                (1..=9)
+                    .find(|&value| fits(&board, first, value) && fits(&board, second, value))
+                    .map(|value| (first, second, value))
+            })
+            .expect("two cells of a row with a common candidate");
+        board[first as usize] = value;
+        board[second as usize] = value;
+
+        let mut game = GameState::new(puzzle);
+        assert!(game.apply(GameAction::Restore(board)));
+        assert_eq!(game.conflicting(), vec![first, second]);
+        let before = game.clone();
+        assert!(!game.apply(GameAction::Hint));
+        assert_eq!(game, before);
+    }
+
+    #[test]
+    fn test_new_game_and_load() {
+        let mut game = GameState::new(create_sudoku());
+        let index = empty_cell(&game);
+        game.apply(GameAction::Place { index, value: 1 });
+
+        let puzzle = create_sudoku();
+        assert!(game.apply(GameAction::NewGame(puzzle)));
+        assert_eq!(game, GameState::new(puzzle));
+
+        let mut moved = puzzle;
+        moved[empty_cell(&game) as usize] = 2;
+        let mut notes = [0; 81];
+        notes[0] = 0b101;
+        game.clicked = 0;
+        game.apply(GameAction::StartTrial);
+        assert!(game.apply(GameAction::Load {
+            moves: vec![puzzle, moved],
+            notes
+        }));
+        assert_eq!(game.current(), moved);
+        assert_eq!(game.notes, notes);
+        // nothing of the previous game is left
+        assert_eq!((game.clicked, game.trial), (90, None));
+        assert!(!game.apply(GameAction::Load {
+            moves: vec![],
+            notes
+        }));
+
+        // a resumed game keeps every branch
+        let mut history = game.history.clone();
+        history.undo();
+        let mut other = puzzle;
+        other[empty_cell(&game) as usize] = 3;
+        history.push(other);
+        assert!(game.apply(GameAction::NewGame(create_sudoku())));
+        assert!(game.apply(GameAction::Resume {
+            history: history.clone(),
+            notes
+        }));
+        assert_eq!(game.initial, puzzle);
+        assert_eq!(game.history, history);
+        assert_eq!(game.notes, notes);
+    }
+
+    #[test]
+    fn test_notes() {
+        let mut game = GameState::new(create_sudoku());
+        let index = empty_cell(&game);
+        assert!(!game.has_progress());
+
+        assert!(game.apply(GameAction::ToggleNote { index, digit: 3 }));
+        assert!(game.apply(GameAction::ToggleNote { index, digit: 5 }));
+        assert_eq!(game.notes[index as usize], 0b1_0100);
+        assert!(game.has_progress());
+        assert!(game.apply(GameAction::ToggleNote { index, digit: 3 }));
+        assert_eq!(game.notes[index as usize], 0b1_0000);
+        // notes are not moves
+        assert_eq!(game.moves().len(), 1);
+
+        assert!(game.apply(GameAction::ToggleNote { index, digit: 0 }));
+        assert!(!game.apply(GameAction::ToggleNote { index, digit: 0 }));
+        assert!(!game.apply(GameAction::ToggleNote { index, digit: 10 }));
+        let clue = game
+            .initial
+            .iter()
+            .position(|&value| value != 0)
+            .expect("the puzzle has a clue");
+        let clue = u8::try_from(clue).expect("index fits in u8");
+        assert!(!game.apply(GameAction::ToggleNote {
+            index: clue,
+            digit: 1
+        }));
+
+        let mut notes = [0; 81];
+        notes[index as usize] = 0x1FF;
+        assert!(game.apply(GameAction::SetNotes(notes)));
+        assert!(!game.apply(GameAction::SetNotes(notes)));
+        assert!(game.apply(GameAction::NewGame(game.initial)));
+        assert_eq!(game.notes, [0; 81]);
+    }
+}
@@ -0,0 +1,304 @@
+//! # Generator Module
+//!
+//! The `generator` module generates the puzzles of a given difficulty for
+//! the new games.
+//!
+//! Hard puzzles are rare, so finding one can take many attempts,
+//! which low-end devices are slow to make.
+//! The puzzle is first searched locally for [`LOCAL_BUDGET`] milliseconds.
+//! If none is found by then, it is asked to the server with the
+//! `generate_puzzle` server function, available with the `hydrate` and
+//! `server` features of the fullstack app.
+//! When the server cannot be reached, such as offline or in the static
+//! app, a puzzle of the curated [`bank`](crate::bank) is picked instead.
+//!
+//! So new games start at once, even offline,
+//! a [`PuzzleQueue`] of puzzles of every difficulty is generated ahead of
+//! time and kept in the local storage.
+//! The queue is filled in the background with [`fill_queue`],
+//! which searches for [`BACKGROUND_BUDGET`] milliseconds at a time,
+//! locally only, so the app stays responsive.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Error, Result};
+#[cfg(any(feature = "server", feature = "hydrate"))]
+use dioxus::prelude::*;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::app::SudokuState;
+use crate::bank::pick_bank_puzzle;
+use crate::browser::{local_storage_get, local_storage_set, now};
+use crate::difficulty::{create_puzzle_until, create_puzzle_with_rng, Difficulty};
+use crate::formats::{parse_line, to_line};
+use crate::rng::GameRng;
+#[cfg(any(feature = "server", feature = "hydrate"))]
+use crate::utils::validate_puzzle;
+
+/// Time spent searching a puzzle locally before asking the server,
+/// in milliseconds
+pub const LOCAL_BUDGET: u64 = 500;
+
+/// Time spent searching a puzzle of the [`PuzzleQueue`] at once,
+/// in milliseconds, short enough to go unnoticed while playing
+pub const BACKGROUND_BUDGET: u64 = 50;
+
+/// Number of puzzles generated ahead of time for every difficulty
+pub const QUEUE_SIZE: usize = 2;
+
+/// Local storage key of the [`PuzzleQueue`]
+const QUEUE_KEY: &str = "puzzle-queue";
+
+/// The puzzles generated ahead of time, by difficulty
+///
+/// The puzzles are kept in the line format, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleQueue(pub BTreeMap<Difficulty, Vec<String>>);
+
+impl PuzzleQueue {
+    /// Takes the oldest puzzle of a difficulty out of the queue.
+    ///
+    /// ## Parameters
+    ///
+    /// - `difficulty: Difficulty` - The difficulty of the puzzle.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the puzzle, or `None` if there is no puzzle of the difficulty.
+    /// Puzzles that cannot be read are dropped.
+    pub fn pop(&mut self, difficulty: Difficulty) -> Option<SudokuState> {
+        let puzzles = self.0.get_mut(&difficulty)?;
+        while !puzzles.is_empty() {
+            if let Ok(puzzle) = parse_line(&puzzles.remove(0)) {
+                return Some(puzzle);
+            }
+        }
+        None
+    }
+
+    /// Adds a puzzle of a difficulty to the queue.
+    ///
+    /// ## Parameters
+    ///
+    /// - `difficulty: Difficulty` - The difficulty of the puzzle.
+    /// - `puzzle: &SudokuState` - The puzzle.
+    pub fn push(&mut self, difficulty: Difficulty, puzzle: &SudokuState) {
+        self.0
+            .entry(difficulty)
+            .or_default()
+            .push(to_line(puzzle, '.'));
+    }
+
+    /// Returns the easiest difficulty with fewer than [`QUEUE_SIZE`] puzzles,
+    /// or `None` if the queue is full.
+    #[must_use]
+    pub fn missing(&self) -> Option<Difficulty> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| self.0.get(difficulty).map_or(0, Vec::len) < QUEUE_SIZE)
+    }
+}
+
+/// Loads the [`PuzzleQueue`] from the local storage.
+///
+/// ## Returns
+///
+/// Returns the stored queue, or an empty one if none is stored or it cannot
+/// be read.
+#[must_use]
+pub fn load_queue() -> PuzzleQueue {
+    local_storage_get(QUEUE_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the [`PuzzleQueue`] in the local storage.
+///
+/// ## Parameters
+///
+/// - `queue: &PuzzleQueue` - The queue to store.
+pub fn store_queue(queue: &PuzzleQueue) {
+    match serde_json::to_string(queue) {
+        Ok(json) => local_storage_set(QUEUE_KEY, &json),
+        Err(err) => log::error!("failed to store the puzzle queue: {err:?}"),
+    }
+}
+
+/// Generates a puzzle of a given difficulty on the server.
+///
+/// The puzzle is searched like
+/// [`create_puzzle_with_rng`] with a generator seeded from `seed`,
+/// so the same seed always gives the same puzzle.
+///
+/// ## Parameters
+///
+/// - `difficulty: Difficulty` - The difficulty of the puzzle.
+/// - `seed: u64` - The seed of the random number generator.
+///
+/// ## Returns
+///
+/// Returns the 81 cells of the puzzle.
+///
+/// ## Errors
+///
+/// Returns an error if the server cannot be reached.
+#[cfg(any(feature = "server", feature = "hydrate"))]
+#[server]
+#[allow(clippy::unused_async)]
+pub async fn generate_puzzle(difficulty: Difficulty, seed: u64) -> Result<Vec<u8>, ServerFnError> {
+    // arrays of 81 cells are not serializable, vectors are
+    Ok(create_puzzle_with_rng(difficulty, &mut GameRng::seeded(seed)).to_vec())
+}
+
+/// Asks the server for a puzzle of a given difficulty.
+///
+/// ## Errors
+///
+/// Returns an error if the server cannot be reached or answers with an
+/// invalid puzzle.
+#[cfg(any(feature = "server", feature = "hydrate"))]
+async fn fetch_puzzle(difficulty: Difficulty, seed: u64) -> Result<SudokuState> {
+    let cells = generate_puzzle(difficulty, seed)
+        .await
+        .map_err(|err| Error::msg(format!("The server did not generate a puzzle: {err}")))?;
+    let puzzle = SudokuState::try_from(cells.as_slice())
+        .map_err(|_| Error::msg("The server puzzle does not have 81 cells"))?;
+    validate_puzzle(&puzzle)?;
+    Ok(puzzle)
+}
+
+/// Asks the server for a puzzle of a given difficulty.
+///
+/// ## Errors
+///
+/// Always returns an error, since the static app has no server.
+#[cfg(not(any(feature = "server", feature = "hydrate")))]
+#[allow(clippy::unused_async)]
+async fn fetch_puzzle(_difficulty: Difficulty, _seed: u64) -> Result<SudokuState> {
+    Err(Error::msg("The app has no server"))
+}
+
+/// Searches a puzzle of a given difficulty for [`LOCAL_BUDGET`]
+/// milliseconds, then asks the server for one.
+///
+/// ## Errors
+///
+/// Returns an error if no puzzle is found in time and the server cannot be
+/// reached.
+async fn search(difficulty: Difficulty, rng: &mut GameRng) -> Result<SudokuState> {
+    let deadline = now().saturating_add(LOCAL_BUDGET);
+    if let Some(puzzle) = create_puzzle_until(difficulty, rng, || now() >= deadline) {
+        return Ok(puzzle);
+    }
+    fetch_puzzle(difficulty, rng.next_u64()).await
+}
+
+/// Generates a new Sudoku puzzle of a given difficulty,
+/// on the server if it takes too long locally.
+///
+/// A puzzle of the [`PuzzleQueue`] is taken if there is one.
+/// Otherwise, the puzzle is searched locally for [`LOCAL_BUDGET`]
+/// milliseconds, then asked to the server.
+/// If the server cannot be reached, a puzzle of the
+/// [`bank`](crate::bank) is picked, or searched locally again if the bank
+/// has none of the difficulty.
+///
+/// ## Parameters
+///
+/// - `difficulty: Difficulty` - The difficulty of the puzzle.
+///
+/// ## Returns
+///
+/// Returns a `SudokuState`, which represents a 9x9 Sudoku puzzle.
+pub async fn generate(difficulty: Difficulty) -> SudokuState {
+    let mut queue = load_queue();
+    if let Some(puzzle) = queue.pop(difficulty) {
+        store_queue(&queue);
+        return puzzle;
+    }
+    let mut rng = GameRng::new();
+    match search(difficulty, &mut rng).await {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            log::info!("picking the puzzle from the bank: {err}");
+            pick_bank_puzzle(difficulty, &mut rng)
+                .unwrap_or_else(|| create_puzzle_with_rng(difficulty, &mut rng))
+        }
+    }
+}
+
+/// The outcome of a [`fill_queue`] search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueFill {
+    /// A puzzle was added to the queue
+    Added,
+    /// No puzzle was found in time, the search can go on later
+    Searching,
+    /// The queue is full
+    Full,
+}
+
+/// Searches a puzzle for the [`PuzzleQueue`] and stores it.
+///
+/// The puzzle is searched for the easiest difficulty missing puzzles,
+/// locally and for [`BACKGROUND_BUDGET`] milliseconds only,
+/// unlike the [`LOCAL_BUDGET`] of [`generate`],
+/// so it does not freeze the app.
+/// The server is never asked, since no one is waiting for the puzzle.
+///
+/// ## Returns
+///
+/// Returns whether a puzzle was added, none was found in time,
+/// or the queue is full.
+#[must_use]
+pub fn fill_queue() -> QueueFill {
+    let Some(difficulty) = load_queue().missing() else {
+        return QueueFill::Full;
+    };
+    let deadline = now().saturating_add(BACKGROUND_BUDGET);
+    let Some(puzzle) = create_puzzle_until(difficulty, &mut GameRng::new(), || now() >= deadline)
+    else {
+        return QueueFill::Searching;
+    };
+    let mut queue = load_queue();
+    queue.push(difficulty, &puzzle);
+    store_queue(&queue);
+    QueueFill::Added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_sudoku;
+
+    #[test]
+    fn test_puzzle_queue() {
+        let mut queue = PuzzleQueue::default();
+        assert_eq!(queue.missing(), Some(Difficulty::Easy));
+        assert_eq!(queue.pop(Difficulty::Easy), None);
+
+        let first = create_sudoku();
+        let second = create_sudoku();
+        queue.push(Difficulty::Easy, &first);
+        queue.push(Difficulty::Easy, &second);
+        assert_eq!(queue.missing(), Some(Difficulty::Medium));
+
+        let json = serde_json::to_string(&queue).expect("serialize");
+        let mut queue: PuzzleQueue = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(queue.pop(Difficulty::Easy), Some(first));
+        assert_eq!(queue.pop(Difficulty::Easy), Some(second));
+        assert_eq!(queue.pop(Difficulty::Easy), None);
+    }
+
+    #[test]
+    fn test_unreadable_puzzles_are_dropped() {
+        let puzzle = create_sudoku();
+        let mut queue = PuzzleQueue::default();
+        queue
+            .0
+            .insert(Difficulty::Hard, vec!["not a puzzle".to_string()]);
+        queue.push(Difficulty::Hard, &puzzle);
+        assert_eq!(queue.pop(Difficulty::Hard), Some(puzzle));
+    }
+}
@@ -0,0 +1,75 @@
+//! # Haptics Module
+//!
+//! The `haptics` module contains the [`Haptic`] feedback of the game,
+//! short vibrations for placements, conflicts and completion on devices
+//! that support the Vibration API.
+//!
+//! Haptics can be turned off in the [`Settings`](crate::settings::Settings).
+
+use crate::app::SudokuState;
+use crate::utils::{get_all_conflicting_cells, is_solved};
+
+/// A haptic feedback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Haptic {
+    /// A value was placed
+    Placement,
+    /// A value was placed that conflicts with another one
+    Conflict,
+    /// The puzzle was solved
+    Completion,
+}
+
+impl Haptic {
+    /// Returns the feedback of placing a value.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to the board after the placement.
+    #[must_use]
+    pub fn of_placement(board: &SudokuState) -> Self {
+        if is_solved(board) {
+            Self::Completion
+        } else if get_all_conflicting_cells(board).is_empty() {
+            Self::Placement
+        } else {
+            Self::Conflict
+        }
+    }
+
+    /// Returns the vibration pattern of the feedback,
+    /// as alternating vibration and pause durations in milliseconds.
+    #[must_use]
+    pub const fn pattern(self) -> &'static [u32] {
+        match self {
+            Self::Placement => &[10],
+            Self::Conflict => &[40, 60, 40],
+            Self::Completion => &[60, 40, 60, 40, 120],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::parse_line;
+    use crate::utils::find_solution;
+
+    #[test]
+    fn test_of_placement() {
+        let puzzle = parse_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .expect("valid puzzle");
+
+        let mut board = puzzle;
+        board[2] = 4;
+        assert_eq!(Haptic::of_placement(&board), Haptic::Placement);
+
+        board[2] = 5;
+        assert_eq!(Haptic::of_placement(&board), Haptic::Conflict);
+
+        let solution = find_solution(&puzzle).expect("solution");
+        assert_eq!(Haptic::of_placement(&solution), Haptic::Completion);
+    }
+}
@@ -0,0 +1,198 @@
+//! # History Module
+//!
+//! The `history` module stores the history of moves of a game as deltas,
+//! the cells changed by every move,
+//! instead of a whole board per move.
+//!
+//! A move usually changes a single cell, so a [`MoveDelta`] takes a few
+//! bytes where a board takes 81,
+//! which keeps the [`UndoTree`](crate::undo_tree::UndoTree) of the game,
+//! and the [`save`](crate::save) files and slots, compact.
+//! The boards are reconstructed from the deltas with [`from_deltas`].
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::SudokuState;
+
+/// A change of the value of a cell
+///
+/// - `index: u8`: the index of the cell, from 0 to 80.
+/// - `old: u8`: the value before the change, where 0 is an empty cell.
+/// - `new: u8`: the value after the change.
+///
+/// It is serialized as `[index, old, new]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "[u8; 3]", into = "[u8; 3]")]
+pub struct CellChange {
+    pub index: u8,
+    pub old: u8,
+    pub new: u8,
+}
+
+impl From<[u8; 3]> for CellChange {
+    fn from([index, old, new]: [u8; 3]) -> Self {
+        Self { index, old, new }
+    }
+}
+
+impl From<CellChange> for [u8; 3] {
+    fn from(change: CellChange) -> Self {
+        [change.index, change.old, change.new]
+    }
+}
+
+/// The cells changed by a move
+pub type MoveDelta = Vec<CellChange>;
+
+/// Computes the cells changed between two boards.
+///
+/// ## Parameters
+///
+/// - `previous: &SudokuState` - The board before the move.
+/// - `current: &SudokuState` - The board after the move.
+///
+/// ## Returns
+///
+/// Returns the changed cells, in the order of the board.
+///
+/// ## Panics
+///
+/// The function will panic if cannot convert a cell index into a `u8`.
+#[must_use]
+pub fn diff(previous: &SudokuState, current: &SudokuState) -> MoveDelta {
+    previous
+        .iter()
+        .zip(current)
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(index, (&old, &new))| CellChange {
+            index: u8::try_from(index).expect("cannot convert from u8"),
+            old,
+            new,
+        })
+        .collect()
+}
+
+/// Applies a move to a board.
+///
+/// ## Parameters
+///
+/// - `board: &mut SudokuState` - The board before the move,
+///   changed in place.
+/// - `delta: &[CellChange]` - The cells changed by the move.
+///
+/// ## Errors
+///
+/// Returns an error if a change is not on the board, has a value other than
+/// 0 to 9, or does not start from the value of its cell.
+pub fn apply_delta(board: &mut SudokuState, delta: &[CellChange]) -> Result<()> {
+    for change in delta {
+        let cell = board
+            .get_mut(change.index as usize)
+            .ok_or_else(|| Error::msg("The move changes a cell outside the board"))?;
+        if *cell != change.old || change.new > 9 {
+            return Err(Error::msg("The move does not fit the board"));
+        }
+        *cell = change.new;
+    }
+    Ok(())
+}
+
+/// Computes the deltas of a history of boards.
+///
+/// ## Parameters
+///
+/// - `moves: &[SudokuState]` - The boards, starting at the puzzle.
+///
+/// ## Returns
+///
+/// Returns the [`MoveDelta`] of every board after the first.
+#[must_use]
+pub fn to_deltas(moves: &[SudokuState]) -> Vec<MoveDelta> {
+    moves
+        .windows(2)
+        .map(|pair| diff(&pair[0], &pair[1]))
+        .collect()
+}
+
+/// Reconstructs a history of boards from its deltas.
+///
+/// ## Parameters
+///
+/// - `initial: SudokuState` - The puzzle.
+/// - `deltas: &[MoveDelta]` - The deltas of the moves, as computed by
+///   [`to_deltas`].
+///
+/// ## Returns
+///
+/// Returns a Result containing the boards, starting at the puzzle.
+///
+/// ## Errors
+///
+/// Returns an error if a delta does not fit the board it is applied to.
+pub fn from_deltas(initial: SudokuState, deltas: &[MoveDelta]) -> Result<Vec<SudokuState>> {
+    let mut board = initial;
+    let mut moves = vec![initial];
+    for delta in deltas {
+        apply_delta(&mut board, delta)?;
+        moves.push(board);
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_sudoku;
+
+    #[test]
+    fn test_round_trip() {
+        let initial = create_sudoku();
+        let empty: Vec<usize> = (0..81).filter(|&idx| initial[idx] == 0).collect();
+        let mut first = initial;
+        first[empty[0]] = 4;
+        let mut second = first;
+        second[empty[0]] = 0;
+        second[empty[1]] = 7;
+        let moves = vec![initial, first, second];
+
+        let deltas = to_deltas(&moves);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].len(), 1);
+        assert_eq!(deltas[1].len(), 2);
+        assert_eq!(from_deltas(initial, &deltas).expect("reconstruct"), moves);
+    }
+
+    #[test]
+    fn test_mismatched_delta() {
+        let initial = create_sudoku();
+        let clue = (0..81).find(|&idx| initial[idx] != 0).expect("clue");
+        let change = CellChange {
+            index: u8::try_from(clue).expect("index fits in u8"),
+            old: 0,
+            new: 1,
+        };
+        assert!(from_deltas(initial, &[vec![change]]).is_err());
+
+        let outside = CellChange {
+            index: 81,
+            old: 0,
+            new: 1,
+        };
+        assert!(from_deltas(initial, &[vec![outside]]).is_err());
+    }
+
+    #[test]
+    fn test_serialized_as_triples() {
+        let change = CellChange {
+            index: 10,
+            old: 0,
+            new: 5,
+        };
+        assert_eq!(
+            serde_json::to_string(&change).expect("serialize"),
+            "[10,0,5]"
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! # Hot Seat Module
+//!
+//! The `hotseat` module keeps the turns and the scores of the pass-and-play
+//! mode, where two players take turns placing digits on the same board.
+//!
+//! Every cell a player fills is theirs, until they erase it or fill it
+//! again, and only they can change it.
+//! Placing a digit passes the turn, erasing one does not.
+//! A player scores a point for every cell of theirs that matches the
+//! solution, so a wrong digit scores nothing.
+//!
+//! Undoing, hints and the bulk fills would play for the other player,
+//! so they are not allowed during the game, see [`HotSeat::allows`].
+
+use std::fmt;
+
+use crate::app::SudokuState;
+use crate::game::GameAction;
+
+/// A player of the pass-and-play mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Player {
+    /// The player who starts
+    #[default]
+    One,
+    /// The other player
+    Two,
+}
+
+impl Player {
+    /// Returns the other player.
+    #[must_use]
+    pub const fn other(self) -> Self {
+        match self {
+            Self::One => Self::Two,
+            Self::Two => Self::One,
+        }
+    }
+
+    /// Returns the name of the player in the stylesheet.
+    #[must_use]
+    pub const fn css_name(self) -> &'static str {
+        match self {
+            Self::One => "player-one",
+            Self::Two => "player-two",
+        }
+    }
+}
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::One => "Player 1",
+            Self::Two => "Player 2",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A game of the pass-and-play mode
+///
+/// - `puzzle: SudokuState`: the puzzle of the game.
+/// - `board: SudokuState`: the last board recorded.
+/// - `turn: Player`: the player placing the next digit.
+/// - `owners: [Option<Player>; 81]`: the player who filled every cell,
+///   if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotSeat {
+    pub puzzle: SudokuState,
+    pub board: SudokuState,
+    pub turn: Player,
+    pub owners: [Option<Player>; 81],
+}
+
+impl HotSeat {
+    /// Starts a game from a board, whose filled cells belong to no one.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: SudokuState` - The puzzle of the game.
+    /// - `board: SudokuState` - The current board.
+    #[must_use]
+    pub const fn new(puzzle: SudokuState, board: SudokuState) -> Self {
+        Self {
+            puzzle,
+            board,
+            turn: Player::One,
+            owners: [None; 81],
+        }
+    }
+
+    /// Records the cells changed since the last board recorded.
+    ///
+    /// The cells filled belong to the player whose turn it is,
+    /// and the turn passes if any was filled.
+    /// The cells erased belong to no one again.
+    /// The game starts over for another puzzle.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: &SudokuState` - The puzzle of the game.
+    /// - `board: &SudokuState` - The current board.
+    pub fn record(&mut self, puzzle: &SudokuState, board: &SudokuState) {
+        if self.puzzle != *puzzle {
+            *self = Self::new(*puzzle, *board);
+            return;
+        }
+        let mut placed = false;
+        for (index, owner) in self.owners.iter_mut().enumerate() {
+            if self.board[index] == board[index] {
+                continue;
+            }
+            if board[index] == 0 {
+                *owner = None;
+            } else {
+                *owner = Some(self.turn);
+                placed = true;
+            }
+        }
+        if placed {
+            self.turn = self.turn.other();
+        }
+        self.board = *board;
+    }
+
+    /// Returns whether the player whose turn it is can change a cell:
+    /// the cell is theirs or belongs to no one.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: u8` - The index of the cell.
+    #[must_use]
+    pub fn can_change(&self, index: u8) -> bool {
+        self.owners
+            .get(index as usize)
+            .is_some_and(|owner| owner.is_none_or(|owner| owner == self.turn))
+    }
+
+    /// Returns whether an action is allowed during the game.
+    ///
+    /// The player whose turn it is only changes the cells they can change,
+    /// see [`HotSeat::can_change`].
+    /// Undoing, redoing, going back in the undo tree, trials, bookmarks,
+    /// hints and filling the singles are not allowed,
+    /// since they would change the cells of the other player or play for
+    /// them.
+    ///
+    /// ## Parameters
+    ///
+    /// - `action: &GameAction` - The action.
+    #[must_use]
+    pub fn allows(&self, action: &GameAction) -> bool {
+        match action {
+            GameAction::Select(_)
+            | GameAction::Annotate(_)
+            | GameAction::SetNotes(_)
+            | GameAction::NewGame(_)
+            | GameAction::Load { .. }
+            | GameAction::Resume { .. } => true,
+            GameAction::Place { index, .. }
+            | GameAction::Erase(index)
+            | GameAction::ToggleNote { index, .. } => self.can_change(*index),
+            GameAction::EraseDigit(digit) => (0..81_u8).all(|index| {
+                self.board[index as usize] != *digit
+                    || self.puzzle[index as usize] != 0
+                    || self.can_change(index)
+            }),
+            GameAction::FillSingles
+            | GameAction::Undo
+            | GameAction::Redo
+            | GameAction::UndoMistakes
+            | GameAction::Hint
+            | GameAction::Restore(_)
+            | GameAction::StartTrial
+            | GameAction::CommitTrial
+            | GameAction::DiscardTrial
+            | GameAction::GoTo(_) => false,
+        }
+    }
+
+    /// Returns the score of a player,
+    /// the number of their cells that match the solution.
+    ///
+    /// ## Parameters
+    ///
+    /// - `player: Player` - The player.
+    /// - `solution: &SudokuState` - The solution of the puzzle.
+    #[must_use]
+    pub fn score(&self, player: Player, solution: &SudokuState) -> usize {
+        (0..81)
+            .filter(|&index| {
+                self.owners[index] == Some(player) && self.board[index] == solution[index]
+            })
+            .count()
+    }
+
+    /// Returns the player with the highest score,
+    /// or `None` if the scores are tied.
+    ///
+    /// ## Parameters
+    ///
+    /// - `solution: &SudokuState` - The solution of the puzzle.
+    #[must_use]
+    pub fn leader(&self, solution: &SudokuState) -> Option<Player> {
+        let one = self.score(Player::One, solution);
+        let two = self.score(Player::Two, solution);
+        match one.cmp(&two) {
+            std::cmp::Ordering::Greater => Some(Player::One),
+            std::cmp::Ordering::Less => Some(Player::Two),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku, find_solution};
+
+    #[test]
+    fn test_allows() {
+        let puzzle = create_sudoku();
+        let game = HotSeat::new(puzzle, puzzle);
+        for action in [
+            GameAction::Undo,
+            GameAction::Redo,
+            GameAction::UndoMistakes,
+            GameAction::Hint,
+            GameAction::FillSingles,
+            GameAction::StartTrial,
+            GameAction::GoTo(0),
+        ] {
+            assert!(!game.allows(&action), "{action:?} is allowed");
+        }
+        assert!(game.allows(&GameAction::Select(0)));
+        assert!(game.allows(&GameAction::NewGame(puzzle)));
+    }
+
+    #[test]
+    fn test_turns_and_scores() {
+        let puzzle = create_sudoku();
+        let solution = find_solution(&puzzle).expect("a solution");
+        let empty: Vec<usize> = (0..81).filter(|&index| puzzle[index] == 0).collect();
+        let mut game = HotSeat::new(puzzle, puzzle);
+
+        // a correct digit scores, and passes the turn
+        let mut board = puzzle;
+        board[empty[0]] = solution[empty[0]];
+        game.record(&puzzle, &board);
+        assert_eq!(game.owners[empty[0]], Some(Player::One));
+        assert_eq!(game.turn, Player::Two);
+        assert_eq!(game.score(Player::One, &solution), 1);
+
+        // a wrong digit does not score
+        board[empty[1]] = solution[empty[1]] % 9 + 1;
+        game.record(&puzzle, &board);
+        assert_eq!(game.owners[empty[1]], Some(Player::Two));
+        assert_eq!(game.score(Player::Two, &solution), 0);
+        assert_eq!(game.leader(&solution), Some(Player::One));
+
+        // erasing frees the cell, and keeps the turn
+        board[empty[1]] = 0;
+        game.record(&puzzle, &board);
+        assert_eq!(game.owners[empty[1]], None);
+        assert_eq!(game.turn, Player::One);
+
+        // only the own cells can be changed
+        let cell = |n: usize| u8::try_from(empty[n]).expect("a cell index");
+        assert!(game.can_change(cell(0)));
+        board[empty[1]] = solution[empty[1]];
+        game.record(&puzzle, &board);
+        assert_eq!(game.turn, Player::Two);
+        assert!(!game.can_change(cell(0)));
+        assert!(game.can_change(cell(2)));
+        assert!(!game.allows(&GameAction::Erase(cell(1))));
+        assert!(!game.allows(&GameAction::Place {
+            index: cell(0),
+            value: 1,
+        }));
+        assert!(!game.allows(&GameAction::EraseDigit(solution[empty[0]])));
+        assert!(game.allows(&GameAction::Place {
+            index: cell(2),
+            value: 1,
+        }));
+
+        // another puzzle starts over
+        let other = create_sudoku();
+        game.record(&other, &other);
+        assert_eq!(game, HotSeat::new(other, other));
+        assert_eq!(game.leader(&solution), None);
+    }
+}
@@ -0,0 +1,241 @@
+//! # Interop Module
+//!
+//! The `interop` module exposes a small JavaScript API on `window.sudoku`,
+//! so the game can be scripted, or driven by other pages embedding it.
+//!
+//! ```js
+//! // start a new game, from the line format or a game link
+//! window.sudoku.loadPuzzle("53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79");
+//!
+//! // read the current game
+//! const { initial, current, notes, moves, solved } = window.sudoku.getState();
+//!
+//! // get notified of every move
+//! const id = window.sudoku.subscribe((state) => console.log(state.current));
+//! window.sudoku.unsubscribe(id);
+//! ```
+//!
+//! `loadPuzzle` throws if the puzzle is not recognized or is invalid.
+//! Boards are in the line format, with `.` for empty cells.
+//!
+//! The API is built with `wasm-bindgen` and only exists in the browser.
+
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use serde::Serialize;
+
+use crate::app::{SudokuNotes, SudokuState};
+use crate::components::import::use_import_game;
+use crate::encoding::GameSnapshot;
+use crate::formats::to_line;
+use crate::game::GameState;
+use crate::utils::is_solved;
+
+/// The state of the game returned by `getState` and sent to subscribers
+///
+/// - `initial: String`: the initial puzzle in the line format.
+/// - `current: String`: the current board in the line format.
+/// - `notes: Vec<u16>`: the notes of every cell as bitmasks.
+/// - `moves: usize`: the number of moves played.
+/// - `solved: bool`: whether the board is solved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiState {
+    pub initial: String,
+    pub current: String,
+    pub notes: Vec<u16>,
+    pub moves: usize,
+    pub solved: bool,
+}
+
+impl ApiState {
+    /// Creates the state of a game.
+    ///
+    /// ## Parameters
+    ///
+    /// - `initial: &SudokuState` - A reference to the initial puzzle.
+    /// - `moves: &[SudokuState]` - The history of boards, starting at the
+    ///   initial puzzle.
+    /// - `notes: &SudokuNotes` - A reference to the notes of the board.
+    #[must_use]
+    pub fn new(initial: &SudokuState, moves: &[SudokuState], notes: &SudokuNotes) -> Self {
+        let current = moves.last().unwrap_or(initial);
+        Self {
+            initial: to_line(initial, '.'),
+            current: to_line(current, '.'),
+            notes: notes.to_vec(),
+            moves: moves.len().saturating_sub(1),
+            solved: is_solved(current),
+        }
+    }
+}
+
+/// The `window.sudoku` JavaScript API
+///
+/// Keeps the latest [`ApiState`] and the subscribers to notify of new ones.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Default)]
+pub struct JsApi {
+    state: std::rc::Rc<std::cell::RefCell<String>>,
+    subscribers: std::rc::Rc<std::cell::RefCell<Vec<(u32, js_sys::Function)>>>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl JsApi {
+    /// Installs the API on `window.sudoku`.
+    ///
+    /// ## Parameters
+    ///
+    /// - `loads: UnboundedSender<GameSnapshot>` - Where the games loaded
+    ///   with `loadPuzzle` are sent.
+    #[must_use]
+    pub fn install(loads: UnboundedSender<GameSnapshot>) -> Self {
+        use js_sys::{Function, Object, Reflect, JSON};
+        use wasm_bindgen::closure::Closure;
+        use wasm_bindgen::JsValue;
+
+        use crate::formats::recognize_import;
+
+        let api = Self::default();
+        let Some(window) = web_sys::window() else {
+            return api;
+        };
+
+        let load_puzzle =
+            Closure::<dyn Fn(String) -> Result<(), JsValue>>::new(move |puzzle: String| {
+                match recognize_import(&puzzle) {
+                    Some(Ok(game)) => loads
+                        .unbounded_send(game)
+                        .map_err(|err| JsValue::from_str(&err.to_string())),
+                    Some(Err(err)) => Err(JsValue::from_str(&err.to_string())),
+                    None => Err(JsValue::from_str("Not a Sudoku puzzle")),
+                }
+            });
+
+        let state = std::rc::Rc::clone(&api.state);
+        let get_state = Closure::<dyn Fn() -> JsValue>::new(move || {
+            JSON::parse(&state.borrow()).unwrap_or(JsValue::NULL)
+        });
+
+        let subscribers = std::rc::Rc::clone(&api.subscribers);
+        let subscribe = Closure::<dyn Fn(Function) -> u32>::new(move |callback: Function| {
+            let mut subscribers = subscribers.borrow_mut();
+            let id = subscribers.iter().map(|(id, _)| id + 1).max().unwrap_or(0);
+            subscribers.push((id, callback));
+            id
+        });
+
+        let subscribers = std::rc::Rc::clone(&api.subscribers);
+        let unsubscribe = Closure::<dyn Fn(u32)>::new(move |id: u32| {
+            subscribers.borrow_mut().retain(|(other, _)| *other != id);
+        });
+
+        let object = Object::new();
+        for (name, function) in [
+            ("loadPuzzle", load_puzzle.as_ref()),
+            ("getState", get_state.as_ref()),
+            ("subscribe", subscribe.as_ref()),
+            ("unsubscribe", unsubscribe.as_ref()),
+        ] {
+            let _ = Reflect::set(&object, &JsValue::from_str(name), function);
+        }
+        let _ = Reflect::set(&window, &JsValue::from_str("sudoku"), &object);
+
+        // the API lives as long as the page
+        load_puzzle.forget();
+        get_state.forget();
+        subscribe.forget();
+        unsubscribe.forget();
+
+        api
+    }
+
+    /// Publishes a new state of the game, notifying the subscribers.
+    ///
+    /// ## Parameters
+    ///
+    /// - `state: &ApiState` - A reference to the new state.
+    pub fn publish(&self, state: &ApiState) {
+        let Ok(json) = serde_json::to_string(state) else {
+            return;
+        };
+        let Ok(value) = js_sys::JSON::parse(&json) else {
+            return;
+        };
+        *self.state.borrow_mut() = json;
+        // subscribers may unsubscribe while being notified
+        let subscribers = self.subscribers.borrow().clone();
+        for (_, callback) in subscribers {
+            let _ = callback.call1(&wasm_bindgen::JsValue::NULL, &value);
+        }
+    }
+}
+
+/// The `window.sudoku` JavaScript API
+///
+/// Does nothing outside of the browser.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Default)]
+pub struct JsApi;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl JsApi {
+    /// Installs the API on `window.sudoku`.
+    ///
+    /// A no-op outside of the browser.
+    #[must_use]
+    pub fn install(_loads: UnboundedSender<GameSnapshot>) -> Self {
+        Self
+    }
+
+    /// Publishes a new state of the game, notifying the subscribers.
+    ///
+    /// A no-op outside of the browser.
+    pub const fn publish(&self, _state: &ApiState) {}
+}
+
+/// Hook that installs the `window.sudoku` JavaScript API.
+///
+/// Games loaded through the API replace the current one, as any import,
+/// and every change of the game is published to the subscribers.
+pub fn use_js_api() {
+    // Unpack shared states
+    let game = use_context::<Signal<GameState>>();
+    let mut import_game = use_import_game();
+
+    let loads = use_coroutine(
+        move |mut loads: UnboundedReceiver<GameSnapshot>| async move {
+            while let Some(game) = loads.next().await {
+                import_game(game);
+            }
+        },
+    );
+    let api = use_hook(|| JsApi::install(loads.tx()));
+    // selecting a cell does not change the published state
+    let state = use_memo(move || {
+        let game = game.read();
+        ApiState::new(&game.initial, &game.moves(), &game.notes)
+    });
+
+    use_effect(move || api.publish(&state.read()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku, find_solution};
+
+    #[test]
+    fn test_api_state() {
+        let initial = create_sudoku();
+        let state = ApiState::new(&initial, &[initial], &[0; 81]);
+        assert_eq!(state.initial, state.current);
+        assert_eq!(state.moves, 0);
+        assert!(!state.solved);
+
+        let solution = find_solution(&initial).expect("solution");
+        let state = ApiState::new(&initial, &[initial, solution], &[0; 81]);
+        assert_eq!(state.current, to_line(&solution, '.'));
+        assert_eq!(state.moves, 1);
+        assert!(state.solved);
+    }
+}
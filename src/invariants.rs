@@ -0,0 +1,213 @@
+//! # Invariants Module
+//!
+//! The `invariants` module contains checks of the invariants of the board
+//! logic, the properties that hold for every board and every game:
+//!
+//! - every cell has 20 related cells, and is related to each of them;
+//! - conflicts are symmetric, and the [`BitBoard`] finds the same ones;
+//! - undoing a move and redoing it leaves the game as it was;
+//! - a hint never breaks a valid board.
+//!
+//! Every check returns an error describing the broken invariant,
+//! so they can be asserted on any board,
+//! such as the random boards and move sequences of the property tests.
+
+use anyhow::{Error, Result};
+
+use crate::app::SudokuState;
+use crate::bitboard::BitBoard;
+use crate::game::{GameAction, GameState};
+use crate::rng::GameRng;
+use crate::utils::{get_conflicting_cells, get_hint_with_rng, get_related_cells, validate_puzzle};
+
+/// Checks the related cells of a cell.
+///
+/// ## Parameters
+///
+/// - `index: u8` - The index of the cell, from 0 to 80.
+///
+/// ## Errors
+///
+/// Returns an error if the cell does not have 20 related cells,
+/// is related to itself, or is not related to one of its related cells.
+pub fn check_related_cells(index: u8) -> Result<()> {
+    let related = get_related_cells(index);
+    if related.len() != 20 {
+        return Err(Error::msg(format!(
+            "Cell {index} has {} related cells",
+            related.len()
+        )));
+    }
+    if related.contains(&index) {
+        return Err(Error::msg(format!("Cell {index} is related to itself")));
+    }
+    if let Some(other) = related
+        .iter()
+        .find(|&&other| !get_related_cells(other).contains(&index))
+    {
+        return Err(Error::msg(format!(
+            "Cell {index} is related to {other}, but not the other way around"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the conflicts of a board.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - The board, which may have conflicts.
+///
+/// ## Errors
+///
+/// Returns an error if a cell conflicts with another that does not conflict
+/// with it, or if the [`BitBoard`] does not find the conflicting cells.
+pub fn check_conflicts(board: &SudokuState) -> Result<()> {
+    let mut conflicting = vec![];
+    for index in 0..81 {
+        for other in get_conflicting_cells(board, index) {
+            if !get_conflicting_cells(board, other).contains(&index) {
+                return Err(Error::msg(format!(
+                    "Cell {index} conflicts with {other}, but not the other way around"
+                )));
+            }
+            conflicting.push(index);
+        }
+    }
+    conflicting.dedup();
+    if BitBoard::new(*board).conflicting() != conflicting {
+        return Err(Error::msg("The bitboard finds other conflicting cells"));
+    }
+    Ok(())
+}
+
+/// Checks that undoing the last move of a game and redoing it leaves the
+/// game as it was.
+///
+/// ## Parameters
+///
+/// - `game: &GameState` - The game, which is not changed.
+///
+/// ## Errors
+///
+/// Returns an error if the undone move cannot be redone,
+/// or if the moves differ after redoing it.
+pub fn check_undo_redo(game: &GameState) -> Result<()> {
+    let mut replayed = game.clone();
+    if !replayed.apply(GameAction::Undo) {
+        return Ok(());
+    }
+    if !replayed.apply(GameAction::Redo) {
+        return Err(Error::msg("The undone move cannot be redone"));
+    }
+    if replayed.history != game.history {
+        return Err(Error::msg("Undoing and redoing a move changes the game"));
+    }
+    Ok(())
+}
+
+/// Checks that a hint keeps a valid board valid.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - The board, which is not checked if it is
+///   invalid or filled.
+/// - `rng: &mut GameRng` - The random number generator of the hint.
+///
+/// ## Errors
+///
+/// Returns an error if the hint does not fill exactly one empty cell,
+/// or if the board is invalid after the hint.
+pub fn check_hint(board: &SudokuState, rng: &mut GameRng) -> Result<()> {
+    if !board.contains(&0) || validate_puzzle(board).is_err() {
+        return Ok(());
+    }
+    let hinted = get_hint_with_rng(board, rng)?;
+    let changed: Vec<usize> = (0..81).filter(|&idx| board[idx] != hinted[idx]).collect();
+    if changed.len() != 1 || board[changed[0]] != 0 {
+        return Err(Error::msg("The hint does not fill exactly one empty cell"));
+    }
+    validate_puzzle(&hinted).map_err(|_| Error::msg("The hint breaks the board"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku_with_rng, find_solution};
+    use proptest::prelude::*;
+
+    /// A puzzle generated from a seed, with some of its empty cells
+    /// filled from its solution
+    fn valid_board() -> impl Strategy<Value = SudokuState> {
+        (any::<u64>(), prop::collection::vec(0..81_usize, 0..40)).prop_map(|(seed, filled)| {
+            let mut board = create_sudoku_with_rng(&mut GameRng::seeded(seed));
+            let solution = find_solution(&board).expect("the puzzle has a solution");
+            for index in filled {
+                board[index] = solution[index];
+            }
+            board
+        })
+    }
+
+    /// Any board, with or without conflicts
+    fn any_board() -> impl Strategy<Value = SudokuState> {
+        prop::collection::vec(0..=9_u8, 81).prop_map(|cells| {
+            let mut board = [0; 81];
+            board.copy_from_slice(&cells);
+            board
+        })
+    }
+
+    /// An action that does not depend on a random choice
+    fn action() -> impl Strategy<Value = GameAction> {
+        prop_oneof![
+            (0..81_u8, 0..=9_u8).prop_map(|(index, value)| GameAction::Place { index, value }),
+            (0..81_u8).prop_map(GameAction::Erase),
+            (1..=9_u8).prop_map(GameAction::EraseDigit),
+            Just(GameAction::FillSingles),
+            (0..91_u8).prop_map(GameAction::Select),
+            Just(GameAction::Undo),
+            Just(GameAction::Redo),
+            Just(GameAction::UndoMistakes),
+            any_board().prop_map(GameAction::Restore),
+            Just(GameAction::StartTrial),
+            Just(GameAction::CommitTrial),
+            Just(GameAction::DiscardTrial),
+            (0..40_usize).prop_map(GameAction::GoTo),
+        ]
+    }
+
+    #[test]
+    fn test_related_cells() {
+        for index in 0..81 {
+            check_related_cells(index).expect("related cells");
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_conflicts(board in any_board()) {
+            prop_assert!(check_conflicts(&board).is_ok());
+        }
+
+        #[test]
+        fn test_undo_redo(
+            seed in any::<u64>(),
+            actions in prop::collection::vec(action(), 0..40),
+        ) {
+            let mut game = GameState::new(create_sudoku_with_rng(&mut GameRng::seeded(seed)));
+            for action in actions {
+                game.apply(action);
+                prop_assert!(check_undo_redo(&game).is_ok());
+                prop_assert!(check_conflicts(&game.current()).is_ok());
+            }
+        }
+
+        #[test]
+        fn test_hint(board in valid_board(), seed in any::<u64>()) {
+            prop_assert!(check_hint(&board, &mut GameRng::seeded(seed)).is_ok());
+        }
+    }
+}
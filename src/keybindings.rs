@@ -0,0 +1,253 @@
+//! # Keybindings Module
+//!
+//! The `keybindings` module contains the registry of the keyboard
+//! shortcuts of the game.
+//!
+//! Every shortcut is a [`Keybinding`] in [`KEYBINDINGS`],
+//! which maps keys to an [`Action`].
+//! The same registry dispatches the key presses and lists the shortcuts in
+//! the shortcuts overlay, so the two never disagree.
+
+/// A direction to move the selection on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Returns the index of the cell next to a cell in the direction.
+    ///
+    /// The selection stays on the edges of the board.
+    /// If no cell is selected, i.e. the index is not on the board,
+    /// the top-left cell is returned.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: u8` - The index of the cell, from 0 to 80.
+    #[must_use]
+    pub const fn step(self, index: u8) -> u8 {
+        if index >= 81 {
+            return 0;
+        }
+        let (row, col) = (index / 9, index % 9);
+        let (row, col) = match self {
+            Self::Up => (row.saturating_sub(1), col),
+            Self::Down => (if row < 8 { row + 1 } else { row }, col),
+            Self::Left => (row, col.saturating_sub(1)),
+            Self::Right => (row, if col < 8 { col + 1 } else { col }),
+        };
+        row * 9 + col
+    }
+}
+
+/// An action triggered by a keyboard shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Enter the digit of the key, as the number pad does
+    Digit,
+    /// Erase the selected cell
+    Erase,
+    /// Move the selection
+    Move(Direction),
+    /// Undo the last move
+    Undo,
+    /// Redo the last undone move
+    Redo,
+    /// Switch the selected cell between value and note entry
+    ToggleNotes,
+    /// Fill a cell with a hint
+    Hint,
+    /// Show or hide the heatmap of the empty cells
+    ToggleHeatmap,
+    /// Start a new puzzle of the same difficulty, in speedrun mode
+    QuickRestart,
+    /// Open or close the shortcuts overlay
+    Shortcuts,
+}
+
+impl Action {
+    /// Returns what the action does, as listed in the shortcuts overlay.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Digit => "Enter a digit",
+            Self::Erase => "Erase the cell",
+            Self::Move(Direction::Up) => "Move up",
+            Self::Move(Direction::Down) => "Move down",
+            Self::Move(Direction::Left) => "Move left",
+            Self::Move(Direction::Right) => "Move right",
+            Self::Undo => "Undo",
+            Self::Redo => "Redo",
+            Self::ToggleNotes => "Toggle note entry",
+            Self::Hint => "Hint",
+            Self::ToggleHeatmap => "Show or hide the heatmap",
+            Self::QuickRestart => "New puzzle of the same difficulty, in speedrun mode",
+            Self::Shortcuts => "Show or hide the shortcuts",
+        }
+    }
+}
+
+/// A keyboard shortcut
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keybinding {
+    /// The keys, as the `key` of a `KeyboardEvent`,
+    /// prefixed with `Ctrl+` if the Control or Command key is held
+    pub keys: &'static [&'static str],
+    /// How the keys are shown in the shortcuts overlay
+    pub label: &'static str,
+    /// The action of the keys
+    pub action: Action,
+}
+
+/// All the keyboard shortcuts of the game, in the order they are listed
+pub const KEYBINDINGS: &[Keybinding] = &[
+    Keybinding {
+        keys: &["1", "2", "3", "4", "5", "6", "7", "8", "9"],
+        label: "1 – 9",
+        action: Action::Digit,
+    },
+    Keybinding {
+        keys: &["0", "Backspace", "Delete"],
+        label: "0, Backspace, Delete",
+        action: Action::Erase,
+    },
+    Keybinding {
+        keys: &["ArrowUp"],
+        label: "↑",
+        action: Action::Move(Direction::Up),
+    },
+    Keybinding {
+        keys: &["ArrowDown"],
+        label: "↓",
+        action: Action::Move(Direction::Down),
+    },
+    Keybinding {
+        keys: &["ArrowLeft"],
+        label: "←",
+        action: Action::Move(Direction::Left),
+    },
+    Keybinding {
+        keys: &["ArrowRight"],
+        label: "→",
+        action: Action::Move(Direction::Right),
+    },
+    Keybinding {
+        keys: &["Ctrl+z", "u"],
+        label: "Ctrl+Z, U",
+        action: Action::Undo,
+    },
+    Keybinding {
+        keys: &["Ctrl+y", "Ctrl+Z", "r"],
+        label: "Ctrl+Y, Ctrl+Shift+Z, R",
+        action: Action::Redo,
+    },
+    Keybinding {
+        keys: &["n"],
+        label: "N",
+        action: Action::ToggleNotes,
+    },
+    Keybinding {
+        keys: &["h"],
+        label: "H",
+        action: Action::Hint,
+    },
+    Keybinding {
+        keys: &["m"],
+        label: "M",
+        action: Action::ToggleHeatmap,
+    },
+    Keybinding {
+        keys: &["R"],
+        label: "Shift+R",
+        action: Action::QuickRestart,
+    },
+    Keybinding {
+        keys: &["?"],
+        label: "?",
+        action: Action::Shortcuts,
+    },
+];
+
+/// Returns the action of a key, if it is a shortcut.
+///
+/// ## Parameters
+///
+/// - `key: &str` - The key, as in [`Keybinding::keys`].
+#[must_use]
+pub fn action_for_key(key: &str) -> Option<Action> {
+    KEYBINDINGS
+        .iter()
+        .find(|binding| binding.keys.contains(&key))
+        .map(|binding| binding.action)
+}
+
+/// Returns all the keys that are shortcuts.
+#[must_use]
+pub fn shortcut_keys() -> Vec<&'static str> {
+    KEYBINDINGS
+        .iter()
+        .flat_map(|binding| binding.keys.iter().copied())
+        .collect()
+}
+
+/// Returns how the keys of an action are shown, if it has a shortcut.
+///
+/// ## Parameters
+///
+/// - `action: Action` - The action.
+#[must_use]
+pub fn shortcut_label(action: Action) -> Option<&'static str> {
+    KEYBINDINGS
+        .iter()
+        .find(|binding| binding.action == action)
+        .map(|binding| binding.label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_for_key() {
+        assert_eq!(action_for_key("5"), Some(Action::Digit));
+        assert_eq!(action_for_key("Backspace"), Some(Action::Erase));
+        assert_eq!(
+            action_for_key("ArrowLeft"),
+            Some(Action::Move(Direction::Left))
+        );
+        assert_eq!(action_for_key("Ctrl+z"), Some(Action::Undo));
+        assert_eq!(action_for_key("Ctrl+Z"), Some(Action::Redo));
+        assert_eq!(action_for_key("?"), Some(Action::Shortcuts));
+        assert_eq!(action_for_key("m"), Some(Action::ToggleHeatmap));
+        assert_eq!(action_for_key("R"), Some(Action::QuickRestart));
+        assert_eq!(action_for_key("x"), None);
+    }
+
+    #[test]
+    fn test_shortcut_label() {
+        assert_eq!(shortcut_label(Action::Hint), Some("H"));
+        assert_eq!(shortcut_label(Action::Undo), Some("Ctrl+Z, U"));
+    }
+
+    #[test]
+    fn test_keys_are_unique() {
+        let keys = shortcut_keys();
+        for (index, key) in keys.iter().enumerate() {
+            assert!(!keys[index + 1..].contains(key), "{key} is bound twice");
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        assert_eq!(Direction::Right.step(90), 0);
+        assert_eq!(Direction::Up.step(4), 4);
+        assert_eq!(Direction::Down.step(4), 13);
+        assert_eq!(Direction::Left.step(9), 9);
+        assert_eq!(Direction::Right.step(9), 10);
+        assert_eq!(Direction::Down.step(76), 76);
+        assert_eq!(Direction::Right.step(80), 80);
+    }
+}
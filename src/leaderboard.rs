@@ -0,0 +1,575 @@
+//! # Leaderboard Module
+//!
+//! The `leaderboard` module ranks the times of the daily puzzle,
+//! so a player can compare their time with everyone else's.
+//!
+//! Submitting a time is optional, and done with the `submit_daily_time`
+//! server function, available with the `hydrate` and `server` features of
+//! the fullstack app.
+//! Only signed in accounts submit times, once a day each,
+//! along with the solved board,
+//! which the server checks with [`check_solve`] before ranking the time.
+//! The server verifies the session of the account with the auth server
+//! set in the [`AUTH_SERVER_ENV`] environment variable,
+//! see [`verify_session`](crate::auth::verify_session),
+//! so a time is tied to the account the auth server knows.
+//! The server keeps the [`Leaderboards`] of the days that take times in
+//! the JSON file set in the [`LEADERBOARDS_ENV`] environment variable,
+//! so they outlive restarts,
+//! and answers with the [`DailyStanding`] of the player:
+//! their rank, their percentile and the [`TOP_SIZE`] fastest times.
+//!
+//! The standings are kept in a [`StandingCache`] in the local storage of
+//! every [`Profile`](crate::profiles::Profile),
+//! so they can be viewed offline.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Error, Result};
+#[cfg(any(feature = "server", feature = "hydrate"))]
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::app::SudokuState;
+#[cfg(any(feature = "server", feature = "hydrate"))]
+use crate::auth::Session;
+use crate::browser::{local_storage_get, local_storage_set};
+use crate::daily::DAY_MILLIS;
+use crate::profiles::profile_key;
+use crate::utils::{create_daily_sudoku, is_solved};
+
+/// How many of the fastest times a standing lists
+pub const TOP_SIZE: usize = 10;
+
+/// How many characters a name on the leaderboard keeps,
+/// the rest is dropped
+pub const MAX_NAME_CHARS: usize = 24;
+
+/// The fastest time a daily puzzle can be solved in, in milliseconds,
+/// faster times are refused
+pub const MIN_SOLVE_MILLIS: u64 = 20_000;
+
+/// How long before midnight in UTC the earliest time zone starts a day,
+/// in milliseconds
+const EARLIEST_OFFSET_MILLIS: u64 = 14 * 60 * 60 * 1000;
+
+/// How many days of standings are kept in the [`StandingCache`]
+const CACHED_DAYS: usize = 7;
+
+/// Local storage key of the [`StandingCache`]
+const STANDINGS_KEY: &str = "daily-standings";
+
+/// Environment variable of the URL of the auth server,
+/// which verifies the sessions of the submitted times on the server
+pub const AUTH_SERVER_ENV: &str = "SUDOKU_AUTH_SERVER";
+
+/// Environment variable of the path of the file the server keeps the
+/// [`Leaderboards`] in, `leaderboards.json` if not set
+pub const LEADERBOARDS_ENV: &str = "SUDOKU_LEADERBOARDS";
+
+/// A time on the leaderboard
+///
+/// - `name: String`: the name of the player.
+/// - `millis: u64`: the time the puzzle was solved in, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub millis: u64,
+}
+
+/// The times of the daily puzzle of a day
+///
+/// - `entries: Vec<LeaderboardEntry>`: the times, fastest first.
+/// - `accounts: BTreeSet<String>`: the accounts that submitted a time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub entries: Vec<LeaderboardEntry>,
+    accounts: BTreeSet<String>,
+}
+
+impl Leaderboard {
+    /// Adds the time of an account to the leaderboard.
+    ///
+    /// The name is trimmed and cut to [`MAX_NAME_CHARS`],
+    /// and is "Anonymous" if empty.
+    ///
+    /// ## Parameters
+    ///
+    /// - `account: &str` - The email of the account, compared ignoring case.
+    /// - `name: &str` - The name of the player.
+    /// - `millis: u64` - The time the puzzle was solved in, in milliseconds.
+    ///
+    /// ## Returns
+    ///
+    /// Returns a Result containing the rank of the time, from 1,
+    /// after the equal times submitted before.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the account already submitted a time.
+    pub fn submit(&mut self, account: &str, name: &str, millis: u64) -> Result<usize> {
+        if !self.accounts.insert(account.trim().to_lowercase()) {
+            return Err(Error::msg("The account already submitted a time today"));
+        }
+        let name: String = name.trim().chars().take(MAX_NAME_CHARS).collect();
+        let name = if name.is_empty() {
+            "Anonymous".to_string()
+        } else {
+            name
+        };
+        let index = self.entries.partition_point(|entry| entry.millis <= millis);
+        self.entries
+            .insert(index, LeaderboardEntry { name, millis });
+        Ok(index + 1)
+    }
+
+    /// Returns the standing of a rank on the leaderboard.
+    ///
+    /// ## Parameters
+    ///
+    /// - `day: u64` - The day of the daily puzzle.
+    /// - `rank: usize` - The rank of the player, from 1.
+    #[must_use]
+    pub fn standing(&self, day: u64, rank: usize) -> DailyStanding {
+        DailyStanding {
+            day,
+            rank,
+            total: self.entries.len(),
+            top: self.entries.iter().take(TOP_SIZE).cloned().collect(),
+        }
+    }
+}
+
+/// The leaderboards of the days that take times, by day
+///
+/// Only the daily puzzles of today and yesterday, in UTC, take times,
+/// so every time zone can submit its day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboards(pub BTreeMap<u64, Leaderboard>);
+
+impl Leaderboards {
+    /// Adds the time of an account to the leaderboard of a day,
+    /// with [`Leaderboard::submit`],
+    /// and drops the leaderboards of the days that do not take times anymore.
+    ///
+    /// ## Parameters
+    ///
+    /// - `day: u64` - The day of the daily puzzle.
+    /// - `today: u64` - The current day, in UTC.
+    /// - `account: &str` - The email of the account.
+    /// - `name: &str` - The name of the player.
+    /// - `millis: u64` - The time the puzzle was solved in, in milliseconds.
+    ///
+    /// ## Returns
+    ///
+    /// Returns a Result containing the [`DailyStanding`] of the time.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the day does not take times
+    /// or the account already submitted a time.
+    pub fn submit(
+        &mut self,
+        day: u64,
+        today: u64,
+        account: &str,
+        name: &str,
+        millis: u64,
+    ) -> Result<DailyStanding> {
+        self.0.retain(|&other, _| other + 1 >= today);
+        if day + 1 < today || day > today {
+            return Err(Error::msg("The leaderboard of this day is closed"));
+        }
+        let leaderboard = self.0.entry(day).or_default();
+        let rank = leaderboard.submit(account, name, millis)?;
+        Ok(leaderboard.standing(day, rank))
+    }
+}
+
+/// Checks that a time was spent solving the daily puzzle of a day.
+///
+/// The board must be a solution of the daily puzzle,
+/// and the time no faster than [`MIN_SOLVE_MILLIS`]
+/// nor longer than the day has lasted in the earliest time zone.
+///
+/// ## Parameters
+///
+/// - `day: u64` - The day of the daily puzzle.
+/// - `board: &SudokuState` - The solved board.
+/// - `millis: u64` - The time the puzzle was solved in, in milliseconds.
+/// - `now: u64` - The current time, in milliseconds since the Unix epoch.
+///
+/// ## Errors
+///
+/// Returns an error if the time is impossible
+/// or the board does not solve the daily puzzle.
+pub fn check_solve(day: u64, board: &SudokuState, millis: u64, now: u64) -> Result<()> {
+    let started = (day * DAY_MILLIS).saturating_sub(EARLIEST_OFFSET_MILLIS);
+    if millis < MIN_SOLVE_MILLIS || millis > now.saturating_sub(started) {
+        return Err(Error::msg("The time is impossible"));
+    }
+    let puzzle = create_daily_sudoku(day);
+    let keeps_givens = puzzle
+        .iter()
+        .zip(board)
+        .all(|(&given, &value)| given == 0 || given == value);
+    if !keeps_givens || !is_solved(board) {
+        return Err(Error::msg("The board does not solve the daily puzzle"));
+    }
+    Ok(())
+}
+
+/// The standing of a player on the leaderboard of a day
+///
+/// - `day: u64`: the day of the daily puzzle.
+/// - `rank: usize`: the rank of the player, from 1.
+/// - `total: usize`: the number of times on the leaderboard.
+/// - `top: Vec<LeaderboardEntry>`: the [`TOP_SIZE`] fastest times,
+///   fastest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyStanding {
+    pub day: u64,
+    pub rank: usize,
+    pub total: usize,
+    pub top: Vec<LeaderboardEntry>,
+}
+
+impl DailyStanding {
+    /// Returns the percentage of the other players who were slower,
+    /// from 0 to 100, or 100 if the player is the only one.
+    #[must_use]
+    pub const fn percentile(&self) -> usize {
+        let others = self.total.saturating_sub(1);
+        if others == 0 {
+            return 100;
+        }
+        self.total.saturating_sub(self.rank) * 100 / others
+    }
+}
+
+/// The standings of a profile on the last days it submitted a time,
+/// by day
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StandingCache(pub BTreeMap<u64, DailyStanding>);
+
+impl StandingCache {
+    /// Returns the standing of a day, if it is cached.
+    ///
+    /// ## Parameters
+    ///
+    /// - `day: u64` - The day of the daily puzzle.
+    #[must_use]
+    pub fn get(&self, day: u64) -> Option<&DailyStanding> {
+        self.0.get(&day)
+    }
+
+    /// Caches a standing, dropping the oldest days beyond the last
+    /// [`CACHED_DAYS`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `standing: DailyStanding` - The standing to cache.
+    pub fn insert(&mut self, standing: DailyStanding) {
+        self.0.insert(standing.day, standing);
+        while self.0.len() > CACHED_DAYS {
+            self.0.pop_first();
+        }
+    }
+}
+
+/// Loads the cached standings of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`StandingCache`],
+/// or an empty one if none is stored or it cannot be read.
+#[must_use]
+pub fn load_standings(profile: u32) -> StandingCache {
+    local_storage_get(&profile_key(profile, STANDINGS_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the cached standings of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `cache: &StandingCache` - The standings to store.
+pub fn store_standings(profile: u32, cache: &StandingCache) {
+    match serde_json::to_string(cache) {
+        Ok(json) => local_storage_set(&profile_key(profile, STANDINGS_KEY), &json),
+        Err(err) => log::error!("failed to store the daily standings: {err:?}"),
+    }
+}
+
+/// The leaderboards of the server, loaded from their file on the first
+/// submission
+#[cfg(feature = "server")]
+static LEADERBOARDS: std::sync::Mutex<Option<Leaderboards>> = std::sync::Mutex::new(None);
+
+/// Returns the path of the file the server keeps the [`Leaderboards`] in,
+/// set in the [`LEADERBOARDS_ENV`] environment variable.
+#[cfg(feature = "server")]
+fn leaderboards_path() -> std::path::PathBuf {
+    std::env::var_os(LEADERBOARDS_ENV).map_or_else(|| "leaderboards.json".into(), Into::into)
+}
+
+/// Reads the [`Leaderboards`] from their file,
+/// or returns empty ones if there is no file yet.
+///
+/// ## Errors
+///
+/// Returns an error if the file cannot be read,
+/// so the leaderboards are not overwritten.
+#[cfg(feature = "server")]
+fn read_leaderboards(path: &std::path::Path) -> Result<Leaderboards> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Leaderboards::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Writes the [`Leaderboards`] to their file.
+///
+/// They are written to a temporary file first, which replaces the file,
+/// so a crash never leaves a partially written file.
+///
+/// ## Errors
+///
+/// Returns an error if the file cannot be written.
+#[cfg(feature = "server")]
+fn write_leaderboards(path: &std::path::Path, leaderboards: &Leaderboards) -> Result<()> {
+    let temporary = path.with_extension("json.tmp");
+    std::fs::write(&temporary, serde_json::to_string(leaderboards)?)?;
+    std::fs::rename(&temporary, path)?;
+    Ok(())
+}
+
+/// Submits a time to the leaderboard of a day, on the server.
+///
+/// The session token is verified with the auth server,
+/// and the time is ranked under the email it answers,
+/// once a day per account, with [`Leaderboards::submit`].
+/// The time is checked with [`check_solve`] first,
+/// and the leaderboards are written to their file before answering.
+///
+/// ## Parameters
+///
+/// - `day: u64` - The day of the daily puzzle.
+/// - `token: String` - The token of the session of the signed in account.
+/// - `name: String` - The name of the player.
+/// - `board: String` - The solved board, in the line format.
+/// - `millis: u64` - The time the puzzle was solved in, in milliseconds.
+///
+/// ## Returns
+///
+/// Returns the [`DailyStanding`] of the time.
+///
+/// ## Errors
+///
+/// Returns an error if the server cannot be reached,
+/// the session is not verified, the solve is refused,
+/// the day does not take times anymore,
+/// the account already submitted a time,
+/// or the leaderboards cannot be read or written.
+#[cfg(any(feature = "server", feature = "hydrate"))]
+#[server]
+pub async fn submit_daily_time(
+    day: u64,
+    token: String,
+    name: String,
+    board: String,
+    millis: u64,
+) -> Result<DailyStanding, ServerFnError> {
+    let now = crate::browser::now();
+    crate::formats::parse_line(&board)
+        .and_then(|board| check_solve(day, &board, millis, now))
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    let server = std::env::var(AUTH_SERVER_ENV)
+        .map_err(|_| ServerFnError::new("The leaderboard has no auth server"))?;
+    let account = crate::auth::verify_session(&server, &token)
+        .await
+        .map_err(|err| {
+            log::warn!("failed to verify a session: {err:?}");
+            ServerFnError::new("Sign in to submit a time")
+        })?;
+
+    let unavailable = |err: Error| {
+        log::error!("failed to keep the leaderboards: {err:?}");
+        ServerFnError::new("The leaderboards are unavailable")
+    };
+    let mut leaderboards = LEADERBOARDS
+        .lock()
+        .map_err(|_| ServerFnError::new("The leaderboards are unavailable"))?;
+    let path = leaderboards_path();
+    if leaderboards.is_none() {
+        *leaderboards = Some(read_leaderboards(&path).map_err(unavailable)?);
+    }
+    let Some(loaded) = leaderboards.as_mut() else {
+        return Err(ServerFnError::new("The leaderboards are unavailable"));
+    };
+    let standing = loaded
+        .submit(day, crate::daily::day_of(now), &account, &name, millis)
+        .map_err(|err| ServerFnError::new(err.to_string()))?;
+    write_leaderboards(&path, loaded).map_err(unavailable)?;
+    drop(leaderboards);
+    Ok(standing)
+}
+
+/// Submits the time of the daily puzzle of a day to its leaderboard.
+///
+/// ## Parameters
+///
+/// - `day: u64` - The day of the daily puzzle.
+/// - `session: Session` - The session of the signed in account,
+///   whose token the server verifies.
+/// - `name: String` - The name of the player.
+/// - `board: &SudokuState` - The solved board.
+/// - `millis: u64` - The time the puzzle was solved in, in milliseconds.
+///
+/// ## Errors
+///
+/// Returns an error if the server cannot be reached or refuses the time.
+#[cfg(any(feature = "server", feature = "hydrate"))]
+pub async fn submit_daily(
+    day: u64,
+    session: Session,
+    name: String,
+    board: &SudokuState,
+    millis: u64,
+) -> Result<DailyStanding> {
+    let board = crate::formats::to_line(board, '.');
+    submit_daily_time(day, session.token, name, board, millis)
+        .await
+        .map_err(|err| Error::msg(format!("The time was not submitted: {err}")))
+}
+
+/// Submits the time of the daily puzzle of a day to its leaderboard.
+///
+/// ## Errors
+///
+/// Always returns an error, since the static app has no server.
+#[cfg(not(any(feature = "server", feature = "hydrate")))]
+#[allow(clippy::unused_async)]
+pub async fn submit_daily(
+    _day: u64,
+    _session: crate::auth::Session,
+    _name: String,
+    _board: &SudokuState,
+    _millis: u64,
+) -> Result<DailyStanding> {
+    Err(Error::msg("The app has no server"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit() {
+        let mut leaderboard = Leaderboard::default();
+        assert_eq!(leaderboard.submit("ana@a.io", "Ana", 300_000).ok(), Some(1));
+        assert_eq!(leaderboard.submit("bo@b.io", "Bo", 200_000).ok(), Some(1));
+        assert_eq!(leaderboard.submit("c@c.io", "  ", 300_000).ok(), Some(3));
+        assert_eq!(leaderboard.entries[2].name, "Anonymous");
+        let long = "x".repeat(40);
+        assert_eq!(leaderboard.submit("x@x.io", &long, 100_000).ok(), Some(1));
+        assert_eq!(leaderboard.entries[0].name.len(), MAX_NAME_CHARS);
+
+        // once a day per account
+        assert!(leaderboard.submit(" Ana@A.io", "Ana", 1_000).is_err());
+        assert_eq!(leaderboard.entries.len(), 4);
+
+        for millis in 0..20 {
+            let _ = leaderboard.submit(&format!("{millis}@fast.io"), "Fast", millis);
+        }
+        let standing = leaderboard.standing(20_000, 24);
+        assert_eq!(standing.total, 24);
+        assert_eq!(standing.top.len(), TOP_SIZE);
+        assert_eq!(standing.top[0].millis, 0);
+    }
+
+    #[test]
+    fn test_leaderboards() {
+        let mut leaderboards = Leaderboards::default();
+        let standing = leaderboards
+            .submit(10, 10, "ana@a.io", "Ana", 300_000)
+            .expect("today takes times");
+        assert_eq!((standing.day, standing.rank), (10, 1));
+        assert!(leaderboards
+            .submit(9, 10, "ana@a.io", "Ana", 300_000)
+            .is_ok());
+        assert!(leaderboards
+            .submit(10, 10, "ana@a.io", "Ana", 1_000)
+            .is_err());
+
+        // the closed days take no times
+        assert!(leaderboards
+            .submit(8, 10, "bo@b.io", "Bo", 300_000)
+            .is_err());
+        assert!(leaderboards
+            .submit(11, 10, "bo@b.io", "Bo", 300_000)
+            .is_err());
+
+        // the accounts are kept with the times
+        let json = serde_json::to_string(&leaderboards).expect("serialize");
+        let mut loaded: Leaderboards = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(loaded, leaderboards);
+        assert!(loaded.submit(10, 10, "Ana@A.io", "Ana", 1_000).is_err());
+
+        // the closed days are dropped
+        assert!(loaded.submit(11, 11, "bo@b.io", "Bo", 300_000).is_ok());
+        assert_eq!(loaded.0.keys().copied().collect::<Vec<_>>(), vec![10, 11]);
+    }
+
+    #[test]
+    fn test_check_solve() {
+        let day = 20_000;
+        let now = day * DAY_MILLIS + 3_600_000;
+        let puzzle = create_daily_sudoku(day);
+        let solution = crate::utils::find_solution(&puzzle).expect("the daily puzzle is solvable");
+        assert!(check_solve(day, &solution, 300_000, now).is_ok());
+
+        // impossible times
+        assert!(check_solve(day, &solution, 0, now).is_err());
+        assert!(check_solve(day, &solution, MIN_SOLVE_MILLIS - 1, now).is_err());
+        assert!(check_solve(day, &solution, 16 * 3_600_000, now).is_err());
+
+        // not a solve of the daily puzzle
+        assert!(check_solve(day, &puzzle, 300_000, now).is_err());
+        let other = create_daily_sudoku(day + 1);
+        let other = crate::utils::find_solution(&other).expect("the daily puzzle is solvable");
+        assert!(check_solve(day, &other, 300_000, now).is_err());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let standing = |rank, total| DailyStanding {
+            day: 0,
+            rank,
+            total,
+            top: vec![],
+        };
+        assert_eq!(standing(1, 1).percentile(), 100);
+        assert_eq!(standing(1, 5).percentile(), 100);
+        assert_eq!(standing(5, 5).percentile(), 0);
+        assert_eq!(standing(3, 5).percentile(), 50);
+    }
+
+    #[test]
+    fn test_standing_cache() {
+        let mut cache = StandingCache::default();
+        for day in 0..10 {
+            cache.insert(Leaderboard::default().standing(day, 1));
+        }
+        assert_eq!(cache.0.len(), CACHED_DAYS);
+        assert!(cache.get(2).is_none());
+        assert_eq!(cache.get(9).map(|standing| standing.day), Some(9));
+    }
+}
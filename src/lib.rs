@@ -0,0 +1,102 @@
+//! # Sudoku App
+//!
+//! This crate is a Rust library and binary for generating and solving Sudoku
+//! puzzles.
+//! It provides functionality to create random Sudoku puzzles, solve them,
+//! and manipulate or interact with Sudoku boards in various ways.
+//!
+//! ## Features
+//!
+//! - Generate random Sudoku puzzles.
+//! - Solve Sudoku puzzles programmatically.
+//! - Render Sudoku puzzles as interactive boards in a web interface using Dioxus.
+//! - Generate, solve and rate puzzles from the command line,
+//!   see [`cli`].
+//! - Support for various difficulty levels and puzzle variants.
+//!
+//! ## Usage
+//!
+//! To render a Sudoku board in a web interface:
+//!
+//! ```rust
+//! use dioxus::prelude::*;
+//!
+//! use crate::components::board::SudokuBoard;
+//!
+//! pub fn App() -> Element {
+//!     rsx!(SudokuBoard { sudoku: None })
+//! }
+//! ```
+//!
+//! ## Dependecies
+//!
+//! Under the hood Sudoku app uses:
+//!
+//! - [Dioxus](https://dioxuslabs.com/):  fullstack, crossplatform, fast Apps.
+//! - [`sudoku`](https::/docs.rs/sudoku): sudoku solving and generator.
+//!
+//! ## Contributing
+//!
+//! Contributions to the are welcome!
+//! Whether it's bug fixes, feature implementations,
+//! or improvements to the documentation,
+//! feel free to create pull requests or open issues.
+//!
+//! ## License
+//!
+//! This crate is licensed under
+//! [MIT license](https://opensource.org/licenses/MIT).
+
+#![allow(non_snake_case)]
+
+pub mod analytics;
+pub mod animation;
+pub mod app;
+pub mod auth;
+pub mod backtrack;
+pub mod bank;
+pub mod bitboard;
+pub mod browser;
+pub mod challenge;
+pub mod charts;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+pub mod components;
+pub mod daily;
+pub mod difficulty;
+pub mod dlx;
+pub mod embed;
+pub mod encoding;
+pub mod formats;
+pub mod game;
+pub mod generator;
+pub mod haptics;
+pub mod history;
+pub mod hotseat;
+pub mod interop;
+pub mod invariants;
+pub mod keybindings;
+pub mod leaderboard;
+pub mod library;
+pub mod links;
+pub mod locale;
+pub mod logic;
+pub mod ocr;
+pub mod packs;
+pub mod profiles;
+pub mod rng;
+pub mod save;
+pub mod settings;
+pub mod speedrun;
+pub mod splits;
+pub mod stats;
+pub mod storage;
+pub mod symbols;
+pub mod sync;
+pub mod techniques;
+pub mod theme;
+pub mod toast;
+pub mod undo_tree;
+pub mod utils;
+pub mod validation;
+pub mod weekly;
@@ -0,0 +1,329 @@
+//! # Library Module
+//!
+//! The `library` module gathers every puzzle the app knows of in a single
+//! list, to be browsed and filtered:
+//! the curated [`bank`](crate::bank), the imported
+//! [`packs`](crate::packs), the games in progress, such as imported ones,
+//! and the history of completed games.
+//!
+//! A puzzle found in several places is listed once,
+//! from the first of them in that order,
+//! and its [`Completion`] comes from the progress of the active profile.
+//! Only classic puzzles exist, so they are not filtered by variant.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+
+use crate::app::SudokuState;
+use crate::difficulty::{grade_puzzle, Difficulty};
+use crate::packs::{PackProgress, PuzzlePack};
+use crate::save::SaveSlot;
+use crate::storage::GameRecord;
+
+/// Where a puzzle of the library comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleSource {
+    /// The curated puzzle bank
+    Bank,
+    /// A puzzle pack, with its id and the position of the puzzle
+    Pack(u32, usize),
+    /// A game in progress, with the id of its save slot
+    Slot(u32),
+    /// The history of completed games
+    History,
+}
+
+/// How far the active profile got with a puzzle of the library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Completion {
+    /// Never played
+    Unplayed,
+    /// Played, but not solved yet
+    InProgress,
+    /// Solved at least once
+    Solved,
+}
+
+impl Completion {
+    /// All the completion statuses, from the least to the most complete
+    pub const ALL: [Self; 3] = [Self::Unplayed, Self::InProgress, Self::Solved];
+
+    /// Returns the name of the CSS class of the status.
+    #[must_use]
+    pub const fn css_name(self) -> &'static str {
+        match self {
+            Self::Unplayed => "unplayed",
+            Self::InProgress => "in-progress",
+            Self::Solved => "solved",
+        }
+    }
+}
+
+impl fmt::Display for Completion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Unplayed => "Unplayed",
+            Self::InProgress => "In progress",
+            Self::Solved => "Solved",
+        })
+    }
+}
+
+/// A puzzle of the library
+///
+/// - `puzzle: SudokuState`: the initial puzzle.
+/// - `current: SudokuState`: the board of the game in progress,
+///   the solution once solved, or the puzzle if it was never played.
+/// - `difficulty: Difficulty`: the difficulty of the puzzle.
+/// - `source: PuzzleSource`: where the puzzle comes from.
+/// - `completion: Completion`: how far the active profile got with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibraryEntry {
+    pub puzzle: SudokuState,
+    pub current: SudokuState,
+    pub difficulty: Difficulty,
+    pub source: PuzzleSource,
+    pub completion: Completion,
+}
+
+/// The filters of the library, where `None` matches everything
+///
+/// - `difficulty: Option<Difficulty>`: the difficulty of the puzzles.
+/// - `completion: Option<Completion>`: how far the puzzles got.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LibraryFilter {
+    pub difficulty: Option<Difficulty>,
+    pub completion: Option<Completion>,
+}
+
+impl LibraryFilter {
+    /// Returns whether an entry of the library passes the filters.
+    ///
+    /// ## Parameters
+    ///
+    /// - `entry: &LibraryEntry` - The entry of the library.
+    #[must_use]
+    pub fn matches(&self, entry: &LibraryEntry) -> bool {
+        self.difficulty.is_none_or(|d| d == entry.difficulty)
+            && self.completion.is_none_or(|c| c == entry.completion)
+    }
+}
+
+/// Builds the library from all the sources of puzzles.
+///
+/// ## Parameters
+///
+/// - `bank: impl IntoIterator<Item = (Difficulty, SudokuState)>` - The
+///   puzzles of the bank, with their difficulty.
+/// - `packs: &[PuzzlePack]` - The imported packs.
+/// - `progress: &PackProgress` - The pack progress of the active profile.
+/// - `slots: &[SaveSlot]` - The games in progress of the active profile.
+/// - `records: &[GameRecord]` - The completed games of the active profile.
+///
+/// ## Returns
+///
+/// Returns an entry for every distinct puzzle,
+/// from the bank, the packs, the save slots and then the history.
+#[must_use]
+pub fn build_library(
+    bank: impl IntoIterator<Item = (Difficulty, SudokuState)>,
+    packs: &[PuzzlePack],
+    progress: &PackProgress,
+    slots: &[SaveSlot],
+    records: &[GameRecord],
+) -> Vec<LibraryEntry> {
+    let solved: BTreeMap<SudokuState, SudokuState> = records
+        .iter()
+        .map(|record| (record.game.initial, record.game.current()))
+        .collect();
+    let in_progress: BTreeMap<SudokuState, &SaveSlot> = slots
+        .iter()
+        .rev()
+        .map(|slot| (slot.game.initial, slot))
+        .collect();
+    let solved_in_packs: BTreeSet<SudokuState> = packs
+        .iter()
+        .flat_map(|pack| {
+            (0..pack.puzzles.len())
+                .filter(|&index| progress.is_solved(pack.id, index))
+                .filter_map(|index| pack.puzzle(index))
+        })
+        .collect();
+
+    let entry = |puzzle: SudokuState, difficulty: Difficulty, source: PuzzleSource| {
+        let (current, completion) = if let Some(&solution) = solved.get(&puzzle) {
+            (solution, Completion::Solved)
+        } else if solved_in_packs.contains(&puzzle) {
+            (puzzle, Completion::Solved)
+        } else if let Some(slot) = in_progress.get(&puzzle) {
+            (slot.game.current(), Completion::InProgress)
+        } else {
+            (puzzle, Completion::Unplayed)
+        };
+        LibraryEntry {
+            puzzle,
+            current,
+            difficulty,
+            source,
+            completion,
+        }
+    };
+
+    let packs = packs.iter().flat_map(|pack| {
+        (0..pack.puzzles.len()).filter_map(move |index| {
+            let puzzle = pack.puzzle(index)?;
+            let difficulty = pack.difficulty.unwrap_or_else(|| grade_puzzle(&puzzle));
+            Some((puzzle, difficulty, PuzzleSource::Pack(pack.id, index)))
+        })
+    });
+    let all = bank
+        .into_iter()
+        .map(|(difficulty, puzzle)| (puzzle, difficulty, PuzzleSource::Bank))
+        .chain(packs)
+        .chain(slots.iter().map(|slot| {
+            (
+                slot.game.initial,
+                slot.difficulty,
+                PuzzleSource::Slot(slot.id),
+            )
+        }))
+        .chain(records.iter().map(|record| {
+            (
+                record.game.initial,
+                record.difficulty,
+                PuzzleSource::History,
+            )
+        }));
+
+    let mut seen = HashSet::new();
+    all.filter(|&(puzzle, _, _)| seen.insert(puzzle))
+        .map(|(puzzle, difficulty, source)| entry(puzzle, difficulty, source))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::to_line;
+    use crate::save::SavedGame;
+    use crate::settings::Settings;
+    use crate::utils::create_sudoku;
+
+    fn saved_game(initial: SudokuState, current: SudokuState) -> SavedGame {
+        SavedGame {
+            initial,
+            moves: vec![initial, current],
+            notes: [0; 81],
+            timer: 0,
+            settings: Settings::default(),
+            move_notes: vec![],
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_build_library() {
+        let bank = create_sudoku();
+        let mut solution = bank;
+        if let Some(index) = solution.iter().position(|&value| value == 0) {
+            solution[index] = 1;
+        }
+        let slot = create_sudoku();
+        let mut current = slot;
+        if let Some(index) = current.iter().position(|&value| value == 0) {
+            current[index] = 2;
+        }
+        let slots = [SaveSlot {
+            id: 4,
+            difficulty: Difficulty::Hard,
+            game: saved_game(slot, current),
+            modified_at: 0,
+        }];
+        let records = [
+            GameRecord {
+                profile: 0,
+                difficulty: Difficulty::Easy,
+                finished_at: 0,
+                game: saved_game(bank, solution),
+                hints: 0,
+            },
+            GameRecord {
+                profile: 0,
+                difficulty: Difficulty::Hard,
+                finished_at: 0,
+                game: saved_game(slot, current),
+                hints: 0,
+            },
+        ];
+
+        let library = build_library(
+            [(Difficulty::Easy, bank)],
+            &[],
+            &PackProgress::default(),
+            &slots,
+            &records,
+        );
+        assert_eq!(library.len(), 2);
+        assert_eq!(library[0].source, PuzzleSource::Bank);
+        assert_eq!(library[0].completion, Completion::Solved);
+        assert_eq!(library[0].current, solution);
+        // a puzzle solved once, and now played again, is solved
+        assert_eq!(library[1].source, PuzzleSource::Slot(4));
+        assert_eq!(library[1].completion, Completion::Solved);
+
+        let library = build_library(
+            [(Difficulty::Easy, bank)],
+            &[],
+            &PackProgress::default(),
+            &slots,
+            &[],
+        );
+        assert_eq!(library[0].completion, Completion::Unplayed);
+        assert_eq!(library[1].completion, Completion::InProgress);
+        assert_eq!(library[1].current, current);
+    }
+
+    #[test]
+    fn test_pack_entries() {
+        let puzzle = create_sudoku();
+        let pack = PuzzlePack {
+            id: 2,
+            name: "Pack".to_string(),
+            author: String::new(),
+            difficulty: Some(Difficulty::Medium),
+            puzzles: vec![to_line(&puzzle, '.'); 2],
+        };
+        let mut progress = PackProgress::default();
+        progress.complete(2, 1);
+
+        // the same puzzle twice in a pack is listed once
+        let library = build_library([], &[pack], &progress, &[], &[]);
+        assert_eq!(library.len(), 1);
+        assert_eq!(library[0].source, PuzzleSource::Pack(2, 0));
+        assert_eq!(library[0].difficulty, Difficulty::Medium);
+        assert_eq!(library[0].completion, Completion::Solved);
+    }
+
+    #[test]
+    fn test_library_filter() {
+        let puzzle = create_sudoku();
+        let entry = LibraryEntry {
+            puzzle,
+            current: puzzle,
+            difficulty: Difficulty::Hard,
+            source: PuzzleSource::Bank,
+            completion: Completion::Unplayed,
+        };
+        assert!(LibraryFilter::default().matches(&entry));
+        let hard = LibraryFilter {
+            difficulty: Some(Difficulty::Hard),
+            completion: None,
+        };
+        assert!(hard.matches(&entry));
+        let solved = LibraryFilter {
+            completion: Some(Completion::Solved),
+            ..hard
+        };
+        assert!(!solved.matches(&entry));
+    }
+}
@@ -0,0 +1,248 @@
+//! # Links Module
+//!
+//! The `links` module parses the links that open the app directly into a
+//! specific puzzle or difficulty, without touching the UI.
+//!
+//! A puzzle is given in the `p` query parameter, in the line format,
+//! and a difficulty in the `d` query parameter:
+//!
+//! ```text
+//! https://sudoku.example.com/?p=53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79
+//! https://sudoku.example.com/?d=hard
+//! ```
+//!
+//! If both are given, the puzzle wins.
+//!
+//! A linked puzzle can also be a [`Challenge`](crate::challenge::Challenge),
+//! with the time to beat, in milliseconds, in the `c` query parameter,
+//! and the name of the challenger in the `n` query parameter.
+//!
+//! There are also [`Route`]s that start a game mode, so bookmarks and
+//! home-screen shortcuts can jump straight into it:
+//!
+//! ```text
+//! https://sudoku.example.com/play/expert
+//! https://sudoku.example.com/daily
+//! https://sudoku.example.com/weekly
+//! ```
+
+use anyhow::Result;
+
+use crate::app::SudokuState;
+use crate::daily::day_of;
+use crate::difficulty::{create_puzzle, Difficulty};
+use crate::formats::parse_line;
+use crate::utils::{create_daily_sudoku, validate_puzzle};
+use crate::weekly::{create_weekly_sudoku, week_of};
+
+/// Query parameter of the linked puzzle
+pub const PUZZLE_PARAM: &str = "p";
+
+/// Query parameter of the linked difficulty
+pub const DIFFICULTY_PARAM: &str = "d";
+
+/// Query parameter of the time of a challenge, in milliseconds
+pub const CHALLENGE_PARAM: &str = "c";
+
+/// Query parameter of the name of the challenger
+pub const CHALLENGER_PARAM: &str = "n";
+
+/// Parses the value of the [`PUZZLE_PARAM`].
+///
+/// ## Parameters
+///
+/// - `value: &str` - The puzzle in the line format.
+///
+/// ## Returns
+///
+/// Returns a Result containing the puzzle as a [`SudokuState`].
+///
+/// ## Errors
+///
+/// Returns an error if the puzzle cannot be parsed,
+/// or has conflicting cells or no solution.
+pub fn parse_puzzle(value: &str) -> Result<SudokuState> {
+    let puzzle = parse_line(value)?;
+    validate_puzzle(&puzzle)?;
+    Ok(puzzle)
+}
+
+/// Returns the puzzle a link opens into.
+///
+/// ## Parameters
+///
+/// - `puzzle: Option<&str>` - The value of the [`PUZZLE_PARAM`], if any.
+/// - `difficulty: Option<&str>` - The value of the [`DIFFICULTY_PARAM`],
+///   if any.
+///
+/// ## Returns
+///
+/// Returns the linked puzzle if it is valid,
+/// otherwise a new puzzle of the linked [`Difficulty`] if it is known,
+/// otherwise returns `None`.
+#[must_use]
+pub fn linked_puzzle(puzzle: Option<&str>, difficulty: Option<&str>) -> Option<SudokuState> {
+    puzzle
+        .and_then(|value| parse_puzzle(value).ok())
+        .or_else(|| {
+            difficulty
+                .and_then(|value| value.parse::<Difficulty>().ok())
+                .map(create_puzzle)
+        })
+}
+
+/// A route that starts a game mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    /// `/play/<difficulty>`: a new puzzle of the difficulty
+    Play(Difficulty),
+    /// `/daily`: the puzzle of the day, the same for everyone
+    Daily,
+    /// `/weekly`: the challenge of the week, the same for everyone
+    Weekly,
+}
+
+impl Route {
+    /// Parses the path of a URL.
+    ///
+    /// The route is matched at the end of the path,
+    /// so the app can be served under any base path.
+    ///
+    /// ## Parameters
+    ///
+    /// - `path: &str` - The path of the URL.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the [`Route`] and the base path before it,
+    /// or `None` if the path has no route.
+    #[must_use]
+    pub fn parse(path: &str) -> Option<(Self, &str)> {
+        let path = path.trim_end_matches('/');
+        let (rest, last) = path.rsplit_once('/')?;
+        match last {
+            "daily" => return Some((Self::Daily, &path[..=rest.len()])),
+            "weekly" => return Some((Self::Weekly, &path[..=rest.len()])),
+            _ => {}
+        }
+        let (base, segment) = rest.rsplit_once('/')?;
+        if segment != "play" {
+            return None;
+        }
+        let difficulty = last.parse().ok()?;
+        Some((Self::Play(difficulty), &path[..=base.len()]))
+    }
+
+    /// Returns the puzzle the route starts.
+    ///
+    /// ## Parameters
+    ///
+    /// - `now: u64` - The current time, in milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn puzzle(self, now: u64) -> SudokuState {
+        match self {
+            Self::Play(difficulty) => create_puzzle(difficulty),
+            Self::Daily => create_daily_sudoku(day_of(now)),
+            Self::Weekly => create_weekly_sudoku(week_of(now)),
+        }
+    }
+
+    /// Returns the day of the daily puzzle the route starts,
+    /// or `None` if it does not start a daily puzzle.
+    ///
+    /// ## Parameters
+    ///
+    /// - `now: u64` - The current time, in milliseconds since the Unix epoch.
+    #[must_use]
+    pub const fn day(self, now: u64) -> Option<u64> {
+        match self {
+            Self::Play(_) | Self::Weekly => None,
+            Self::Daily => Some(day_of(now)),
+        }
+    }
+
+    /// Returns the week of the challenge the route starts,
+    /// or `None` if it does not start a weekly challenge.
+    ///
+    /// ## Parameters
+    ///
+    /// - `now: u64` - The current time, in milliseconds since the Unix epoch.
+    #[must_use]
+    pub const fn week(self, now: u64) -> Option<u64> {
+        match self {
+            Self::Play(_) | Self::Daily => None,
+            Self::Weekly => Some(week_of(now)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daily::DAY_MILLIS;
+    use crate::difficulty::grade_puzzle;
+    use crate::formats::to_line;
+    use crate::utils::create_sudoku;
+
+    #[test]
+    fn test_parse_puzzle() {
+        let puzzle = create_sudoku();
+        assert_eq!(parse_puzzle(&to_line(&puzzle, '.')).expect("parse"), puzzle);
+        assert!(parse_puzzle("123").is_err());
+        assert!(parse_puzzle(&format!("11{}", ".".repeat(79))).is_err());
+    }
+
+    #[test]
+    fn test_linked_puzzle() {
+        let puzzle = create_sudoku();
+        let line = to_line(&puzzle, '.');
+        assert_eq!(linked_puzzle(Some(&line), Some("easy")), Some(puzzle));
+
+        let easy = linked_puzzle(Some("invalid"), Some("easy")).expect("easy puzzle");
+        assert_eq!(grade_puzzle(&easy), Difficulty::Easy);
+
+        assert_eq!(linked_puzzle(None, Some("impossible")), None);
+        assert_eq!(linked_puzzle(None, None), None);
+    }
+
+    #[test]
+    fn test_parse_route() {
+        assert_eq!(
+            Route::parse("/play/expert"),
+            Some((Route::Play(Difficulty::Expert), "/"))
+        );
+        assert_eq!(
+            Route::parse("/sudoku/play/easy/"),
+            Some((Route::Play(Difficulty::Easy), "/sudoku/"))
+        );
+        assert_eq!(Route::parse("/daily"), Some((Route::Daily, "/")));
+        assert_eq!(
+            Route::parse("/sudoku/daily"),
+            Some((Route::Daily, "/sudoku/"))
+        );
+        assert_eq!(
+            Route::parse("/sudoku/weekly/"),
+            Some((Route::Weekly, "/sudoku/"))
+        );
+        assert_eq!(Route::parse("/play/impossible"), None);
+        assert_eq!(Route::parse("/"), None);
+        assert_eq!(Route::parse("/sudoku"), None);
+    }
+
+    #[test]
+    fn test_daily_route() {
+        let today = 20_000 * DAY_MILLIS;
+        assert_eq!(
+            Route::Daily.puzzle(today),
+            Route::Daily.puzzle(today + 1000)
+        );
+        assert_ne!(
+            Route::Daily.puzzle(today),
+            Route::Daily.puzzle(today + DAY_MILLIS)
+        );
+        assert_eq!(Route::Daily.day(today + 1000), Some(20_000));
+        assert_eq!(Route::Play(Difficulty::Easy).day(today), None);
+        assert_eq!(Route::Weekly.day(today), None);
+        assert_eq!(Route::Weekly.week(today), Some(week_of(today)));
+    }
+}
@@ -0,0 +1,71 @@
+//! # Locale Module
+//!
+//! The `locale` module derives the writing direction of the page from the
+//! language of the browser,
+//! see [`browser_language`](crate::browser::browser_language).
+//!
+//! The app is only written in English,
+//! but the chrome around the board, such as the menus, the status bar and
+//! the dialogs, follows the direction of the language of the user,
+//! so it mirrors for the right-to-left languages, Arabic and Hebrew.
+//! The grid itself always reads from left to right,
+//! so its rows, columns and coordinates stay the same in every language.
+
+/// The primary subtags of the right-to-left languages,
+/// where `iw` is the former code of Hebrew
+const RTL_LANGUAGES: [&str; 3] = ["ar", "he", "iw"];
+
+/// The writing direction of a language
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    /// From left to right, as in English
+    #[default]
+    Ltr,
+    /// From right to left, as in Arabic and Hebrew
+    Rtl,
+}
+
+impl Direction {
+    /// Returns the writing direction of a language.
+    ///
+    /// ## Parameters
+    ///
+    /// - `language: &str` - The language, as a BCP 47 tag such as `he-IL`.
+    #[must_use]
+    pub fn of_language(language: &str) -> Self {
+        let primary = language.split(['-', '_']).next().unwrap_or_default();
+        if RTL_LANGUAGES
+            .iter()
+            .any(|rtl| primary.eq_ignore_ascii_case(rtl))
+        {
+            Self::Rtl
+        } else {
+            Self::Ltr
+        }
+    }
+
+    /// Returns the value of the direction in the `dir` HTML attribute.
+    #[must_use]
+    pub const fn html_name(self) -> &'static str {
+        match self {
+            Self::Ltr => "ltr",
+            Self::Rtl => "rtl",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction_of_language() {
+        assert_eq!(Direction::of_language("ar"), Direction::Rtl);
+        assert_eq!(Direction::of_language("he-IL"), Direction::Rtl);
+        assert_eq!(Direction::of_language("AR_eg"), Direction::Rtl);
+        assert_eq!(Direction::of_language("en-US"), Direction::Ltr);
+        // not a prefix match: Arapaho is written from left to right
+        assert_eq!(Direction::of_language("arp"), Direction::Ltr);
+        assert_eq!(Direction::of_language(""), Direction::Ltr);
+    }
+}
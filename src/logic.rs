@@ -0,0 +1,810 @@
+//! # Logic Module
+//!
+//! The `logic` module solves Sudoku puzzles the way a human does,
+//! with solving techniques instead of trial and error.
+//!
+//! A [`Grid`] holds the values and the candidates of the cells.
+//! [`next_deduction`] finds the next [`Deduction`] of the easiest
+//! [`Technique`] that applies,
+//! and [`solve_logically`] applies them one after the other until the board
+//! is solved, or no technique applies,
+//! and returns the ordered trace of the deductions.
+//!
+//! The trace grades the puzzles,
+//! see [`grade_puzzle`](crate::difficulty::grade_puzzle),
+//! and explains the hints,
+//! see [`next_step`](crate::techniques::next_step).
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::app::{SudokuNotes, SudokuState};
+use crate::bitboard::BitBoard;
+use crate::difficulty::Difficulty;
+use crate::utils::is_solved;
+use crate::validation::Unit;
+
+/// A solving technique, from the easiest to the hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+    /// A cell with a single candidate
+    NakedSingle,
+    /// The only cell of a unit that can hold a digit
+    HiddenSingle,
+    /// The cells of a box that can hold a digit are in a single line,
+    /// so the rest of the line cannot hold it
+    Pointing,
+    /// The cells of a line that can hold a digit are in a single box,
+    /// so the rest of the box cannot hold it
+    Claiming,
+    /// Two cells of a unit with the same two candidates
+    NakedPair,
+    /// Two digits that fit only the same two cells of a unit
+    HiddenPair,
+    /// Three cells of a unit with three candidates between them
+    NakedTriple,
+    /// Three digits that fit only the same three cells of a unit
+    HiddenTriple,
+    /// A digit that fits only the same two columns of two rows,
+    /// or the same two rows of two columns
+    XWing,
+    /// A digit that fits only the same three columns of three rows,
+    /// or the same three rows of three columns
+    Swordfish,
+    /// A cell with two candidates, each forcing one of two other cells to
+    /// the same third digit
+    XyWing,
+    /// A chain of the only two cells of units that can hold a digit,
+    /// one of whose two alternate colors holds it
+    SimpleChain,
+}
+
+impl Technique {
+    /// All the techniques, from the easiest to the hardest
+    pub const ALL: [Self; 12] = [
+        Self::NakedSingle,
+        Self::HiddenSingle,
+        Self::Pointing,
+        Self::Claiming,
+        Self::NakedPair,
+        Self::HiddenPair,
+        Self::NakedTriple,
+        Self::HiddenTriple,
+        Self::XWing,
+        Self::Swordfish,
+        Self::XyWing,
+        Self::SimpleChain,
+    ];
+
+    /// Returns the easiest difficulty that needs the technique.
+    #[must_use]
+    pub const fn difficulty(self) -> Difficulty {
+        match self {
+            Self::NakedSingle | Self::HiddenSingle => Difficulty::Easy,
+            Self::Pointing
+            | Self::Claiming
+            | Self::NakedPair
+            | Self::HiddenPair
+            | Self::NakedTriple
+            | Self::HiddenTriple => Difficulty::Medium,
+            Self::XWing | Self::Swordfish | Self::XyWing | Self::SimpleChain => Difficulty::Hard,
+        }
+    }
+
+    /// Returns the name of the technique, as shown to the user.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::NakedSingle => "Naked single",
+            Self::HiddenSingle => "Hidden single",
+            Self::Pointing => "Pointing",
+            Self::Claiming => "Claiming",
+            Self::NakedPair => "Naked pair",
+            Self::HiddenPair => "Hidden pair",
+            Self::NakedTriple => "Naked triple",
+            Self::HiddenTriple => "Hidden triple",
+            Self::XWing => "X-Wing",
+            Self::Swordfish => "Swordfish",
+            Self::XyWing => "XY-Wing",
+            Self::SimpleChain => "Simple chain",
+        }
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A deduction of a solving technique
+///
+/// - `technique: Technique`: the technique.
+/// - `cells: Vec<u8>`: the indices of the cells of its pattern.
+/// - `links: Vec<(u8, u8)>`: the implication links between cells,
+///   as the indices of the cells they go from and to.
+/// - `placement: Option<(u8, u8)>`: the index of the cell and the digit
+///   it places, if any.
+/// - `eliminations: Vec<(u8, u8)>`: the indices of the cells and the
+///   candidates it eliminates.
+/// - `unit: Option<Unit>`: the unit of the pattern, if it has one.
+/// - `digits: u16`: the digits of the pattern, as a bitmask like the notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    pub technique: Technique,
+    pub cells: Vec<u8>,
+    pub links: Vec<(u8, u8)>,
+    pub placement: Option<(u8, u8)>,
+    pub eliminations: Vec<(u8, u8)>,
+    pub unit: Option<Unit>,
+    pub digits: u16,
+}
+
+impl Deduction {
+    /// Creates a deduction that places a digit in a cell.
+    fn placement(technique: Technique, cell: u8, digit: u8, unit: Option<Unit>) -> Self {
+        Self {
+            technique,
+            cells: vec![cell],
+            links: vec![],
+            placement: Some((cell, digit)),
+            eliminations: vec![],
+            unit,
+            digits: bit(digit),
+        }
+    }
+
+    /// Creates a deduction that eliminates candidates,
+    /// or `None` if there are none to eliminate.
+    fn elimination(
+        technique: Technique,
+        cells: Vec<u8>,
+        digits: u16,
+        unit: Option<Unit>,
+        eliminations: Vec<(u8, u8)>,
+    ) -> Option<Self> {
+        (!eliminations.is_empty()).then_some(Self {
+            technique,
+            cells,
+            links: vec![],
+            placement: None,
+            eliminations,
+            unit,
+            digits,
+        })
+    }
+}
+
+/// A board being solved, with the candidates of its cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    values: SudokuState,
+    candidates: SudokuNotes,
+}
+
+impl Grid {
+    /// Creates the grid of a board,
+    /// where the candidates of an empty cell are the digits that are not in
+    /// any of its related cells.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to the board.
+    #[must_use]
+    pub fn new(board: &SudokuState) -> Self {
+        let bitboard = BitBoard::new(*board);
+        let mut candidates = [0; 81];
+        for (index, cell) in (0..).zip(candidates.iter_mut()) {
+            *cell = bitboard.candidates(index);
+        }
+        Self {
+            values: *board,
+            candidates,
+        }
+    }
+
+    /// Creates the grid of a board with notes,
+    /// where the candidates of an empty cell with notes are limited to them.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to the board.
+    /// - `notes: &SudokuNotes` - A reference to the notes of the board.
+    #[must_use]
+    pub fn with_notes(board: &SudokuState, notes: &SudokuNotes) -> Self {
+        let mut grid = Self::new(board);
+        for (candidates, &notes) in grid.candidates.iter_mut().zip(notes) {
+            if notes != 0 {
+                *candidates &= notes;
+            }
+        }
+        grid
+    }
+
+    /// Returns the values of the cells.
+    #[must_use]
+    pub const fn values(&self) -> &SudokuState {
+        &self.values
+    }
+
+    /// Returns the candidates of a cell, as a bitmask like the notes.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: u8` - The index of the cell, from 0 to 80.
+    #[must_use]
+    pub const fn candidates(&self, index: u8) -> u16 {
+        self.candidates[index as usize]
+    }
+
+    /// Applies a deduction:
+    /// places its digit, if any, and eliminates its candidates.
+    ///
+    /// ## Parameters
+    ///
+    /// - `deduction: &Deduction` - A reference to the deduction.
+    pub fn apply(&mut self, deduction: &Deduction) {
+        if let Some((cell, digit)) = deduction.placement {
+            self.values[cell as usize] = digit;
+            self.candidates[cell as usize] = 0;
+            for peer in 0..81 {
+                if sees(cell, peer) {
+                    self.candidates[peer as usize] &= !bit(digit);
+                }
+            }
+        }
+        for &(cell, digit) in &deduction.eliminations {
+            self.candidates[cell as usize] &= !bit(digit);
+        }
+    }
+
+    /// Returns whether a cell can hold a digit.
+    const fn has(&self, index: u8, digit: u8) -> bool {
+        self.candidates[index as usize] & bit(digit) != 0
+    }
+
+    /// Returns the cells of a unit that can hold a digit.
+    fn positions(&self, unit: Unit, digit: u8) -> Vec<u8> {
+        unit.cells()
+            .into_iter()
+            .filter(|&cell| self.has(cell, digit))
+            .collect()
+    }
+}
+
+/// The result of solving a board with solving techniques
+///
+/// - `deductions: Vec<Deduction>`: the deductions, in the order they were
+///   made.
+/// - `board: SudokuState`: the board after all the deductions.
+/// - `solved: bool`: whether the board is solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogicalSolution {
+    pub deductions: Vec<Deduction>,
+    pub board: SudokuState,
+    pub solved: bool,
+}
+
+impl LogicalSolution {
+    /// Returns the hardest difficulty of the techniques used,
+    /// or [`Difficulty::Easy`] if none was needed.
+    #[must_use]
+    pub fn difficulty(&self) -> Difficulty {
+        self.deductions
+            .iter()
+            .map(|deduction| deduction.technique.difficulty())
+            .max()
+            .unwrap_or(Difficulty::Easy)
+    }
+}
+
+/// Returns the bitmask of a digit, like the notes.
+const fn bit(digit: u8) -> u16 {
+    1 << (digit - 1)
+}
+
+/// Returns the digits of a bitmask, in order.
+fn digits(mask: u16) -> impl Iterator<Item = u8> {
+    (1..=9).filter(move |&digit| mask & bit(digit) != 0)
+}
+
+/// Returns the box of a cell.
+const fn box_of(index: u8) -> u8 {
+    index / 27 * 3 + index % 9 / 3
+}
+
+/// Returns whether two different cells are in the same row, column or box.
+const fn sees(a: u8, b: u8) -> bool {
+    a != b && (a / 9 == b / 9 || a % 9 == b % 9 || box_of(a) == box_of(b))
+}
+
+/// Returns all the combinations of a number of items, in order.
+fn combinations(items: &[u8], size: usize) -> Vec<Vec<u8>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+    items
+        .iter()
+        .enumerate()
+        .flat_map(|(index, &item)| {
+            combinations(&items[index + 1..], size - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, item);
+                    rest
+                })
+        })
+        .collect()
+}
+
+/// Finds a cell with a single candidate.
+fn naked_single(grid: &Grid) -> Option<Deduction> {
+    let cell = (0..81).find(|&cell| grid.candidates(cell).is_power_of_two())?;
+    let digit = digits(grid.candidates(cell)).next()?;
+    Some(Deduction::placement(
+        Technique::NakedSingle,
+        cell,
+        digit,
+        None,
+    ))
+}
+
+/// Finds a digit that fits a single cell of a unit.
+fn hidden_single(grid: &Grid) -> Option<Deduction> {
+    Unit::all().find_map(|unit| {
+        (1..=9).find_map(|digit| match grid.positions(unit, digit)[..] {
+            [cell] => Some(Deduction::placement(
+                Technique::HiddenSingle,
+                cell,
+                digit,
+                Some(unit),
+            )),
+            _ => None,
+        })
+    })
+}
+
+/// Finds a digit whose cells in a unit are all in another unit,
+/// from the boxes to the lines for [`Technique::Pointing`],
+/// or from the lines to the boxes for [`Technique::Claiming`].
+fn locked_candidates(grid: &Grid, technique: Technique) -> Option<Deduction> {
+    Unit::all()
+        .filter(|unit| matches!(unit, Unit::Box(_)) == (technique == Technique::Pointing))
+        .find_map(|unit| {
+            (1..=9).find_map(|digit| {
+                let cells = grid.positions(unit, digit);
+                let &first = cells.first()?;
+                if cells.len() < 2 {
+                    return None;
+                }
+                Unit::of(first)
+                    .into_iter()
+                    .filter(|&other| other != unit)
+                    .filter(|&other| cells.iter().all(|&cell| Unit::of(cell).contains(&other)))
+                    .find_map(|other| {
+                        let eliminations = other
+                            .cells()
+                            .into_iter()
+                            .filter(|cell| !cells.contains(cell) && grid.has(*cell, digit))
+                            .map(|cell| (cell, digit))
+                            .collect();
+                        Deduction::elimination(
+                            technique,
+                            cells.clone(),
+                            bit(digit),
+                            Some(unit),
+                            eliminations,
+                        )
+                    })
+            })
+        })
+}
+
+/// Finds cells of a unit with as many candidates between them as cells.
+fn naked_subset(grid: &Grid, size: usize, technique: Technique) -> Option<Deduction> {
+    Unit::all().find_map(|unit| {
+        let open: Vec<u8> = unit
+            .cells()
+            .into_iter()
+            .filter(|&cell| {
+                let count = grid.candidates(cell).count_ones() as usize;
+                (2..=size).contains(&count)
+            })
+            .collect();
+        combinations(&open, size).into_iter().find_map(|cells| {
+            let union = cells
+                .iter()
+                .fold(0, |union, &cell| union | grid.candidates(cell));
+            if union.count_ones() as usize != size {
+                return None;
+            }
+            let eliminations = unit
+                .cells()
+                .into_iter()
+                .filter(|cell| !cells.contains(cell))
+                .flat_map(|cell| {
+                    digits(grid.candidates(cell) & union).map(move |digit| (cell, digit))
+                })
+                .collect();
+            Deduction::elimination(technique, cells, union, Some(unit), eliminations)
+        })
+    })
+}
+
+/// Finds digits that fit only as many cells of a unit as digits.
+fn hidden_subset(grid: &Grid, size: usize, technique: Technique) -> Option<Deduction> {
+    Unit::all().find_map(|unit| {
+        let open: Vec<u8> = (1..=9)
+            .filter(|&digit| (2..=size).contains(&grid.positions(unit, digit).len()))
+            .collect();
+        combinations(&open, size).into_iter().find_map(|subset| {
+            let mask = subset.iter().fold(0, |mask, &digit| mask | bit(digit));
+            let cells: Vec<u8> = unit
+                .cells()
+                .into_iter()
+                .filter(|&cell| grid.candidates(cell) & mask != 0)
+                .collect();
+            if cells.len() != size {
+                return None;
+            }
+            let eliminations = cells
+                .iter()
+                .flat_map(|&cell| {
+                    digits(grid.candidates(cell) & !mask).map(move |digit| (cell, digit))
+                })
+                .collect();
+            Deduction::elimination(technique, cells, mask, Some(unit), eliminations)
+        })
+    })
+}
+
+/// Finds a digit that fits only the same lines across as many other lines.
+fn fish(grid: &Grid, size: usize, technique: Technique) -> Option<Deduction> {
+    (1..=9).find_map(|digit| {
+        [true, false].into_iter().find_map(|rows| {
+            let base = |line: u8| {
+                if rows {
+                    Unit::Row(line)
+                } else {
+                    Unit::Column(line)
+                }
+            };
+            let cover = |cell: u8| if rows { cell % 9 } else { cell / 9 };
+            let lines: Vec<u8> = (0..9)
+                .filter(|&line| (2..=size).contains(&grid.positions(base(line), digit).len()))
+                .collect();
+            combinations(&lines, size).into_iter().find_map(|chosen| {
+                let cells: Vec<u8> = chosen
+                    .iter()
+                    .flat_map(|&line| grid.positions(base(line), digit))
+                    .collect();
+                let mut covers: Vec<u8> = cells.iter().map(|&cell| cover(cell)).collect();
+                covers.sort_unstable();
+                covers.dedup();
+                if covers.len() != size {
+                    return None;
+                }
+                let eliminations = (0..81)
+                    .filter(|&cell| covers.contains(&cover(cell)) && !cells.contains(&cell))
+                    .filter(|&cell| grid.has(cell, digit))
+                    .map(|cell| (cell, digit))
+                    .collect();
+                Deduction::elimination(technique, cells, bit(digit), None, eliminations)
+            })
+        })
+    })
+}
+
+/// Finds a cell with two candidates and two cells it sees,
+/// each with one of them and the same third digit.
+fn xy_wing(grid: &Grid) -> Option<Deduction> {
+    let pairs: Vec<u8> = (0..81)
+        .filter(|&cell| grid.candidates(cell).count_ones() == 2)
+        .collect();
+    pairs.iter().find_map(|&hinge| {
+        let hinge_digits = grid.candidates(hinge);
+        let wings: Vec<u8> = pairs
+            .iter()
+            .copied()
+            .filter(|&cell| sees(hinge, cell))
+            .filter(|&cell| (grid.candidates(cell) & hinge_digits).is_power_of_two())
+            .collect();
+        combinations(&wings, 2).into_iter().find_map(|pincers| {
+            let [first, second] = pincers[..] else {
+                return None;
+            };
+            let (a, b) = (grid.candidates(first), grid.candidates(second));
+            // the pincers share the third digit, and one each of the hinge
+            let third = a & b & !hinge_digits;
+            if third.count_ones() != 1 || a & hinge_digits == b & hinge_digits {
+                return None;
+            }
+            let digit = digits(third).next()?;
+            let eliminations: Vec<(u8, u8)> = (0..81)
+                .filter(|&cell| sees(cell, first) && sees(cell, second))
+                .filter(|&cell| grid.has(cell, digit))
+                .map(|cell| (cell, digit))
+                .collect();
+            // the hinge forces one of the pincers,
+            // and the pincers see the cells they eliminate from
+            let links =
+                pincers
+                    .iter()
+                    .map(|&pincer| (hinge, pincer))
+                    .chain(pincers.iter().flat_map(|&pincer| {
+                        eliminations.iter().map(move |&(cell, _)| (pincer, cell))
+                    }))
+                    .collect();
+            let cells = [hinge, first, second].to_vec();
+            Deduction::elimination(Technique::XyWing, cells, hinge_digits, None, eliminations)
+                .map(|deduction| Deduction { links, ..deduction })
+        })
+    })
+}
+
+/// Finds a chain of the only two cells of units that can hold a digit,
+/// colored alternately,
+/// where two cells of a color see each other, so the color is wrong,
+/// or a cell sees both colors, so it cannot hold the digit.
+fn simple_chain(grid: &Grid) -> Option<Deduction> {
+    (1..=9).find_map(|digit| {
+        // the units with only two cells that can hold the digit
+        let mut pairs: Vec<(u8, u8)> = vec![];
+        for unit in Unit::all() {
+            if let [a, b] = grid.positions(unit, digit)[..] {
+                if !pairs.contains(&(a, b)) {
+                    pairs.push((a, b));
+                }
+            }
+        }
+
+        let mut colored = [None::<bool>; 81];
+        pairs.iter().find_map(|&(start, _)| {
+            if colored[start as usize].is_some() {
+                return None;
+            }
+            // color the chain of the cell, with the links followed
+            colored[start as usize] = Some(true);
+            let mut chain = vec![start];
+            let mut links = vec![];
+            let mut queue = VecDeque::from([start]);
+            while let Some(cell) = queue.pop_front() {
+                let color = colored[cell as usize] == Some(true);
+                for &(a, b) in &pairs {
+                    let next = match cell {
+                        _ if a == cell => b,
+                        _ if b == cell => a,
+                        _ => continue,
+                    };
+                    if colored[next as usize].is_none() {
+                        colored[next as usize] = Some(!color);
+                        chain.push(next);
+                        links.push((cell, next));
+                        queue.push_back(next);
+                    }
+                }
+            }
+
+            let color_of = |cell: u8| colored[cell as usize];
+            let wrong = [true, false].into_iter().find(|&color| {
+                let cells: Vec<u8> = chain
+                    .iter()
+                    .copied()
+                    .filter(|&cell| color_of(cell) == Some(color))
+                    .collect();
+                combinations(&cells, 2)
+                    .iter()
+                    .any(|pair| sees(pair[0], pair[1]))
+            });
+            // a wrong color cannot hold the digit, and if neither is wrong,
+            // the cells that see both colors cannot
+            let eliminations = wrong.map_or_else(
+                || {
+                    (0..81)
+                        .filter(|cell| !chain.contains(cell) && grid.has(*cell, digit))
+                        .filter(|&cell| {
+                            [true, false].into_iter().all(|color| {
+                                chain.iter().any(|&other| {
+                                    color_of(other) == Some(color) && sees(cell, other)
+                                })
+                            })
+                        })
+                        .map(|cell| (cell, digit))
+                        .collect()
+                },
+                |color| {
+                    chain
+                        .iter()
+                        .filter(|&&cell| color_of(cell) == Some(color))
+                        .map(|&cell| (cell, digit))
+                        .collect()
+                },
+            );
+            Deduction::elimination(
+                Technique::SimpleChain,
+                chain.clone(),
+                bit(digit),
+                None,
+                eliminations,
+            )
+            .map(|deduction| Deduction { links, ..deduction })
+        })
+    })
+}
+
+/// Finds the next deduction of a grid.
+///
+/// ## Parameters
+///
+/// - `grid: &Grid` - A reference to the grid.
+/// - `techniques: &[Technique]` - The techniques to use,
+///   tried from the first to the last.
+///
+/// ## Returns
+///
+/// Returns the first deduction of the first technique that applies,
+/// or `None` if none applies.
+#[must_use]
+pub fn next_deduction(grid: &Grid, techniques: &[Technique]) -> Option<Deduction> {
+    techniques.iter().find_map(|&technique| match technique {
+        Technique::NakedSingle => naked_single(grid),
+        Technique::HiddenSingle => hidden_single(grid),
+        Technique::Pointing | Technique::Claiming => locked_candidates(grid, technique),
+        Technique::NakedPair => naked_subset(grid, 2, technique),
+        Technique::NakedTriple => naked_subset(grid, 3, technique),
+        Technique::HiddenPair => hidden_subset(grid, 2, technique),
+        Technique::HiddenTriple => hidden_subset(grid, 3, technique),
+        Technique::XWing => fish(grid, 2, technique),
+        Technique::Swordfish => fish(grid, 3, technique),
+        Technique::XyWing => xy_wing(grid),
+        Technique::SimpleChain => simple_chain(grid),
+    })
+}
+
+/// Solves a board with solving techniques.
+///
+/// Every deduction is made with the easiest technique that applies,
+/// so the hardest technique of the trace is the one the board needs.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - A reference to the board.
+/// - `techniques: &[Technique]` - The techniques to use,
+///   from the easiest to the hardest.
+///
+/// ## Returns
+///
+/// Returns the [`LogicalSolution`],
+/// with the deductions made until the board was solved,
+/// or until no technique applied.
+#[must_use]
+pub fn solve_logically(board: &SudokuState, techniques: &[Technique]) -> LogicalSolution {
+    let mut grid = Grid::new(board);
+    let mut deductions = vec![];
+    while grid.values.contains(&0) {
+        let Some(deduction) = next_deduction(&grid, techniques) else {
+            break;
+        };
+        grid.apply(&deduction);
+        deductions.push(deduction);
+    }
+    LogicalSolution {
+        deductions,
+        board: grid.values,
+        solved: is_solved(&grid.values),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formats::parse_line;
+    use crate::utils::find_solution;
+
+    /// The notes of a board where a digit fits all the cells but some
+    fn without(digit: u8, cells: &[u8]) -> SudokuNotes {
+        let mut notes = [0; 81];
+        for &cell in cells {
+            notes[cell as usize] = 0x1FF & !bit(digit);
+        }
+        notes
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(&[1, 2, 3], 2),
+            vec![vec![1, 2], vec![1, 3], vec![2, 3]]
+        );
+        assert_eq!(combinations(&[1, 2], 3), Vec::<Vec<u8>>::new());
+    }
+
+    #[test]
+    fn test_solve_with_singles() {
+        let puzzle = parse_line(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        )
+        .expect("valid puzzle");
+        let solution = solve_logically(&puzzle, &Technique::ALL);
+        assert!(solution.solved);
+        assert_eq!(solution.difficulty(), Difficulty::Easy);
+        assert_eq!(Some(solution.board), find_solution(&puzzle).ok());
+        let empty = (0..81).filter(|&cell| puzzle[cell] == 0).count();
+        // every deduction places a digit
+        assert_eq!(solution.deductions.len(), empty);
+        assert!(solution
+            .deductions
+            .iter()
+            .all(|deduction| deduction.placement.is_some()));
+
+        // without techniques, nothing is deduced
+        let none = solve_logically(&puzzle, &[]);
+        assert!(!none.solved);
+        assert!(none.deductions.is_empty());
+        assert_eq!(none.board, puzzle);
+    }
+
+    #[test]
+    fn test_pointing() {
+        // the 1 of the top-left box is in the first row
+        let grid = Grid::with_notes(&[0; 81], &without(1, &[2, 9, 10, 11, 18, 19, 20]));
+        let deduction = next_deduction(&grid, &Technique::ALL).expect("failed to deduce");
+        assert_eq!(deduction.technique, Technique::Pointing);
+        assert_eq!(deduction.cells, vec![0, 1]);
+        assert_eq!(deduction.unit, Some(Unit::Box(0)));
+        assert_eq!(
+            deduction.eliminations,
+            (3..9).map(|cell| (cell, 1)).collect::<Vec<_>>()
+        );
+
+        let mut grid = grid;
+        grid.apply(&deduction);
+        assert!(!grid.has(3, 1));
+        assert!(grid.has(1, 1));
+    }
+
+    #[test]
+    fn test_naked_pair() {
+        let mut notes = [0; 81];
+        notes[0] = 0b11;
+        notes[1] = 0b11;
+        let grid = Grid::with_notes(&[0; 81], &notes);
+        let deduction = naked_subset(&grid, 2, Technique::NakedPair).expect("failed to deduce");
+        assert_eq!(deduction.cells, vec![0, 1]);
+        assert_eq!(deduction.digits, 0b11);
+        // the first row, from the third cell
+        assert_eq!(deduction.unit, Some(Unit::Row(0)));
+        assert_eq!(deduction.eliminations[..2], [(2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_x_wing() {
+        // the 5 of the first and the last rows is in the first two columns
+        let cells: Vec<u8> = (2..9).chain(74..81).collect();
+        let grid = Grid::with_notes(&[0; 81], &without(5, &cells));
+        let deduction = fish(&grid, 2, Technique::XWing).expect("failed to deduce");
+        assert_eq!(deduction.cells, vec![0, 1, 72, 73]);
+        assert_eq!(deduction.eliminations.len(), 14);
+        assert!(deduction.eliminations.contains(&(9, 5)));
+    }
+
+    #[test]
+    fn test_simple_chain() {
+        // the 1 of the first row, the fifth column and the fifth row
+        // is in two cells each, chained from R1C1 to R5C1
+        let cells: Vec<u8> = (1..9)
+            .filter(|&cell| cell != 4)
+            .chain((1..9).map(|row| row * 9 + 4).filter(|&cell| cell != 40))
+            .chain((37..45).filter(|&cell| cell != 40))
+            .collect();
+        let grid = Grid::with_notes(&[0; 81], &without(1, &cells));
+        let deduction = simple_chain(&grid).expect("failed to deduce");
+        assert_eq!(deduction.cells, vec![0, 4, 40, 36]);
+        assert_eq!(deduction.links, vec![(0, 4), (4, 40), (40, 36)]);
+        // the first column sees both ends of the chain
+        assert_eq!(
+            deduction.eliminations,
+            [9, 18, 27, 45, 54, 63, 72].map(|cell| (cell, 1)).to_vec()
+        );
+    }
+}
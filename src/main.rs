@@ -1,48 +1,8 @@
 //! # Sudoku App
 //!
-//! This crate is a Rust binary for generating and solving Sudoku puzzles.
-//! It provides functionality to create random Sudoku puzzles, solve them,
-//! and manipulate or interact with Sudoku boards in various ways.
-//!
-//! ## Features
-//!
-//! - Generate random Sudoku puzzles.
-//! - Solve Sudoku puzzles programmatically.
-//! - Render Sudoku puzzles as interactive boards in a web interface using Dioxus.
-//! - Support for various difficulty levels and puzzle variants.
-//!
-//! ## Usage
-//!
-//! To render a Sudoku board in a web interface:
-//!
-//! ```rust
-//! use dioxus::prelude::*;
-//!
-//! use crate::components::board::SudokuBoard;
-//!
-//! pub fn App() -> Element {
-//!     rsx!(SudokuBoard { sudoku: None })
-//! }
-//! ```
-//!
-//! ## Dependecies
-//!
-//! Under the hood Sudoku app uses:
-//!
-//! - [Dioxus](https://dioxuslabs.com/):  fullstack, crossplatform, fast Apps.
-//! - [`sudoku`](https::/docs.rs/sudoku): sudoku solving and generator.
-//!
-//! ## Contributing
-//!
-//! Contributions to the are welcome!
-//! Whether it's bug fixes, feature implementations,
-//! or improvements to the documentation,
-//! feel free to create pull requests or open issues.
-//!
-//! ## License
-//!
-//! This crate is licensed under
-//! [MIT license](https://opensource.org/licenses/MIT).
+//! The binary of the Sudoku app, which launches the web interface of the
+//! [`sudoku_dioxus`] library,
+//! or its command line interface with the `--cli` flag on native targets.
 
 #![allow(non_snake_case)]
 use dioxus::launch;
@@ -51,11 +11,9 @@ use dioxus::launch;
 use dioxus_logger::tracing::Level;
 use log::info;
 
-pub mod app;
-pub mod components;
-pub mod utils;
-
-use app::App;
+use sudoku_dioxus::app::App;
+#[cfg(not(target_arch = "wasm32"))]
+use sudoku_dioxus::cli;
 
 /// Main entry point for the Sudoku web application.
 ///
@@ -65,11 +23,26 @@ use app::App;
 /// component, along with any additional components or context providers
 /// required for the application.
 ///
+/// With the `server` feature, it launches the server of the fullstack app
+/// instead, which renders the board before the browser hydrates it.
+///
+/// On native targets, the `--cli` flag runs the
+/// [`cli`](sudoku_dioxus::cli) command that follows it instead,
+/// and exits with a non-zero status if it fails.
+///
 /// ## Panics
 ///
 /// If in debug mode, this function will panic if it fails to initialize the
 /// Dioxuslogger.
 pub fn main() {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        if args.first().map(String::as_str) == Some(cli::CLI_FLAG) {
+            run_cli(&args[1..]);
+        }
+    }
+
     #[cfg(debug_assertions)]
     {
         // init logger for Dioxus
@@ -80,3 +53,21 @@ pub fn main() {
     info!("Launching Sudoku app");
     launch(App);
 }
+
+/// Runs a command of the command line interface, and exits.
+///
+/// ## Parameters
+///
+/// - `args: &[String]` - The arguments after the `--cli` flag.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_cli(args: &[String]) -> ! {
+    let result = cli::Command::parse(args)
+        .and_then(|command| command.run(std::io::stdin().lock(), std::io::stdout().lock()));
+    match result {
+        Ok(()) => std::process::exit(0),
+        Err(err) => {
+            eprintln!("error: {err}\n\n{}", cli::USAGE);
+            std::process::exit(1);
+        }
+    }
+}
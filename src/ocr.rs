@@ -0,0 +1,395 @@
+//! # OCR Module
+//!
+//! The `ocr` module contains an experimental recognizer that reads a Sudoku
+//! puzzle from a photo or a scanned image of a printed grid.
+//!
+//! The recognition runs in a few simple steps:
+//!
+//! 1. The image is binarized with [Otsu's method](https://en.wikipedia.org/wiki/Otsu%27s_method),
+//!    so that the ink of the grid and digits is told apart from the paper.
+//! 2. The outer grid is detected from the rows and columns that have long
+//!    runs of ink, i.e. the grid lines.
+//! 3. The grid is split into 81 cells and cells with barely any ink are
+//!    considered empty.
+//! 4. The digit of every other cell is recognized by matching its ink
+//!    against 5x7 templates of the digits 1 to 9.
+//!
+//! The recognizer assumes the grid is roughly axis-aligned and fills most of
+//! the image, so the recognized puzzle should always be reviewed by the user.
+
+use anyhow::{Error, Result};
+
+use crate::app::SudokuState;
+
+/// 5x7 templates of the digits 1 to 9, where `#` is ink
+const TEMPLATES: [[&str; 7]; 9] = [
+    [
+        "..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###.",
+    ],
+    [
+        ".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####",
+    ],
+    [
+        "#####", "...#.", "..#..", "...#.", "....#", "#...#", ".###.",
+    ],
+    [
+        "...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#.",
+    ],
+    [
+        "#####", "#....", "####.", "....#", "....#", "#...#", ".###.",
+    ],
+    [
+        "..##.", ".#...", "#....", "####.", "#...#", "#...#", ".###.",
+    ],
+    [
+        "#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#...",
+    ],
+    [
+        ".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###.",
+    ],
+    [
+        ".###.", "#...#", "#...#", ".####", "....#", "...#.", ".##..",
+    ],
+];
+
+/// Width of the digit templates
+const TEMPLATE_WIDTH: usize = 5;
+
+/// Height of the digit templates
+const TEMPLATE_HEIGHT: usize = 7;
+
+/// Fraction of a cell, on each side, that is ignored to skip the grid lines
+const CELL_MARGIN: f32 = 0.15;
+
+/// Minimum fraction of ink for a cell not to be considered empty
+const MIN_INK: f32 = 0.02;
+
+/// A grayscale image, where 0 is black and 255 is white
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrayImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl GrayImage {
+    /// Creates a grayscale image from its luma pixels, row by row.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the number of pixels does not match the size.
+    pub fn new(width: usize, height: usize, pixels: Vec<u8>) -> Result<Self> {
+        if pixels.len() != width * height {
+            return Err(Error::msg("Image size does not match its pixels"));
+        }
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Creates a grayscale image from RGBA pixels, such as a canvas'
+    /// `ImageData`.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the number of pixels does not match the size.
+    pub fn from_rgba(width: usize, height: usize, rgba: &[u8]) -> Result<Self> {
+        let pixels = rgba
+            .chunks_exact(4)
+            .map(|pixel| {
+                // ITU-R BT.601 luma
+                let luma = 299 * u32::from(pixel[0])
+                    + 587 * u32::from(pixel[1])
+                    + 114 * u32::from(pixel[2]);
+                u8::try_from(luma / 1000).unwrap_or(u8::MAX)
+            })
+            .collect();
+        Self::new(width, height, pixels)
+    }
+}
+
+/// A binary image, where `true` is ink
+struct InkMask {
+    width: usize,
+    height: usize,
+    ink: Vec<bool>,
+}
+
+impl InkMask {
+    /// Binarizes an image with Otsu's method.
+    fn from_image(image: &GrayImage) -> Self {
+        let threshold = otsu_threshold(&image.pixels);
+        Self {
+            width: image.width,
+            height: image.height,
+            ink: image
+                .pixels
+                .iter()
+                .map(|&pixel| pixel <= threshold)
+                .collect(),
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.ink[y * self.width + x]
+    }
+
+    /// Longest run of consecutive ink pixels in the given line of pixels.
+    fn longest_run(line: impl Iterator<Item = bool>) -> usize {
+        line.fold((0, 0), |(longest, current), ink| {
+            let current = if ink { current + 1 } else { 0 };
+            (longest.max(current), current)
+        })
+        .0
+    }
+
+    /// Counts the ink pixels in a rectangle.
+    fn count(&self, left: usize, top: usize, right: usize, bottom: usize) -> usize {
+        (top..bottom)
+            .map(|y| (left..right).filter(|&x| self.get(x, y)).count())
+            .sum()
+    }
+}
+
+/// Computes the threshold that best separates dark and light pixels.
+#[allow(clippy::cast_precision_loss)]
+fn otsu_threshold(pixels: &[u8]) -> u8 {
+    let mut histogram = [0_u64; 256];
+    for &pixel in pixels {
+        histogram[pixel as usize] += 1;
+    }
+
+    let total = pixels.len() as u64;
+    let sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, &count)| value as f64 * count as f64)
+        .sum();
+
+    let (mut best, mut best_variance) = (0, 0.0);
+    let (mut weight, mut weighted_sum) = (0, 0.0);
+    for (value, &count) in histogram.iter().enumerate() {
+        weight += count;
+        weighted_sum += value as f64 * count as f64;
+        if weight == 0 || weight == total {
+            continue;
+        }
+        let (dark, light) = (weight as f64, (total - weight) as f64);
+        let dark_mean = weighted_sum / dark;
+        let light_mean = (sum - weighted_sum) / light;
+        let variance = dark * light * (dark_mean - light_mean).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            best = value;
+        }
+    }
+    u8::try_from(best).unwrap_or(u8::MAX)
+}
+
+/// Finds the first and last lines whose longest ink run is at least 60% of
+/// the longest run among all lines.
+fn grid_bounds(runs: &[usize]) -> Option<(usize, usize)> {
+    let longest = *runs.iter().max()?;
+    if longest == 0 {
+        return None;
+    }
+    let is_line = |run: &usize| *run * 5 >= longest * 3;
+    let first = runs.iter().position(is_line)?;
+    let last = runs.iter().rposition(is_line)?;
+    (last > first).then_some((first, last))
+}
+
+/// Recognizes the digit in a rectangle of the ink mask.
+///
+/// The ink bounding box is sampled into a 5x7 grid of ink densities,
+/// preserving its aspect ratio, and compared against every template.
+#[allow(clippy::cast_precision_loss)]
+fn recognize_digit(mask: &InkMask, left: usize, top: usize, right: usize, bottom: usize) -> u8 {
+    // Bounding box of the ink
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (right, bottom, left, top);
+    for y in top..bottom {
+        for x in left..right {
+            if mask.get(x, y) {
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x + 1);
+                max_y = max_y.max(y + 1);
+            }
+        }
+    }
+    let height = max_y - min_y;
+
+    // Widen narrow digits, such as 1, to the template aspect ratio
+    let width = (max_x - min_x).max(height * TEMPLATE_WIDTH / TEMPLATE_HEIGHT);
+    let center = usize::midpoint(min_x, max_x);
+    let start_x = center.saturating_sub(width / 2);
+
+    let mut densities = [0.0_f32; TEMPLATE_WIDTH * TEMPLATE_HEIGHT];
+    for (index, density) in densities.iter_mut().enumerate() {
+        let (column, row) = (index % TEMPLATE_WIDTH, index / TEMPLATE_WIDTH);
+        let x0 = start_x + column * width / TEMPLATE_WIDTH;
+        let x1 = (start_x + (column + 1) * width / TEMPLATE_WIDTH).max(x0 + 1);
+        let y0 = min_y + row * height / TEMPLATE_HEIGHT;
+        let y1 = (min_y + (row + 1) * height / TEMPLATE_HEIGHT).max(y0 + 1);
+        let (x1, y1) = (x1.min(mask.width), y1.min(mask.height));
+        let area = (x1.saturating_sub(x0) * y1.saturating_sub(y0)).max(1);
+        *density = mask.count(x0.min(x1), y0.min(y1), x1, y1) as f32 / area as f32;
+    }
+
+    let distance = |template: &[&str; 7]| -> f32 {
+        template
+            .iter()
+            .flat_map(|row| row.chars())
+            .zip(densities)
+            .map(|(pixel, density)| {
+                let expected = if pixel == '#' { 1.0 } else { 0.0 };
+                (expected - density).powi(2)
+            })
+            .sum()
+    };
+
+    TEMPLATES
+        .iter()
+        .zip(1..=9)
+        .min_by(|(a, _), (b, _)| distance(a).total_cmp(&distance(b)))
+        .map_or(0, |(_, digit)| digit)
+}
+
+/// Recognizes a Sudoku puzzle in an image.
+///
+/// ## Parameters
+///
+/// - `image: &GrayImage` - The image of a printed Sudoku grid.
+///
+/// ## Returns
+///
+/// Returns a Result containing the recognized puzzle as a [`SudokuState`].
+///
+/// ## Errors
+///
+/// Returns an error if no grid is found in the image.
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss)]
+pub fn recognize_sudoku(image: &GrayImage) -> Result<SudokuState> {
+    let mask = InkMask::from_image(image);
+
+    // Detect the outer grid from its lines
+    let row_runs: Vec<usize> = (0..mask.height)
+        .map(|y| InkMask::longest_run((0..mask.width).map(|x| mask.get(x, y))))
+        .collect();
+    let column_runs: Vec<usize> = (0..mask.width)
+        .map(|x| InkMask::longest_run((0..mask.height).map(|y| mask.get(x, y))))
+        .collect();
+    let ((top, bottom), (left, right)) = grid_bounds(&row_runs)
+        .zip(grid_bounds(&column_runs))
+        .ok_or_else(|| Error::msg("No Sudoku grid found in the image"))?;
+
+    let cell_width = (right - left) as f32 / 9.0;
+    let cell_height = (bottom - top) as f32 / 9.0;
+    if cell_width < TEMPLATE_WIDTH as f32 || cell_height < TEMPLATE_HEIGHT as f32 {
+        return Err(Error::msg("The Sudoku grid is too small in the image"));
+    }
+
+    let mut sudoku: SudokuState = [0; 81];
+    for (index, value) in sudoku.iter_mut().enumerate() {
+        let (row, column) = ((index / 9) as f32, (index % 9) as f32);
+        let x0 = (column + CELL_MARGIN).mul_add(cell_width, left as f32) as usize;
+        let x1 = (column + 1.0 - CELL_MARGIN).mul_add(cell_width, left as f32) as usize;
+        let y0 = (row + CELL_MARGIN).mul_add(cell_height, top as f32) as usize;
+        let y1 = (row + 1.0 - CELL_MARGIN).mul_add(cell_height, top as f32) as usize;
+
+        let area = ((x1 - x0) * (y1 - y0)) as f32;
+        if (mask.count(x0, y0, x1, y1) as f32) / area >= MIN_INK {
+            *value = recognize_digit(&mask, x0, y0, x1, y1);
+        }
+    }
+
+    Ok(sudoku)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Size, in pixels, of the cells of the rendered grids
+    const CELL: usize = 40;
+
+    /// Scale of the rendered digits
+    const SCALE: usize = 4;
+
+    /// Renders a Sudoku grid with the template font, with a white margin.
+    fn render(sudoku: &SudokuState, margin: usize) -> GrayImage {
+        let size = 9 * CELL + 2 + 2 * margin;
+        let mut pixels = vec![255_u8; size * size];
+        let mut paint = |x: usize, y: usize| pixels[(y + margin) * size + x + margin] = 0;
+
+        // Grid lines
+        for line in 0..=9 {
+            let thickness = if line % 3 == 0 { 3 } else { 1 };
+            for offset in 0..thickness {
+                let position = (line * CELL + offset).min(9 * CELL + 1);
+                for along in 0..=9 * CELL + 1 {
+                    paint(position, along);
+                    paint(along, position);
+                }
+            }
+        }
+
+        // Digits
+        for (index, &value) in sudoku.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            let left = (index % 9) * CELL + (CELL - TEMPLATE_WIDTH * SCALE) / 2;
+            let top = (index / 9) * CELL + (CELL - TEMPLATE_HEIGHT * SCALE) / 2;
+            let template = TEMPLATES[value as usize - 1];
+            for (row, line) in template.iter().enumerate() {
+                for (column, pixel) in line.chars().enumerate() {
+                    if pixel == '#' {
+                        for dy in 0..SCALE {
+                            for dx in 0..SCALE {
+                                paint(left + column * SCALE + dx, top + row * SCALE + dy);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        GrayImage::new(size, size, pixels).expect("valid image")
+    }
+
+    #[test]
+    fn test_recognize_rendered_sudoku() {
+        let sudoku = crate::utils::create_sudoku();
+        let image = render(&sudoku, 0);
+        assert_eq!(recognize_sudoku(&image).expect("grid found"), sudoku);
+    }
+
+    #[test]
+    fn test_recognize_rendered_sudoku_with_margin() {
+        let mut sudoku: SudokuState = [0; 81];
+        for (index, value) in sudoku.iter_mut().enumerate().step_by(4) {
+            *value = u8::try_from(index % 9 + 1).expect("digit");
+        }
+        let image = render(&sudoku, 25);
+        assert_eq!(recognize_sudoku(&image).expect("grid found"), sudoku);
+    }
+
+    #[test]
+    fn test_recognize_blank_image() {
+        let image = GrayImage::new(100, 100, vec![255; 100 * 100]).expect("valid image");
+        assert!(recognize_sudoku(&image).is_err());
+    }
+
+    #[test]
+    fn test_from_rgba() {
+        let image =
+            GrayImage::from_rgba(2, 1, &[0, 0, 0, 255, 255, 255, 255, 255]).expect("valid image");
+        assert_eq!(image.pixels, vec![0, 255]);
+        assert!(GrayImage::from_rgba(2, 2, &[0; 4]).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! # Packs Module
+//!
+//! The `packs` module contains the puzzle packs,
+//! community collections of puzzles that are played in order.
+//!
+//! A pack is imported from a JSON file with its name, its author,
+//! optionally its difficulty, and its puzzles in the line format:
+//!
+//! ```json
+//! {
+//!   "name": "Sunday Classics",
+//!   "author": "Jane Doe",
+//!   "difficulty": "medium",
+//!   "puzzles": [
+//!     "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79"
+//!   ]
+//! }
+//! ```
+//!
+//! The imported packs are kept in the local storage and shared by all the
+//! profiles, while the [`PackProgress`] of every profile records which of
+//! their puzzles it solved.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::SudokuState;
+use crate::browser::{local_storage_get, local_storage_set};
+use crate::difficulty::Difficulty;
+use crate::formats::to_line;
+use crate::links::parse_puzzle;
+use crate::profiles::profile_key;
+
+/// Local storage key of the imported packs
+const PACKS_KEY: &str = "puzzle-packs";
+
+/// Local storage key of the [`PackProgress`]
+const PROGRESS_KEY: &str = "pack-progress";
+
+/// A puzzle pack
+///
+/// - `id: u32`: the unique id of the pack, given when it is imported.
+/// - `name: String`: the name of the pack.
+/// - `author: String`: who made the pack.
+/// - `difficulty: Option<Difficulty>`: the difficulty of the pack, if any.
+/// - `puzzles: Vec<String>`: the puzzles, in the order they are played,
+///   in the line format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzlePack {
+    #[serde(default)]
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    pub puzzles: Vec<String>,
+}
+
+impl PuzzlePack {
+    /// Returns a puzzle of the pack.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: usize` - The position of the puzzle in the pack, from 0.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the puzzle as a [`SudokuState`],
+    /// or `None` if there is no such puzzle or it is invalid.
+    #[must_use]
+    pub fn puzzle(&self, index: usize) -> Option<SudokuState> {
+        self.puzzles
+            .get(index)
+            .and_then(|line| parse_puzzle(line).ok())
+    }
+}
+
+/// Parses a pack file.
+///
+/// The puzzles are validated and normalized to the line format,
+/// with `.` for the empty cells.
+///
+/// ## Parameters
+///
+/// - `json: &str` - The contents of the pack file.
+/// - `packs: &[PuzzlePack]` - The packs already imported,
+///   so the new pack gets an id none of them uses.
+///
+/// ## Returns
+///
+/// Returns a Result containing the [`PuzzlePack`].
+///
+/// ## Errors
+///
+/// Returns an error if the file is not a valid pack,
+/// has no name or no puzzles, or any of its puzzles is invalid.
+pub fn parse_pack(json: &str, packs: &[PuzzlePack]) -> Result<PuzzlePack> {
+    let mut pack: PuzzlePack = serde_json::from_str(json)?;
+    pack.name = pack.name.trim().to_string();
+    pack.author = pack.author.trim().to_string();
+    if pack.name.is_empty() {
+        return Err(Error::msg("The pack has no name"));
+    }
+    if pack.puzzles.is_empty() {
+        return Err(Error::msg("The pack has no puzzles"));
+    }
+    pack.puzzles = pack
+        .puzzles
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            parse_puzzle(line)
+                .map(|puzzle| to_line(&puzzle, '.'))
+                .map_err(|err| Error::msg(format!("Puzzle {}: {err}", index + 1)))
+        })
+        .collect::<Result<_>>()?;
+    pack.id = packs.iter().map(|pack| pack.id + 1).max().unwrap_or(0);
+    Ok(pack)
+}
+
+/// Loads the imported packs from the local storage.
+///
+/// ## Returns
+///
+/// Returns the stored [`PuzzlePack`]s, in the order they were imported,
+/// or none if they cannot be read.
+#[must_use]
+pub fn load_packs() -> Vec<PuzzlePack> {
+    local_storage_get(PACKS_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the imported packs in the local storage.
+///
+/// ## Parameters
+///
+/// - `packs: &[PuzzlePack]` - The packs to store.
+pub fn store_packs(packs: &[PuzzlePack]) {
+    match serde_json::to_string(packs) {
+        Ok(json) => local_storage_set(PACKS_KEY, &json),
+        Err(err) => log::error!("failed to store the puzzle packs: {err:?}"),
+    }
+}
+
+/// The solved puzzles of the packs of a profile,
+/// by the id of the pack and the position of the puzzle
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackProgress(pub BTreeMap<u32, BTreeSet<usize>>);
+
+impl PackProgress {
+    /// Returns whether a puzzle of a pack was solved.
+    ///
+    /// ## Parameters
+    ///
+    /// - `pack: u32` - The id of the pack.
+    /// - `index: usize` - The position of the puzzle in the pack.
+    #[must_use]
+    pub fn is_solved(&self, pack: u32, index: usize) -> bool {
+        self.0
+            .get(&pack)
+            .is_some_and(|solved| solved.contains(&index))
+    }
+
+    /// Returns the number of solved puzzles of a pack.
+    ///
+    /// ## Parameters
+    ///
+    /// - `pack: u32` - The id of the pack.
+    #[must_use]
+    pub fn solved(&self, pack: u32) -> usize {
+        self.0.get(&pack).map_or(0, BTreeSet::len)
+    }
+
+    /// Returns the first puzzle of a pack that was not solved yet,
+    /// or `None` if all were.
+    ///
+    /// ## Parameters
+    ///
+    /// - `pack: &PuzzlePack` - The pack.
+    #[must_use]
+    pub fn next(&self, pack: &PuzzlePack) -> Option<usize> {
+        (0..pack.puzzles.len()).find(|&index| !self.is_solved(pack.id, index))
+    }
+
+    /// Records a puzzle of a pack as solved.
+    ///
+    /// ## Parameters
+    ///
+    /// - `pack: u32` - The id of the pack.
+    /// - `index: usize` - The position of the puzzle in the pack.
+    pub fn complete(&mut self, pack: u32, index: usize) {
+        self.0.entry(pack).or_default().insert(index);
+    }
+
+    /// Forgets the progress of a pack, once it is deleted.
+    ///
+    /// ## Parameters
+    ///
+    /// - `pack: u32` - The id of the pack.
+    pub fn remove(&mut self, pack: u32) {
+        self.0.remove(&pack);
+    }
+}
+
+/// Loads the pack progress of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`PackProgress`],
+/// or an empty one if none is stored or it cannot be read.
+#[must_use]
+pub fn load_pack_progress(profile: u32) -> PackProgress {
+    local_storage_get(&profile_key(profile, PROGRESS_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the pack progress of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `progress: &PackProgress` - The progress to store.
+pub fn store_pack_progress(profile: u32, progress: &PackProgress) {
+    match serde_json::to_string(progress) {
+        Ok(json) => local_storage_set(&profile_key(profile, PROGRESS_KEY), &json),
+        Err(err) => log::error!("failed to store the pack progress: {err:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+    #[test]
+    fn test_parse_pack() {
+        let json = format!(
+            r#"{{"name": " Classics ", "author": "Jane", "difficulty": "medium",
+                "puzzles": ["{}", "{PUZZLE}"]}}"#,
+            PUZZLE.replace('.', "0")
+        );
+        let pack = parse_pack(&json, &[]).expect("valid pack");
+        assert_eq!(pack.id, 0);
+        assert_eq!(pack.name, "Classics");
+        assert_eq!(pack.difficulty, Some(Difficulty::Medium));
+        assert_eq!(pack.puzzles, vec![PUZZLE, PUZZLE]);
+        assert!(pack.puzzle(0).is_some());
+        assert_eq!(pack.puzzle(2), None);
+
+        let next = parse_pack(&json, &[pack]).expect("valid pack");
+        assert_eq!(next.id, 1);
+
+        let minimal = format!(r#"{{"name": "Minimal", "puzzles": ["{PUZZLE}"]}}"#);
+        let minimal = parse_pack(&minimal, &[]).expect("valid pack");
+        assert_eq!((minimal.author.as_str(), minimal.difficulty), ("", None));
+    }
+
+    #[test]
+    fn test_parse_invalid_pack() {
+        assert!(parse_pack("not json", &[]).is_err());
+        assert!(parse_pack(r#"{"name": "Empty", "puzzles": []}"#, &[]).is_err());
+        let unnamed = format!(r#"{{"name": " ", "puzzles": ["{PUZZLE}"]}}"#);
+        assert!(parse_pack(&unnamed, &[]).is_err());
+        let invalid = format!(r#"{{"name": "Bad", "puzzles": ["{PUZZLE}", "55"]}}"#);
+        let err = parse_pack(&invalid, &[]).expect_err("invalid puzzle");
+        assert!(err.to_string().starts_with("Puzzle 2:"));
+    }
+
+    #[test]
+    fn test_pack_progress() {
+        let pack = PuzzlePack {
+            id: 3,
+            name: "Pack".to_string(),
+            author: String::new(),
+            difficulty: None,
+            puzzles: vec![PUZZLE.to_string(); 3],
+        };
+        let mut progress = PackProgress::default();
+        assert_eq!(progress.next(&pack), Some(0));
+        progress.complete(3, 0);
+        progress.complete(3, 2);
+        progress.complete(3, 2);
+        assert!(progress.is_solved(3, 2));
+        assert_eq!(progress.solved(3), 2);
+        assert_eq!(progress.next(&pack), Some(1));
+        progress.complete(3, 1);
+        assert_eq!(progress.next(&pack), None);
+        progress.remove(3);
+        assert_eq!(progress.solved(3), 0);
+    }
+}
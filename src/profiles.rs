@@ -0,0 +1,154 @@
+//! # Profiles Module
+//!
+//! The `profiles` module contains the local player profiles.
+//!
+//! Every profile has its own settings, save slots and, in general,
+//! everything the app keeps in the local storage.
+//! This is done by namespacing the local storage keys with
+//! [`profile_key`].
+//! The first profile uses the keys as they are,
+//! so the data stored before profiles existed belongs to it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::browser::{local_storage_get, local_storage_set};
+
+/// Local storage key of the profiles
+const PROFILES_KEY: &str = "sudoku-profiles";
+
+/// Local storage key of the active profile
+const ACTIVE_PROFILE_KEY: &str = "sudoku-profile";
+
+/// A local player profile
+///
+/// - `id: u32`: the unique id of the profile.
+/// - `name: String`: the name of the player.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: "Player".to_string(),
+        }
+    }
+}
+
+/// Returns the local storage key of a profile's data.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `key: &str` - The key of the data.
+///
+/// ## Returns
+///
+/// Returns `key` itself for the first profile,
+/// otherwise `key` suffixed with the profile id.
+#[must_use]
+pub fn profile_key(profile: u32, key: &str) -> String {
+    if profile == 0 {
+        key.to_string()
+    } else {
+        format!("{key}-{profile}")
+    }
+}
+
+/// Loads the profiles from the local storage.
+///
+/// ## Returns
+///
+/// Returns the stored [`Profile`]s,
+/// or only the default one if none are stored or they cannot be read.
+#[must_use]
+pub fn load_profiles() -> Vec<Profile> {
+    local_storage_get(PROFILES_KEY)
+        .and_then(|json| serde_json::from_str::<Vec<Profile>>(&json).ok())
+        .filter(|profiles| !profiles.is_empty())
+        .unwrap_or_else(|| vec![Profile::default()])
+}
+
+/// Stores the profiles in the local storage.
+///
+/// ## Parameters
+///
+/// - `profiles: &[Profile]` - The profiles to store.
+pub fn store_profiles(profiles: &[Profile]) {
+    match serde_json::to_string(profiles) {
+        Ok(json) => local_storage_set(PROFILES_KEY, &json),
+        Err(err) => log::error!("failed to store the profiles: {err:?}"),
+    }
+}
+
+/// Loads the id of the active profile from the local storage.
+///
+/// ## Returns
+///
+/// Returns the stored id, or the id of the first profile if none is stored.
+#[must_use]
+pub fn load_active_profile() -> u32 {
+    local_storage_get(ACTIVE_PROFILE_KEY)
+        .and_then(|id| id.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Stores the id of the active profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the active profile.
+pub fn store_active_profile(profile: u32) {
+    local_storage_set(ACTIVE_PROFILE_KEY, &profile.to_string());
+}
+
+/// Creates a new profile.
+///
+/// ## Parameters
+///
+/// - `profiles: &[Profile]` - The existing profiles.
+/// - `name: &str` - The name of the player.
+///
+/// ## Returns
+///
+/// Returns the new [`Profile`] with an id not used by any other profile,
+/// or `None` if the name is blank.
+#[must_use]
+pub fn new_profile(profiles: &[Profile], name: &str) -> Option<Profile> {
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    let id = profiles
+        .iter()
+        .map(|profile| profile.id + 1)
+        .max()
+        .unwrap_or(0);
+    Some(Profile {
+        id,
+        name: name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_key() {
+        assert_eq!(profile_key(0, "sudoku-settings"), "sudoku-settings");
+        assert_eq!(profile_key(3, "sudoku-settings"), "sudoku-settings-3");
+    }
+
+    #[test]
+    fn test_new_profile() {
+        let profiles = vec![Profile::default()];
+        let profile = new_profile(&profiles, "  Ana ").expect("valid name");
+        assert_eq!(profile.id, 1);
+        assert_eq!(profile.name, "Ana");
+        assert!(new_profile(&profiles, "   ").is_none());
+    }
+}
@@ -0,0 +1,76 @@
+//! # RNG Module
+//!
+//! The `rng` module contains the [`GameRng`],
+//! the random number generator behind every random choice of the game,
+//! such as the generated puzzles and the cell filled by a hint.
+//!
+//! A [`GameRng`] is either seeded from the entropy of the platform,
+//! for a new random game,
+//! or from a known seed, so the same choices are made every time,
+//! as for the daily puzzle, the tests and the replays.
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+/// The random number generator of the game
+///
+/// It is a [`RngCore`], so it can be used wherever a [`rand::Rng`] is.
+#[derive(Debug, Clone)]
+pub struct GameRng(StdRng);
+
+impl GameRng {
+    /// Creates a random number generator seeded from the entropy of the
+    /// platform.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(StdRng::from_entropy())
+    }
+
+    /// Creates a random number generator from a seed.
+    ///
+    /// Generators with the same seed make the same choices.
+    ///
+    /// ## Parameters
+    ///
+    /// - `seed: u64` - The seed of the generator.
+    #[must_use]
+    pub fn seeded(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        let mut first = GameRng::seeded(42);
+        let mut second = GameRng::seeded(42);
+        assert_eq!(first.next_u64(), second.next_u64());
+        assert_ne!(first.next_u64(), GameRng::seeded(43).next_u64());
+    }
+}
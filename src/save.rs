@@ -0,0 +1,681 @@
+//! # Save Module
+//!
+//! The `save` module provides full-fidelity save files of a Sudoku game,
+//! so a game can be backed up and moved across devices without a server.
+//!
+//! Unlike the [`encoding`](crate::encoding) of a game in the URL,
+//! a save file keeps the whole history of moves, with the notes on them,
+//! the timer and a snapshot of the [`Settings`].
+//!
+//! The in-progress games are kept in [`SaveSlot`]s in the local storage,
+//! so several games can be played at the same time.
+//! Every [`Profile`](crate::profiles::Profile) has its own save slots.
+//! The slots deleted by the user are remembered as [`DeletedSlot`]s,
+//! so a [`sync`](crate::sync) does not bring them back from another device.
+//!
+//! ## Format
+//!
+//! Save files are JSON documents with a `version` field.
+//! The puzzle is written in the line format, with `.` for empty cells,
+//! so the files stay readable,
+//! and the moves as the `[index, old, new]` cells they change,
+//! as [`history`](crate::history) deltas:
+//!
+//! ```json
+//! {
+//!   "version": 2,
+//!   "initial": "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+//!   "history": [[[2, 0, 4]], [[2, 4, 0], [3, 0, 6]]],
+//!   "move_notes": [null, "guessing 6 here"],
+//!   "notes": [0, 0, 3, ...],
+//!   "timer": 93000,
+//!   "settings": { "show_timer": true }
+//! }
+//! ```
+//!
+//! The notes on the moves, if any, are in `move_notes`, one per delta.
+//!
+//! The whole [`UndoTree`] of the game, with the branches undone,
+//! is in `tree`, if saved:
+//! every node with the index of its parent, the cells it changes from it,
+//! the child it redoes and its note, and the index of the current node.
+//! The moves in `history` are the path from the puzzle to the current node,
+//! so a file without a `tree` has a single branch.
+//!
+//! ```json
+//! "tree": {
+//!   "nodes": [
+//!     { "changes": [], "redo": 2 },
+//!     { "parent": 0, "changes": [[2, 0, 4]] },
+//!     { "parent": 0, "changes": [[3, 0, 6]], "note": "guessing 6 here" }
+//!   ],
+//!   "current": 2
+//! }
+//! ```
+//!
+//! Files of version 1, with a whole board in the line format per move in
+//! `moves`, are still imported.
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{SudokuNotes, SudokuState};
+use crate::browser::{local_storage_get, local_storage_set};
+use crate::difficulty::Difficulty;
+use crate::formats::{parse_line, to_line};
+use crate::history::{from_deltas, to_deltas, MoveDelta};
+use crate::profiles::profile_key;
+use crate::settings::Settings;
+use crate::undo_tree::{Node, UndoTree};
+use crate::utils::validate_puzzle;
+
+/// Current version of the save file format
+const VERSION: u32 = 2;
+
+/// Local storage key of the save slots
+const SLOTS_KEY: &str = "sudoku-slots";
+
+/// Local storage key of the deleted save slots
+const DELETED_SLOTS_KEY: &str = "sudoku-deleted-slots";
+
+/// A saved Sudoku game
+///
+/// - `initial: SudokuState`: the initial puzzle, where non-zero cells are clues.
+/// - `moves: Vec<SudokuState>`: the history of boards, starting at the initial
+///   puzzle and ending at the current board.
+/// - `notes: SudokuNotes`: the notes of every cell.
+/// - `timer: u64`: the elapsed time of the game, in milliseconds.
+/// - `settings: Settings`: the settings when the game was saved.
+/// - `move_notes: Vec<Option<String>>`: the note on every move after the
+///   puzzle, if any, or none if no move has a note.
+///   See also: [`UndoTree::annotate`].
+/// - `tree: Option<UndoTree>`: the whole tree of the moves,
+///   with the branches undone, whose path to the current node is `moves`,
+///   or `None` for the single branch of `moves`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "SaveFile", into = "SaveFile")]
+pub struct SavedGame {
+    pub initial: SudokuState,
+    pub moves: Vec<SudokuState>,
+    pub notes: SudokuNotes,
+    pub timer: u64,
+    pub settings: Settings,
+    pub move_notes: Vec<Option<String>>,
+    pub tree: Option<UndoTree>,
+}
+
+impl SavedGame {
+    /// Returns the current board of the game.
+    ///
+    /// ## Panics
+    ///
+    /// The function will panic if the game has no moves.
+    #[must_use]
+    pub fn current(&self) -> SudokuState {
+        *self
+            .moves
+            .last()
+            .expect("failed to get the current sudoku state")
+    }
+
+    /// Returns the note on a move, if it has one.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: usize` - The index of the move in `moves`,
+    ///   where 0 is the puzzle, which has no note.
+    #[must_use]
+    pub fn move_note(&self, index: usize) -> Option<&str> {
+        self.move_notes.get(index.checked_sub(1)?)?.as_deref()
+    }
+
+    /// Returns the [`UndoTree`] of the moves of the game.
+    ///
+    /// Without a saved tree, the tree is the single branch of the moves,
+    /// at its last move and with the notes on the moves.
+    #[must_use]
+    pub fn undo_tree(&self) -> UndoTree {
+        if let Some(tree) = &self.tree {
+            return tree.clone();
+        }
+        let mut tree =
+            UndoTree::from_moves(&self.moves).unwrap_or_else(|| UndoTree::new(self.initial));
+        // the nodes of a single branch are its moves, in order
+        for (node, note) in (1..).zip(&self.move_notes) {
+            if let Some(note) = note {
+                tree.annotate(node, note);
+            }
+        }
+        tree
+    }
+
+    /// Returns the progress of the game.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the number of filled non-clue cells,
+    /// and the number of non-clue cells.
+    #[must_use]
+    pub fn progress(&self) -> (usize, usize) {
+        let current = self.current();
+        let empty: Vec<usize> = (0..81).filter(|&idx| self.initial[idx] == 0).collect();
+        let filled = empty.iter().filter(|&&idx| current[idx] != 0).count();
+        (filled, empty.len())
+    }
+}
+
+/// The JSON representation of a [`SavedGame`]
+///
+/// The moves are in `history` since version 2, and in `moves` before.
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    initial: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    moves: Vec<String>,
+    #[serde(default)]
+    history: Vec<MoveDelta>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    move_notes: Vec<Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tree: Option<TreeFile>,
+    notes: Vec<u16>,
+    timer: u64,
+    #[serde(default)]
+    settings: Settings,
+}
+
+/// The JSON representation of an [`UndoTree`] in a [`SaveFile`]
+#[derive(Serialize, Deserialize)]
+struct TreeFile {
+    nodes: Vec<NodeFile>,
+    current: usize,
+}
+
+/// The JSON representation of a [`Node`] in a [`TreeFile`]
+#[derive(Serialize, Deserialize)]
+struct NodeFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<usize>,
+    changes: MoveDelta,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    redo: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
+impl From<&UndoTree> for TreeFile {
+    fn from(tree: &UndoTree) -> Self {
+        Self {
+            nodes: tree
+                .nodes()
+                .iter()
+                .map(|node| NodeFile {
+                    parent: node.parent,
+                    changes: node.changes.clone(),
+                    redo: node.redo,
+                    note: node.note.clone(),
+                })
+                .collect(),
+            current: tree.current(),
+        }
+    }
+}
+
+impl TreeFile {
+    /// Rebuilds the [`UndoTree`] of a puzzle.
+    fn into_tree(self, initial: SudokuState) -> Result<UndoTree> {
+        let nodes = self
+            .nodes
+            .into_iter()
+            .map(|node| Node {
+                changes: node.changes,
+                parent: node.parent,
+                redo: node.redo,
+                note: node.note,
+            })
+            .collect();
+        UndoTree::from_nodes(initial, nodes, self.current)
+            .ok_or_else(|| Error::msg("Save file has an invalid undo tree"))
+    }
+}
+
+impl From<SavedGame> for SaveFile {
+    fn from(game: SavedGame) -> Self {
+        Self {
+            version: VERSION,
+            initial: to_line(&game.initial, '.'),
+            moves: vec![],
+            history: to_deltas(&game.moves),
+            move_notes: game.move_notes,
+            tree: game.tree.as_ref().map(TreeFile::from),
+            notes: game.notes.to_vec(),
+            timer: game.timer,
+            settings: game.settings,
+        }
+    }
+}
+
+impl TryFrom<SaveFile> for SavedGame {
+    type Error = Error;
+
+    fn try_from(file: SaveFile) -> Result<Self> {
+        let initial = parse_line(&file.initial)?;
+        validate_puzzle(&initial)?;
+
+        let moves = match file.version {
+            1 => file
+                .moves
+                .iter()
+                .map(|line| parse_line(line))
+                .collect::<Result<Vec<_>>>()?,
+            VERSION => from_deltas(initial, &file.history)?,
+            _ => return Err(Error::msg("Unknown save file version")),
+        };
+        if moves.first() != Some(&initial) {
+            return Err(Error::msg("Save file moves do not start at the puzzle"));
+        }
+        let changes_clue = |board: &SudokuState| {
+            initial
+                .iter()
+                .zip(board)
+                .any(|(&clue, &value)| clue != 0 && clue != value)
+        };
+        if moves.iter().any(changes_clue) {
+            return Err(Error::msg("Save file moves change the puzzle clues"));
+        }
+        if !file.move_notes.is_empty() && file.move_notes.len() + 1 != moves.len() {
+            return Err(Error::msg("Save file must have a note for every move"));
+        }
+        let tree = file.tree.map(|tree| tree.into_tree(initial)).transpose()?;
+        if let Some(tree) = &tree {
+            if tree.boards().iter().any(changes_clue) {
+                return Err(Error::msg("Save file moves change the puzzle clues"));
+            }
+            if tree.moves() != moves || tree.move_notes() != file.move_notes {
+                return Err(Error::msg("Save file undo tree does not lead to its moves"));
+            }
+        }
+
+        let notes: SudokuNotes = file
+            .notes
+            .try_into()
+            .map_err(|_| Error::msg("Save file must have notes for 81 cells"))?;
+        if notes.iter().any(|&mask| mask > 0x1FF) {
+            return Err(Error::msg("Save file has invalid notes"));
+        }
+
+        Ok(Self {
+            initial,
+            moves,
+            notes,
+            timer: file.timer,
+            settings: file.settings,
+            move_notes: file.move_notes,
+            tree,
+        })
+    }
+}
+
+/// Exports a [`SavedGame`] to a JSON save file.
+///
+/// ## Parameters
+///
+/// - `game: &SavedGame` - A reference to the game to export.
+///
+/// ## Returns
+///
+/// Returns a Result containing the pretty-printed JSON `String`.
+///
+/// ## Errors
+///
+/// Returns an error if the game cannot be serialized.
+pub fn export_save(game: &SavedGame) -> Result<String> {
+    Ok(serde_json::to_string_pretty(game)?)
+}
+
+/// Imports a JSON save file created by [`export_save`].
+///
+/// ## Parameters
+///
+/// - `json: &str` - The contents of the save file.
+///
+/// ## Returns
+///
+/// Returns a Result containing the [`SavedGame`].
+///
+/// ## Errors
+///
+/// Returns an error if the file is not valid JSON, has an unknown version,
+/// its initial puzzle is invalid, or its moves or notes do not belong to
+/// the initial puzzle or to its moves.
+pub fn import_save(json: &str) -> Result<SavedGame> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// A save slot holding one of the in-progress games
+///
+/// - `id: u32`: the unique id of the slot.
+/// - `difficulty: Difficulty`: the difficulty of the puzzle.
+/// - `game: SavedGame`: the saved game.
+/// - `modified_at: u64`: when the game was last saved,
+///   in milliseconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveSlot {
+    pub id: u32,
+    pub difficulty: Difficulty,
+    pub game: SavedGame,
+    #[serde(default)]
+    pub modified_at: u64,
+}
+
+/// Loads the save slots of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`SaveSlot`]s, most recently played first,
+/// or none if they cannot be read.
+/// Slots that cannot be read are skipped, keeping the others.
+#[must_use]
+pub fn load_slots(profile: u32) -> Vec<SaveSlot> {
+    local_storage_get(&profile_key(profile, SLOTS_KEY))
+        .map(|json| parse_slots(&json))
+        .unwrap_or_default()
+}
+
+/// Parses the stored save slots, skipping the ones that cannot be read.
+fn parse_slots(json: &str) -> Vec<SaveSlot> {
+    serde_json::from_str::<Vec<serde_json::Value>>(json)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|slot| match serde_json::from_value(slot) {
+            Ok(slot) => Some(slot),
+            Err(err) => {
+                log::error!("skipped an unreadable save slot: {err:?}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Stores the save slots of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `slots: &[SaveSlot]` - The slots to store.
+pub fn store_slots(profile: u32, slots: &[SaveSlot]) {
+    match serde_json::to_string(slots) {
+        Ok(json) => local_storage_set(&profile_key(profile, SLOTS_KEY), &json),
+        Err(err) => log::error!("failed to store the save slots: {err:?}"),
+    }
+}
+
+/// A save slot deleted by the user
+///
+/// - `puzzle: String`: the puzzle of the slot, in the line format.
+/// - `deleted_at: u64`: when the slot was deleted,
+///   in milliseconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeletedSlot {
+    pub puzzle: String,
+    pub deleted_at: u64,
+}
+
+impl DeletedSlot {
+    /// Creates the deletion of the slot of a puzzle.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: &SudokuState` - The puzzle of the slot.
+    /// - `deleted_at: u64` - When the slot is deleted,
+    ///   in milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn new(puzzle: &SudokuState, deleted_at: u64) -> Self {
+        Self {
+            puzzle: to_line(puzzle, '.'),
+            deleted_at,
+        }
+    }
+
+    /// Returns whether a slot is the one deleted,
+    /// i.e. it has its puzzle and was not saved again since.
+    #[must_use]
+    pub fn deletes(&self, slot: &SaveSlot) -> bool {
+        slot.modified_at <= self.deleted_at && self.is_of(&slot.game.initial)
+    }
+
+    /// Returns whether the deleted slot had a puzzle.
+    #[must_use]
+    pub fn is_of(&self, puzzle: &SudokuState) -> bool {
+        self.puzzle == to_line(puzzle, '.')
+    }
+}
+
+/// Loads the deleted save slots of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+#[must_use]
+pub fn load_deleted_slots(profile: u32) -> Vec<DeletedSlot> {
+    local_storage_get(&profile_key(profile, DELETED_SLOTS_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the deleted save slots of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `deleted: &[DeletedSlot]` - The deleted slots to store.
+pub fn store_deleted_slots(profile: u32, deleted: &[DeletedSlot]) {
+    match serde_json::to_string(deleted) {
+        Ok(json) => local_storage_set(&profile_key(profile, DELETED_SLOTS_KEY), &json),
+        Err(err) => log::error!("failed to store the deleted save slots: {err:?}"),
+    }
+}
+
+/// Returns an id that is not used by any of the save slots.
+///
+/// ## Parameters
+///
+/// - `slots: &[SaveSlot]` - The existing slots.
+#[must_use]
+pub fn next_slot_id(slots: &[SaveSlot]) -> u32 {
+    slots.iter().map(|slot| slot.id + 1).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_sudoku;
+
+    fn saved_game() -> SavedGame {
+        let initial = create_sudoku();
+        let mut current = initial;
+        let mut notes: SudokuNotes = [0; 81];
+        let empty: Vec<usize> = (0..81).filter(|&idx| initial[idx] == 0).collect();
+        current[empty[0]] = 4;
+        notes[empty[1]] = 0b1_0010_0001;
+        SavedGame {
+            initial,
+            moves: vec![initial, current],
+            notes,
+            timer: 93_000,
+            settings: Settings {
+                show_timer: false,
+                ..Settings::default()
+            },
+            move_notes: vec![Some("guessing 4 here".to_string())],
+            tree: None,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let game = saved_game();
+        let json = export_save(&game).expect("export");
+        assert_eq!(import_save(&json).expect("import"), game);
+        assert_eq!(game.move_note(1), Some("guessing 4 here"));
+        assert_eq!(game.move_note(0), None);
+
+        // the notes are kept on the moves of the undo tree
+        let tree = game.undo_tree();
+        assert_eq!(tree.moves(), game.moves);
+        assert_eq!(tree.move_notes(), game.move_notes);
+
+        let without_notes = SavedGame {
+            move_notes: vec![],
+            tree: None,
+            ..game
+        };
+        let json = export_save(&without_notes).expect("export");
+        assert!(!json.contains("move_notes"));
+        assert_eq!(import_save(&json).expect("import"), without_notes);
+    }
+
+    #[test]
+    fn test_round_trip_undo_tree() {
+        let game = saved_game();
+        let empty: Vec<usize> = (0..81).filter(|&idx| game.initial[idx] == 0).collect();
+        let mut tree = game.undo_tree();
+        // a branch undone, and the move before it
+        let mut branch = game.initial;
+        branch[empty[2]] = 7;
+        tree.undo();
+        tree.push(branch);
+        tree.annotate(tree.current(), "guessing 7 here");
+        tree.undo();
+        let game = SavedGame {
+            moves: tree.moves(),
+            move_notes: tree.move_notes(),
+            tree: Some(tree.clone()),
+            ..game
+        };
+        let json = export_save(&game).expect("export");
+        let imported = import_save(&json).expect("import");
+        assert_eq!(imported, game);
+        assert_eq!(imported.undo_tree(), tree);
+        assert_eq!(imported.undo_tree().node_count(), 3);
+
+        // a tree that does not lead to the moves
+        let mut file = serde_json::to_value(&game).expect("serialize");
+        file["tree"]["current"] = 1.into();
+        assert!(import_save(&file.to_string()).is_err());
+        // a tree node changing a clue
+        let clue = (0..81).find(|&idx| game.initial[idx] != 0).expect("clue");
+        let mut file = serde_json::to_value(&game).expect("serialize");
+        file["tree"]["nodes"][1]["changes"] = serde_json::json!([[clue, game.initial[clue], 0]]);
+        assert!(import_save(&file.to_string()).is_err());
+        // a node before its parent
+        let mut file = serde_json::to_value(&game).expect("serialize");
+        file["tree"]["nodes"][1]["parent"] = 2.into();
+        assert!(import_save(&file.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_saves() {
+        let game = saved_game();
+
+        let mut changed_clue = game.clone();
+        let clue = (0..81).find(|&idx| game.initial[idx] != 0).expect("clue");
+        changed_clue.moves[1][clue] = game.initial[clue] % 9 + 1;
+        let json = export_save(&changed_clue).expect("export");
+        assert!(import_save(&json).is_err());
+
+        let json = export_save(&game)
+            .expect("export")
+            .replace("\"version\": 2", "\"version\": 3");
+        assert!(import_save(&json).is_err());
+
+        let mut extra_note = game;
+        extra_note.move_notes.push(None);
+        let json = export_save(&extra_note).expect("export");
+        assert!(import_save(&json).is_err());
+
+        assert!(import_save("not json").is_err());
+    }
+
+    #[test]
+    fn test_import_rejects_unsafe_themes() {
+        let json = export_save(&saved_game()).expect("export");
+        let mut file: serde_json::Value = serde_json::from_str(&json).expect("json");
+        file["settings"]["custom_theme"] = serde_json::json!({
+            "background": "url(https://example.com/track.png)",
+        });
+        assert!(import_save(&file.to_string()).is_err());
+
+        file["settings"]["custom_theme"] = serde_json::json!({ "background": "#fdf6e3" });
+        let game = import_save(&file.to_string()).expect("import");
+        let theme = game.settings.custom_theme.expect("a theme");
+        assert_eq!(theme.background, "#fdf6e3");
+    }
+
+    #[test]
+    fn test_import_version_1() {
+        let game = saved_game();
+        let json = serde_json::json!({
+            "version": 1,
+            "initial": to_line(&game.initial, '.'),
+            "moves": game.moves.iter().map(|board| to_line(board, '.')).collect::<Vec<_>>(),
+            "notes": game.notes.to_vec(),
+            "timer": game.timer,
+            "settings": game.settings,
+        });
+        let game = SavedGame {
+            move_notes: vec![],
+            tree: None,
+            ..game
+        };
+        assert_eq!(import_save(&json.to_string()).expect("import"), game);
+    }
+
+    #[test]
+    fn test_progress() {
+        let game = saved_game();
+        let (filled, empty) = game.progress();
+        assert_eq!(filled, 1);
+        assert_eq!(
+            empty,
+            81 - game.initial.iter().filter(|&&value| value != 0).count()
+        );
+    }
+
+    #[test]
+    fn test_parse_slots_skips_unreadable_slots() {
+        let slot = SaveSlot {
+            id: 3,
+            difficulty: Difficulty::Easy,
+            game: saved_game(),
+            modified_at: 1_000,
+        };
+        let json = serde_json::json!([
+            serde_json::to_value(&slot).expect("serialize"),
+            { "id": 4, "difficulty": "easy", "game": { "version": 99 } },
+            "not a slot",
+        ]);
+        assert_eq!(parse_slots(&json.to_string()), vec![slot]);
+        assert!(parse_slots("not json").is_empty());
+    }
+
+    #[test]
+    fn test_next_slot_id() {
+        assert_eq!(next_slot_id(&[]), 0);
+        let slots: Vec<SaveSlot> = [3, 1]
+            .into_iter()
+            .map(|id| SaveSlot {
+                id,
+                difficulty: Difficulty::Easy,
+                game: saved_game(),
+                modified_at: 0,
+            })
+            .collect();
+        assert_eq!(next_slot_id(&slots), 4);
+    }
+}
@@ -0,0 +1,559 @@
+//! # Settings Module
+//!
+//! The `settings` module contains the user's preferences for the Sudoku game
+//! and their persistence in the local storage of the browser.
+//!
+//! Settings are stored as JSON, separately for every
+//! [`Profile`](crate::profiles::Profile).
+//! Missing fields take their default value,
+//! so settings stored by older versions of the app keep loading.
+//! When they were last changed is stored next to them,
+//! so the [`sync`](crate::sync) keeps the latest changed settings.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::AnalyticsSink;
+use crate::browser::{local_storage_get, local_storage_set, now};
+use crate::difficulty::Difficulty;
+use crate::profiles::profile_key;
+use crate::symbols::SymbolSet;
+use crate::theme::Theme;
+
+/// Local storage key of the settings
+const SETTINGS_KEY: &str = "sudoku-settings";
+
+/// Local storage key of when the settings were last changed
+const SETTINGS_MODIFIED_KEY: &str = "sudoku-settings-modified";
+
+/// Range of the board scale, in percent
+pub const BOARD_SCALE: RangeInclusive<u32> = 50..=200;
+
+/// Range of the digit size, in percent of the cell size
+pub const DIGIT_SCALE: RangeInclusive<u32> = 60..=160;
+
+/// The board scale of the kids mode, in percent, for oversized cells
+pub const KIDS_BOARD_SCALE: u32 = 150;
+
+/// The hint budgets a game can have, where `None` is unlimited
+pub const HINT_BUDGETS: [Option<u32>; 5] = [None, Some(10), Some(5), Some(3), Some(0)];
+
+/// The colors of the app
+///
+/// Every palette has its [`Theme`](crate::theme::Theme).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Palette {
+    /// Blue highlights and red conflicts
+    #[default]
+    Default,
+    /// Blue highlights and orange conflicts,
+    /// safe for deuteranopia and protanopia
+    Colorblind,
+    /// Black on white with strong highlights
+    HighContrast,
+    /// Light on dark grays
+    Dark,
+}
+
+impl Palette {
+    /// All palettes
+    pub const ALL: [Self; 4] = [
+        Self::Default,
+        Self::Colorblind,
+        Self::HighContrast,
+        Self::Dark,
+    ];
+}
+
+impl fmt::Display for Palette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Default => "Default",
+            Self::Colorblind => "Colorblind-safe",
+            Self::HighContrast => "High contrast",
+            Self::Dark => "Dark",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Where the number pad is placed next to the board
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PadPlacement {
+    /// Below the board
+    #[default]
+    Bottom,
+    /// On the left of the board
+    Left,
+    /// On the right of the board
+    Right,
+}
+
+impl PadPlacement {
+    /// All placements
+    pub const ALL: [Self; 3] = [Self::Bottom, Self::Left, Self::Right];
+
+    /// Returns the name of the placement in the stylesheet.
+    #[must_use]
+    pub const fn css_name(self) -> &'static str {
+        match self {
+            Self::Bottom => "bottom",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+}
+
+impl fmt::Display for PadPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Bottom => "Bottom",
+            Self::Left => "Left",
+            Self::Right => "Right",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// How the digits of the number pad are laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PadLayout {
+    /// A single row, from 1 to 9
+    #[default]
+    Row,
+    /// A 3x3 grid with 7, 8 and 9 on top, like a computer keypad
+    Grid,
+    /// A 3x3 grid with 1, 2 and 3 on top, like a phone keypad
+    Phone,
+}
+
+impl PadLayout {
+    /// All layouts
+    pub const ALL: [Self; 3] = [Self::Row, Self::Grid, Self::Phone];
+
+    /// Returns the name of the layout in the stylesheet.
+    #[must_use]
+    pub const fn css_name(self) -> &'static str {
+        match self {
+            Self::Row => "row",
+            Self::Grid => "grid",
+            Self::Phone => "phone",
+        }
+    }
+
+    /// Returns the numbers of the pad in the order they are laid out,
+    /// where 0 is the delete button, which comes last.
+    #[must_use]
+    pub const fn numbers(self) -> [u8; 10] {
+        match self {
+            Self::Row | Self::Phone => [1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+            Self::Grid => [7, 8, 9, 4, 5, 6, 1, 2, 3, 0],
+        }
+    }
+}
+
+impl fmt::Display for PadLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Row => "Row",
+            Self::Grid => "Keypad",
+            Self::Phone => "Phone",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The size of the control buttons
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlSize {
+    /// Smaller than the cells
+    Small,
+    /// As large as the cells
+    #[default]
+    Medium,
+    /// Larger than the cells
+    Large,
+}
+
+impl ControlSize {
+    /// All sizes
+    pub const ALL: [Self; 3] = [Self::Small, Self::Medium, Self::Large];
+
+    /// Returns the factor the control buttons are scaled by.
+    #[must_use]
+    pub const fn scale(self) -> f64 {
+        match self {
+            Self::Small => 0.8,
+            Self::Medium => 1.0,
+            Self::Large => 1.25,
+        }
+    }
+}
+
+impl fmt::Display for ControlSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Small => "Small",
+            Self::Medium => "Medium",
+            Self::Large => "Large",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// How values are entered on the board
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputMode {
+    /// A cell is picked, then the digit to enter in it
+    #[default]
+    CellFirst,
+    /// A digit is picked, then every cell to stamp it in
+    NumberFirst,
+}
+
+impl InputMode {
+    /// All input modes
+    pub const ALL: [Self; 2] = [Self::CellFirst, Self::NumberFirst];
+}
+
+impl fmt::Display for InputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::CellFirst => "Cell first",
+            Self::NumberFirst => "Number first",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The user's preferences
+///
+/// - `show_timer: bool`: whether the game timer is displayed.
+/// - `show_track: bool`: whether the status bar shows if all the entries
+///   match the solution, off by default.
+/// - `auto_complete: bool`: whether to offer to finish the board once only
+///   naked singles are left, off by default.
+/// - `haptics: bool`: whether the device vibrates for placements, conflicts
+///   and completion.
+///   See also: [`haptics`](crate::haptics).
+/// - `reduce_motion: bool`: whether animations are turned off,
+///   even if the operating system does not ask for reduced motion.
+/// - `confirm_discard: bool`: whether to ask before discarding a game with
+///   progress, such as when starting a new one.
+/// - `speedrun: bool`: whether the timer shows the milliseconds,
+///   a new puzzle of the same difficulty starts from the keyboard,
+///   and the times are kept as personal bests, off by default.
+///   See also: [`speedrun`](crate::speedrun).
+/// - `ghost: bool`: whether a puzzle of a difficulty with a personal best
+///   is raced against the progress of that best game, off by default.
+/// - `palette: Palette`: the colors of the app.
+/// - `custom_theme: Option<Theme>`: the theme imported by the user,
+///   which takes the place of the palette, if any.
+/// - `symbols: SymbolSet`: the symbols the digits are shown in.
+/// - `large_print: bool`: whether the board is laid out for low vision,
+///   with the largest digits, thick box borders, strong highlights and
+///   the number pad in a large strip fixed to the bottom of the screen,
+///   whatever the board scale, off by default.
+/// - `kids: bool`: whether the child-friendly layout is on,
+///   set with [`Settings::set_kids`], off by default.
+/// - `board_scale: u32`: the size of the board, in percent,
+///   within [`BOARD_SCALE`].
+/// - `digit_scale: u32`: the size of the digits, in percent of the cell size,
+///   within [`DIGIT_SCALE`].
+/// - `pad_placement: PadPlacement`: where the number pad is placed.
+/// - `pad_layout: PadLayout`: how the digits of the number pad are laid out.
+/// - `control_size: ControlSize`: the size of the control buttons.
+/// - `input_mode: InputMode`: how values are entered on the board.
+/// - `hint_budget: Option<u32>`: how many hints a game can use,
+///   one of [`HINT_BUDGETS`], unlimited by default.
+/// - `server_url: String`: the URL of the server accounts sign in to and
+///   games are synced to, or empty if there is none.
+///   See also: [`auth`](crate::auth) and [`sync`](crate::sync).
+/// - `analytics: AnalyticsSink`: where anonymous gameplay events are
+///   recorded, off by default.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub show_timer: bool,
+    pub show_track: bool,
+    pub auto_complete: bool,
+    pub haptics: bool,
+    pub reduce_motion: bool,
+    pub confirm_discard: bool,
+    pub speedrun: bool,
+    pub ghost: bool,
+    pub palette: Palette,
+    pub custom_theme: Option<Theme>,
+    pub symbols: SymbolSet,
+    pub large_print: bool,
+    pub kids: bool,
+    pub board_scale: u32,
+    pub digit_scale: u32,
+    pub pad_placement: PadPlacement,
+    pub pad_layout: PadLayout,
+    pub control_size: ControlSize,
+    pub input_mode: InputMode,
+    pub hint_budget: Option<u32>,
+    pub server_url: String,
+    pub analytics: AnalyticsSink,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            show_timer: true,
+            show_track: false,
+            auto_complete: false,
+            haptics: true,
+            reduce_motion: false,
+            confirm_discard: true,
+            speedrun: false,
+            ghost: false,
+            palette: Palette::Default,
+            custom_theme: None,
+            symbols: SymbolSet::Digits,
+            large_print: false,
+            kids: false,
+            board_scale: 100,
+            digit_scale: 100,
+            pad_placement: PadPlacement::Bottom,
+            pad_layout: PadLayout::Row,
+            control_size: ControlSize::Medium,
+            input_mode: InputMode::CellFirst,
+            hint_budget: None,
+            server_url: String::new(),
+            analytics: AnalyticsSink::Off,
+        }
+    }
+}
+
+impl Settings {
+    /// Sets the board scale, clamped to [`BOARD_SCALE`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `percent: u32` - The size of the board, in percent.
+    pub fn set_board_scale(&mut self, percent: u32) {
+        self.board_scale = percent.clamp(*BOARD_SCALE.start(), *BOARD_SCALE.end());
+    }
+
+    /// Sets the digit size, clamped to [`DIGIT_SCALE`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `percent: u32` - The size of the digits, in percent of the cell size.
+    pub fn set_digit_scale(&mut self, percent: u32) {
+        self.digit_scale = percent.clamp(*DIGIT_SCALE.start(), *DIGIT_SCALE.end());
+    }
+
+    /// Turns the kids mode on or off.
+    ///
+    /// The kids mode is a preset of the layout:
+    /// the digits are shown in a [`SymbolSet`] made for children,
+    /// the colors unless another one is picked,
+    /// the cells are oversized, at least [`KIDS_BOARD_SCALE`],
+    /// the control buttons are large, and the timer is hidden.
+    /// New games are easy, see [`Settings::new_game_difficulty`].
+    /// Turning it off puts these settings back to their defaults.
+    ///
+    /// ## Parameters
+    ///
+    /// - `on: bool` - Whether the kids mode is on.
+    pub fn set_kids(&mut self, on: bool) {
+        let default = Self::default();
+        self.kids = on;
+        if on {
+            if !self.symbols.is_for_kids() {
+                self.symbols = SymbolSet::Colors;
+            }
+            self.board_scale = self.board_scale.max(KIDS_BOARD_SCALE);
+            self.control_size = ControlSize::Large;
+            self.show_timer = false;
+        } else {
+            self.symbols = default.symbols;
+            self.board_scale = default.board_scale;
+            self.control_size = default.control_size;
+            self.show_timer = default.show_timer;
+        }
+    }
+
+    /// Returns the difficulty of a new game.
+    ///
+    /// The kids mode only plays [`Difficulty::Easy`] puzzles,
+    /// which are solved with singles,
+    /// whatever difficulty was chosen.
+    ///
+    /// ## Parameters
+    ///
+    /// - `chosen: Option<Difficulty>` - The difficulty chosen by the player,
+    ///   or `None` for any difficulty.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the difficulty of the new puzzle,
+    /// or `None` for any difficulty.
+    #[must_use]
+    pub const fn new_game_difficulty(&self, chosen: Option<Difficulty>) -> Option<Difficulty> {
+        if self.kids {
+            Some(Difficulty::Easy)
+        } else {
+            chosen
+        }
+    }
+}
+
+/// Loads the settings of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`Settings`],
+/// or the default ones if none are stored or they cannot be read.
+#[must_use]
+pub fn load_settings(profile: u32) -> Settings {
+    local_storage_get(&profile_key(profile, SETTINGS_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the settings of a profile in the local storage.
+///
+/// If they changed, they are stamped with the current time,
+/// see [`load_settings_modified_at`].
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `settings: &Settings` - A reference to the settings to store.
+pub fn store_settings(profile: u32, settings: &Settings) {
+    let key = profile_key(profile, SETTINGS_KEY);
+    match serde_json::to_string(settings) {
+        Ok(json) if local_storage_get(&key).as_deref() != Some(json.as_str()) => {
+            local_storage_set(&key, &json);
+            store_settings_modified_at(profile, now());
+        }
+        Ok(_) => {}
+        Err(err) => log::error!("failed to store the settings: {err:?}"),
+    }
+}
+
+/// Stores the settings of a profile synced from another device,
+/// stamped with when they were changed there.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `settings: &Settings` - A reference to the settings to store.
+/// - `modified_at: u64` - When the settings were last changed,
+///   in milliseconds since the Unix epoch.
+pub fn store_synced_settings(profile: u32, settings: &Settings, modified_at: u64) {
+    match serde_json::to_string(settings) {
+        Ok(json) => {
+            local_storage_set(&profile_key(profile, SETTINGS_KEY), &json);
+            store_settings_modified_at(profile, modified_at);
+        }
+        Err(err) => log::error!("failed to store the settings: {err:?}"),
+    }
+}
+
+/// Loads when the settings of a profile were last changed.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the time in milliseconds since the Unix epoch,
+/// or 0 if the settings were never changed.
+#[must_use]
+pub fn load_settings_modified_at(profile: u32) -> u64 {
+    local_storage_get(&profile_key(profile, SETTINGS_MODIFIED_KEY))
+        .and_then(|millis| millis.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Stores when the settings of a profile were last changed.
+fn store_settings_modified_at(profile: u32, modified_at: u64) {
+    local_storage_set(
+        &profile_key(profile, SETTINGS_MODIFIED_KEY),
+        &modified_at.to_string(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_are_defaulted() {
+        let settings: Settings = serde_json::from_str("{}").expect("valid settings");
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
+    fn test_scales_are_clamped() {
+        let mut settings = Settings::default();
+        settings.set_board_scale(1000);
+        assert_eq!(settings.board_scale, *BOARD_SCALE.end());
+        settings.set_board_scale(120);
+        assert_eq!(settings.board_scale, 120);
+        settings.set_digit_scale(0);
+        assert_eq!(settings.digit_scale, *DIGIT_SCALE.start());
+    }
+
+    #[test]
+    fn test_kids_preset() {
+        let mut settings = Settings::default();
+        settings.set_kids(true);
+        assert!(settings.kids);
+        assert_eq!(settings.symbols, SymbolSet::Colors);
+        assert_eq!(settings.board_scale, KIDS_BOARD_SCALE);
+        assert!(!settings.show_timer);
+
+        // a symbol set for kids is kept
+        settings.symbols = SymbolSet::Animals;
+        settings.set_kids(true);
+        assert_eq!(settings.symbols, SymbolSet::Animals);
+
+        assert_eq!(
+            settings.new_game_difficulty(Some(Difficulty::Expert)),
+            Some(Difficulty::Easy)
+        );
+        assert_eq!(settings.new_game_difficulty(None), Some(Difficulty::Easy));
+
+        settings.set_kids(false);
+        assert_eq!(settings, Settings::default());
+        assert_eq!(settings.new_game_difficulty(None), None);
+    }
+
+    #[test]
+    fn test_palette_serde() {
+        let json = serde_json::to_string(&Palette::HighContrast).expect("serialize");
+        assert_eq!(json, "\"high_contrast\"");
+        assert_eq!(
+            serde_json::from_str::<Palette>(&json).expect("deserialize"),
+            Palette::HighContrast
+        );
+    }
+
+    #[test]
+    fn test_pad_layouts_have_every_number() {
+        for layout in PadLayout::ALL {
+            let mut numbers = layout.numbers();
+            numbers.sort_unstable();
+            assert_eq!(numbers, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9], "{layout}");
+        }
+    }
+}
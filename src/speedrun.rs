@@ -0,0 +1,302 @@
+//! # Speedrun Module
+//!
+//! The `speedrun` module keeps the personal bests of the speedrun mode,
+//! see [`speedrun`](crate::settings::Settings::speedrun).
+//!
+//! Every puzzle solved in speedrun mode submits its time to the
+//! [`PersonalBests`] of its difficulty,
+//! which are kept in the local storage for every
+//! [`Profile`](crate::profiles::Profile).
+//!
+//! The progress of every personal best is kept as a [`Ghost`],
+//! so the next puzzle of its difficulty can race it.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::SudokuState;
+use crate::browser::{local_storage_get, local_storage_set};
+use crate::difficulty::Difficulty;
+use crate::profiles::profile_key;
+
+/// Local storage key of the personal bests
+const BESTS_KEY: &str = "speedrun-bests";
+
+/// Local storage key of the ghosts of the personal bests
+const GHOSTS_KEY: &str = "speedrun-ghosts";
+
+/// The fastest time of a profile for every difficulty, in milliseconds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalBests(pub BTreeMap<Difficulty, u64>);
+
+impl PersonalBests {
+    /// Returns the personal best of a difficulty,
+    /// or `None` if no puzzle of it was solved in speedrun mode.
+    ///
+    /// ## Parameters
+    ///
+    /// - `difficulty: Difficulty` - The difficulty.
+    #[must_use]
+    pub fn best(&self, difficulty: Difficulty) -> Option<u64> {
+        self.0.get(&difficulty).copied()
+    }
+
+    /// Submits the time a puzzle was solved in.
+    ///
+    /// ## Parameters
+    ///
+    /// - `difficulty: Difficulty` - The difficulty of the puzzle.
+    /// - `millis: u64` - The time it was solved in, kept if it is the best.
+    ///
+    /// ## Returns
+    ///
+    /// Returns whether the time is a new personal best.
+    pub fn submit(&mut self, difficulty: Difficulty, millis: u64) -> bool {
+        if self.best(difficulty).is_some_and(|best| best <= millis) {
+            return false;
+        }
+        self.0.insert(difficulty, millis);
+        true
+    }
+}
+
+/// Loads the personal bests of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`PersonalBests`],
+/// or none if none are stored or they cannot be read.
+#[must_use]
+pub fn load_personal_bests(profile: u32) -> PersonalBests {
+    local_storage_get(&profile_key(profile, BESTS_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the personal bests of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `bests: &PersonalBests` - The personal bests to store.
+pub fn store_personal_bests(profile: u32, bests: &PersonalBests) {
+    match serde_json::to_string(bests) {
+        Ok(json) => local_storage_set(&profile_key(profile, BESTS_KEY), &json),
+        Err(err) => log::error!("failed to store the personal bests: {err:?}"),
+    }
+}
+
+/// The progress of a game over time, raced as a ghost
+///
+/// - `cells: u8`: the number of cells to fill, the empty cells of the
+///   puzzle.
+/// - `marks: Vec<(u64, u8)>`: the number of cells filled after every move
+///   that changed it, with the time of the move, in milliseconds, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ghost {
+    pub cells: u8,
+    pub marks: Vec<(u64, u8)>,
+}
+
+impl Ghost {
+    /// Records the number of cells filled at a time.
+    ///
+    /// The marks after the time are dropped, as when the game is restarted.
+    ///
+    /// ## Parameters
+    ///
+    /// - `filled: u8` - The number of cells filled.
+    /// - `millis: u64` - The elapsed time of the game, in milliseconds.
+    pub fn record(&mut self, filled: u8, millis: u64) {
+        self.marks.retain(|&(mark, _)| mark <= millis);
+        if self.filled(millis) != filled {
+            self.marks.push((millis, filled));
+        }
+    }
+
+    /// Returns the number of cells filled at a time.
+    ///
+    /// ## Parameters
+    ///
+    /// - `millis: u64` - The elapsed time of the game, in milliseconds.
+    #[must_use]
+    pub fn filled(&self, millis: u64) -> u8 {
+        self.marks
+            .iter()
+            .take_while(|&&(mark, _)| mark <= millis)
+            .last()
+            .map_or(0, |&(_, filled)| filled)
+    }
+
+    /// Returns the progress at a time, in percent of the cells to fill.
+    ///
+    /// ## Parameters
+    ///
+    /// - `millis: u64` - The elapsed time of the game, in milliseconds.
+    #[must_use]
+    pub fn progress(&self, millis: u64) -> u8 {
+        percent(self.filled(millis), self.cells)
+    }
+}
+
+/// Returns a number of cells in percent of another, or 100 if it is 0.
+///
+/// ## Parameters
+///
+/// - `filled: u8` - The number of cells filled.
+/// - `cells: u8` - The number of cells to fill.
+#[must_use]
+pub fn percent(filled: u8, cells: u8) -> u8 {
+    if cells == 0 {
+        return 100;
+    }
+    u8::try_from(u16::from(filled.min(cells)) * 100 / u16::from(cells)).unwrap_or(100)
+}
+
+/// Returns the number of empty cells of a puzzle filled on a board.
+///
+/// ## Parameters
+///
+/// - `puzzle: &SudokuState` - The puzzle of the game.
+/// - `board: &SudokuState` - The current board.
+#[must_use]
+pub fn filled_cells(puzzle: &SudokuState, board: &SudokuState) -> u8 {
+    let filled = (0..81)
+        .filter(|&index| puzzle[index] == 0 && board[index] != 0)
+        .count();
+    u8::try_from(filled).unwrap_or(u8::MAX)
+}
+
+/// The progress of the current game, recorded to become a [`Ghost`]
+///
+/// - `puzzle: SudokuState`: the puzzle of the game.
+/// - `ghost: Ghost`: the progress of the game so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressLog {
+    pub puzzle: SudokuState,
+    pub ghost: Ghost,
+}
+
+impl ProgressLog {
+    /// Creates the progress of a game with no cell filled yet.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: SudokuState` - The puzzle of the game.
+    #[must_use]
+    pub fn new(puzzle: SudokuState) -> Self {
+        Self {
+            puzzle,
+            ghost: Ghost {
+                cells: filled_cells(&puzzle, &[1; 81]),
+                marks: vec![],
+            },
+        }
+    }
+
+    /// Records the progress of a board.
+    ///
+    /// The progress starts over for another puzzle.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: &SudokuState` - The puzzle of the game.
+    /// - `board: &SudokuState` - The current board.
+    /// - `millis: u64` - The elapsed time of the game, in milliseconds.
+    pub fn record(&mut self, puzzle: &SudokuState, board: &SudokuState, millis: u64) {
+        if self.puzzle != *puzzle {
+            *self = Self::new(*puzzle);
+        }
+        self.ghost.record(filled_cells(puzzle, board), millis);
+    }
+}
+
+/// The ghosts of the personal bests of a profile, by difficulty
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ghosts(pub BTreeMap<Difficulty, Ghost>);
+
+/// Loads the ghosts of the personal bests of a profile from the local
+/// storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`Ghosts`],
+/// or none if none are stored or they cannot be read.
+#[must_use]
+pub fn load_ghosts(profile: u32) -> Ghosts {
+    local_storage_get(&profile_key(profile, GHOSTS_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the ghosts of the personal bests of a profile in the local
+/// storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `ghosts: &Ghosts` - The ghosts to store.
+pub fn store_ghosts(profile: u32, ghosts: &Ghosts) {
+    match serde_json::to_string(ghosts) {
+        Ok(json) => local_storage_set(&profile_key(profile, GHOSTS_KEY), &json),
+        Err(err) => log::error!("failed to store the ghosts: {err:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku, find_solution};
+
+    #[test]
+    fn test_submit() {
+        let mut bests = PersonalBests::default();
+        assert_eq!(bests.best(Difficulty::Easy), None);
+        assert!(bests.submit(Difficulty::Easy, 90_000));
+        assert!(!bests.submit(Difficulty::Easy, 120_000));
+        assert!(!bests.submit(Difficulty::Easy, 90_000));
+        assert!(bests.submit(Difficulty::Easy, 75_250));
+        assert_eq!(bests.best(Difficulty::Easy), Some(75_250));
+        assert_eq!(bests.best(Difficulty::Hard), None);
+
+        let json = serde_json::to_string(&bests).expect("serialize");
+        assert_eq!(json, r#"{"easy":75250}"#);
+    }
+
+    #[test]
+    fn test_ghost() {
+        let puzzle = create_sudoku();
+        let solution = find_solution(&puzzle).expect("a solution");
+        let mut log = ProgressLog::new(puzzle);
+        let cells = log.ghost.cells;
+        assert_eq!(cells, filled_cells(&puzzle, &solution));
+
+        let index = (0..81).find(|&index| puzzle[index] == 0).expect("a cell");
+        let mut board = puzzle;
+        board[index] = solution[index];
+        log.record(&puzzle, &puzzle, 0);
+        log.record(&puzzle, &board, 4000);
+        log.record(&puzzle, &board, 6000);
+        log.record(&puzzle, &solution, 90_000);
+        assert_eq!(log.ghost.marks, vec![(4000, 1), (90_000, cells)]);
+        assert_eq!(log.ghost.filled(3999), 0);
+        assert_eq!(log.ghost.filled(50_000), 1);
+        assert_eq!(log.ghost.progress(90_000), 100);
+
+        // a restart drops the marks after it
+        log.record(&puzzle, &puzzle, 1000);
+        assert_eq!(log.ghost.marks, vec![]);
+
+        assert_eq!(percent(1, 3), 33);
+        assert_eq!(percent(0, 0), 100);
+    }
+}
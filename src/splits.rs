@@ -0,0 +1,195 @@
+//! # Splits Module
+//!
+//! The `splits` module records the split times of a game,
+//! for speed-solvers analyzing the order they solve a puzzle in.
+//!
+//! A [`Split`] is reached when a digit has all its nine placements,
+//! or when a box is filled without repeating a digit.
+//! [`SplitTimes`] keeps the time of the [`GameTimer`] when every split was
+//! reached, in order.
+//!
+//! [`GameTimer`]: crate::components::timer::GameTimer
+
+use std::fmt;
+
+use crate::app::SudokuState;
+use crate::validation::{Unit, BOX_NAMES};
+
+/// A milestone of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Split {
+    /// A digit, from 1 to 9, placed nine times
+    Digit(u8),
+    /// A box, numbered from the top-left one, row by row, filled
+    Box(u8),
+}
+
+impl Split {
+    /// Returns whether the split is reached on a board.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to a Sudoku board.
+    #[must_use]
+    pub fn is_reached(self, board: &SudokuState) -> bool {
+        match self {
+            Self::Digit(digit) => (0..81).filter(|&index| board[index] == digit).count() == 9,
+            Self::Box(square) => {
+                let mut seen = 0_u16;
+                Unit::Box(square).cells().iter().all(|&index| {
+                    let value = board[index as usize];
+                    let bit = 1 << value;
+                    let fresh = value != 0 && seen & bit == 0;
+                    seen |= bit;
+                    fresh
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for Split {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Digit(digit) => write!(f, "All {digit}s"),
+            Self::Box(square) => write!(f, "The {} box", BOX_NAMES[*square as usize]),
+        }
+    }
+}
+
+/// Returns the splits reached on a board,
+/// the digits first, then the boxes.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - A reference to a Sudoku board.
+#[must_use]
+pub fn reached_splits(board: &SudokuState) -> Vec<Split> {
+    (1..=9)
+        .map(Split::Digit)
+        .chain((0..9).map(Split::Box))
+        .filter(|split| split.is_reached(board))
+        .collect()
+}
+
+/// The split times of a game
+///
+/// - `puzzle: SudokuState`: the puzzle of the game.
+/// - `splits: Vec<(Split, u64)>`: the splits reached on the current board,
+///   with the time they were reached at, in milliseconds, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitTimes {
+    pub puzzle: SudokuState,
+    pub splits: Vec<(Split, u64)>,
+}
+
+impl SplitTimes {
+    /// Creates the split times of a game with no split reached yet.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: SudokuState` - The puzzle of the game.
+    #[must_use]
+    pub const fn new(puzzle: SudokuState) -> Self {
+        Self {
+            puzzle,
+            splits: vec![],
+        }
+    }
+
+    /// Records the splits reached on a board.
+    ///
+    /// The splits no longer reached, such as a digit erased,
+    /// are dropped, so a split reached again is timed again.
+    /// The times start over for another puzzle, or if the timer went back,
+    /// as when the game is restarted.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: &SudokuState` - The puzzle of the game.
+    /// - `board: &SudokuState` - The current board.
+    /// - `millis: u64` - The elapsed time of the game, in milliseconds.
+    pub fn record(&mut self, puzzle: &SudokuState, board: &SudokuState, millis: u64) {
+        let went_back = self.splits.last().is_some_and(|&(_, last)| millis < last);
+        if self.puzzle != *puzzle || went_back {
+            *self = Self::new(*puzzle);
+        }
+        let reached = reached_splits(board);
+        self.splits.retain(|(split, _)| reached.contains(split));
+        for split in reached {
+            if !self.splits.iter().any(|&(other, _)| other == split) {
+                self.splits.push((split, millis));
+            }
+        }
+    }
+
+    /// Returns the splits with their time and the time since the previous
+    /// one, in milliseconds, in order.
+    #[must_use]
+    pub fn laps(&self) -> Vec<(Split, u64, u64)> {
+        let mut previous = 0;
+        self.splits
+            .iter()
+            .map(|&(split, millis)| {
+                let lap = millis.saturating_sub(previous);
+                previous = millis;
+                (split, millis, lap)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_sudoku, find_solution};
+
+    #[test]
+    fn test_reached_splits() {
+        let solution = find_solution(&create_sudoku()).expect("a solution");
+        assert_eq!(reached_splits(&solution).len(), 18);
+        assert_eq!(reached_splits(&[0; 81]), vec![]);
+
+        // a box repeating a digit is not reached
+        let mut board = [0; 81];
+        for index in Unit::Box(0).cells() {
+            board[index as usize] = 1;
+        }
+        assert!(!Split::Box(0).is_reached(&board));
+        assert_eq!(Split::Box(4).to_string(), "The center box");
+    }
+
+    #[test]
+    fn test_record() {
+        let puzzle = create_sudoku();
+        let solution = find_solution(&puzzle).expect("a solution");
+        let mut times = SplitTimes::new(puzzle);
+        times.record(&puzzle, &puzzle, 0);
+        let at_start = times.splits.len();
+
+        // a digit is timed when its last placement is made
+        let digit = (1..=9)
+            .find(|&digit| !Split::Digit(digit).is_reached(&puzzle))
+            .expect("a digit to place");
+        let mut board = puzzle;
+        for (cell, &value) in board.iter_mut().zip(&solution) {
+            if value == digit {
+                *cell = digit;
+            }
+        }
+        times.record(&puzzle, &board, 5000);
+        times.record(&puzzle, &board, 6000);
+        assert!(times.splits.contains(&(Split::Digit(digit), 5000)));
+        assert_eq!(times.laps().last().map(|&(_, _, lap)| lap), Some(5000));
+
+        // an erased digit is timed again
+        times.record(&puzzle, &puzzle, 7000);
+        assert_eq!(times.splits.len(), at_start);
+        times.record(&puzzle, &board, 8000);
+        assert!(times.splits.contains(&(Split::Digit(digit), 8000)));
+
+        // a restart starts over
+        times.record(&puzzle, &board, 1000);
+        assert!(times.splits.iter().all(|&(_, millis)| millis == 1000));
+    }
+}
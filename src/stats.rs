@@ -0,0 +1,403 @@
+//! # Stats Module
+//!
+//! The `stats` module computes the statistics of a player from the
+//! history of completed games in the [`storage`](crate::storage).
+//!
+//! Statistics are computed per [`Difficulty`]:
+//! how many games were solved, the best and average times,
+//! and the hints used.
+//! The [`trend`] of the last games shows how the times and the
+//! [`accuracy`] evolve,
+//! and the [`MistakeMap`] shows where the wrong digits are placed.
+//!
+//! The completed games and the statistics are exported as CSV with
+//! [`games_csv`] and [`stats_csv`], to be analyzed in other tools.
+
+use std::fmt::Write;
+
+use crate::daily::{date_of, day_of};
+use crate::difficulty::Difficulty;
+use crate::formats::to_line;
+use crate::save::SavedGame;
+use crate::storage::GameRecord;
+use crate::utils::find_solution;
+
+/// How many of the last games a [`trend`] covers
+pub const TREND_SIZE: usize = 30;
+
+/// The header of the CSV export of the completed games
+pub const GAMES_CSV_HEADER: &str =
+    "finished_at,date,difficulty,time_ms,moves,hints,accuracy,puzzle";
+
+/// The header of the CSV export of the statistics
+pub const STATS_CSV_HEADER: &str = "difficulty,solved,best_ms,average_ms,hints";
+
+/// The statistics of the games of a difficulty
+///
+/// - `difficulty: Difficulty`: the difficulty of the games.
+/// - `solved: usize`: how many games were solved.
+/// - `best: Option<u64>`: the fastest time, in milliseconds,
+///   or `None` if no game was solved.
+/// - `average: Option<u64>`: the average time, in milliseconds,
+///   or `None` if no game was solved.
+/// - `hints: u32`: how many hints the games used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DifficultyStats {
+    pub difficulty: Difficulty,
+    pub solved: usize,
+    pub best: Option<u64>,
+    pub average: Option<u64>,
+    pub hints: u32,
+}
+
+/// Computes the statistics of every difficulty
+///
+/// ## Parameters
+///
+/// - `records: &[GameRecord]` - The completed games.
+///
+/// ## Returns
+///
+/// Returns the [`DifficultyStats`] of every difficulty,
+/// from the easiest to the hardest, even the ones without games.
+#[must_use]
+pub fn compute_stats(records: &[GameRecord]) -> Vec<DifficultyStats> {
+    Difficulty::ALL
+        .into_iter()
+        .map(|difficulty| {
+            let times: Vec<u64> = records
+                .iter()
+                .filter(|record| record.difficulty == difficulty)
+                .map(|record| record.game.timer)
+                .collect();
+            let total: u64 = times.iter().sum();
+            let hints = records
+                .iter()
+                .filter(|record| record.difficulty == difficulty)
+                .map(|record| record.hints)
+                .sum();
+            DifficultyStats {
+                difficulty,
+                solved: times.len(),
+                best: times.iter().min().copied(),
+                average: u64::try_from(times.len())
+                    .ok()
+                    .filter(|&count| count > 0)
+                    .map(|count| total / count),
+                hints,
+            }
+        })
+        .collect()
+}
+
+/// A digit placed in a game
+///
+/// - `index: usize`: the cell, from 0 to 80.
+/// - `value: u8`: the digit placed.
+/// - `expected: u8`: the digit of the solution in the cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub index: usize,
+    pub value: u8,
+    pub expected: u8,
+}
+
+impl Placement {
+    /// Returns whether the digit matches the solution.
+    #[must_use]
+    pub const fn is_correct(self) -> bool {
+        self.value == self.expected
+    }
+}
+
+/// Returns every digit placed in a game, move by move.
+///
+/// ## Parameters
+///
+/// - `game: &SavedGame` - The game, with its whole history of moves.
+///
+/// ## Returns
+///
+/// Returns the [`Placement`]s in order,
+/// or `None` if the puzzle has no solution.
+#[must_use]
+pub fn placements(game: &SavedGame) -> Option<Vec<Placement>> {
+    let solution = find_solution(&game.initial).ok()?;
+    let mut placed = vec![];
+    for boards in game.moves.windows(2) {
+        for index in 0..81 {
+            let value = boards[1][index];
+            if value != 0 && value != boards[0][index] {
+                placed.push(Placement {
+                    index,
+                    value,
+                    expected: solution[index],
+                });
+            }
+        }
+    }
+    Some(placed)
+}
+
+/// Returns the accuracy of a game,
+/// the percentage of the digits placed that matched the solution.
+///
+/// ## Parameters
+///
+/// - `game: &SavedGame` - The game, with its whole history of moves.
+///
+/// ## Returns
+///
+/// Returns the accuracy, from 0 to 100,
+/// or `None` if no digit was placed or the puzzle has no solution.
+#[must_use]
+pub fn accuracy(game: &SavedGame) -> Option<u64> {
+    let placed = placements(game)?;
+    let total = u64::try_from(placed.len())
+        .ok()
+        .filter(|&total| total > 0)?;
+    let correct = placed.iter().filter(|placement| placement.is_correct());
+    Some(u64::try_from(correct.count()).ok()? * 100 / total)
+}
+
+/// Where the wrong digits were placed, across games
+///
+/// - `cells: [u32; 81]`: how many wrong digits were placed in every cell.
+/// - `digits: [u32; 9]`: how many wrong digits were placed in the cells of
+///   every digit of the solution, from 1 to 9,
+///   so the digits most often missed stand out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MistakeMap {
+    pub cells: [u32; 81],
+    pub digits: [u32; 9],
+}
+
+impl MistakeMap {
+    /// Returns the highest number of wrong digits of a cell.
+    #[must_use]
+    pub fn max_cell(&self) -> u32 {
+        self.cells.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Aggregates the wrong digits placed in the completed games.
+///
+/// ## Parameters
+///
+/// - `records: &[GameRecord]` - The completed games.
+#[must_use]
+pub fn mistake_map(records: &[GameRecord]) -> MistakeMap {
+    let mut map = MistakeMap {
+        cells: [0; 81],
+        digits: [0; 9],
+    };
+    let wrong = records
+        .iter()
+        .filter_map(|record| placements(&record.game))
+        .flatten()
+        .filter(|placement| !placement.is_correct());
+    for placement in wrong {
+        map.cells[placement.index] += 1;
+        map.digits[usize::from(placement.expected - 1)] += 1;
+    }
+    map
+}
+
+/// Returns a value of the last [`TREND_SIZE`] games,
+/// from the oldest to the latest.
+///
+/// ## Parameters
+///
+/// - `records: &[GameRecord]` - The completed games.
+/// - `value: impl Fn(&GameRecord) -> Option<u64>` - The value of a game,
+///   or `None` to leave it out.
+#[must_use]
+pub fn trend(records: &[GameRecord], value: impl Fn(&GameRecord) -> Option<u64>) -> Vec<u64> {
+    let mut records: Vec<&GameRecord> = records.iter().collect();
+    records.sort_by_key(|record| record.finished_at);
+    let values: Vec<u64> = records.into_iter().filter_map(value).collect();
+    values[values.len().saturating_sub(TREND_SIZE)..].to_vec()
+}
+
+/// Exports the completed games as CSV.
+///
+/// Every game is a row with the columns of the [`GAMES_CSV_HEADER`],
+/// from the oldest to the latest:
+/// when it was completed, in milliseconds since the Unix epoch and as a
+/// UTC date, its difficulty, its time in milliseconds,
+/// its number of moves, the hints it used, its [`accuracy`] in percent,
+/// empty if unknown, and its puzzle in the line format.
+///
+/// ## Parameters
+///
+/// - `records: &[GameRecord]` - The completed games.
+#[must_use]
+pub fn games_csv(records: &[GameRecord]) -> String {
+    let mut records: Vec<&GameRecord> = records.iter().collect();
+    records.sort_by_key(|record| record.finished_at);
+    let mut csv = format!("{GAMES_CSV_HEADER}\n");
+    for record in records {
+        let (year, month, day) = date_of(day_of(record.finished_at));
+        let accuracy = accuracy(&record.game).map_or_else(String::new, |value| value.to_string());
+        let _ = writeln!(
+            csv,
+            "{},{year:04}-{month:02}-{day:02},{},{},{},{},{accuracy},{}",
+            record.finished_at,
+            record.difficulty,
+            record.game.timer,
+            record.game.moves.len().saturating_sub(1),
+            record.hints,
+            to_line(&record.game.initial, '.'),
+        );
+    }
+    csv
+}
+
+/// Exports the statistics of every difficulty as CSV.
+///
+/// Every difficulty is a row with the columns of the [`STATS_CSV_HEADER`],
+/// where the times are empty if no game was solved.
+///
+/// ## Parameters
+///
+/// - `stats: &[DifficultyStats]` - The statistics, from [`compute_stats`].
+#[must_use]
+pub fn stats_csv(stats: &[DifficultyStats]) -> String {
+    let time = |millis: Option<u64>| millis.map_or_else(String::new, |millis| millis.to_string());
+    let mut csv = format!("{STATS_CSV_HEADER}\n");
+    for row in stats {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{}",
+            row.difficulty,
+            row.solved,
+            time(row.best),
+            time(row.average),
+            row.hints,
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use crate::utils::create_sudoku;
+
+    fn record(difficulty: Difficulty, timer: u64) -> GameRecord {
+        GameRecord {
+            profile: 0,
+            difficulty,
+            finished_at: 0,
+            game: SavedGame {
+                initial: [0; 81],
+                moves: vec![[0; 81]],
+                notes: [0; 81],
+                timer,
+                settings: Settings::default(),
+                move_notes: vec![],
+                tree: None,
+            },
+            hints: 0,
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let records = [
+            record(Difficulty::Easy, 60_000),
+            record(Difficulty::Easy, 120_000),
+            record(Difficulty::Hard, 600_000),
+        ];
+        let stats = compute_stats(&records);
+        assert_eq!(stats.len(), Difficulty::ALL.len());
+
+        assert_eq!(
+            stats[0],
+            DifficultyStats {
+                difficulty: Difficulty::Easy,
+                solved: 2,
+                best: Some(60_000),
+                average: Some(90_000),
+                hints: 0,
+            }
+        );
+        assert_eq!(stats[1].solved, 0);
+        assert_eq!(stats[1].best, None);
+        assert_eq!(stats[1].average, None);
+        assert_eq!(stats[2].best, Some(600_000));
+        assert_eq!(stats[0].hints, 0);
+    }
+
+    #[test]
+    fn test_accuracy() {
+        let initial = create_sudoku();
+        let solution = find_solution(&initial).expect("a solution");
+        let empty: Vec<usize> = (0..81).filter(|&index| initial[index] == 0).collect();
+        let mut game = record(Difficulty::Easy, 0).game;
+        game.initial = initial;
+        game.moves = vec![initial];
+        assert_eq!(accuracy(&game), None);
+
+        // a right digit, a wrong one, then the wrong one corrected
+        let mut board = initial;
+        board[empty[0]] = solution[empty[0]];
+        game.moves.push(board);
+        board[empty[1]] = solution[empty[1]] % 9 + 1;
+        game.moves.push(board);
+        board[empty[1]] = solution[empty[1]];
+        game.moves.push(board);
+        assert_eq!(accuracy(&game), Some(66));
+
+        // the wrong digit is mapped to its cell and the digit it missed
+        let map = mistake_map(&[GameRecord {
+            game: game.clone(),
+            ..record(Difficulty::Easy, 0)
+        }]);
+        assert_eq!(map.cells[empty[1]], 1);
+        assert_eq!(map.max_cell(), 1);
+        assert_eq!(map.digits[usize::from(solution[empty[1]] - 1)], 1);
+        assert_eq!(map.digits.iter().sum::<u32>(), 1);
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut records = [
+            record(Difficulty::Hard, 600_000),
+            record(Difficulty::Easy, 60_000),
+        ];
+        records[0].finished_at = 1_700_000_000_000;
+        records[0].hints = 2;
+        let games = games_csv(&records);
+        let lines: Vec<&str> = games.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], GAMES_CSV_HEADER);
+        // the oldest game first
+        assert!(lines[1].starts_with("0,1970-01-01,Easy,60000,0,0,,"));
+        assert!(lines[2].starts_with("1700000000000,2023-11-14,Hard,600000,0,2,,"));
+        assert_eq!(lines[2].split(',').count(), 8);
+
+        let stats = stats_csv(&compute_stats(&records));
+        let lines: Vec<&str> = stats.lines().collect();
+        assert_eq!(lines.len(), Difficulty::ALL.len() + 1);
+        assert_eq!(lines[0], STATS_CSV_HEADER);
+        assert_eq!(lines[1], "Easy,1,60000,60000,0");
+        assert_eq!(lines[2], "Medium,0,,,0");
+    }
+
+    #[test]
+    fn test_trend() {
+        let mut records: Vec<GameRecord> = (0..40)
+            .map(|minutes| record(Difficulty::Easy, minutes * 60_000))
+            .collect();
+        for (finished_at, record) in (0..40).rev().zip(&mut records) {
+            record.finished_at = finished_at;
+        }
+        let times = trend(&records, |record| Some(record.game.timer));
+        assert_eq!(times.len(), TREND_SIZE);
+        // the latest game was the first one recorded
+        assert_eq!(times.last(), Some(&0));
+        assert!(trend(&records, |_| None).is_empty());
+    }
+}
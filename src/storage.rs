@@ -0,0 +1,347 @@
+//! # Storage Module
+//!
+//! The `storage` module is the long-term storage of the Sudoku game.
+//! It keeps the history of completed games, with their whole history of
+//! moves so they can be replayed, and is the source of the statistics.
+//!
+//! The history is kept in the browser's `IndexedDB`,
+//! which is not limited in size like the local storage and can be queried
+//! by profile.
+//! `IndexedDB` is accessed through JavaScript,
+//! so the functions of this module are asynchronous and return an error
+//! outside of the browser.
+//!
+//! The favorite puzzles of every profile are kept next to the history,
+//! so they can be replayed later.
+//!
+//! Small, frequently written data, such as the settings and save slots,
+//! stays in the local storage.
+
+use anyhow::{Error, Result};
+use dioxus::prelude::eval;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app::SudokuState;
+use crate::difficulty::Difficulty;
+use crate::formats::to_line;
+use crate::links::parse_puzzle;
+use crate::save::SavedGame;
+
+/// JavaScript that runs a request against the `IndexedDB` of the game
+///
+/// The database has a `history` object store of [`GameRecord`]s and a
+/// `favorites` object store of [`Favorite`]s, both indexed by profile.
+/// The request is received as `{ store, op, ... }`,
+/// where the store is `history` if not given,
+/// and the response is sent back as `{ ok }` or `{ error }`.
+const INDEXED_DB: &str = r#"
+    const request = await dioxus.recv();
+    const promisify = (req) => new Promise((resolve, reject) => {
+        req.onsuccess = () => resolve(req.result);
+        req.onerror = () => reject(req.error);
+    });
+    try {
+        const open = indexedDB.open("sudoku", 2);
+        open.onupgradeneeded = (event) => {
+            if (event.oldVersion < 1) {
+                const history = open.result.createObjectStore("history", {
+                    keyPath: "id",
+                    autoIncrement: true,
+                });
+                history.createIndex("profile", "profile");
+            }
+            if (event.oldVersion < 2) {
+                const favorites = open.result.createObjectStore("favorites", {
+                    keyPath: ["profile", "puzzle"],
+                });
+                favorites.createIndex("profile", "profile");
+            }
+        };
+        const db = await promisify(open);
+        const name = request.store ?? "history";
+        const mode = request.op === "list" ? "readonly" : "readwrite";
+        const store = db.transaction(name, mode).objectStore(name);
+        let result = null;
+        switch (request.op) {
+            case "add":
+                result = await promisify(store.add(request.record));
+                break;
+            case "put":
+                result = await promisify(store.put(request.record));
+                break;
+            case "delete":
+                await promisify(store.delete(request.key));
+                break;
+            case "list":
+                result = await promisify(store.index("profile").getAll(request.profile));
+                break;
+            case "clear": {
+                const keys = await promisify(store.index("profile").getAllKeys(request.profile));
+                await Promise.all(keys.map((key) => promisify(store.delete(key))));
+                break;
+            }
+        }
+        db.close();
+        dioxus.send({ ok: result });
+    } catch (error) {
+        dioxus.send({ error: String(error) });
+    }
+"#;
+
+/// A completed game in the history
+///
+/// - `profile: u32`: the id of the profile that played the game.
+/// - `difficulty: Difficulty`: the difficulty of the puzzle.
+/// - `finished_at: u64`: when the game was completed,
+///   in milliseconds since the Unix epoch.
+/// - `game: SavedGame`: the game, with its whole history of moves.
+/// - `hints: u32`: how many hints the game used,
+///   0 for the games recorded before it was kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub profile: u32,
+    pub difficulty: Difficulty,
+    pub finished_at: u64,
+    pub game: SavedGame,
+    #[serde(default)]
+    pub hints: u32,
+}
+
+/// A favorite puzzle
+///
+/// - `profile: u32`: the id of the profile that starred the puzzle.
+/// - `puzzle: String`: the puzzle, in the line format.
+/// - `difficulty: Difficulty`: the difficulty of the puzzle.
+/// - `added_at: u64`: when the puzzle was starred,
+///   in milliseconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Favorite {
+    pub profile: u32,
+    pub puzzle: String,
+    pub difficulty: Difficulty,
+    pub added_at: u64,
+}
+
+impl Favorite {
+    /// Creates a favorite from a puzzle.
+    ///
+    /// ## Parameters
+    ///
+    /// - `profile: u32` - The id of the profile that stars the puzzle.
+    /// - `puzzle: &SudokuState` - The puzzle.
+    /// - `difficulty: Difficulty` - The difficulty of the puzzle.
+    /// - `added_at: u64` - When the puzzle is starred,
+    ///   in milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn new(profile: u32, puzzle: &SudokuState, difficulty: Difficulty, added_at: u64) -> Self {
+        Self {
+            profile,
+            puzzle: to_line(puzzle, '.'),
+            difficulty,
+            added_at,
+        }
+    }
+
+    /// Returns the puzzle as a [`SudokuState`],
+    /// or `None` if it is invalid.
+    #[must_use]
+    pub fn puzzle(&self) -> Option<SudokuState> {
+        parse_puzzle(&self.puzzle).ok()
+    }
+}
+
+/// Sends a request to the `IndexedDB` of the game.
+#[allow(clippy::future_not_send)] // JavaScript handles are not `Send`
+async fn request(request: Value) -> Result<Value> {
+    let mut database = eval(INDEXED_DB);
+    database
+        .send(request)
+        .map_err(|err| Error::msg(format!("Failed to reach the database: {err:?}")))?;
+    let mut response = database
+        .recv()
+        .await
+        .map_err(|err| Error::msg(format!("Failed to reach the database: {err:?}")))?;
+    if let Some(error) = response.get("error").and_then(Value::as_str) {
+        return Err(Error::msg(error.to_string()));
+    }
+    Ok(response["ok"].take())
+}
+
+/// Adds a completed game to the history.
+///
+/// ## Parameters
+///
+/// - `record: &GameRecord` - A reference to the completed game.
+///
+/// ## Errors
+///
+/// Returns an error if the database cannot be reached.
+#[allow(clippy::future_not_send)]
+pub async fn add_record(record: &GameRecord) -> Result<()> {
+    request(json!({ "op": "add", "record": serde_json::to_value(record)? })).await?;
+    Ok(())
+}
+
+/// Loads the history of a profile.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns a Result containing the completed games of the profile,
+/// in the order they were completed.
+/// Records that cannot be read are skipped.
+///
+/// ## Errors
+///
+/// Returns an error if the database cannot be reached.
+#[allow(clippy::future_not_send)]
+pub async fn load_records(profile: u32) -> Result<Vec<GameRecord>> {
+    let records = request(json!({ "op": "list", "profile": profile })).await?;
+    Ok(parse_records(records))
+}
+
+/// Deletes the history of a profile.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Errors
+///
+/// Returns an error if the database cannot be reached.
+#[allow(clippy::future_not_send)]
+pub async fn clear_records(profile: u32) -> Result<()> {
+    request(json!({ "op": "clear", "profile": profile })).await?;
+    Ok(())
+}
+
+/// Adds a puzzle to the favorites of its profile.
+///
+/// Adding a puzzle that is already a favorite replaces it.
+///
+/// ## Parameters
+///
+/// - `favorite: &Favorite` - A reference to the favorite puzzle.
+///
+/// ## Errors
+///
+/// Returns an error if the database cannot be reached.
+#[allow(clippy::future_not_send)]
+pub async fn add_favorite(favorite: &Favorite) -> Result<()> {
+    request(json!({
+        "store": "favorites",
+        "op": "put",
+        "record": serde_json::to_value(favorite)?,
+    }))
+    .await?;
+    Ok(())
+}
+
+/// Removes a puzzle from the favorites of a profile.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `puzzle: &SudokuState` - The puzzle.
+///
+/// ## Errors
+///
+/// Returns an error if the database cannot be reached.
+#[allow(clippy::future_not_send)]
+pub async fn remove_favorite(profile: u32, puzzle: &SudokuState) -> Result<()> {
+    request(json!({
+        "store": "favorites",
+        "op": "delete",
+        "key": [profile, to_line(puzzle, '.')],
+    }))
+    .await?;
+    Ok(())
+}
+
+/// Loads the favorite puzzles of a profile.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns a Result containing the favorites of the profile,
+/// most recently starred first.
+/// Favorites that cannot be read are skipped.
+///
+/// ## Errors
+///
+/// Returns an error if the database cannot be reached.
+#[allow(clippy::future_not_send)]
+pub async fn load_favorites(profile: u32) -> Result<Vec<Favorite>> {
+    let favorites =
+        request(json!({ "store": "favorites", "op": "list", "profile": profile })).await?;
+    let mut favorites: Vec<Favorite> = parse_records(favorites);
+    favorites.sort_by_key(|favorite| std::cmp::Reverse(favorite.added_at));
+    Ok(favorites)
+}
+
+/// Parses the records returned by the database, skipping invalid ones.
+fn parse_records<T: DeserializeOwned>(records: Value) -> Vec<T> {
+    match records {
+        Value::Array(records) => records
+            .into_iter()
+            .filter_map(|record| serde_json::from_value(record).ok())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use crate::utils::create_sudoku;
+
+    #[test]
+    fn test_parse_records() {
+        let initial = create_sudoku();
+        let mut solved = initial;
+        let empty = solved.iter().position(|&value| value == 0).expect("empty");
+        solved[empty] = 1;
+        let record = GameRecord {
+            profile: 1,
+            difficulty: Difficulty::Medium,
+            finished_at: 1_700_000_000_000,
+            game: SavedGame {
+                initial,
+                moves: vec![initial, solved],
+                notes: [0; 81],
+                timer: 60_000,
+                settings: Settings::default(),
+                // the notes on the moves are kept with the game
+                move_notes: vec![Some("only a 1 fits".to_string())],
+                tree: None,
+            },
+            hints: 2,
+        };
+
+        // the database adds its own key to every record
+        let mut stored = serde_json::to_value(&record).expect("serialize");
+        stored["id"] = json!(7);
+        let records: Vec<GameRecord> = parse_records(json!([stored, { "profile": 1 }]));
+        assert_eq!(records, vec![record]);
+        assert!(parse_records::<GameRecord>(Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_favorite() {
+        let puzzle = create_sudoku();
+        let favorite = Favorite::new(2, &puzzle, Difficulty::Hard, 1_700_000_000_000);
+        assert_eq!(favorite.puzzle(), Some(puzzle));
+
+        let favorites: Vec<Favorite> = parse_records(json!([favorite, { "profile": 2 }]));
+        assert_eq!(favorites, vec![favorite]);
+    }
+}
@@ -0,0 +1,186 @@
+//! # Symbols Module
+//!
+//! The `symbols` module contains the symbol sets the values of the board
+//! can be shown in.
+//!
+//! The board is always kept as digits from 1 to 9,
+//! and only their display changes:
+//! the cells, the notes and the number pad show the symbol of every digit,
+//! and the line format is exported in it when every symbol is a character
+//! the line format can read back, as the letters are.
+//! See also: [`to_symbol_line`](crate::formats::to_symbol_line).
+//!
+//! The colors and the animals are made for children,
+//! and are picked by the kids mode of the
+//! [`Settings`](crate::settings::Settings).
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The letters standing for the digits from 1 to 9
+const LETTERS: [&str; 9] = ["A", "B", "C", "D", "E", "F", "G", "H", "I"];
+
+/// The Roman numerals standing for the digits from 1 to 9
+const ROMAN: [&str; 9] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"];
+
+/// The colored circles standing for the digits from 1 to 9
+const COLORS: [&str; 9] = ["🔴", "🟠", "🟡", "🟢", "🔵", "🟣", "🟤", "⚫", "⚪"];
+
+/// The animals standing for the digits from 1 to 9
+const ANIMALS: [&str; 9] = ["🐶", "🐱", "🐭", "🐰", "🦊", "🐻", "🐼", "🐸", "🐵"];
+
+/// The symbols the values of the board are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolSet {
+    /// The digits from 1 to 9
+    #[default]
+    Digits,
+    /// The letters from A to I
+    Letters,
+    /// The Roman numerals from I to IX
+    Roman,
+    /// Colored circles, for children who cannot read numbers yet
+    Colors,
+    /// Animal faces, for children who cannot read numbers yet
+    Animals,
+}
+
+impl SymbolSet {
+    /// All symbol sets
+    pub const ALL: [Self; 5] = [
+        Self::Digits,
+        Self::Letters,
+        Self::Roman,
+        Self::Colors,
+        Self::Animals,
+    ];
+
+    /// Returns whether the set is made for children, with pictures instead
+    /// of numbers.
+    #[must_use]
+    pub const fn is_for_kids(self) -> bool {
+        matches!(self, Self::Colors | Self::Animals)
+    }
+
+    /// Returns the name of the symbol set in the stylesheet,
+    /// or `None` for the sets as wide as the digits, which need none.
+    #[must_use]
+    pub const fn css_name(self) -> Option<&'static str> {
+        match self {
+            Self::Digits | Self::Letters => None,
+            Self::Roman => Some("roman"),
+            Self::Colors | Self::Animals => Some("emoji"),
+        }
+    }
+
+    /// Returns the symbol of a digit,
+    /// or an empty string for an empty cell or an invalid digit.
+    ///
+    /// ## Parameters
+    ///
+    /// - `digit: u8` - The digit, from 1 to 9, or 0 for an empty cell.
+    #[must_use]
+    pub const fn symbol(self, digit: u8) -> &'static str {
+        const DIGITS: [&str; 9] = ["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        if digit == 0 || digit > 9 {
+            return "";
+        }
+        let index = (digit - 1) as usize;
+        match self {
+            Self::Digits => DIGITS[index],
+            Self::Letters => LETTERS[index],
+            Self::Roman => ROMAN[index],
+            Self::Colors => COLORS[index],
+            Self::Animals => ANIMALS[index],
+        }
+    }
+
+    /// Returns the single character standing for a digit in the line format,
+    /// or `None` for an invalid digit or a set with longer symbols,
+    /// whose lines could not be read back.
+    ///
+    /// ## Parameters
+    ///
+    /// - `digit: u8` - The digit, from 1 to 9.
+    #[must_use]
+    pub const fn line_char(self, digit: u8) -> Option<char> {
+        if digit == 0 || digit > 9 {
+            return None;
+        }
+        match self {
+            Self::Digits => Some((b'0' + digit) as char),
+            Self::Letters => Some((b'A' + digit - 1) as char),
+            Self::Roman | Self::Colors | Self::Animals => None,
+        }
+    }
+
+    /// Returns the digit a letter stands for, from `A` or `a` for 1 to
+    /// `I` or `i` for 9, or `None` for any other character.
+    ///
+    /// ## Parameters
+    ///
+    /// - `c: char` - The letter.
+    #[must_use]
+    pub fn letter_digit(c: char) -> Option<u8> {
+        match c.to_ascii_uppercase() {
+            letter @ 'A'..='I' => u8::try_from(letter).ok().map(|letter| letter - b'A' + 1),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SymbolSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Digits => "Digits",
+            Self::Letters => "Letters",
+            Self::Roman => "Roman numerals",
+            Self::Colors => "Colors",
+            Self::Animals => "Animals",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols() {
+        assert_eq!(SymbolSet::Digits.symbol(5), "5");
+        assert_eq!(SymbolSet::Letters.symbol(1), "A");
+        assert_eq!(SymbolSet::Roman.symbol(9), "IX");
+        assert_eq!(SymbolSet::Roman.symbol(0), "");
+        assert_eq!(SymbolSet::Letters.symbol(10), "");
+
+        assert_eq!(SymbolSet::Letters.line_char(9), Some('I'));
+        assert_eq!(SymbolSet::Digits.line_char(3), Some('3'));
+        assert_eq!(SymbolSet::Roman.line_char(1), None);
+        assert_eq!(SymbolSet::Colors.line_char(1), None);
+
+        // every set tells its digits apart
+        for symbols in SymbolSet::ALL {
+            let mut all: Vec<&str> = (1..=9).map(|digit| symbols.symbol(digit)).collect();
+            all.sort_unstable();
+            all.dedup();
+            assert_eq!(all.len(), 9, "{symbols}");
+        }
+    }
+
+    #[test]
+    fn test_letters_round_trip() {
+        for digit in 1..=9 {
+            let letter = SymbolSet::Letters.line_char(digit).expect("a letter");
+            assert_eq!(SymbolSet::letter_digit(letter), Some(digit));
+            assert_eq!(
+                SymbolSet::letter_digit(letter.to_ascii_lowercase()),
+                Some(digit)
+            );
+        }
+        assert_eq!(SymbolSet::letter_digit('J'), None);
+        assert_eq!(SymbolSet::letter_digit('5'), None);
+    }
+}
@@ -0,0 +1,346 @@
+//! # Sync Module
+//!
+//! The `sync` module provides the optional cloud sync of the Sudoku game,
+//! so the user's progress follows them between devices.
+//!
+//! Sync is opt-in: it is only done when the user sets a server URL in the
+//! [`Settings`] and signs in with [`auth`](crate::auth).
+//! The server keeps a JSON [`SyncDocument`] for every account at
+//! `{server}/sync`, answering `GET` and `PUT` requests authorized by the
+//! account's [`Session`].
+//!
+//! Syncing downloads the remote document, merges it with the local one,
+//! and uploads the result.
+//! Conflicts are resolved in favor of the latest modified data:
+//!
+//! - Save slots are matched by puzzle and the most recently saved is kept.
+//!   A slot completed or deleted on either device since it was last saved
+//!   is dropped, so it does not come back as in progress.
+//! - Completed games are only ever added, so both histories are merged.
+//! - Deleted slots are remembered as [`DeletedSlot`]s until their puzzle is
+//!   saved again.
+//! - Settings come from the document where they were most recently
+//!   changed.
+//!
+//! The statistics and streaks are computed from the history,
+//! so they are synced with it.
+
+use std::cmp::Reverse;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::auth::{auth_headers, Session};
+use crate::browser::fetch_json;
+use crate::save::{next_slot_id, DeletedSlot, SaveSlot};
+use crate::settings::Settings;
+use crate::storage::GameRecord;
+
+/// The synced data of a profile
+///
+/// - `modified_at: u64`: when the document was last modified,
+///   in milliseconds since the Unix epoch.
+/// - `settings: Settings`: the settings of the profile.
+/// - `settings_modified_at: u64`: when the settings were last changed,
+///   in milliseconds since the Unix epoch.
+/// - `slots: Vec<SaveSlot>`: the in-progress games.
+/// - `history: Vec<GameRecord>`: the completed games.
+/// - `deleted: Vec<DeletedSlot>`: the deleted in-progress games.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncDocument {
+    pub modified_at: u64,
+    pub settings: Settings,
+    pub settings_modified_at: u64,
+    pub slots: Vec<SaveSlot>,
+    pub history: Vec<GameRecord>,
+    pub deleted: Vec<DeletedSlot>,
+}
+
+/// Merges the local and remote documents.
+///
+/// ## Parameters
+///
+/// - `local: SyncDocument` - The document of this device.
+/// - `remote: SyncDocument` - The document of the server.
+///
+/// ## Returns
+///
+/// Returns the merged [`SyncDocument`], with the latest modified data of
+/// both, and the latest changed settings.
+/// Slots are most recently saved first, and given new ids if both
+/// documents use the same id for different puzzles.
+/// The slots completed or deleted since they were last saved are dropped.
+/// The history is in the order games were completed.
+#[must_use]
+pub fn merge(local: SyncDocument, remote: SyncDocument) -> SyncDocument {
+    let (newest, oldest) = if remote.modified_at > local.modified_at {
+        (remote, local)
+    } else {
+        (local, remote)
+    };
+
+    let mut history = newest.history;
+    for record in oldest.history {
+        if !history.iter().any(|other| is_same_record(other, &record)) {
+            history.push(record);
+        }
+    }
+    history.sort_by_key(|record| record.finished_at);
+
+    let mut deleted: Vec<DeletedSlot> = vec![];
+    for slot in newest.deleted.into_iter().chain(oldest.deleted) {
+        match deleted.iter_mut().find(|other| other.puzzle == slot.puzzle) {
+            Some(other) if other.deleted_at < slot.deleted_at => *other = slot,
+            Some(_) => {}
+            None => deleted.push(slot),
+        }
+    }
+
+    let mut slots: Vec<SaveSlot> = vec![];
+    for slot in newest.slots.into_iter().chain(oldest.slots) {
+        match slots
+            .iter_mut()
+            .find(|other| other.game.initial == slot.game.initial)
+        {
+            Some(other) if other.modified_at < slot.modified_at => *other = slot,
+            Some(_) => {}
+            None => slots.push(slot),
+        }
+    }
+    slots.retain(|slot| {
+        let completed = history
+            .iter()
+            .filter(|record| record.finished_at >= slot.modified_at)
+            .any(|record| record.game.initial == slot.game.initial);
+        !completed && !deleted.iter().any(|other| other.deletes(slot))
+    });
+    // a puzzle saved again since it was deleted is no longer deleted
+    deleted.retain(|other| !slots.iter().any(|slot| other.is_of(&slot.game.initial)));
+    slots.sort_by_key(|slot| Reverse(slot.modified_at));
+    for index in 0..slots.len() {
+        if slots[..index]
+            .iter()
+            .any(|other| other.id == slots[index].id)
+        {
+            slots[index].id = next_slot_id(&slots);
+        }
+    }
+
+    let (settings, settings_modified_at) =
+        if oldest.settings_modified_at > newest.settings_modified_at {
+            (oldest.settings, oldest.settings_modified_at)
+        } else {
+            (newest.settings, newest.settings_modified_at)
+        };
+
+    SyncDocument {
+        modified_at: newest.modified_at.max(settings_modified_at),
+        settings,
+        settings_modified_at,
+        slots,
+        history,
+        deleted,
+    }
+}
+
+/// Returns whether two records are the same completed game,
+/// whatever profile they were recorded by.
+#[must_use]
+pub fn is_same_record(a: &GameRecord, b: &GameRecord) -> bool {
+    a.finished_at == b.finished_at && a.game.initial == b.game.initial
+}
+
+/// Syncs a document with the server.
+///
+/// ## Parameters
+///
+/// - `server: &str` - The URL of the server.
+/// - `session: &Session` - The session of the signed in account.
+/// - `local: SyncDocument` - The document of this device.
+///
+/// ## Returns
+///
+/// Returns a Result containing the merged [`SyncDocument`],
+/// which is also uploaded to the server.
+///
+/// ## Errors
+///
+/// Returns an error if the remote document cannot be downloaded,
+/// read or uploaded.
+#[allow(clippy::future_not_send)]
+pub async fn sync(server: &str, session: &Session, local: SyncDocument) -> Result<SyncDocument> {
+    let url = format!("{}/sync", server.trim_end_matches('/'));
+    let remote = match fetch_json("GET", &url, auth_headers(session), Value::Null).await? {
+        Value::Null => SyncDocument::default(),
+        remote => serde_json::from_value(remote)?,
+    };
+    let merged = merge(local, remote);
+    let body = serde_json::to_value(&merged)?;
+    fetch_json("PUT", &url, auth_headers(session), body).await?;
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::save::SavedGame;
+    use crate::utils::create_sudoku;
+
+    fn slot(id: u32, initial: [u8; 81], modified_at: u64) -> SaveSlot {
+        SaveSlot {
+            id,
+            difficulty: Difficulty::Easy,
+            game: SavedGame {
+                initial,
+                moves: vec![initial],
+                notes: [0; 81],
+                timer: modified_at,
+                settings: Settings::default(),
+                move_notes: vec![],
+                tree: None,
+            },
+            modified_at,
+        }
+    }
+
+    #[test]
+    fn test_merge_favors_latest_modified() {
+        let shared = create_sudoku();
+        let local_only = create_sudoku();
+        let remote_only = create_sudoku();
+
+        let local = SyncDocument {
+            modified_at: 20,
+            settings: Settings {
+                show_timer: false,
+                ..Settings::default()
+            },
+            settings_modified_at: 3,
+            slots: vec![slot(0, shared, 20), slot(1, local_only, 5)],
+            history: vec![],
+            deleted: vec![],
+        };
+        let remote = SyncDocument {
+            modified_at: 10,
+            settings: Settings::default(),
+            settings_modified_at: 2,
+            slots: vec![slot(0, remote_only, 10), slot(1, shared, 8)],
+            history: vec![],
+            deleted: vec![],
+        };
+
+        let merged = merge(local.clone(), remote);
+        assert_eq!(merged.settings, local.settings);
+        assert_eq!(merged.slots.len(), 3);
+        // the newest version of the shared puzzle is kept
+        assert_eq!(merged.slots[0].game.initial, shared);
+        assert_eq!(merged.slots[0].modified_at, 20);
+        // slots are most recently saved first, with unique ids
+        assert_eq!(merged.slots[1].game.initial, remote_only);
+        assert_eq!(merged.slots[2].game.initial, local_only);
+        let mut ids: Vec<u32> = merged.slots.iter().map(|slot| slot.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn test_merge_keeps_latest_changed_settings() {
+        let changed = Settings {
+            show_timer: false,
+            ..Settings::default()
+        };
+        // the settings were changed on this device after the remote slots
+        let local = SyncDocument {
+            modified_at: 15,
+            settings: changed.clone(),
+            settings_modified_at: 15,
+            ..SyncDocument::default()
+        };
+        let remote = SyncDocument {
+            modified_at: 20,
+            slots: vec![slot(0, create_sudoku(), 20)],
+            settings_modified_at: 5,
+            ..SyncDocument::default()
+        };
+
+        let merged = merge(local, remote);
+        assert_eq!(merged.settings, changed);
+        assert_eq!(merged.settings_modified_at, 15);
+        assert_eq!(merged.modified_at, 20);
+        assert_eq!(merged.slots.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_unions_history() {
+        let record = |finished_at| GameRecord {
+            profile: 0,
+            difficulty: Difficulty::Easy,
+            finished_at,
+            game: slot(0, create_sudoku(), 0).game,
+            hints: 0,
+        };
+        let (first, second, third) = (record(1), record(2), record(3));
+        let local = SyncDocument {
+            history: vec![first.clone(), third.clone()],
+            ..SyncDocument::default()
+        };
+        // the same game, recorded by another profile on the other device
+        let other_profile = GameRecord {
+            profile: 1,
+            ..first.clone()
+        };
+        let remote = SyncDocument {
+            history: vec![other_profile, second.clone()],
+            ..SyncDocument::default()
+        };
+
+        let merged = merge(local, remote);
+        assert_eq!(merged.history, vec![first, second, third]);
+    }
+
+    #[test]
+    fn test_merge_drops_completed_and_deleted_slots() {
+        let (completed, deleted, saved_again) = (create_sudoku(), create_sudoku(), create_sudoku());
+        let record = GameRecord {
+            profile: 0,
+            difficulty: Difficulty::Easy,
+            finished_at: 30,
+            game: slot(0, completed, 30).game,
+            hints: 0,
+        };
+        // this device completed a game and deleted two slots
+        let local = SyncDocument {
+            modified_at: 40,
+            history: vec![record],
+            deleted: vec![
+                DeletedSlot::new(&deleted, 35),
+                DeletedSlot::new(&saved_again, 15),
+            ],
+            ..SyncDocument::default()
+        };
+        // the other device still has them in progress,
+        // one of them saved again since it was deleted here
+        let remote = SyncDocument {
+            modified_at: 20,
+            slots: vec![
+                slot(0, completed, 10),
+                slot(1, deleted, 20),
+                slot(2, saved_again, 20),
+            ],
+            ..SyncDocument::default()
+        };
+
+        let merged = merge(local, remote);
+        let puzzles: Vec<[u8; 81]> = merged.slots.iter().map(|slot| slot.game.initial).collect();
+        assert_eq!(puzzles, vec![saved_again]);
+        assert_eq!(merged.deleted, vec![DeletedSlot::new(&deleted, 35)]);
+
+        // the deletion is kept by the next syncs
+        let again = merge(merged.clone(), SyncDocument::default());
+        assert_eq!(again.deleted, merged.deleted);
+    }
+}
@@ -0,0 +1,234 @@
+//! # Techniques Module
+//!
+//! The `techniques` module finds the next step a human would take to solve
+//! a board, and explains it.
+//!
+//! [`next_step`] finds the next deduction of the logical solver of the
+//! [`logic`](crate::logic) module, and returns it as a [`TechniqueStep`]:
+//! the cells of its pattern, the implication links between them,
+//! the digit it places or the candidates it eliminates,
+//! and an explanation in words.
+//!
+//! The candidates of an empty cell are its notes, if it has any,
+//! so the steps follow the eliminations the user already made.
+
+use crate::app::{SudokuNotes, SudokuState};
+use crate::bitboard::BitBoard;
+use crate::logic::{next_deduction, Deduction, Grid, Technique};
+use crate::utils::get_all_conflicting_cells;
+use crate::validation::cell_name;
+
+/// A step towards the solution of a board, found by a solving technique
+///
+/// - `technique: Technique`: the technique.
+/// - `cells: Vec<u8>`: the indices of the cells of its pattern.
+/// - `links: Vec<(u8, u8)>`: the implication links between cells,
+///   as the indices of the cells they go from and to.
+/// - `placement: Option<(u8, u8)>`: the index of the cell and the digit
+///   the step places, if any.
+/// - `eliminations: Vec<(u8, u8)>`: the indices of the cells and the
+///   candidates the step eliminates.
+/// - `description: String`: the explanation of the step for the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TechniqueStep {
+    pub technique: Technique,
+    pub cells: Vec<u8>,
+    pub links: Vec<(u8, u8)>,
+    pub placement: Option<(u8, u8)>,
+    pub eliminations: Vec<(u8, u8)>,
+    pub description: String,
+}
+
+impl TechniqueStep {
+    /// Returns the candidates the step eliminates,
+    /// as a bitmask for every cell, like the notes.
+    #[must_use]
+    pub fn eliminated(&self) -> SudokuNotes {
+        let mut eliminated = [0; 81];
+        for &(cell, digit) in &self.eliminations {
+            eliminated[cell as usize] |= 1 << (digit - 1);
+        }
+        eliminated
+    }
+
+    /// Crosses out the candidates the step eliminates from the notes.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to the current board.
+    /// - `notes: &SudokuNotes` - A reference to the notes of the board.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the notes without the eliminated candidates.
+    /// An empty cell without notes gets all of its candidates,
+    /// but the eliminated ones, as its notes.
+    #[must_use]
+    pub fn apply_eliminations(&self, board: &SudokuState, notes: &SudokuNotes) -> SudokuNotes {
+        let bitboard = BitBoard::new(*board);
+        let mut notes = *notes;
+        for (index, (&eliminated, cell)) in (0..).zip(self.eliminated().iter().zip(&mut notes)) {
+            if eliminated == 0 {
+                continue;
+            }
+            if *cell == 0 {
+                *cell = bitboard.candidates(index);
+            }
+            *cell &= !eliminated;
+        }
+        notes
+    }
+}
+
+/// Returns the names of cells, separated by commas.
+fn cell_names(cells: &[u8]) -> String {
+    cells
+        .iter()
+        .map(|&index| cell_name(index))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the digits of a bitmask, like the notes, as "3, 5 or 7".
+fn digit_names(digits: u16) -> String {
+    let mut digits: Vec<String> = (1..=9)
+        .filter(|digit| digits & (1 << (digit - 1)) != 0)
+        .map(|digit: u8| digit.to_string())
+        .collect();
+    let last = digits.pop().unwrap_or_default();
+    if digits.is_empty() {
+        last
+    } else {
+        format!("{} or {last}", digits.join(", "))
+    }
+}
+
+/// Returns the candidates eliminated by a step, as "5 from R1C2, R1C3".
+fn elimination_names(eliminations: &[(u8, u8)]) -> String {
+    let mut digits: Vec<u8> = eliminations.iter().map(|&(_, digit)| digit).collect();
+    digits.sort_unstable();
+    digits.dedup();
+    digits
+        .into_iter()
+        .map(|digit| {
+            let cells: Vec<u8> = eliminations
+                .iter()
+                .filter(|&&(_, other)| other == digit)
+                .map(|&(cell, _)| cell)
+                .collect();
+            format!("{digit} from {}", cell_names(&cells))
+        })
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// Explains a deduction of the logical solver.
+fn describe(deduction: &Deduction) -> String {
+    let Deduction {
+        technique,
+        cells,
+        placement,
+        eliminations,
+        unit,
+        digits,
+        ..
+    } = deduction;
+    match (technique, *placement, unit) {
+        (Technique::HiddenSingle, Some((cell, digit)), Some(unit)) => {
+            format!(
+                "{technique}: {digit} fits only {} in {unit}",
+                cell_name(cell)
+            )
+        }
+        (_, Some((cell, digit)), _) => {
+            format!("{technique}: {} can only be {digit}", cell_name(cell))
+        }
+        (Technique::XyWing, None, _) => {
+            let digit = eliminations.first().map_or(0, |&(_, digit)| digit);
+            format!(
+                "{technique}: {} is {}, so {} is {digit}, which removes {}",
+                cell_name(cells[0]),
+                digit_names(*digits),
+                cell_names(&cells[1..]).replace(", ", " or "),
+                elimination_names(eliminations),
+            )
+        }
+        _ => format!(
+            "{technique} on {}: removes {}",
+            cell_names(cells),
+            elimination_names(eliminations)
+        ),
+    }
+}
+
+/// Finds the next step towards the solution of a board.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - A reference to the current board.
+/// - `notes: &SudokuNotes` - A reference to the notes of the board,
+///   which are the candidates of the empty cells that have any.
+///
+/// ## Returns
+///
+/// Returns the first step of the easiest technique that applies,
+/// or `None` if none applies, or the board has conflicting cells.
+#[must_use]
+pub fn next_step(board: &SudokuState, notes: &SudokuNotes) -> Option<TechniqueStep> {
+    if !get_all_conflicting_cells(board).is_empty() {
+        return None;
+    }
+
+    let deduction = next_deduction(&Grid::with_notes(board, notes), &Technique::ALL)?;
+    Some(TechniqueStep {
+        description: describe(&deduction),
+        technique: deduction.technique,
+        cells: deduction.cells,
+        links: deduction.links,
+        placement: deduction.placement,
+        eliminations: deduction.eliminations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_sudoku;
+
+    #[test]
+    fn test_single() {
+        let board = create_sudoku();
+        let step = next_step(&board, &[0; 81]).expect("failed to find a step");
+        let (cell, _) = step.placement.expect("failed to place a digit");
+        assert_eq!(board[cell as usize], 0);
+        assert_eq!(step.cells, vec![cell]);
+        assert!(step.links.is_empty());
+    }
+
+    #[test]
+    fn test_xy_wing() {
+        let board: SudokuState = [0; 81];
+        let mut notes: SudokuNotes = [0; 81];
+        notes[0] = 0b011; // R1C1 is 1 or 2, the hinge
+        notes[4] = 0b101; // R1C5 is 1 or 3, a pincer
+        notes[27] = 0b110; // R4C1 is 2 or 3, the other pincer
+
+        let step = next_step(&board, &notes).expect("failed to find a step");
+        assert_eq!(step.technique, Technique::XyWing);
+        assert_eq!(step.cells, vec![0, 4, 27]);
+        assert_eq!(step.eliminations, vec![(31, 3)]);
+        assert_eq!(step.links, vec![(0, 4), (0, 27), (4, 31), (27, 31)]);
+        assert_eq!(
+            step.description,
+            "XY-Wing: R1C1 is 1 or 2, so R1C5 or R4C1 is 3, which removes 3 from R4C5"
+        );
+
+        assert_eq!(step.eliminated()[31], 0b100);
+        // the notes of the target are all of its candidates, but the 3
+        let crossed = step.apply_eliminations(&board, &notes);
+        assert_eq!(crossed[31], 0b1_1111_1011);
+        assert_eq!(crossed[0], notes[0]);
+        notes[31] = 0b1100;
+        assert_eq!(step.apply_eliminations(&board, &notes)[31], 0b1000);
+    }
+}
@@ -0,0 +1,342 @@
+//! # Theme Module
+//!
+//! The `theme` module contains the [`Theme`] of the app:
+//! the colors the stylesheet draws everything in.
+//!
+//! Every color of a theme is set as a CSS custom property of the root of
+//! the page by the [`use_theme`](crate::components::theme::use_theme) hook,
+//! and the stylesheet only uses these properties.
+//! So the light and dark themes, the colorblind-safe and high-contrast
+//! [`Palette`]s, the strong highlights of the large print, and the custom
+//! themes share the same mechanism.
+//!
+//! A custom theme is a JSON file with the fields of a [`Theme`],
+//! where the missing ones take the value of the default theme:
+//!
+//! ```json
+//! { "background": "#fdf6e3", "text": "#586e75", "font": "Georgia, serif" }
+//! ```
+//!
+//! Every value of a theme is checked when it is read,
+//! whether from a theme file, the stored settings, a save file or a sync,
+//! since it ends up in the `style` attribute of the page.
+
+use anyhow::{Error, Result};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::settings::{Palette, Settings};
+
+/// The colors of the app
+///
+/// Every color is a CSS color, such as `#3b59a9` or `white`,
+/// set as the CSS custom property named after the field.
+/// Missing fields take the value of the default theme,
+/// and a value that is not safe to set as a CSS custom property fails the
+/// deserialization.
+///
+/// - `dark: bool`: whether the background is dark,
+///   so the form controls of the browser are drawn dark too.
+/// - `background: String`: the background of the page and the board.
+/// - `text: String`: the text.
+/// - `accent: String`: the buttons, the links and the selected items.
+/// - `border: String`: the borders of the boxes and the panels.
+/// - `grid: String`: the lines between the cells.
+/// - `notes: String`: the notes of the cells.
+/// - `selected: String`: the selected cell.
+/// - `highlight: String`: the cells related to the selected one.
+/// - `same_digit: String`: the cells with the digit of the selected one.
+/// - `conflict: String`: the cells in conflict.
+/// - `conflict_text: String`: the text of the cells in conflict.
+/// - `heat: String`: the cells with few candidates.
+/// - `trial: String`: the entries of a trial.
+/// - `player_one: String`: the cells of the first player of the
+///   pass-and-play mode.
+/// - `player_two: String`: the cells of the second player.
+/// - `font: String`: the CSS font family of the text and the digits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub dark: bool,
+    #[serde(deserialize_with = "safe_value")]
+    pub background: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub text: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub accent: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub border: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub grid: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub notes: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub selected: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub highlight: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub same_digit: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub conflict: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub conflict_text: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub heat: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub trial: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub player_one: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub player_two: String,
+    #[serde(deserialize_with = "safe_value")]
+    pub font: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::of_palette(Palette::Default)
+    }
+}
+
+impl Theme {
+    /// Returns the theme of a palette.
+    ///
+    /// ## Parameters
+    ///
+    /// - `palette: Palette` - The palette.
+    #[must_use]
+    pub fn of_palette(palette: Palette) -> Self {
+        // blue highlights and red conflicts
+        let default = Self {
+            dark: false,
+            background: "white".into(),
+            text: "black".into(),
+            accent: "#3b59a9".into(),
+            border: "#38485f".into(),
+            grid: "black".into(),
+            notes: "#6b7a90".into(),
+            selected: "#e4ebf2".into(),
+            highlight: "#c2ddf8".into(),
+            same_digit: "#a3c4ec".into(),
+            conflict: "#d5656f".into(),
+            conflict_text: "inherit".into(),
+            heat: "#e8823a".into(),
+            trial: "#8a3ffc".into(),
+            player_one: "#1f7a4d".into(),
+            player_two: "#c0501f".into(),
+            font: "sans-serif".into(),
+        };
+        match palette {
+            Palette::Default => default,
+            // blue and orange, which deuteranopes and protanopes tell apart
+            Palette::Colorblind => Self {
+                accent: "#0072b2".into(),
+                selected: "#fff1b8".into(),
+                highlight: "#cfe5f5".into(),
+                same_digit: "#8ec3e6".into(),
+                conflict: "#e69f00".into(),
+                heat: "#cc79a7".into(),
+                trial: "#009e73".into(),
+                player_one: "#0072b2".into(),
+                player_two: "#d55e00".into(),
+                ..default
+            },
+            Palette::HighContrast => Self {
+                accent: "#0000c0".into(),
+                border: "black".into(),
+                notes: "#303030".into(),
+                selected: "#ffe600".into(),
+                highlight: "#d0d0d0".into(),
+                same_digit: "#9a9a9a".into(),
+                conflict: "#b00020".into(),
+                conflict_text: "white".into(),
+                heat: "#ff8c00".into(),
+                trial: "#6a00a8".into(),
+                player_one: "#005a00".into(),
+                player_two: "#a00000".into(),
+                ..default
+            },
+            // light text on dark grays, with dimmed highlights
+            Palette::Dark => Self {
+                dark: true,
+                background: "#1e1f24".into(),
+                text: "#e6e6e6".into(),
+                accent: "#8fb0ff".into(),
+                border: "#9aa7bd".into(),
+                grid: "#c8c8c8".into(),
+                notes: "#9aa5b5".into(),
+                selected: "#3a4252".into(),
+                highlight: "#2c3e5c".into(),
+                same_digit: "#3d5a8a".into(),
+                conflict: "#b84a55".into(),
+                heat: "#c46a2a".into(),
+                trial: "#c29bff".into(),
+                player_one: "#5cc592".into(),
+                player_two: "#f08a5d".into(),
+                ..default
+            },
+        }
+    }
+
+    /// Returns the theme of the settings.
+    ///
+    /// The custom theme of the settings, if any,
+    /// or else the theme of their palette,
+    /// with stronger highlights in the large print,
+    /// unless the palette already has strong ones.
+    ///
+    /// ## Parameters
+    ///
+    /// - `settings: &Settings` - A reference to the settings.
+    #[must_use]
+    pub fn of_settings(settings: &Settings) -> Self {
+        if let Some(theme) = &settings.custom_theme {
+            return theme.clone();
+        }
+        let mut theme = Self::of_palette(settings.palette);
+        if settings.large_print && settings.palette == Palette::Default {
+            theme.selected = "#ffd54a".into();
+            theme.highlight = "#a9cdf5".into();
+            theme.same_digit = "#6f9fdc".into();
+        }
+        theme
+    }
+
+    /// Parses a custom theme from its JSON file.
+    ///
+    /// ## Parameters
+    ///
+    /// - `json: &str` - The contents of the file.
+    ///
+    /// ## Returns
+    ///
+    /// Returns a Result containing the [`Theme`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if the file is not a JSON theme,
+    /// or if a value could break out of its CSS declaration.
+    pub fn parse(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|err| Error::msg(format!("Invalid theme file: {err}")))
+    }
+
+    /// Exports the theme to a JSON file, as read by [`Theme::parse`].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Returns the CSS custom properties of the theme with their values.
+    fn values(&self) -> [(&'static str, &str); 16] {
+        [
+            ("background", &self.background),
+            ("text", &self.text),
+            ("accent", &self.accent),
+            ("border", &self.border),
+            ("grid", &self.grid),
+            ("notes", &self.notes),
+            ("selected", &self.selected),
+            ("highlight", &self.highlight),
+            ("same-digit", &self.same_digit),
+            ("conflict", &self.conflict),
+            ("conflict-text", &self.conflict_text),
+            ("heat", &self.heat),
+            ("trial", &self.trial),
+            ("player-one", &self.player_one),
+            ("player-two", &self.player_two),
+            ("font", &self.font),
+        ]
+    }
+
+    /// Returns the CSS declarations of the theme,
+    /// its colors and font as CSS custom properties and its color scheme.
+    #[must_use]
+    pub fn css_variables(&self) -> String {
+        let scheme = if self.dark { "dark" } else { "light" };
+        self.values()
+            .iter()
+            .map(|(name, value)| format!("--{name}: {value}; "))
+            .chain([format!("color-scheme: {scheme};")])
+            .collect()
+    }
+}
+
+/// Deserializes a value of a theme, failing if it is not safe to set,
+/// see [`is_safe_value`].
+fn safe_value<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    if is_safe_value(&value) {
+        Ok(value)
+    } else {
+        Err(de::Error::custom(format!("invalid theme value {value:?}")))
+    }
+}
+
+/// Returns whether a value of a custom theme is safe to set as the value
+/// of a CSS custom property: not empty, and without the characters that
+/// would end the declaration or open a block, a comment or a URL.
+fn is_safe_value(value: &str) -> bool {
+    !value.trim().is_empty()
+        && !value.to_ascii_lowercase().contains("url(")
+        && value.chars().all(|c| {
+            c.is_alphanumeric()
+                || matches!(
+                    c,
+                    ' ' | '#' | '(' | ')' | ',' | '.' | '%' | '-' | '"' | '\''
+                )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_css_variables() {
+        let css = Theme::default().css_variables();
+        assert!(css.starts_with("--background: white; --text: black; --accent: #3b59a9;"));
+        assert!(css.contains("--same-digit: #a3c4ec;"));
+        assert!(css.ends_with("color-scheme: light;"));
+        assert!(Theme::of_palette(Palette::Dark)
+            .css_variables()
+            .ends_with("color-scheme: dark;"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let theme =
+            Theme::parse(r##"{"background": "#fdf6e3", "font": "'Comic Sans MS', cursive"}"##)
+                .expect("a theme");
+        assert_eq!(theme.background, "#fdf6e3");
+        assert_eq!(theme.font, "'Comic Sans MS', cursive");
+        // missing fields are defaulted
+        assert_eq!(theme.accent, Theme::default().accent);
+        assert_eq!(Theme::parse(&theme.to_json()).expect("a theme"), theme);
+
+        assert!(Theme::parse("not json").is_err());
+        assert!(Theme::parse(r#"{"text": "red; background: url(x)"}"#).is_err());
+        assert!(Theme::parse(r#"{"accent": "url(https://example.com)"}"#).is_err());
+        assert!(Theme::parse(r#"{"grid": ""}"#).is_err());
+
+        // the settings holding a theme check it too
+        let settings = r#"{"custom_theme": {"font": "x; } body { display: none"}}"#;
+        assert!(serde_json::from_str::<Settings>(settings).is_err());
+    }
+
+    #[test]
+    fn test_large_print_highlights() {
+        let mut settings = Settings {
+            large_print: true,
+            ..Settings::default()
+        };
+        assert_eq!(Theme::of_settings(&settings).selected, "#ffd54a");
+        settings.palette = Palette::HighContrast;
+        assert_eq!(
+            Theme::of_settings(&settings),
+            Theme::of_palette(Palette::HighContrast)
+        );
+        // a custom theme takes the place of the palette
+        let custom = Theme::of_palette(Palette::Dark);
+        settings.custom_theme = Some(custom.clone());
+        assert_eq!(Theme::of_settings(&settings), custom);
+    }
+}
@@ -0,0 +1,201 @@
+//! # Toast Module
+//!
+//! The `toast` module contains the [`ToastQueue`],
+//! short messages shown over the page for a few seconds,
+//! such as "Puzzle imported" or "The file could not be read".
+//!
+//! Features push their messages to the queue in the
+//! [`components`](crate::components::toast), which renders them.
+
+use std::collections::VecDeque;
+
+/// How long a toast is shown, in milliseconds
+pub const TOAST_MILLIS: u64 = 4000;
+
+/// How long a toast with an action is shown, in milliseconds,
+/// so there is time to use the action
+pub const ACTION_TOAST_MILLIS: u64 = 8000;
+
+/// How many toasts are shown at most, older ones are dropped
+pub const MAX_TOASTS: usize = 3;
+
+/// The kind of a toast, which sets its color
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToastKind {
+    /// Something happened
+    #[default]
+    Info,
+    /// Something the user asked for succeeded
+    Success,
+    /// Something the user asked for failed
+    Error,
+}
+
+impl ToastKind {
+    /// Returns the name of the kind in the stylesheet.
+    #[must_use]
+    pub const fn css_name(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Success => "success",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// An action offered by a toast, as a button next to its message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastAction {
+    /// Bring back the game replaced by a new one
+    UndoNewGame,
+}
+
+impl ToastAction {
+    /// Returns the label of the button of the action.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::UndoNewGame => "Undo",
+        }
+    }
+}
+
+/// A message shown for a few seconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    /// The id of the toast, unique in its queue
+    pub id: u64,
+    pub kind: ToastKind,
+    pub message: String,
+    pub action: Option<ToastAction>,
+}
+
+impl Toast {
+    /// Returns how long the toast is shown, in milliseconds.
+    #[must_use]
+    pub const fn millis(&self) -> u64 {
+        if self.action.is_some() {
+            ACTION_TOAST_MILLIS
+        } else {
+            TOAST_MILLIS
+        }
+    }
+}
+
+/// The toasts shown, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToastQueue {
+    toasts: VecDeque<Toast>,
+    next_id: u64,
+}
+
+impl ToastQueue {
+    /// Adds a toast at the end of the queue,
+    /// dropping the oldest one if there are more than [`MAX_TOASTS`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `kind: ToastKind` - The kind of the toast.
+    /// - `message: String` - The message of the toast.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the id of the toast.
+    pub fn push(&mut self, kind: ToastKind, message: String) -> u64 {
+        self.push_toast(kind, message, None)
+    }
+
+    /// Adds a toast with an action at the end of the queue,
+    /// like [`ToastQueue::push`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `kind: ToastKind` - The kind of the toast.
+    /// - `message: String` - The message of the toast.
+    /// - `action: ToastAction` - The action offered by the toast.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the id of the toast.
+    pub fn push_action(&mut self, kind: ToastKind, message: String, action: ToastAction) -> u64 {
+        self.push_toast(kind, message, Some(action))
+    }
+
+    /// Adds a toast, with or without an action.
+    fn push_toast(&mut self, kind: ToastKind, message: String, action: Option<ToastAction>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.toasts.push_back(Toast {
+            id,
+            kind,
+            message,
+            action,
+        });
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        id
+    }
+
+    /// Removes a toast from the queue, if it is still there.
+    ///
+    /// ## Parameters
+    ///
+    /// - `id: u64` - The id of the toast.
+    pub fn dismiss(&mut self, id: u64) {
+        self.toasts.retain(|toast| toast.id != id);
+    }
+
+    /// Returns an iterator over the toasts, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_dismiss() {
+        let mut queue = ToastQueue::default();
+        let first = queue.push(ToastKind::Success, "Puzzle imported".to_string());
+        let second = queue.push(ToastKind::Error, "Invalid file".to_string());
+        assert_ne!(first, second);
+
+        queue.dismiss(first);
+        let ids: Vec<u64> = queue.iter().map(|toast| toast.id).collect();
+        assert_eq!(ids, vec![second]);
+
+        // dismissing twice is harmless
+        queue.dismiss(first);
+        assert_eq!(queue.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_action_toasts() {
+        let mut queue = ToastQueue::default();
+        queue.push(ToastKind::Info, "Puzzle imported".to_string());
+        queue.push_action(
+            ToastKind::Info,
+            "Game replaced".to_string(),
+            ToastAction::UndoNewGame,
+        );
+        let toasts: Vec<&Toast> = queue.iter().collect();
+        assert_eq!(toasts[0].action, None);
+        assert_eq!(toasts[0].millis(), TOAST_MILLIS);
+        assert_eq!(toasts[1].action, Some(ToastAction::UndoNewGame));
+        assert_eq!(toasts[1].millis(), ACTION_TOAST_MILLIS);
+    }
+
+    #[test]
+    fn test_oldest_toasts_are_dropped() {
+        let mut queue = ToastQueue::default();
+        for index in 0..=MAX_TOASTS {
+            queue.push(ToastKind::Info, index.to_string());
+        }
+        let messages: Vec<&str> = queue.iter().map(|toast| toast.message.as_str()).collect();
+        assert_eq!(messages.len(), MAX_TOASTS);
+        assert_eq!(messages[0], "1");
+    }
+}
@@ -0,0 +1,631 @@
+//! # Undo Tree Module
+//!
+//! The `undo_tree` module keeps the moves of a game as a tree,
+//! so making a move after undoing starts a new branch instead of dropping
+//! the moves undone.
+//!
+//! Every node of the [`UndoTree`] is a state of the board,
+//! the root being the puzzle.
+//! A node only keeps the cells its move changed from its parent,
+//! as [`history`](crate::history) deltas,
+//! and the boards are rebuilt from the puzzle,
+//! except the board of the current node, which is kept.
+//! The moves of the game are the states from the root to the current node,
+//! see [`UndoTree::moves`].
+//! Redoing follows the branch last visited from the current node,
+//! and any node can be visited with [`UndoTree::goto`].
+//!
+//! Nodes are only added after their parent,
+//! so the nodes of a branch always have a greater index than its root.
+//!
+//! A move can carry a short note, such as "guessing 5 here",
+//! see [`UndoTree::annotate`].
+//!
+//! The whole tree is saved with the game, see [`UndoTree::nodes`] and
+//! [`UndoTree::from_nodes`],
+//! so the branches survive a reload.
+
+use crate::app::SudokuState;
+use crate::history::{apply_delta, diff, CellChange, MoveDelta};
+
+/// How many characters a note on a move keeps, the rest is dropped
+pub const MAX_NOTE_CHARS: usize = 80;
+
+/// A state of the board in an [`UndoTree`]
+///
+/// - `changes: MoveDelta`: the cells changed from its parent,
+///   none for the puzzle.
+/// - `parent: Option<usize>`: the node it was reached from,
+///   or `None` for the puzzle.
+/// - `redo: Option<usize>`: the child redone from it,
+///   the one last visited.
+/// - `note: Option<String>`: the note on the move that reached it, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub changes: MoveDelta,
+    pub parent: Option<usize>,
+    pub redo: Option<usize>,
+    pub note: Option<String>,
+}
+
+/// The position of a node when drawing an [`UndoTree`]
+///
+/// - `node: usize`: the index of the node.
+/// - `parent: Option<usize>`: the index of its parent, if any.
+/// - `depth: usize`: the number of moves from the puzzle.
+/// - `lane: usize`: the branch it is drawn on;
+///   the first child of a node stays on its lane,
+///   and every other child opens a new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeLayout {
+    pub node: usize,
+    pub parent: Option<usize>,
+    pub depth: usize,
+    pub lane: usize,
+}
+
+/// The tree of the states of the board of a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoTree {
+    puzzle: SudokuState,
+    nodes: Vec<Node>,
+    current: usize,
+    // the board of the current node, so it is not rebuilt on every read
+    board: SudokuState,
+}
+
+/// Plays the changes of a move on a board.
+fn play(board: &mut SudokuState, changes: &[CellChange]) {
+    for change in changes {
+        board[change.index as usize] = change.new;
+    }
+}
+
+/// Takes back the changes of a move from a board.
+fn take_back(board: &mut SudokuState, changes: &[CellChange]) {
+    for change in changes {
+        board[change.index as usize] = change.old;
+    }
+}
+
+impl UndoTree {
+    /// Creates a tree with the puzzle alone.
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: SudokuState` - The puzzle of the game.
+    #[must_use]
+    pub fn new(puzzle: SudokuState) -> Self {
+        Self {
+            puzzle,
+            nodes: vec![Node {
+                changes: vec![],
+                parent: None,
+                redo: None,
+                note: None,
+            }],
+            current: 0,
+            board: puzzle,
+        }
+    }
+
+    /// Creates a tree with a single branch, at its last move.
+    ///
+    /// ## Parameters
+    ///
+    /// - `moves: &[SudokuState]` - The states of the board after every move,
+    ///   starting with the puzzle.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the tree, or `None` if there are no moves.
+    #[must_use]
+    pub fn from_moves(moves: &[SudokuState]) -> Option<Self> {
+        let (&puzzle, rest) = moves.split_first()?;
+        let mut tree = Self::new(puzzle);
+        for &board in rest {
+            tree.add(board);
+        }
+        Some(tree)
+    }
+
+    /// Creates a tree from its nodes, as listed by [`UndoTree::nodes`].
+    ///
+    /// ## Parameters
+    ///
+    /// - `puzzle: SudokuState` - The puzzle of the game.
+    /// - `nodes: Vec<Node>` - The nodes, starting with the puzzle.
+    /// - `current: usize` - The index of the current node.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the tree, or `None` if the nodes do not make a tree:
+    /// the puzzle must be the only node without a parent, changes and note,
+    /// every other node must come after its parent,
+    /// and its changes must fit the board of its parent,
+    /// every node must redo one of its children,
+    /// and the notes must fit in [`MAX_NOTE_CHARS`].
+    #[must_use]
+    pub fn from_nodes(puzzle: SudokuState, nodes: Vec<Node>, current: usize) -> Option<Self> {
+        let is_tree = nodes
+            .first()
+            .is_some_and(|root| root.parent.is_none() && root.changes.is_empty())
+            && nodes.iter().enumerate().all(|(index, node)| {
+                let parent_fits = index == 0 || node.parent.is_some_and(|parent| parent < index);
+                let redo_fits = node.redo.is_none_or(|child| {
+                    nodes
+                        .get(child)
+                        .is_some_and(|child| child.parent == Some(index))
+                });
+                let note_fits = node
+                    .note
+                    .as_ref()
+                    .is_none_or(|note| index > 0 && note.chars().count() <= MAX_NOTE_CHARS);
+                parent_fits && redo_fits && note_fits
+            });
+        if !is_tree || current >= nodes.len() {
+            return None;
+        }
+        // every move must fit the board it was made on
+        let mut boards: Vec<SudokuState> = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            let mut board = node.parent.map_or(puzzle, |parent| boards[parent]);
+            apply_delta(&mut board, &node.changes).ok()?;
+            boards.push(board);
+        }
+        Some(Self {
+            puzzle,
+            nodes,
+            current,
+            board: boards[current],
+        })
+    }
+
+    /// Returns the nodes of the tree, starting with the puzzle.
+    ///
+    /// See also: [`UndoTree::from_nodes`].
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns the index of the current node.
+    #[must_use]
+    pub const fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the board of the current node.
+    #[must_use]
+    pub const fn current_board(&self) -> SudokuState {
+        self.board
+    }
+
+    /// Returns the board of a node, if it is in the tree,
+    /// rebuilt from the puzzle.
+    #[must_use]
+    pub fn board(&self, node: usize) -> Option<SudokuState> {
+        if node >= self.nodes.len() {
+            return None;
+        }
+        if node == self.current {
+            return Some(self.board);
+        }
+        let mut board = self.puzzle;
+        for node in self.path(node) {
+            play(&mut board, &self.nodes[node].changes);
+        }
+        Some(board)
+    }
+
+    /// Returns the boards of every node, in the order of the nodes.
+    #[must_use]
+    pub fn boards(&self) -> Vec<SudokuState> {
+        let mut boards: Vec<SudokuState> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut board = node.parent.map_or(self.puzzle, |parent| boards[parent]);
+            play(&mut board, &node.changes);
+            boards.push(board);
+        }
+        boards
+    }
+
+    /// Returns the cells changed by the move that reached a node,
+    /// if it is in the tree.
+    #[must_use]
+    pub fn changes(&self, node: usize) -> Option<&[CellChange]> {
+        self.nodes.get(node).map(|node| node.changes.as_slice())
+    }
+
+    /// Returns the parent of a node, if it has one.
+    #[must_use]
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.nodes.get(node)?.parent
+    }
+
+    /// Returns the note on the move that reached a node, if any.
+    #[must_use]
+    pub fn note(&self, node: usize) -> Option<&str> {
+        self.nodes.get(node)?.note.as_deref()
+    }
+
+    /// Sets the note on the move that reached a node.
+    ///
+    /// The note is trimmed and cut to [`MAX_NOTE_CHARS`],
+    /// and an empty note removes it.
+    ///
+    /// ## Parameters
+    ///
+    /// - `node: usize` - The node of the move.
+    /// - `text: &str` - The text of the note.
+    ///
+    /// ## Returns
+    ///
+    /// Returns whether the note changed.
+    /// The puzzle is not a move, so it has no note.
+    pub fn annotate(&mut self, node: usize, text: &str) -> bool {
+        let text: String = text.trim().chars().take(MAX_NOTE_CHARS).collect();
+        let text = Some(text).filter(|text| !text.is_empty());
+        match self.nodes.get_mut(node) {
+            Some(node) if node.parent.is_some() && node.note != text => {
+                node.note = text;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the notes on the moves from the puzzle to the current node,
+    /// one per move, or none if no move has a note.
+    ///
+    /// See also: [`UndoTree::moves`].
+    #[must_use]
+    pub fn move_notes(&self) -> Vec<Option<String>> {
+        let notes: Vec<Option<String>> = self.path(self.current)[1..]
+            .iter()
+            .map(|&node| self.nodes[node].note.clone())
+            .collect();
+        if notes.iter().all(Option::is_none) {
+            vec![]
+        } else {
+            notes
+        }
+    }
+
+    /// Returns the number of nodes, i.e. the boards ever reached.
+    #[must_use]
+    pub const fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the nodes from the puzzle to a node, both included.
+    #[must_use]
+    pub fn path(&self, node: usize) -> Vec<usize> {
+        let mut path: Vec<usize> = std::iter::successors(
+            Some(node).filter(|&node| node < self.nodes.len()),
+            |&node| self.nodes[node].parent,
+        )
+        .collect();
+        path.reverse();
+        path
+    }
+
+    /// Returns the number of moves from the puzzle to a node.
+    #[must_use]
+    pub fn depth(&self, node: usize) -> usize {
+        self.path(node).len().saturating_sub(1)
+    }
+
+    /// Returns the states of the board from the puzzle to the current node,
+    /// as a list of moves.
+    #[must_use]
+    pub fn moves(&self) -> Vec<SudokuState> {
+        let mut board = self.puzzle;
+        self.path(self.current)
+            .into_iter()
+            .map(|node| {
+                play(&mut board, &self.nodes[node].changes);
+                board
+            })
+            .collect()
+    }
+
+    /// Returns whether the current node has a child to redo.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        self.nodes[self.current].redo.is_some()
+    }
+
+    /// Moves to a new board.
+    ///
+    /// If the board is already a child of the current node,
+    /// that branch is followed instead of opening another one.
+    pub fn push(&mut self, board: SudokuState) {
+        let changes = diff(&self.board, &board);
+        let existing = (0..self.nodes.len()).find(|&node| {
+            self.nodes[node].parent == Some(self.current) && self.nodes[node].changes == changes
+        });
+        match existing {
+            Some(node) => {
+                self.nodes[self.current].redo = Some(node);
+                self.current = node;
+                self.board = board;
+            }
+            None => self.add(board),
+        }
+    }
+
+    /// Adds a child to the current node, and moves to it.
+    fn add(&mut self, board: SudokuState) {
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            changes: diff(&self.board, &board),
+            parent: Some(self.current),
+            redo: None,
+            note: None,
+        });
+        self.nodes[self.current].redo = Some(node);
+        self.current = node;
+        self.board = board;
+    }
+
+    /// Moves to the parent of the current node.
+    ///
+    /// Returns whether it moved, i.e. the current node is not the puzzle.
+    pub fn undo(&mut self) -> bool {
+        let Some(parent) = self.nodes[self.current].parent else {
+            return false;
+        };
+        take_back(&mut self.board, &self.nodes[self.current].changes);
+        self.current = parent;
+        true
+    }
+
+    /// Moves to the child of the current node last visited.
+    ///
+    /// Returns whether it moved.
+    pub fn redo(&mut self) -> bool {
+        let Some(child) = self.nodes[self.current].redo else {
+            return false;
+        };
+        play(&mut self.board, &self.nodes[child].changes);
+        self.current = child;
+        true
+    }
+
+    /// Moves to any node of the tree.
+    ///
+    /// The branch that leads to it becomes the one redone,
+    /// so undoing and redoing brings back to it.
+    ///
+    /// Returns whether it moved, i.e. the node is in the tree and is not
+    /// the current one.
+    pub fn goto(&mut self, node: usize) -> bool {
+        if node >= self.nodes.len() || node == self.current {
+            return false;
+        }
+        let path = self.path(node);
+        for pair in path.windows(2) {
+            self.nodes[pair[0]].redo = Some(pair[1]);
+        }
+        let mut board = self.puzzle;
+        for &node in &path {
+            play(&mut board, &self.nodes[node].changes);
+        }
+        self.current = node;
+        self.board = board;
+        true
+    }
+
+    /// Forgets the child redone from the current node,
+    /// so the next move is the only one that can be redone.
+    pub fn forget_redo(&mut self) {
+        self.nodes[self.current].redo = None;
+    }
+
+    /// Drops the nodes added after the first ones.
+    ///
+    /// The current node is kept, so nothing is dropped if it is not among
+    /// the first nodes.
+    ///
+    /// ## Parameters
+    ///
+    /// - `len: usize` - The number of nodes to keep.
+    pub fn truncate(&mut self, len: usize) {
+        if self.current >= len {
+            return;
+        }
+        self.nodes.truncate(len);
+        for node in &mut self.nodes {
+            node.redo = node.redo.filter(|&child| child < len);
+        }
+    }
+
+    /// Lays the tree out to draw it,
+    /// every node after its parent.
+    ///
+    /// The children of a node are laid out in the order they were added,
+    /// and the last lane is the number of lanes minus one.
+    #[must_use]
+    pub fn layout(&self) -> Vec<NodeLayout> {
+        let mut layout = Vec::with_capacity(self.nodes.len());
+        let mut lanes = 0;
+        // the nodes to lay out, with their depth and the lane of their
+        // parent if they stay on it, the next one at the end
+        let mut stack = vec![(0, 0, Some(0))];
+        while let Some((node, depth, lane)) = stack.pop() {
+            let lane = lane.unwrap_or_else(|| {
+                lanes += 1;
+                lanes
+            });
+            layout.push(NodeLayout {
+                node,
+                parent: self.nodes[node].parent,
+                depth,
+                lane,
+            });
+            let children: Vec<usize> = (node + 1..self.nodes.len())
+                .filter(|&child| self.nodes[child].parent == Some(node))
+                .collect();
+            // the later children are laid out last, on new lanes
+            for (i, &child) in children.iter().enumerate().rev() {
+                stack.push((child, depth + 1, (i == 0).then_some(lane)));
+            }
+        }
+        layout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board with a value in a cell
+    fn board(index: usize, value: u8) -> SudokuState {
+        let mut board = [0; 81];
+        board[index] = value;
+        board
+    }
+
+    #[test]
+    fn test_branches() {
+        let puzzle = [0; 81];
+        let mut tree = UndoTree::new(puzzle);
+        assert!(!tree.undo());
+        assert!(!tree.redo());
+
+        tree.push(board(0, 1));
+        assert!(tree.undo());
+        // a new move keeps the undone one as a branch
+        tree.push(board(0, 2));
+        assert_eq!(tree.node_count(), 3);
+        assert_eq!(tree.moves(), vec![puzzle, board(0, 2)]);
+
+        assert!(tree.goto(1));
+        assert_eq!(tree.moves(), vec![puzzle, board(0, 1)]);
+        assert!(tree.undo());
+        assert_eq!(tree.current_board(), puzzle);
+        assert!(tree.redo());
+        assert_eq!(tree.current(), 1);
+        assert_eq!(tree.current_board(), board(0, 1));
+        assert!(!tree.goto(1));
+
+        // the nodes keep the cells changed, and the boards are rebuilt
+        let change = CellChange {
+            index: 0,
+            old: 0,
+            new: 2,
+        };
+        assert_eq!(tree.changes(2), Some([change].as_slice()));
+        assert_eq!(tree.board(2), Some(board(0, 2)));
+        assert_eq!(tree.boards(), vec![puzzle, board(0, 1), board(0, 2)]);
+        assert!(!tree.goto(3));
+
+        // a move to an existing child follows its branch
+        tree.undo();
+        tree.push(board(0, 2));
+        assert_eq!(tree.current(), 2);
+        assert_eq!(tree.node_count(), 3);
+    }
+
+    #[test]
+    fn test_from_moves_and_truncate() {
+        let moves = vec![[0; 81], board(0, 1), board(1, 2)];
+        let mut tree = UndoTree::from_moves(&moves).expect("moves");
+        assert_eq!(tree.moves(), moves);
+        assert_eq!(tree.depth(tree.current()), 2);
+        assert_eq!(UndoTree::from_moves(&[]), None);
+
+        tree.undo();
+        tree.push(board(2, 3));
+        tree.truncate(2);
+        assert_eq!(tree.node_count(), 4);
+        tree.undo();
+        tree.truncate(2);
+        assert_eq!(tree.node_count(), 2);
+        assert!(!tree.redo());
+        assert_eq!(tree.moves(), moves[..2]);
+    }
+
+    #[test]
+    fn test_notes() {
+        let mut tree = UndoTree::new([0; 81]);
+        tree.push(board(0, 1));
+        assert!(!tree.annotate(0, "the puzzle"));
+        assert!(!tree.annotate(2, "no such move"));
+
+        assert!(tree.annotate(1, "  guessing 1 here "));
+        assert_eq!(tree.note(1), Some("guessing 1 here"));
+        assert!(!tree.annotate(1, "guessing 1 here"));
+        assert!(tree.annotate(1, &"x".repeat(100)));
+        assert_eq!(tree.note(1).map(str::len), Some(MAX_NOTE_CHARS));
+
+        // the note stays on its move across undo and redo
+        tree.undo();
+        tree.redo();
+        assert!(tree.note(1).is_some());
+        tree.push(board(1, 2));
+        assert_eq!(
+            tree.move_notes(),
+            vec![Some("x".repeat(MAX_NOTE_CHARS)), None]
+        );
+        assert!(tree.annotate(1, " "));
+        assert_eq!(tree.note(1), None);
+        assert!(tree.move_notes().is_empty());
+    }
+
+    #[test]
+    fn test_from_nodes() {
+        let mut tree = UndoTree::new([0; 81]);
+        tree.push(board(0, 1));
+        tree.annotate(1, "guessing 1 here");
+        tree.undo();
+        tree.push(board(0, 2));
+        tree.undo();
+        let rebuilt = UndoTree::from_nodes([0; 81], tree.nodes().to_vec(), tree.current());
+        assert_eq!(rebuilt.as_ref(), Some(&tree));
+
+        // a parent after its child
+        let mut nodes = tree.nodes().to_vec();
+        nodes[1].parent = Some(2);
+        assert_eq!(UndoTree::from_nodes([0; 81], nodes, 0), None);
+        // a redo to another node than a child
+        let mut nodes = tree.nodes().to_vec();
+        nodes[1].redo = Some(2);
+        assert_eq!(UndoTree::from_nodes([0; 81], nodes, 0), None);
+        // a note on the puzzle
+        let mut nodes = tree.nodes().to_vec();
+        nodes[0].note = Some("the puzzle".to_string());
+        assert_eq!(UndoTree::from_nodes([0; 81], nodes, 0), None);
+        assert_eq!(
+            UndoTree::from_nodes([0; 81], tree.nodes().to_vec(), 3),
+            None
+        );
+        assert_eq!(UndoTree::from_nodes([0; 81], vec![], 0), None);
+        // a move that does not fit the board it was made on
+        assert_eq!(
+            UndoTree::from_nodes([1; 81], tree.nodes().to_vec(), 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut tree = UndoTree::new([0; 81]);
+        tree.push(board(0, 1));
+        tree.push(board(1, 1));
+        tree.goto(0);
+        tree.push(board(0, 2));
+        tree.goto(1);
+        tree.push(board(1, 2));
+        let lanes: Vec<(usize, usize, usize)> = tree
+            .layout()
+            .iter()
+            .map(|layout| (layout.node, layout.depth, layout.lane))
+            .collect();
+        assert_eq!(
+            lanes,
+            vec![(0, 0, 0), (1, 1, 0), (2, 2, 0), (4, 2, 1), (3, 1, 2)]
+        );
+    }
+}
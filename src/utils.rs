@@ -9,12 +9,16 @@
 //! or calculations.
 
 use std::borrow::Cow;
+use std::str::FromStr;
 
 use anyhow::{Error, Result};
-use rand::{seq::SliceRandom, thread_rng};
-use sudoku::Sudoku;
+use rand::seq::SliceRandom;
+use sudoku::{Sudoku, Symmetry};
 
 use crate::app::SudokuState;
+use crate::bitboard::BitBoard;
+use crate::dlx;
+use crate::rng::GameRng;
 
 /// Generates a new Sudoku puzzle.
 ///
@@ -33,7 +37,44 @@ use crate::app::SudokuState;
 /// Returns a `SudokuState`, which represents a 9x9 Sudoku puzzle.
 #[must_use]
 pub fn create_sudoku() -> SudokuState {
-    Sudoku::generate().to_bytes()
+    create_sudoku_with_rng(&mut GameRng::new())
+}
+
+/// Generates a new Sudoku puzzle from a random number generator.
+///
+/// The puzzle is generated like [`create_sudoku`],
+/// but all the random choices are drawn from the given generator,
+/// so a seeded generator always gives the same puzzle.
+///
+/// ## Parameters
+///
+/// - `rng: &mut GameRng` - The random number generator.
+///
+/// ## Returns
+///
+/// Returns a `SudokuState`, which represents a 9x9 Sudoku puzzle.
+#[must_use]
+pub fn create_sudoku_with_rng(rng: &mut GameRng) -> SudokuState {
+    let solved = Sudoku::generate_solved_with_rng(rng);
+    Sudoku::generate_with_symmetry_and_rng_from(solved, Symmetry::HalfRotation, rng).to_bytes()
+}
+
+/// Generates the Sudoku puzzle of a day.
+///
+/// The puzzle is generated with [`create_sudoku_with_rng`],
+/// from a random number generator seeded with the day,
+/// so everyone gets the same puzzle on the same day.
+///
+/// ## Parameters
+///
+/// - `day: u64` - The number of days since the Unix epoch.
+///
+/// ## Returns
+///
+/// Returns a `SudokuState`, which represents a 9x9 Sudoku puzzle.
+#[must_use]
+pub fn create_daily_sudoku(day: u64) -> SudokuState {
+    create_sudoku_with_rng(&mut GameRng::seeded(day))
 }
 
 /// Returns the CSS class for a Sudoku cell based on its ID and mutability.
@@ -94,7 +135,7 @@ pub fn get_class(id: u8, mutable: bool) -> Cow<'static, str> {
 /// ## Parameters
 ///
 /// - `index: u8`: The index of the cell in the Sudoku grid.
-///    Must be in the range 0 to 80.
+///   Must be in the range 0 to 80.
 ///
 /// ## Returns
 ///
@@ -157,7 +198,7 @@ pub fn get_related_cells(index: u8) -> Vec<u8> {
 ///
 /// - `board: &SudokuState`: A reference to a Sudoku board.
 /// - `index: u8`: The index of the cell in the Sudoku grid.
-///    Must be in the range 0 to 80.
+///   Must be in the range 0 to 80.
 ///
 /// ## Returns
 ///
@@ -194,6 +235,36 @@ pub fn get_conflicting_cells(board: &SudokuState, index: u8) -> Vec<u8> {
         .collect()
 }
 
+/// Calculates the candidates of a cell in a Sudoku puzzle.
+///
+/// The candidates of an empty cell are the values that are not already
+/// in any of its related cells.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState`: A reference to a Sudoku board.
+/// - `index: u8`: The index of the cell in the Sudoku grid.
+///   Must be in the range 0 to 80.
+///
+/// ## Returns
+///
+/// Returns the candidates as a `u16` bitmask, in the same representation as
+/// [`SudokuNotes`](crate::app::SudokuNotes).
+/// Filled cells have no candidates.
+///
+/// To get the candidates of many cells, see [`BitBoard::candidates`].
+#[must_use]
+pub fn get_candidates(board: &SudokuState, index: u8) -> u16 {
+    if board[index as usize] != 0 {
+        return 0;
+    }
+    get_related_cells(index)
+        .into_iter()
+        .map(|related| board[related as usize])
+        .filter(|&value| value != 0)
+        .fold(0x1FF, |candidates, value| candidates & !(1 << (value - 1)))
+}
+
 /// Finds the index at which two given [`SudokuState`]
 /// differ by exactly one item.
 ///
@@ -247,31 +318,47 @@ pub fn find_changed_cell(previous: &SudokuState, current: &SudokuState) -> Optio
 ///
 /// Returns a `Vec<u8>` representing all cell's indices that are conflicting
 /// with the current Sudoku board.
+///
+/// See also: [`BitBoard::conflicting`].
 #[must_use]
 pub fn get_all_conflicting_cells(current_sudoku: &SudokuState) -> Vec<u8> {
-    let filled: Vec<u8> = current_sudoku
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, &value)| {
-            if value != 0 {
-                u8::try_from(idx).ok()
-            } else {
-                None // Filter out the item if the value is 0
-            }
-        })
-        .collect();
-
-    // Get all conflicting cells for the filled cells
-    let mut conflicting: Vec<u8> = filled
-        .iter()
-        .flat_map(|&v| get_conflicting_cells(current_sudoku, v))
-        .collect::<Vec<u8>>();
+    BitBoard::new(*current_sudoku).conflicting()
+}
 
-    // Retain unique
-    conflicting.sort_unstable();
-    conflicting.dedup();
+/// Checks if a Sudoku board is solved
+///
+/// A board is solved if all of its cells are filled and none of them are
+/// conflicting.
+///
+/// ## Parameters
+///
+/// - `current_sudoku: &SudokuState` - A reference to the current [`SudokuState`]
+///
+/// ## Returns
+///
+/// Returns `true` if the board is solved, otherwise returns `false`.
+#[must_use]
+pub fn is_solved(current_sudoku: &SudokuState) -> bool {
+    BitBoard::new(*current_sudoku).is_solved()
+}
 
-    conflicting
+/// Checks if a Sudoku board can still be solved
+///
+/// Unlike the conflicting cells, which only compare a cell to its related
+/// cells, this searches the whole board,
+/// so a board without conflicts can still be unsolvable.
+///
+/// ## Parameters
+///
+/// - `current_sudoku: &SudokuState` - A reference to the current [`SudokuState`]
+///
+/// ## Returns
+///
+/// Returns `true` if the board has at least one solution,
+/// otherwise returns `false`.
+#[must_use]
+pub fn is_solvable(current_sudoku: &SudokuState) -> bool {
+    SolverBackend::DEFAULT.count_solutions(current_sudoku, 1) > 0
 }
 
 /// Finds a solution for a given Sudoku puzzle.
@@ -298,15 +385,134 @@ pub fn get_all_conflicting_cells(current_sudoku: &SudokuState) -> Vec<u8> {
 /// The function will panic if it cannot convert the current sudoku to a
 /// `sudoku::Sudoku` or if it cannot find a solution.
 pub fn find_solution(current_sudoku: &SudokuState) -> Result<SudokuState> {
-    // Convert back the SudokuState to a sudoku::Sudoku
-    let solution = Sudoku::from_bytes_slice(current_sudoku)
-        .expect("could not convert the current sudoku to a sudoku::Sudoku")
-        .some_solution();
-
-    solution.map_or_else(
-        || Err(Error::msg("No solution found")),
-        |solution| Ok(solution.to_bytes()),
-    )
+    find_solution_with(current_sudoku, SolverBackend::DEFAULT)
+}
+
+/// Finds a solution for a given Sudoku puzzle, with a solver backend.
+///
+/// The solution is searched like [`find_solution`],
+/// but with the given backend instead of the default one.
+///
+/// ## Parameters
+///
+/// - `current_sudoku: &SudokuState` - A reference to the current [`SudokuState`]
+/// - `backend: SolverBackend` - The solver backend.
+///
+/// ## Errors
+///
+/// If the Sudoku puzzle has no solution, an error is returned.
+///
+/// ## Panics
+///
+/// The function will panic if the [`SolverBackend::Sudoku`] backend cannot
+/// convert the current sudoku to a `sudoku::Sudoku`.
+pub fn find_solution_with(
+    current_sudoku: &SudokuState,
+    backend: SolverBackend,
+) -> Result<SudokuState> {
+    backend
+        .solve(current_sudoku)
+        .ok_or_else(|| Error::msg("No solution found"))
+}
+
+/// The backend that solves the puzzles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverBackend {
+    /// The backtracking solver of the [`sudoku`] crate
+    Sudoku,
+    /// The exact cover solver of the [`dlx`] module
+    DancingLinks,
+}
+
+impl SolverBackend {
+    /// The backend used unless another is asked for:
+    /// [`SolverBackend::DancingLinks`] with the `dlx` feature,
+    /// [`SolverBackend::Sudoku`] without it
+    pub const DEFAULT: Self = if cfg!(feature = "dlx") {
+        Self::DancingLinks
+    } else {
+        Self::Sudoku
+    };
+
+    /// Solves a board.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to the board to solve.
+    ///
+    /// ## Returns
+    ///
+    /// Returns a solution of the board, or `None` if it has none.
+    ///
+    /// ## Panics
+    ///
+    /// The [`SolverBackend::Sudoku`] backend panics if it cannot convert
+    /// the board to a `sudoku::Sudoku`.
+    #[must_use]
+    pub fn solve(self, board: &SudokuState) -> Option<SudokuState> {
+        match self {
+            Self::Sudoku => Sudoku::from_bytes_slice(board)
+                .expect("could not convert the current sudoku to a sudoku::Sudoku")
+                .some_solution()
+                .map(Sudoku::to_bytes),
+            Self::DancingLinks => dlx::solve(board),
+        }
+    }
+
+    /// Counts the solutions of a board, up to a limit.
+    ///
+    /// ## Parameters
+    ///
+    /// - `board: &SudokuState` - A reference to the board.
+    /// - `limit: usize` - The number of solutions to stop at.
+    ///
+    /// ## Returns
+    ///
+    /// Returns the number of solutions, at most `limit`,
+    /// or 0 if the board has conflicting cells or cannot be read.
+    #[must_use]
+    pub fn count_solutions(self, board: &SudokuState, limit: usize) -> usize {
+        if !get_all_conflicting_cells(board).is_empty() {
+            return 0;
+        }
+        match self {
+            Self::Sudoku => {
+                Sudoku::from_bytes(*board).map_or(0, |sudoku| sudoku.solutions_count_up_to(limit))
+            }
+            Self::DancingLinks => dlx::count_solutions(board, limit),
+        }
+    }
+}
+
+impl FromStr for SolverBackend {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sudoku" => Ok(Self::Sudoku),
+            "dlx" => Ok(Self::DancingLinks),
+            _ => Err(Error::msg(format!("Unknown solver: {s}"))),
+        }
+    }
+}
+
+/// Validates a Sudoku puzzle
+///
+/// A puzzle is valid if none of its cells are conflicting and it has at
+/// least one solution.
+///
+/// ## Parameters
+///
+/// - `sudoku: &SudokuState` - A reference to the [`SudokuState`] to validate
+///
+/// ## Errors
+///
+/// Returns an error if the puzzle has conflicting cells or no solution.
+pub fn validate_puzzle(sudoku: &SudokuState) -> Result<()> {
+    if !get_all_conflicting_cells(sudoku).is_empty() {
+        return Err(Error::msg("Puzzle has conflicting cells"));
+    }
+    find_solution(sudoku).map(|_| ())
 }
 
 /// Returns a hint of the next move towards a solution
@@ -333,6 +539,33 @@ pub fn find_solution(current_sudoku: &SudokuState) -> Result<SudokuState> {
 /// The function will panic if it cannot convert the current sudoku to a
 /// `sudoku::Sudoku` or if it cannot find a unique solution.
 pub fn get_hint(current_sudoku: &SudokuState) -> Result<SudokuState> {
+    get_hint_with_rng(current_sudoku, &mut GameRng::new())
+}
+
+/// Gets a hint for a Sudoku board, from a random number generator.
+///
+/// The hint is chosen like [`get_hint`],
+/// but the cell to fill is drawn from the given generator,
+/// so a seeded generator always fills the same cell.
+///
+/// ## Parameters
+///
+/// - `current_sudoku: &SudokuState` - A reference to the current [`SudokuState`]
+/// - `rng: &mut GameRng` - The random number generator.
+///
+/// ## Returns
+///
+/// Returns a Result containing the a random hint as a `SudokuState` if a solution is found,
+/// otherwise returns an error.
+///
+/// ## Errors
+///
+/// If the Sudoku puzzle has no solution, an error is returned.
+///
+/// ## Panics
+///
+/// The function will panic if it cannot find a unique solution.
+pub fn get_hint_with_rng(current_sudoku: &SudokuState, rng: &mut GameRng) -> Result<SudokuState> {
     // If sudoku is complete, then no hint is possible
     if current_sudoku.iter().all(|&val| val != 0) {
         return Ok(*current_sudoku);
@@ -362,7 +595,7 @@ pub fn get_hint(current_sudoku: &SudokuState) -> Result<SudokuState> {
             }
         })
         .collect::<Vec<_>>()
-        .choose(&mut thread_rng())
+        .choose(rng)
         .expect("could not get a random hint");
 
     // Merge the random hint into the current SudokuState and return a new
@@ -388,6 +621,59 @@ pub fn remove_conflicting_cells(sudoku: &mut SudokuState, conflicting: &[u8]) {
     }
 }
 
+/// Formats a duration as a clock time
+///
+/// ## Parameters
+///
+/// - `millis: u64` - The duration in milliseconds.
+///
+/// ## Returns
+///
+/// Returns a `String` formatted as `m:ss`,
+/// or `h:mm:ss` if the duration is longer than an hour.
+#[must_use]
+pub fn format_duration(millis: u64) -> String {
+    let seconds = millis / 1000;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+/// Formats a duration with its milliseconds, for the speedrun timer
+///
+/// ## Parameters
+///
+/// - `millis: u64` - The duration, in milliseconds.
+///
+/// ## Returns
+///
+/// Returns a `String` formatted as [`format_duration`] does,
+/// followed by the milliseconds, as `m:ss.mmm`.
+#[must_use]
+pub fn format_duration_millis(millis: u64) -> String {
+    format!("{}.{:03}", format_duration(millis), millis % 1000)
+}
+
+/// Counts the mistakes in a Sudoku board
+///
+/// A mistake is a filled cell whose value is not the one of the solution.
+///
+/// ## Parameters
+///
+/// - `current_sudoku: &SudokuState` - A reference to the current [`SudokuState`].
+/// - `solution: &SudokuState` - A reference to the solution of the puzzle.
+#[must_use]
+pub fn count_mistakes(current_sudoku: &SudokuState, solution: &SudokuState) -> usize {
+    current_sudoku
+        .iter()
+        .zip(solution)
+        .filter(|&(&value, &solved)| value != 0 && value != solved)
+        .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -408,6 +694,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_daily_sudoku() {
+        assert_eq!(create_daily_sudoku(20_000), create_daily_sudoku(20_000));
+        assert_ne!(create_daily_sudoku(20_000), create_daily_sudoku(20_001));
+        assert!(validate_puzzle(&create_daily_sudoku(20_000)).is_ok());
+    }
+
+    #[test]
+    fn test_seeded_generation() {
+        let puzzle = create_sudoku_with_rng(&mut GameRng::seeded(7));
+        assert_eq!(puzzle, create_sudoku_with_rng(&mut GameRng::seeded(7)));
+        assert!(validate_puzzle(&puzzle).is_ok());
+
+        let hint = get_hint_with_rng(&puzzle, &mut GameRng::seeded(7)).expect("a hint");
+        assert_eq!(
+            get_hint_with_rng(&puzzle, &mut GameRng::seeded(7)).expect("a hint"),
+            hint
+        );
+    }
+
     #[test]
     fn test_related_cells_middle() {
         let index = 40; // Center cell of the board
@@ -579,4 +885,97 @@ mod tests {
         remove_conflicting_cells(&mut board, &conflicting);
         assert_eq!(get_all_conflicting_cells(&board), Vec::<u8>::new());
     }
+
+    #[test]
+    fn test_validate_puzzle() {
+        let board = create_sudoku();
+        assert!(validate_puzzle(&board).is_ok());
+
+        let mut conflicting: SudokuState = [0; 81];
+        conflicting[0] = 1;
+        conflicting[1] = 1;
+        assert!(validate_puzzle(&conflicting).is_err());
+    }
+
+    #[test]
+    fn test_solver_backends() {
+        let board = create_sudoku();
+        let solution = find_solution_with(&board, SolverBackend::Sudoku).expect("solvable");
+        assert_eq!(
+            find_solution_with(&board, SolverBackend::DancingLinks).ok(),
+            Some(solution)
+        );
+        for backend in [SolverBackend::Sudoku, SolverBackend::DancingLinks] {
+            assert_eq!(backend.count_solutions(&board, 2), 1);
+            assert_eq!(backend.count_solutions(&[0; 81], 2), 2);
+        }
+        assert_eq!("dlx".parse().ok(), Some(SolverBackend::DancingLinks));
+        assert!("guess".parse::<SolverBackend>().is_err());
+    }
+
+    #[test]
+    fn test_is_solved() {
+        let solution = find_solution(&create_sudoku()).expect("could not find a solution");
+        assert!(is_solved(&solution));
+
+        let mut unfinished = solution;
+        unfinished[40] = 0;
+        assert!(!is_solved(&unfinished));
+
+        let mut wrong = solution;
+        wrong.swap(0, 1);
+        assert!(!is_solved(&wrong));
+    }
+
+    #[test]
+    fn test_is_solvable() {
+        let board = create_sudoku();
+        assert!(is_solvable(&board));
+
+        // the first cell cannot hold any digit
+        let mut dead_end: SudokuState = [0; 81];
+        for digit in 1..=8 {
+            dead_end[digit as usize] = digit;
+        }
+        dead_end[9] = 9;
+        assert!(get_all_conflicting_cells(&dead_end).is_empty());
+        assert!(!is_solvable(&dead_end));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0:00");
+        assert_eq!(format_duration(392_500), "6:32");
+        assert_eq!(format_duration(3_723_000), "1:02:03");
+        assert_eq!(format_duration_millis(392_057), "6:32.057");
+    }
+
+    #[test]
+    fn test_get_candidates() {
+        let mut board: SudokuState = [0; 81];
+        assert_eq!(get_candidates(&board, 0), 0x1FF);
+
+        board[8] = 1; // same row
+        board[72] = 2; // same column
+        board[10] = 3; // same sub-grid
+        board[40] = 4; // unrelated
+        assert_eq!(get_candidates(&board, 0), 0x1F8);
+        assert_eq!(get_candidates(&board, 8), 0);
+    }
+
+    #[test]
+    fn test_count_mistakes() {
+        let mut solution: SudokuState = [0; 81];
+        solution[0] = 5;
+        solution[1] = 3;
+        solution[2] = 4;
+
+        let mut board: SudokuState = [0; 81];
+        assert_eq!(count_mistakes(&board, &solution), 0);
+
+        board[0] = 5;
+        board[1] = 4;
+        board[2] = 3;
+        assert_eq!(count_mistakes(&board, &solution), 2);
+    }
 }
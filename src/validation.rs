@@ -0,0 +1,237 @@
+//! # Validation Module
+//!
+//! The `validation` module reports why a board breaks the rules of Sudoku.
+//!
+//! [`validate_board`] lists every [`Violation`], a digit repeated in a row,
+//! a column or a box, and [`explain_conflict`] turns the violations of a
+//! cell into a sentence for the user,
+//! such as "Duplicates 7 in row 4 and in the center box".
+//! [`cell_name`] names a cell by its coordinates, such as "R4C9".
+
+use std::fmt;
+
+use crate::app::SudokuState;
+
+/// The names of the boxes, from the top-left one, row by row
+pub const BOX_NAMES: [&str; 9] = [
+    "top-left",
+    "top",
+    "top-right",
+    "left",
+    "center",
+    "right",
+    "bottom-left",
+    "bottom",
+    "bottom-right",
+];
+
+/// A unit of the board, which cannot repeat a digit
+///
+/// Units are numbered from 0; boxes from the top-left one, row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// A row, from the top
+    Row(u8),
+    /// A column, from the left
+    Column(u8),
+    /// A 3x3 box
+    Box(u8),
+}
+
+impl Unit {
+    /// Returns all the units: the rows first, then the columns,
+    /// then the boxes.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..9)
+            .map(Self::Row)
+            .chain((0..9).map(Self::Column))
+            .chain((0..9).map(Self::Box))
+    }
+
+    /// Returns the units of a cell: its row, its column and its box.
+    ///
+    /// ## Parameters
+    ///
+    /// - `index: u8` - The index of the cell, from 0 to 80.
+    #[must_use]
+    pub const fn of(index: u8) -> [Self; 3] {
+        let (row, col) = (index / 9, index % 9);
+        [
+            Self::Row(row),
+            Self::Column(col),
+            Self::Box(row / 3 * 3 + col / 3),
+        ]
+    }
+
+    /// Returns the indices of the cells of the unit.
+    #[must_use]
+    pub fn cells(self) -> [u8; 9] {
+        let mut cells = [0; 9];
+        for (i, cell) in (0..9).zip(cells.iter_mut()) {
+            *cell = match self {
+                Self::Row(row) => row * 9 + i,
+                Self::Column(col) => i * 9 + col,
+                Self::Box(square) => (square / 3 * 3 + i / 3) * 9 + square % 3 * 3 + i % 3,
+            };
+        }
+        cells
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Row(row) => write!(f, "row {}", row + 1),
+            Self::Column(col) => write!(f, "column {}", col + 1),
+            Self::Box(square) => write!(f, "the {} box", BOX_NAMES[*square as usize]),
+        }
+    }
+}
+
+/// A digit repeated in a unit of the board
+///
+/// - `value: u8`: the repeated digit, from 1 to 9.
+/// - `unit: Unit`: the unit that repeats it.
+/// - `cells: Vec<u8>`: the indices of the cells with the digit in the unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub value: u8,
+    pub unit: Unit,
+    pub cells: Vec<u8>,
+}
+
+/// Returns the name of a cell, from its row and its column.
+///
+/// ## Parameters
+///
+/// - `index: u8` - The index of the cell, from 0 to 80.
+///
+/// ## Returns
+///
+/// Returns the coordinates of the cell, counted from 1,
+/// as "R4C9" for the last cell of the fourth row.
+#[must_use]
+pub fn cell_name(index: u8) -> String {
+    format!("R{}C{}", index / 9 + 1, index % 9 + 1)
+}
+
+/// Lists the violations of the rules of a board.
+///
+/// ## Parameters
+///
+/// - `board: &SudokuState` - A reference to a Sudoku board.
+///
+/// ## Returns
+///
+/// Returns a [`Violation`] for every digit repeated in a unit,
+/// the rows first, then the columns, then the boxes.
+/// A valid board has none.
+#[must_use]
+pub fn validate_board(board: &SudokuState) -> Vec<Violation> {
+    Unit::all()
+        .flat_map(|unit| {
+            (1..=9).filter_map(move |value| {
+                let cells: Vec<u8> = unit
+                    .cells()
+                    .into_iter()
+                    .filter(|&index| board[index as usize] == value)
+                    .collect();
+                (cells.len() > 1).then_some(Violation { value, unit, cells })
+            })
+        })
+        .collect()
+}
+
+/// Explains why a cell is in conflict.
+///
+/// ## Parameters
+///
+/// - `violations: &[Violation]` - The violations of the board,
+///   from [`validate_board`].
+/// - `index: u8` - The index of the cell, from 0 to 80.
+///
+/// ## Returns
+///
+/// Returns the units where the digit of the cell is repeated,
+/// as "Duplicates 7 in row 4 and in the center box",
+/// or `None` if the cell is not in any violation.
+#[must_use]
+pub fn explain_conflict(violations: &[Violation], index: u8) -> Option<String> {
+    let mut value = 0;
+    let units: Vec<String> = violations
+        .iter()
+        .filter(|violation| violation.cells.contains(&index))
+        .map(|violation| {
+            value = violation.value;
+            violation.unit.to_string()
+        })
+        .collect();
+    match units.as_slice() {
+        [] => None,
+        [unit] => Some(format!("Duplicates {value} in {unit}")),
+        [units @ .., last] => Some(format!(
+            "Duplicates {value} in {} and in {last}",
+            units.join(", in ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_cells() {
+        assert_eq!(Unit::Row(1).cells(), [9, 10, 11, 12, 13, 14, 15, 16, 17]);
+        assert_eq!(Unit::Column(2).cells()[8], 74);
+        assert_eq!(Unit::Box(4).cells(), [30, 31, 32, 39, 40, 41, 48, 49, 50]);
+        for index in 0..81 {
+            for unit in Unit::of(index) {
+                assert!(unit.cells().contains(&index));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cell_name() {
+        assert_eq!(cell_name(0), "R1C1");
+        assert_eq!(cell_name(35), "R4C9");
+        assert_eq!(cell_name(80), "R9C9");
+    }
+
+    #[test]
+    fn test_validate_board() {
+        let mut board: SudokuState = [0; 81];
+        assert!(validate_board(&board).is_empty());
+
+        board[30] = 7; // row 4, column 4, center box
+        board[35] = 7; // row 4, column 9, right box
+        board[40] = 7; // row 5, column 5, center box
+        let violations = validate_board(&board);
+        assert_eq!(
+            violations,
+            vec![
+                Violation {
+                    value: 7,
+                    unit: Unit::Row(3),
+                    cells: vec![30, 35],
+                },
+                Violation {
+                    value: 7,
+                    unit: Unit::Box(4),
+                    cells: vec![30, 40],
+                },
+            ]
+        );
+
+        assert_eq!(
+            explain_conflict(&violations, 30).as_deref(),
+            Some("Duplicates 7 in row 4 and in the center box")
+        );
+        assert_eq!(
+            explain_conflict(&violations, 35).as_deref(),
+            Some("Duplicates 7 in row 4")
+        );
+        assert_eq!(explain_conflict(&violations, 0), None);
+    }
+}
@@ -0,0 +1,240 @@
+//! # Weekly Module
+//!
+//! The `weekly` module contains the weekly challenge,
+//! a puzzle of the [`CHALLENGE_DIFFICULTY`] that is the same for everyone
+//! during a week, and changes every Monday at midnight, UTC.
+//!
+//! Completed challenges are tracked apart from the daily puzzles and the
+//! statistics, in the [`WeeklyLog`] of the profile,
+//! with the time every challenge was solved in.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::SudokuState;
+use crate::browser::{local_storage_get, local_storage_set};
+use crate::daily::DAY_MILLIS;
+use crate::difficulty::{create_puzzle_with_rng, Difficulty};
+use crate::profiles::profile_key;
+use crate::rng::GameRng;
+
+/// Milliseconds in a week
+pub const WEEK_MILLIS: u64 = 7 * DAY_MILLIS;
+
+/// The difficulty of the weekly challenges
+pub const CHALLENGE_DIFFICULTY: Difficulty = Difficulty::Expert;
+
+/// Milliseconds from the Monday before the Unix epoch,
+/// a Thursday, to the epoch
+const EPOCH_OFFSET: u64 = 3 * DAY_MILLIS;
+
+/// Seed added to the week, so the challenges are not daily puzzles,
+/// which are seeded with the day
+const WEEKLY_SEED: u64 = 1 << 63;
+
+/// Local storage key of the [`WeeklyLog`]
+const WEEKLY_KEY: &str = "weekly";
+
+/// Returns the week of a time.
+///
+/// ## Parameters
+///
+/// - `millis: u64` - The time, in milliseconds since the Unix epoch.
+///
+/// ## Returns
+///
+/// Returns the number of weeks since the Monday before the Unix epoch.
+#[must_use]
+pub const fn week_of(millis: u64) -> u64 {
+    (millis + EPOCH_OFFSET) / WEEK_MILLIS
+}
+
+/// Returns the time left until the next challenge.
+///
+/// ## Parameters
+///
+/// - `millis: u64` - The current time, in milliseconds since the Unix epoch.
+///
+/// ## Returns
+///
+/// Returns the milliseconds until the next Monday at midnight, UTC.
+#[must_use]
+pub const fn next_challenge_in(millis: u64) -> u64 {
+    WEEK_MILLIS - (millis + EPOCH_OFFSET) % WEEK_MILLIS
+}
+
+/// Returns the first day of a week.
+///
+/// ## Parameters
+///
+/// - `week: u64` - The number of weeks since the Monday before the Unix
+///   epoch.
+///
+/// ## Returns
+///
+/// Returns the number of days since the Unix epoch of its Monday,
+/// or 0 for the week of the epoch.
+#[must_use]
+pub const fn first_day_of(week: u64) -> u64 {
+    (week * 7).saturating_sub(3)
+}
+
+/// Generates the challenge of a week.
+///
+/// The puzzle is generated with [`create_puzzle_with_rng`],
+/// from a random number generator seeded with the week,
+/// so everyone gets the same challenge in the same week.
+///
+/// ## Parameters
+///
+/// - `week: u64` - The number of weeks since the Monday before the Unix
+///   epoch.
+///
+/// ## Returns
+///
+/// Returns a `SudokuState`, which represents a 9x9 Sudoku puzzle.
+#[must_use]
+pub fn create_weekly_sudoku(week: u64) -> SudokuState {
+    create_puzzle_with_rng(
+        CHALLENGE_DIFFICULTY,
+        &mut GameRng::seeded(WEEKLY_SEED | week),
+    )
+}
+
+/// Formats the time left until the next challenge.
+///
+/// ## Parameters
+///
+/// - `millis: u64` - The time left, in milliseconds.
+///
+/// ## Returns
+///
+/// Returns the time as `"{days}d {hours}:{minutes}:{seconds}"`,
+/// without the days if there is less than a day left.
+#[must_use]
+pub fn format_countdown(millis: u64) -> String {
+    let seconds = millis / 1000;
+    let (days, hours) = (seconds / 86_400, seconds / 3600 % 24);
+    let (minutes, seconds) = (seconds / 60 % 60, seconds % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    }
+}
+
+/// The completed weekly challenges of a profile,
+/// with the best time of every week, in milliseconds
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WeeklyLog(pub BTreeMap<u64, u64>);
+
+impl WeeklyLog {
+    /// Returns the best time of the challenge of a week,
+    /// or `None` if it was not completed.
+    ///
+    /// ## Parameters
+    ///
+    /// - `week: u64` - The week of the challenge.
+    #[must_use]
+    pub fn best(&self, week: u64) -> Option<u64> {
+        self.0.get(&week).copied()
+    }
+
+    /// Records the challenge of a week as completed.
+    ///
+    /// ## Parameters
+    ///
+    /// - `week: u64` - The week of the challenge.
+    /// - `millis: u64` - The time it was solved in, kept if it is the best.
+    pub fn complete(&mut self, week: u64, millis: u64) {
+        self.0
+            .entry(week)
+            .and_modify(|best| *best = (*best).min(millis))
+            .or_insert(millis);
+    }
+
+    /// Returns the number of completed challenges.
+    #[must_use]
+    pub fn completed(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Loads the weekly log of a profile from the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+///
+/// ## Returns
+///
+/// Returns the stored [`WeeklyLog`],
+/// or an empty one if none is stored or it cannot be read.
+#[must_use]
+pub fn load_weekly_log(profile: u32) -> WeeklyLog {
+    local_storage_get(&profile_key(profile, WEEKLY_KEY))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Stores the weekly log of a profile in the local storage.
+///
+/// ## Parameters
+///
+/// - `profile: u32` - The id of the profile.
+/// - `log: &WeeklyLog` - The log to store.
+pub fn store_weekly_log(profile: u32, log: &WeeklyLog) {
+    match serde_json::to_string(log) {
+        Ok(json) => local_storage_set(&profile_key(profile, WEEKLY_KEY), &json),
+        Err(err) => log::error!("failed to store the weekly log: {err:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daily::{date_of, weekday};
+    use crate::difficulty::grade_puzzle;
+    use crate::utils::create_daily_sudoku;
+
+    #[test]
+    fn test_weeks() {
+        // 2024-10-07 was a Monday
+        let monday = 20_003 * DAY_MILLIS;
+        assert_eq!(week_of(monday - 1) + 1, week_of(monday));
+        assert_eq!(week_of(monday), week_of(monday + WEEK_MILLIS - 1));
+        assert_eq!(next_challenge_in(monday), WEEK_MILLIS);
+        assert_eq!(next_challenge_in(monday - 1000), 1000);
+
+        let first = first_day_of(week_of(monday));
+        assert_eq!(date_of(first), (2024, 10, 7));
+        assert_eq!(weekday(first), 0);
+    }
+
+    #[test]
+    fn test_create_weekly_sudoku() {
+        let challenge = create_weekly_sudoku(2857);
+        assert_eq!(challenge, create_weekly_sudoku(2857));
+        assert_ne!(challenge, create_weekly_sudoku(2858));
+        assert_ne!(challenge, create_daily_sudoku(2857));
+        assert_eq!(grade_puzzle(&challenge), CHALLENGE_DIFFICULTY);
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(59_000), "00:00:59");
+        assert_eq!(format_countdown(DAY_MILLIS + 3_661_000), "1d 01:01:01");
+    }
+
+    #[test]
+    fn test_weekly_log() {
+        let mut log = WeeklyLog::default();
+        assert_eq!(log.best(10), None);
+        log.complete(10, 600_000);
+        log.complete(10, 900_000);
+        log.complete(11, 300_000);
+        assert_eq!(log.best(10), Some(600_000));
+        assert_eq!(log.completed(), 2);
+    }
+}